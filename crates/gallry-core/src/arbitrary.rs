@@ -0,0 +1,64 @@
+//! Random slots for property tests, behind the `proptest` feature.
+//!
+//! Slots are generated as text in the notation of [`FromStr`](core::str::FromStr) and
+//! then parsed, so a test can put the same text in a puzzle definition and know it
+//! describes the slot it was given.
+
+use crate::Slot;
+use alloc::string::String;
+use alloc::vec::Vec;
+use proptest::arbitrary::Arbitrary;
+use proptest::collection::{SizeRange, btree_set, vec};
+use proptest::prelude::*;
+
+/// Returns a strategy for lowercase ASCII letters.
+pub fn letter() -> impl Strategy<Value = char> + Clone {
+    proptest::char::range('a', 'z')
+}
+
+/// Returns a strategy for one slot written in the slot notation.
+///
+/// Most slots list one to five letters, as in `cbr`; some are wildcards, `?`, or
+/// exclude a few letters, as in `!qz`.
+///
+/// # Examples
+///
+/// ```
+/// use gallry_core::Slot;
+/// use gallry_core::arbitrary::notation;
+/// use proptest::prelude::*;
+///
+/// proptest!(|(text in notation())| {
+///     prop_assert!(text.parse::<Slot>().is_ok());
+/// });
+/// ```
+pub fn notation() -> impl Strategy<Value = String> + Clone {
+    let letters = btree_set(letter(), 1..=5).prop_map(|set| set.into_iter().collect());
+    let excluded = btree_set(letter(), 1..=3)
+        .prop_map(|set| core::iter::once('!').chain(set).collect::<String>());
+    prop_oneof![
+        8 => letters,
+        1 => Just(String::from("?")),
+        1 => excluded,
+    ]
+}
+
+/// Returns a strategy for a row of slots.
+///
+/// # Parameters
+///
+/// * `count` - How many slots, such as `3..=7`
+pub fn slots(count: impl Into<SizeRange>) -> impl Strategy<Value = Vec<Slot>> {
+    vec(any::<Slot>(), count)
+}
+
+impl Arbitrary for Slot {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        notation()
+            .prop_map(|text| text.parse().expect("generated notation is valid"))
+            .boxed()
+    }
+}
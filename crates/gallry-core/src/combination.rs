@@ -0,0 +1,65 @@
+//! Numbering of the combinations a list of slots produces.
+//!
+//! Combinations are numbered in enumeration order, with the last slot varying
+//! fastest, so a solve can be split into ranges or resumed from a number. Each
+//! combination is identified by the index of the option chosen in every slot.
+
+use crate::Slot;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Returns the number of combinations the slots produce.
+///
+/// The count saturates at `u128::MAX` for absurdly large puzzles.
+pub fn count(slots: &[Slot]) -> u128 {
+    slots.iter().fold(1u128, |count, slot| {
+        count.saturating_mul(slot.options().len() as u128)
+    })
+}
+
+/// Returns the option index of every slot for combination number `position`.
+pub fn decode(slots: &[Slot], position: u128) -> Vec<usize> {
+    let mut indices = vec![0; slots.len()];
+    let mut rest = position;
+    for (index, slot) in indices.iter_mut().zip(slots).rev() {
+        let size = slot.options().len() as u128;
+        *index = (rest % size) as usize;
+        rest /= size;
+    }
+    indices
+}
+
+/// Moves the option indices on to the next combination.
+///
+/// After the last combination the indices wrap around to the first.
+pub fn increment(slots: &[Slot], indices: &mut [usize]) {
+    for (index, slot) in indices.iter_mut().zip(slots).rev() {
+        *index += 1;
+        if *index < slot.options().len() {
+            break;
+        }
+        *index = 0;
+    }
+}
+
+/// Spells the word the option indices choose.
+pub fn spell(slots: &[Slot], indices: &[usize]) -> String {
+    let mut word = String::with_capacity(indices.len());
+    spell_into(slots, indices, &mut word);
+    word
+}
+
+/// Spells the word the option indices choose into `word`, replacing its contents.
+///
+/// A solve spells every combination, so reusing one buffer saves an allocation for
+/// each combination that is rejected.
+pub fn spell_into(slots: &[Slot], indices: &[usize], word: &mut String) {
+    word.clear();
+    word.extend(
+        indices
+            .iter()
+            .zip(slots)
+            .map(|(&index, slot)| slot.options()[index]),
+    );
+}
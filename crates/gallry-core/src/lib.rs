@@ -0,0 +1,204 @@
+//! # Gallery puzzle core
+//!
+//! The slots of a gallery puzzle and the arithmetic of their combinations, for
+//! `no_std` targets with an allocator such as embedded devices and WebAssembly.
+//!
+//! Word lists, constraints and file formats need the standard library and live in
+//! the `gallry-puzzle-soulver` crate, which re-exports everything here.
+//!
+//! ## Example
+//!
+//! ```
+//! use gallry_core::{Slot, combination};
+//!
+//! let slots = vec![Slot::new(vec!['c', 'b']), Slot::new(vec!['a', 'o'])];
+//! assert_eq!(combination::count(&slots), 4);
+//!
+//! let mut indices = combination::decode(&slots, 1);
+//! assert_eq!(combination::spell(&slots, &indices), "co");
+//! combination::increment(&slots, &mut indices);
+//! assert_eq!(combination::spell(&slots, &indices), "ba");
+//! ```
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::collections::BTreeSet;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::ops::Deref;
+
+#[cfg(feature = "proptest")]
+pub mod arbitrary;
+pub mod combination;
+mod notation;
+
+/// A character position with multiple possible character options.
+///
+/// Each `Slot` represents a single position in a word, with a set of possible characters
+/// that could appear in that position. It also implements `Iterator` to allow iterating
+/// through all possible characters in the slot.
+///
+/// # Examples
+///
+/// ```
+/// use gallry_core::Slot;
+///
+/// // Create a slot with three possible characters
+/// let slot = Slot::new(vec!['a', 'b', 'c']);
+///
+/// // Use it as a character via deref coercion (defaults to first option)
+/// assert_eq!(*slot, 'a');
+///
+/// // Convert to string
+/// assert_eq!(slot.to_string(), "a");
+///
+/// // Iterate through all options
+/// let chars: Vec<char> = slot.collect();
+/// assert_eq!(chars, vec!['a', 'b', 'c']);
+/// ```
+///
+/// Slots can also be parsed from text such as `abc`, `?`, `[a-f]` or `!xyz`; see
+/// [`Slot::from_str`](#impl-FromStr-for-Slot).
+#[derive(Clone, Debug)]
+pub struct Slot {
+    /// All possible characters for this position
+    options: Vec<char>,
+    /// Current index when iterating
+    current: usize,
+    /// For wildcard slots, the characters excluded from the dictionary's alphabet
+    excluded: Option<Vec<char>>,
+}
+
+impl Slot {
+    /// Creates a new Slot with the given character options.
+    ///
+    /// # Parameters
+    ///
+    /// * `options` - A vector of possible characters for this position
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gallry_core::Slot;
+    ///
+    /// let slot = Slot::new(vec!['a', 'b', 'c']);
+    /// ```
+    pub fn new(options: Vec<char>) -> Self {
+        Self {
+            options,
+            current: 0,
+            excluded: None,
+        }
+    }
+
+    /// Creates a wildcard slot that accepts any character of the alphabet.
+    ///
+    /// A generator fills the options from the alphabet of its dictionaries, falling
+    /// back to `a`–`z` when it does no filtering; see [`resolved`](Self::resolved).
+    /// Until then the slot has no options of its own.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gallry_core::Slot;
+    ///
+    /// let slot = Slot::any();
+    /// assert!(slot.is_wildcard());
+    /// assert!(slot.options().is_empty());
+    /// ```
+    pub fn any() -> Self {
+        Self::none_of(Vec::new())
+    }
+
+    /// Creates a wildcard slot that accepts any character of the alphabet except `excluded`.
+    ///
+    /// # Parameters
+    ///
+    /// * `excluded` - Characters that may not appear in this position
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gallry_core::Slot;
+    ///
+    /// let alphabet = ('a'..='e').collect();
+    /// let slot = Slot::none_of(vec!['b', 'd']).resolved(&alphabet);
+    /// assert_eq!(slot.options(), ['a', 'c', 'e']);
+    /// ```
+    pub fn none_of(excluded: Vec<char>) -> Self {
+        Self {
+            options: Vec::new(),
+            current: 0,
+            excluded: Some(excluded),
+        }
+    }
+
+    /// Returns the characters this slot can take, in order.
+    ///
+    /// Wildcard slots have no options of their own until they are
+    /// [resolved](Self::resolved) against an alphabet.
+    pub fn options(&self) -> &[char] {
+        &self.options
+    }
+
+    /// Returns `true` if the slot draws its options from the dictionary's alphabet.
+    pub fn is_wildcard(&self) -> bool {
+        self.excluded.is_some()
+    }
+
+    /// Adds a character to the options, unless the slot already has it.
+    pub fn add_option(&mut self, option: char) {
+        if !self.options.contains(&option) {
+            self.options.push(option);
+        }
+    }
+
+    /// Returns a copy of the slot with wildcard options taken from `alphabet`.
+    ///
+    /// Slots that are not wildcards are copied unchanged.
+    pub fn resolved(&self, alphabet: &BTreeSet<char>) -> Self {
+        let mut slot = self.clone();
+        if let Some(excluded) = &self.excluded {
+            slot.options = alphabet
+                .iter()
+                .copied()
+                .filter(|c| !excluded.contains(c))
+                .collect();
+        }
+        slot
+    }
+}
+
+impl From<Slot> for String {
+    /// Converts the slot to a String, using the currently selected character.
+    fn from(val: Slot) -> Self {
+        val.options[val.current].to_string()
+    }
+}
+
+impl Deref for Slot {
+    type Target = char;
+
+    /// Dereferences to the currently selected character.
+    /// By default, this is the first character in the options list.
+    fn deref(&self) -> &Self::Target {
+        &self.options[self.current]
+    }
+}
+
+impl Iterator for Slot {
+    type Item = char;
+
+    /// Iterates through all possible characters in this slot.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.current < self.options.len() {
+            let result = Some(self.options[self.current]);
+            self.current += 1;
+            result
+        } else {
+            None
+        }
+    }
+}
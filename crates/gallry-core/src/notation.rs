@@ -0,0 +1,145 @@
+//! Parsing of slots written as text, such as `abc`, `?`, `[a-f]` or `!xyz`.
+//!
+//! - `?` alone accepts any letter of the alphabet
+//! - `[a-f]` adds every character in the range; brackets may hold several ranges
+//!   and single characters, as in `[a-cx]`
+//! - A leading `!` negates the set, so `!qz` accepts any letter except `q` and `z`
+//! - `\` makes the next character literal, as in `\?` or `\[`
+
+use crate::Slot;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::str::FromStr;
+
+impl FromStr for Slot {
+    type Err = String;
+
+    /// Parses a slot written in the notation described in the module documentation.
+    ///
+    /// Duplicate characters are dropped, keeping the first occurrence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gallry_core::Slot;
+    ///
+    /// let slot: Slot = "[a-c]x\\?".parse().unwrap();
+    /// assert_eq!(slot.options(), ['a', 'b', 'c', 'x', '?']);
+    ///
+    /// assert!("?".parse::<Slot>().unwrap().is_wildcard());
+    /// assert!("!qz".parse::<Slot>().unwrap().is_wildcard());
+    /// assert!("[a-".parse::<Slot>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "?" {
+            return Ok(Slot::any());
+        }
+
+        match s.strip_prefix('!') {
+            Some(excluded) => Ok(Slot::none_of(parse_set(excluded, 1)?)),
+            None => {
+                let options = parse_set(s, 0)?;
+                if options.is_empty() {
+                    return Err("a set needs at least one character".to_string());
+                }
+                Ok(Slot::new(options))
+            }
+        }
+    }
+}
+
+/// Parses a set of characters, ranges and escapes.
+///
+/// `offset` is the number of characters before `text` in the argument, so error
+/// positions count from the start of what the user typed.
+fn parse_set(text: &str, offset: usize) -> Result<Vec<char>, String> {
+    let chars: Vec<char> = text.chars().collect();
+    let at = |index: usize| index + offset + 1;
+
+    let mut options = Vec::new();
+    let mut add = |c: char| {
+        if !options.contains(&c) {
+            options.push(c);
+        }
+    };
+
+    let mut index = 0;
+    while index < chars.len() {
+        match chars[index] {
+            '[' => {
+                let start = index;
+                index += 1;
+                while chars.get(index) != Some(&']') {
+                    if index == chars.len() {
+                        return Err(format!("unclosed '[' at character {}", at(start)));
+                    }
+                    let (first, next) = element(&chars, index, at)?;
+                    index = next;
+
+                    // A '-' between two characters makes a range; elsewhere it is literal
+                    if chars.get(index) == Some(&'-')
+                        && chars.get(index + 1).is_some_and(|&c| c != ']')
+                    {
+                        let (last, next) = element(&chars, index + 1, at)?;
+                        if last < first {
+                            return Err(format!(
+                                "range '{}-{}' at character {} is reversed",
+                                first,
+                                last,
+                                at(index)
+                            ));
+                        }
+                        (first..=last).for_each(&mut add);
+                        index = next;
+                    } else {
+                        add(first);
+                    }
+                }
+                if index == start + 1 {
+                    return Err(format!("empty '[]' at character {}", at(start)));
+                }
+                index += 1;
+            }
+            '?' => {
+                return Err(format!(
+                    "'?' at character {} must be the whole set; write \\? for a literal '?'",
+                    at(index)
+                ));
+            }
+            '!' => {
+                return Err(format!(
+                    "'!' at character {} must start the set; write \\! for a literal '!'",
+                    at(index)
+                ));
+            }
+            ']' => {
+                return Err(format!(
+                    "unmatched ']' at character {}; write \\] for a literal ']'",
+                    at(index)
+                ));
+            }
+            _ => {
+                let (c, next) = element(&chars, index, at)?;
+                add(c);
+                index = next;
+            }
+        }
+    }
+    Ok(options)
+}
+
+/// Reads the possibly escaped character at `index`, returning it and the index after it.
+fn element(
+    chars: &[char],
+    index: usize,
+    at: impl Fn(usize) -> usize,
+) -> Result<(char, usize), String> {
+    match chars[index] {
+        '\\' => chars
+            .get(index + 1)
+            .map(|&c| (c, index + 2))
+            .ok_or_else(|| format!("'\\' at character {} escapes nothing", at(index))),
+        c => Ok((c, index + 1)),
+    }
+}
@@ -0,0 +1,68 @@
+use gallry_core::{Slot, combination};
+use std::collections::BTreeSet;
+
+#[test]
+fn test_combination_numbering() {
+    let slots = vec![
+        Slot::new(vec!['c', 'b', 'r']),
+        Slot::new(vec!['a', 'o']),
+        Slot::new(vec!['t', 's', 'e']),
+    ];
+    let total = combination::count(&slots);
+    assert_eq!(total, 18);
+
+    // Incrementing from the first combination visits every number in order
+    let mut indices = combination::decode(&slots, 0);
+    assert_eq!(indices, [0, 0, 0]);
+    let mut word = String::new();
+    let mut words = Vec::new();
+    for position in 0..total {
+        assert_eq!(indices, combination::decode(&slots, position));
+        combination::spell_into(&slots, &indices, &mut word);
+        assert_eq!(word, combination::spell(&slots, &indices));
+        words.push(word.clone());
+        combination::increment(&slots, &mut indices);
+    }
+    assert_eq!(words[..4], ["cat", "cas", "cae", "cot"]);
+    assert_eq!(words[17], "roe");
+    assert_eq!(words.iter().collect::<BTreeSet<_>>().len(), 18);
+
+    // The last combination wraps around to the first
+    assert_eq!(indices, [0, 0, 0]);
+
+    assert_eq!(combination::count(&[]), 1);
+    assert_eq!(combination::count(&[Slot::new(Vec::new())]), 0);
+    assert_eq!(
+        combination::count(&vec![Slot::new(vec!['a'; 100]); 30]),
+        u128::MAX
+    );
+}
+
+#[test]
+fn test_slots() {
+    let mut slot = Slot::new(vec!['a', 'b']);
+    assert!(!slot.is_wildcard());
+    slot.add_option('c');
+    slot.add_option('a');
+    assert_eq!(slot.options(), ['a', 'b', 'c']);
+
+    let alphabet: BTreeSet<char> = ('a'..='e').collect();
+    assert_eq!(slot.resolved(&alphabet).options(), ['a', 'b', 'c']);
+    assert_eq!(
+        Slot::any().resolved(&alphabet).options(),
+        ['a', 'b', 'c', 'd', 'e']
+    );
+    let excluding = Slot::none_of(vec!['a', 'e']);
+    assert!(excluding.is_wildcard());
+    assert_eq!(excluding.resolved(&alphabet).options(), ['b', 'c', 'd']);
+}
+
+#[test]
+fn test_slot_notation() {
+    let slot: Slot = "[a-c]x".parse().unwrap();
+    assert_eq!(slot.options(), ['a', 'b', 'c', 'x']);
+    assert!("?".parse::<Slot>().unwrap().is_wildcard());
+    assert!("!qz".parse::<Slot>().unwrap().is_wildcard());
+    assert!("".parse::<Slot>().is_err());
+    assert!("[a-".parse::<Slot>().unwrap_err().contains("unclosed '['"));
+}
@@ -1,5 +1,5 @@
 use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
-use gallry_puzzle_soulver::{Slot, WordGenerator};
+use gallry_puzzle_soulver::{Dictionary, LoadOptions, Slot, Strategy, WordGenerator};
 use std::collections::HashSet;
 
 fn generate_benchmark(c: &mut Criterion) {
@@ -91,5 +91,79 @@ fn filter_benchmark(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, generate_benchmark, filter_benchmark);
+fn parallel_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parallel_solving");
+
+    // Six slots of eight options give 262,144 combinations to filter
+    let slots: Vec<Slot> = (0..6)
+        .map(|_| Slot::new("aeiostnr".chars().collect()))
+        .collect();
+    let generator = WordGenerator::with_slots(slots);
+
+    for threads in [1, 2, 4, 8] {
+        group.bench_with_input(
+            BenchmarkId::new("par_matches", format!("threads={}", threads)),
+            &threads,
+            |b, &threads| b.iter(|| generator.par_matches(threads).len()),
+        );
+    }
+
+    group.finish();
+}
+
+fn strategy_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("strategies");
+
+    // Five slots of eight options give 32,768 combinations against the embedded list
+    let slots: Vec<Slot> = (0..5)
+        .map(|_| Slot::new("aeiostnr".chars().collect()))
+        .collect();
+    let mut generator = WordGenerator::with_slots(slots);
+
+    for strategy in Strategy::ALL {
+        generator.set_strategy(Some(strategy));
+        group.bench_function(BenchmarkId::new("iter", strategy), |b| {
+            b.iter(|| generator.iter().count())
+        });
+    }
+
+    group.finish();
+}
+
+fn construction_benchmark(c: &mut Criterion) {
+    // Batch mode and servers build a generator per puzzle over the embedded list
+    c.bench_function("with_slots_embedded", |b| {
+        b.iter(|| WordGenerator::with_slots(vec![Slot::new(vec!['a'])]))
+    });
+}
+
+fn loading_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("loading");
+
+    // The embedded list stands in for a large custom list, loaded and length-indexed
+    let text = include_str!("../data/words.txt");
+    for threads in [1, 2, 4, 8] {
+        let options = LoadOptions {
+            threads,
+            ..Default::default()
+        };
+        group.bench_with_input(
+            BenchmarkId::new("from_text", format!("threads={}", threads)),
+            &options,
+            |b, options| b.iter(|| Dictionary::from_text(text, options).words_of_len(5).count()),
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    generate_benchmark,
+    filter_benchmark,
+    parallel_benchmark,
+    strategy_benchmark,
+    construction_benchmark,
+    loading_benchmark
+);
 criterion_main!(benches);
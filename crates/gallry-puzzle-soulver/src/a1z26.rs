@@ -0,0 +1,212 @@
+//! The A1Z26 cipher, which writes each letter as its place in the alphabet.
+//!
+//! Clues around the house spell words as numbers, with `a` as 1 up to `z` as 26. When
+//! the numbers are run together, or their spacing is unreliable, a string such as
+//! `125` can be read as `1 2 5` ("abe"), `12 5` ("le") or `1 25` ("ay").
+//! [`a1z26_readings`] lists every reading, and [`a1z26_words`] keeps the ones the
+//! dictionary knows.
+
+use crate::Dictionary;
+use anyhow::{Result, bail};
+
+/// Most digits [`a1z26_readings`] accepts, as the number of readings can nearly double
+/// with each digit
+pub const MAX_READING_DIGITS: usize = 32;
+
+/// Returns the letter numbered `number`, from `a` for 1 to `z` for 26.
+fn letter(number: u32) -> Option<char> {
+    (1..=26)
+        .contains(&number)
+        .then(|| char::from(b'a' + number as u8 - 1))
+}
+
+/// Returns the number of a letter, ignoring case, if it is one of `a` to `z`.
+fn number(letter: char) -> Option<u32> {
+    letter
+        .is_ascii_alphabetic()
+        .then(|| u32::from(letter.to_ascii_lowercase() as u8 - b'a') + 1)
+}
+
+/// Writes text as letter numbers.
+///
+/// The numbers of a word are joined with `-` and words are separated by spaces. Other
+/// characters, including letters outside `a` to `z`, are dropped.
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::a1z26_encode;
+///
+/// assert_eq!(a1z26_encode("Blue Prince"), "2-12-21-5 16-18-9-14-3-5");
+/// ```
+pub fn a1z26_encode(text: &str) -> String {
+    text.split_whitespace()
+        .map(|word| {
+            word.chars()
+                .filter_map(number)
+                .map(|number| number.to_string())
+                .collect::<Vec<_>>()
+                .join("-")
+        })
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Reads letter numbers whose separation is known.
+///
+/// When the text contains a `-`, numbers joined by `-` form a word and whitespace
+/// separates words, as written by [`a1z26_encode`]. Otherwise every number separated
+/// by whitespace or commas is a letter of a single word.
+///
+/// # Returns
+///
+/// The decoded text, or an error if a number is not between 1 and 26
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::a1z26_decode;
+///
+/// assert_eq!(a1z26_decode("2-12-21-5 16-18-9-14-3-5").unwrap(), "blue prince");
+/// assert_eq!(a1z26_decode("12 5, 1").unwrap(), "lea");
+/// assert!(a1z26_decode("27").is_err());
+/// ```
+pub fn a1z26_decode(numbers: &str) -> Result<String> {
+    let decode_word = |word: &str, separators: &[char]| -> Result<String> {
+        word.split(separators)
+            .filter(|number| !number.is_empty())
+            .map(|number| {
+                number.parse().ok().and_then(letter).ok_or_else(|| {
+                    anyhow::anyhow!("'{}' is not a letter number from 1 to 26", number)
+                })
+            })
+            .collect()
+    };
+
+    if numbers.contains('-') {
+        let words: Vec<String> = numbers
+            .split_whitespace()
+            .map(|word| decode_word(word, &['-', ',']))
+            .collect::<Result<_>>()?;
+        Ok(words.join(" "))
+    } else {
+        decode_word(numbers, &[' ', '\t', '\n', ','])
+    }
+}
+
+/// Returns the digits of `numbers`, ignoring any separators between them.
+fn digits(numbers: &str) -> Vec<u32> {
+    numbers.chars().filter_map(|c| c.to_digit(10)).collect()
+}
+
+/// Returns every way to read a string of digits as letter numbers.
+///
+/// Separators between the digits are ignored, so `1 2 5` and `12 5` give the same
+/// readings. Each letter takes one digit, or two when they make a number from 10 to
+/// 26; a `0` can only end a two-digit number.
+///
+/// # Returns
+///
+/// The readings in order of their first letters' numbers, or an error if there are
+/// more than [`MAX_READING_DIGITS`] digits
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::a1z26_readings;
+///
+/// assert_eq!(a1z26_readings("1 2 5").unwrap(), ["abe", "ay", "le"]);
+/// assert_eq!(a1z26_readings("2010").unwrap(), ["tj"]);
+/// assert!(a1z26_readings("30").unwrap().is_empty());
+/// ```
+pub fn a1z26_readings(numbers: &str) -> Result<Vec<String>> {
+    let digits = digits(numbers);
+    if digits.len() > MAX_READING_DIGITS {
+        bail!(
+            "{} digits is too many to list every reading, the limit is {}",
+            digits.len(),
+            MAX_READING_DIGITS
+        );
+    }
+
+    let mut readings = Vec::new();
+    read(&digits, &mut String::new(), &mut readings);
+    Ok(readings)
+}
+
+/// Extends `reading` with every way to read the remaining `digits`.
+fn read(digits: &[u32], reading: &mut String, readings: &mut Vec<String>) {
+    if digits.is_empty() {
+        readings.push(reading.clone());
+        return;
+    }
+    for width in 1..=digits.len().min(2) {
+        let number = digits[..width]
+            .iter()
+            .fold(0, |number, digit| number * 10 + digit);
+        // A leading zero would make "05" read the same as "5"
+        let Some(next) = letter(number).filter(|_| digits[0] != 0) else {
+            continue;
+        };
+        reading.push(next);
+        read(&digits[width..], reading, readings);
+        reading.pop();
+    }
+}
+
+/// Returns the dictionary words that a string of digits can be read as.
+///
+/// Separators between the digits are ignored, as by [`a1z26_readings`]. Rather than
+/// listing every reading, each entry of a fitting length is encoded and compared, so
+/// long strings of digits are fine. Entries with characters outside `a` to `z` never
+/// match.
+///
+/// # Returns
+///
+/// The entries as spelled in the dictionary, most frequent first when ranks are known
+/// and alphabetically otherwise
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::{Dictionary, LoadOptions, a1z26_words};
+///
+/// let dictionary = Dictionary::from_text("abe\nle\nlane\n", &LoadOptions::default());
+/// assert_eq!(a1z26_words("12 5", &dictionary), ["abe", "le"]);
+/// assert_eq!(a1z26_words("121145", &dictionary), ["lane"]);
+/// ```
+pub fn a1z26_words<'a>(numbers: &str, dictionary: &'a Dictionary) -> Vec<&'a str> {
+    let digits = digits(numbers);
+    // Each letter takes one or two digits
+    let lengths = digits.len().div_ceil(2)..=digits.len();
+
+    let mut words: Vec<_> = dictionary
+        .iter()
+        .filter(|(word, _)| lengths.contains(&word.chars().count()))
+        .filter(|(word, _)| encodes_to(word, &digits))
+        .collect();
+    words.sort_by(|(a, a_info), (b, b_info)| {
+        (a_info.rank.is_none(), a_info.rank, a).cmp(&(b_info.rank.is_none(), b_info.rank, b))
+    });
+    words.into_iter().map(|(word, _)| word).collect()
+}
+
+/// Returns `true` if the letter numbers of `word` run together into `digits`.
+fn encodes_to(word: &str, mut digits: &[u32]) -> bool {
+    for c in word.chars() {
+        let Some(number) = number(c) else {
+            return false;
+        };
+        let written: &[u32] = if number < 10 {
+            &[number]
+        } else {
+            &[number / 10, number % 10]
+        };
+        match digits.strip_prefix(written) {
+            Some(rest) => digits = rest,
+            None => return false,
+        }
+    }
+    digits.is_empty()
+}
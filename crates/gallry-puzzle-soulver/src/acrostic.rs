@@ -0,0 +1,119 @@
+//! Acrostics, where one letter taken from each of several words spells the answer.
+//!
+//! Many clues around the house come down to "take the first letters of these
+//! things". The letters are taken from each line at the same position, then either
+//! read in order or, when the order of the things is unknown, treated as a bag of
+//! letters whose anagrams are solved like any other puzzle.
+
+use crate::{Constraint, Slot, WordGenerator};
+use anyhow::{Result, bail};
+
+/// How the letters of an [`Acrostic`] make the answer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum AcrosticMode {
+    /// The letters spell the answer in the order of the lines
+    #[default]
+    Sequence,
+    /// The letters spell the answer in some order
+    Bag,
+}
+
+/// The letters taken from one position of each line.
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::{
+///     Acrostic, AcrosticMode, Dictionary, LoadOptions, WordGenerator,
+/// };
+///
+/// let acrostic = Acrostic::extract(&["Candle", "Atlas", "Torch"], 0).unwrap();
+/// assert_eq!(acrostic.letters(), "cat");
+///
+/// let dictionary = Dictionary::from_text("act\ncat\ntac\n", &LoadOptions::default());
+/// let mut generator = WordGenerator::with_dictionary(Vec::new(), dictionary);
+/// acrostic.apply(&mut generator, AcrosticMode::Bag);
+/// assert_eq!(generator.iter().collect::<Vec<_>>(), ["act", "cat", "tac"]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Acrostic {
+    /// The letter taken from each line, in lowercase
+    letters: Vec<char>,
+}
+
+impl Acrostic {
+    /// Takes the letter at `position` of each line.
+    ///
+    /// Only letters are counted, so spaces and punctuation are skipped, and blank lines
+    /// are ignored.
+    ///
+    /// # Parameters
+    ///
+    /// * `lines` - The words or lines to take letters from
+    /// * `position` - The zero-based position of the letter in each line
+    ///
+    /// # Returns
+    ///
+    /// The acrostic, or an error if a line has too few letters or there are no lines
+    pub fn extract<S: AsRef<str>>(lines: &[S], position: usize) -> Result<Self> {
+        let mut letters = Vec::new();
+        for (number, line) in lines.iter().map(AsRef::as_ref).enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let Some(letter) = line.chars().filter(|c| c.is_alphabetic()).nth(position) else {
+                bail!(
+                    "Line {} '{}' has no letter {}",
+                    number + 1,
+                    line,
+                    position + 1
+                );
+            };
+            letters.extend(letter.to_lowercase());
+        }
+        if letters.is_empty() {
+            bail!("No lines to take letters from");
+        }
+        Ok(Acrostic { letters })
+    }
+
+    /// Returns the letters in the order of the lines.
+    pub fn letters(&self) -> String {
+        self.letters.iter().collect()
+    }
+
+    /// Returns slots for the answer.
+    ///
+    /// In sequence each slot holds the letter of its line. As a bag every slot holds
+    /// any of the letters, which [`constraint`](Self::constraint) narrows to
+    /// rearrangements.
+    pub fn slots(&self, mode: AcrosticMode) -> Vec<Slot> {
+        match mode {
+            AcrosticMode::Sequence => self
+                .letters
+                .iter()
+                .map(|&letter| Slot::new(vec![letter]))
+                .collect(),
+            AcrosticMode::Bag => {
+                let mut distinct = self.letters.clone();
+                distinct.sort_unstable();
+                distinct.dedup();
+                vec![Slot::new(distinct); self.letters.len()]
+            }
+        }
+    }
+
+    /// Returns the constraint the slots of `mode` need, if any.
+    pub fn constraint(&self, mode: AcrosticMode) -> Option<Constraint> {
+        (mode == AcrosticMode::Bag).then(|| Constraint::AnagramOf(self.letters()))
+    }
+
+    /// Sets a generator's slots to solve for the answer, adding any constraint they
+    /// need to its own.
+    pub fn apply(&self, generator: &mut WordGenerator, mode: AcrosticMode) {
+        generator.set_slots(self.slots(mode));
+        if let Some(constraint) = self.constraint(mode) {
+            generator.add_constraint(constraint);
+        }
+    }
+}
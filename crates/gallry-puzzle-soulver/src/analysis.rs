@@ -0,0 +1,145 @@
+//! Statistical analysis of dictionary contents.
+//!
+//! These helpers summarize how letters are distributed across the words of a given
+//! length, which helps decide which interpretation of an ambiguous clue is likely.
+
+use crate::Dictionary;
+use std::collections::BTreeMap;
+
+/// Letter counts for every position among dictionary words of one length.
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::{Dictionary, LoadOptions};
+///
+/// let dictionary = Dictionary::from_text("cat\ncot\ncut\nbat\n", &LoadOptions::default());
+/// let stats = dictionary.positional_frequencies(3);
+///
+/// assert_eq!(stats.total_words(), 4);
+/// assert_eq!(stats.count(0, 'c'), 3);
+/// assert_eq!(stats.frequency(0, 'c'), 0.75);
+/// assert_eq!(stats.ranked(1)[0], ('a', 2));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct PositionalFrequencies {
+    /// Number of words the counts were taken from
+    total_words: usize,
+    /// Per-position letter counts
+    counts: Vec<BTreeMap<char, usize>>,
+}
+
+impl PositionalFrequencies {
+    /// Counts the letters at every position among the dictionary's words of `word_len`.
+    pub fn from_dictionary(dictionary: &Dictionary, word_len: usize) -> Self {
+        let mut stats = Self {
+            total_words: 0,
+            counts: vec![BTreeMap::new(); word_len],
+        };
+
+        for word in dictionary.words_of_len(word_len) {
+            stats.total_words += 1;
+            for (position, c) in word.chars().enumerate() {
+                *stats.counts[position].entry(c).or_default() += 1;
+            }
+        }
+        stats
+    }
+
+    /// Returns the word length the statistics were computed for.
+    pub fn word_len(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// Returns the number of words the statistics were computed from.
+    pub fn total_words(&self) -> usize {
+        self.total_words
+    }
+
+    /// Returns how many words have `letter` at `position`.
+    pub fn count(&self, position: usize, letter: char) -> usize {
+        self.counts
+            .get(position)
+            .and_then(|counts| counts.get(&letter))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Returns the fraction of words with `letter` at `position`, from 0.0 to 1.0.
+    pub fn frequency(&self, position: usize, letter: char) -> f64 {
+        if self.total_words == 0 {
+            return 0.0;
+        }
+        self.count(position, letter) as f64 / self.total_words as f64
+    }
+
+    /// Returns the letter counts at `position`, most frequent first.
+    ///
+    /// Letters with equal counts are ordered alphabetically.
+    pub fn ranked(&self, position: usize) -> Vec<(char, usize)> {
+        let mut ranked: Vec<(char, usize)> = self
+            .counts
+            .get(position)
+            .map(|counts| counts.iter().map(|(c, n)| (*c, *n)).collect())
+            .unwrap_or_default();
+        ranked.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        ranked
+    }
+
+    /// Ranks a slot's candidate letters at `position` by how often they occur there.
+    ///
+    /// This orders the interpretations of an ambiguous position from most to least
+    /// statistically likely; letters that never occur there get a frequency of 0.0.
+    ///
+    /// # Parameters
+    ///
+    /// * `position` - The zero-based position of the slot
+    /// * `options` - The candidate letters for the slot
+    pub fn rank_options(&self, position: usize, options: &[char]) -> Vec<(char, f64)> {
+        let mut ranked: Vec<(char, f64)> = options
+            .iter()
+            .map(|c| (*c, self.frequency(position, *c)))
+            .collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+        ranked
+    }
+
+    /// Scores how typical a word's letters are for their positions, from 0.0 to 1.0.
+    ///
+    /// The score is the geometric mean of the frequency of each letter at its position,
+    /// so a single letter never seen at its position scores the whole word 0.0. Words
+    /// of a different length than the statistics also score 0.0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gallry_puzzle_soulver::{Dictionary, LoadOptions};
+    ///
+    /// let dictionary = Dictionary::from_text("cat\ncot\ncut\nbat\n", &LoadOptions::default());
+    /// let stats = dictionary.positional_frequencies(3);
+    ///
+    /// assert!(stats.score("cat") > stats.score("bot"));
+    /// assert_eq!(stats.score("dog"), 0.0);
+    /// ```
+    pub fn score(&self, word: &str) -> f64 {
+        if word.chars().count() != self.word_len() || self.word_len() == 0 {
+            return 0.0;
+        }
+
+        let log_sum: f64 = word
+            .chars()
+            .enumerate()
+            .map(|(position, c)| self.frequency(position, c).ln())
+            .sum();
+        (log_sum / self.word_len() as f64).exp()
+    }
+}
+
+impl Dictionary {
+    /// Computes per-position letter frequencies among entries of `word_len`.
+    ///
+    /// See [`PositionalFrequencies`] for the available statistics.
+    pub fn positional_frequencies(&self, word_len: usize) -> PositionalFrequencies {
+        PositionalFrequencies::from_dictionary(self, word_len)
+    }
+}
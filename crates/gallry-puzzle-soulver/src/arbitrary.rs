@@ -0,0 +1,110 @@
+//! Random puzzles for property tests, behind the `proptest-support` feature.
+//!
+//! The generated puzzles are ones a user could have written: slots of a few lowercase
+//! letters, constraints over the same letters, and no files to read, so every
+//! [`PuzzleConfig`] builds a generator with [`to_generator`](PuzzleConfig::to_generator).
+
+use crate::{Constraint, DictionaryPolicy, NumberTarget, PuzzleConfig, SequenceOrder};
+use proptest::arbitrary::Arbitrary;
+use proptest::collection::{SizeRange, btree_set, vec};
+use proptest::prelude::*;
+
+pub use gallry_core::arbitrary::{letter, notation, slots};
+
+/// Returns a strategy for a short run of lowercase letters.
+fn text(length: impl Into<SizeRange>) -> impl Strategy<Value = String> {
+    vec(letter(), length).prop_map(String::from_iter)
+}
+
+/// Returns a strategy for a number target, any number or a small value.
+fn number_target() -> impl Strategy<Value = NumberTarget> {
+    prop_oneof![
+        Just(NumberTarget::Any),
+        (0..=20u64).prop_map(NumberTarget::Value)
+    ]
+}
+
+/// Returns a strategy for one constraint of any kind.
+///
+/// Texts are one to three letters, rhymes are judged against two- to six-letter words
+/// and regular expressions anchor a few letters to either end of the word.
+pub fn constraint() -> impl Strategy<Value = Constraint> {
+    let pattern = |source: String| Constraint::Regex(source.parse().expect("valid pattern"));
+    prop_oneof![
+        text(1..=3).prop_map(Constraint::Contains),
+        text(1..=2).prop_map(Constraint::StartsWith),
+        text(1..=2).prop_map(Constraint::EndsWith),
+        btree_set(letter(), 1..=3)
+            .prop_map(|letters| Constraint::ExcludesLetters(letters.into_iter().collect())),
+        text(1..=2).prop_map(move |letters| pattern(format!("^{}", letters))),
+        text(1..=2).prop_map(move |letters| pattern(format!("{}$", letters))),
+        text(2..=6).prop_map(Constraint::RhymesWith),
+        (1..=4usize).prop_map(Constraint::Syllables),
+        prop_oneof![
+            Just(SequenceOrder::Increasing),
+            Just(SequenceOrder::Decreasing),
+            Just(SequenceOrder::StrictlyIncreasing),
+            Just(SequenceOrder::StrictlyDecreasing),
+        ]
+        .prop_map(Constraint::Ordered),
+        text(2..=6).prop_map(Constraint::AnagramOf),
+        number_target().prop_map(Constraint::SpelledNumber),
+        number_target().prop_map(Constraint::RomanNumeral),
+        text(1..=3).prop_map(|letters| Constraint::Morse(crate::morse_encode(&letters))),
+        text(2..=5).prop_map(Constraint::RepeatPattern),
+    ]
+}
+
+/// Returns a strategy for puzzle definitions with slots of the given count.
+///
+/// # Parameters
+///
+/// * `slots` - How many slots, such as `3..=7`
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::arbitrary::puzzle;
+/// use gallry_puzzle_soulver::PuzzleConfig;
+/// use proptest::prelude::*;
+///
+/// proptest!(ProptestConfig::with_cases(16), |(config in puzzle(1..=4))| {
+///     let text = config.to_toml_string().unwrap();
+///     prop_assert_eq!(PuzzleConfig::from_toml_str(&text).unwrap(), config);
+/// });
+/// ```
+pub fn puzzle(slots: impl Into<SizeRange>) -> impl Strategy<Value = PuzzleConfig> {
+    let policy = prop_oneof![Just(DictionaryPolicy::Any), Just(DictionaryPolicy::All)];
+    (
+        vec(notation(), slots),
+        vec(constraint(), 0..=2),
+        policy,
+        any::<bool>(),
+    )
+        .prop_map(|(slots, constraints, policy, ignore_case)| PuzzleConfig {
+            slots,
+            constraints,
+            policy,
+            ignore_case,
+            ..Default::default()
+        })
+}
+
+impl Arbitrary for Constraint {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        constraint().boxed()
+    }
+}
+
+impl Arbitrary for PuzzleConfig {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    /// Generates puzzles of one to seven slots; see [`puzzle`] for other sizes.
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        puzzle(1..=7).boxed()
+    }
+}
@@ -0,0 +1,157 @@
+//! A BK-tree for finding words within a small edit distance of a query.
+//!
+//! BK-trees exploit the triangle inequality of the Levenshtein distance: every child
+//! of a node is filed under its distance to that node, so a search only descends
+//! into children whose distance lies within `max_distance` of the query's own.
+
+/// Returns the Levenshtein edit distance between two words, counted in characters.
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::edit_distance;
+///
+/// assert_eq!(edit_distance("kitten", "sitting"), 3);
+/// assert_eq!(edit_distance("cat", "cat"), 0);
+/// ```
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, ca) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+/// A single word in the tree, with children keyed by their distance to it.
+#[derive(Clone, Debug)]
+struct Node {
+    word: String,
+    children: Vec<(usize, usize)>,
+}
+
+/// A metric tree over words supporting nearest-neighbour queries by edit distance.
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::BkTree;
+///
+/// let tree: BkTree = ["cat", "cart", "dog"].into_iter().collect();
+/// let near: Vec<_> = tree.find("cst", 1).into_iter().map(|(word, _)| word).collect();
+/// assert_eq!(near, vec!["cat"]);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct BkTree {
+    /// Node storage; the root is the first node
+    nodes: Vec<Node>,
+}
+
+impl BkTree {
+    /// Creates an empty tree.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a word to the tree. Duplicate words are ignored.
+    pub fn insert(&mut self, word: &str) {
+        if self.nodes.is_empty() {
+            self.nodes.push(Node {
+                word: word.to_string(),
+                children: Vec::new(),
+            });
+            return;
+        }
+
+        let mut current = 0;
+        loop {
+            let distance = edit_distance(word, &self.nodes[current].word);
+            if distance == 0 {
+                return;
+            }
+
+            let child = self.nodes[current]
+                .children
+                .iter()
+                .find(|(child_distance, _)| *child_distance == distance)
+                .map(|(_, child)| *child);
+
+            match child {
+                Some(child) => current = child,
+                None => {
+                    let index = self.nodes.len();
+                    self.nodes.push(Node {
+                        word: word.to_string(),
+                        children: Vec::new(),
+                    });
+                    self.nodes[current].children.push((distance, index));
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Returns every word within `max_distance` edits of `word`.
+    ///
+    /// Results are sorted by distance, then alphabetically.
+    ///
+    /// # Parameters
+    ///
+    /// * `word` - The query word
+    /// * `max_distance` - The largest edit distance to report
+    pub fn find(&self, word: &str, max_distance: usize) -> Vec<(&str, usize)> {
+        let mut results = Vec::new();
+        if self.nodes.is_empty() {
+            return results;
+        }
+
+        let mut pending = vec![0];
+        while let Some(index) = pending.pop() {
+            let node = &self.nodes[index];
+            let distance = edit_distance(word, &node.word);
+            if distance <= max_distance {
+                results.push((node.word.as_str(), distance));
+            }
+
+            let low = distance.saturating_sub(max_distance);
+            let high = distance + max_distance;
+            pending.extend(
+                node.children
+                    .iter()
+                    .filter(|(child_distance, _)| (low..=high).contains(child_distance))
+                    .map(|(_, child)| *child),
+            );
+        }
+
+        results.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(b.0)));
+        results
+    }
+
+    /// Returns the number of words in the tree.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns `true` if the tree has no words.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+}
+
+impl<'a> FromIterator<&'a str> for BkTree {
+    fn from_iter<I: IntoIterator<Item = &'a str>>(iter: I) -> Self {
+        let mut tree = Self::new();
+        for word in iter {
+            tree.insert(word);
+        }
+        tree
+    }
+}
@@ -0,0 +1,99 @@
+//! Suppression of unwanted words from generated output.
+//!
+//! A [`Blocklist`] is checked against every word before it is yielded, regardless of
+//! whether dictionary filtering is enabled. The crate ships a list of offensive words
+//! behind the `profanity-filter` feature, and user-supplied lists can be merged in.
+
+use crate::short::ShortString;
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+
+// Embed the offensive word list at compile time
+#[cfg(feature = "profanity-filter")]
+const EMBEDDED_BLOCKLIST: &str = include_str!("../data/blocklist.txt");
+
+/// A set of words that must never appear in output.
+///
+/// Comparisons ignore case, so blocking `"darn"` also blocks `"Darn"`.
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::Blocklist;
+///
+/// let blocklist = Blocklist::from_text("darn\nheck\n");
+/// assert!(blocklist.blocks("Darn"));
+/// assert!(!blocklist.blocks("dart"));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Blocklist {
+    /// Blocked words, stored in lowercase
+    words: HashSet<String>,
+}
+
+impl Blocklist {
+    /// Creates an empty blocklist that blocks nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the offensive word list embedded in the crate at compile time.
+    #[cfg(feature = "profanity-filter")]
+    pub fn embedded() -> Self {
+        Self::from_text(EMBEDDED_BLOCKLIST)
+    }
+
+    /// Parses a blocklist with one word per line.
+    ///
+    /// Blank lines and lines starting with `#` are ignored.
+    pub fn from_text(text: &str) -> Self {
+        let mut blocklist = Self::new();
+        for line in text.lines() {
+            let word = line.trim();
+            if !word.is_empty() && !word.starts_with('#') {
+                blocklist.insert(word);
+            }
+        }
+        blocklist
+    }
+
+    /// Reads and parses a blocklist file with one word per line.
+    ///
+    /// # Parameters
+    ///
+    /// * `path` - Path to the blocklist file
+    ///
+    /// # Returns
+    ///
+    /// The parsed blocklist, or an error if the file could not be read
+    pub fn from_path(path: &str) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .context(format!("Failed to read blocklist from {}", path))?;
+        Ok(Self::from_text(&content))
+    }
+
+    /// Adds a single word to the blocklist.
+    pub fn insert(&mut self, word: &str) {
+        self.words.insert(word.to_lowercase());
+    }
+
+    /// Adds every word from another blocklist.
+    pub fn merge(&mut self, other: Blocklist) {
+        self.words.extend(other.words);
+    }
+
+    /// Returns `true` if the word must be suppressed.
+    pub fn blocks(&self, word: &str) -> bool {
+        !self.words.is_empty() && self.words.contains(&*ShortString::lowercase(word))
+    }
+
+    /// Returns the number of blocked words.
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Returns `true` if nothing is blocked.
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+}
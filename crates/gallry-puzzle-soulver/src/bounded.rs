@@ -0,0 +1,105 @@
+//! Collecting matches within a memory budget.
+//!
+//! A puzzle of wildcards over a large word list can match hundreds of thousands of
+//! words, which is more than a server or a browser tab should hold for one request.
+//! Bounded collection stops at a count or an approximate size in bytes and says which
+//! bound cut the results short, so callers can report that rather than run out of memory.
+
+use crate::{WordGenerator, WordMatch};
+use std::mem::size_of;
+
+/// Which bound stopped [`collect_bounded`](WordGenerator::collect_bounded) early.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Truncation {
+    /// More matches remained after `max_results` were collected
+    Results,
+    /// The next match would have taken the collected matches past `max_bytes`
+    Bytes,
+}
+
+/// Matches collected by [`collect_bounded`](WordGenerator::collect_bounded).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BoundedMatches {
+    /// The matches that fit within the bounds, in solve order
+    pub matches: Vec<WordMatch>,
+    /// Which bound was reached, or `None` if these are all the matches
+    pub truncated: Option<Truncation>,
+}
+
+impl BoundedMatches {
+    /// Returns `true` if matches were left out to stay within the bounds.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated.is_some()
+    }
+}
+
+impl WordGenerator {
+    /// Returns the matches in solve order, stopping at `max_results` matches or once
+    /// they would take more than about `max_bytes` of memory.
+    ///
+    /// A match is counted as its own size plus the text it holds: the word, any stem
+    /// and dictionary spelling, and the names of the dictionaries containing it. When
+    /// `max_results` matches are collected, the solve continues only until one more
+    /// match is found, to tell whether the results are complete.
+    ///
+    /// # Parameters
+    ///
+    /// * `max_results` - Most matches to collect
+    /// * `max_bytes` - Most bytes the collected matches may take, approximately
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gallry_puzzle_soulver::{Slot, Truncation, WordGenerator};
+    ///
+    /// let generator = WordGenerator::with_no_filtering(vec![
+    ///     Slot::new(vec!['c', 'b', 'r']),
+    ///     Slot::new(vec!['a', 'o']),
+    /// ]);
+    ///
+    /// let bounded = generator.collect_bounded(4, usize::MAX);
+    /// assert_eq!(bounded.matches.len(), 4);
+    /// assert_eq!(bounded.truncated, Some(Truncation::Results));
+    ///
+    /// let bounded = generator.collect_bounded(10, usize::MAX);
+    /// assert_eq!(bounded.matches.len(), 6);
+    /// assert!(!bounded.is_truncated());
+    /// ```
+    pub fn collect_bounded(&self, max_results: usize, max_bytes: usize) -> BoundedMatches {
+        let mut matches = Vec::new();
+        let mut bytes = 0usize;
+        for word_match in self.iter_matches() {
+            if matches.len() == max_results {
+                return BoundedMatches {
+                    matches,
+                    truncated: Some(Truncation::Results),
+                };
+            }
+            bytes = bytes.saturating_add(footprint(&word_match));
+            if bytes > max_bytes {
+                trace_event!(debug, matches = matches.len(), "stopped at the byte limit");
+                return BoundedMatches {
+                    matches,
+                    truncated: Some(Truncation::Bytes),
+                };
+            }
+            matches.push(word_match);
+        }
+
+        BoundedMatches {
+            matches,
+            truncated: None,
+        }
+    }
+}
+
+/// Returns the approximate memory a match takes, including the text it holds.
+fn footprint(word_match: &WordMatch) -> usize {
+    let text = [&word_match.stem, &word_match.entry]
+        .into_iter()
+        .flatten()
+        .chain(&word_match.dictionaries)
+        .map(String::len)
+        .sum::<usize>();
+    size_of::<WordMatch>() + word_match.word.len() + text
+}
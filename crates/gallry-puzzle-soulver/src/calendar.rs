@@ -0,0 +1,359 @@
+//! Reading dates as letters.
+//!
+//! Clues often hide a word in a list of dates: the initial of each month, the letter
+//! numbered by each day, or the initial of each weekday. A [`CalendarDate`] gives each
+//! of these readings, described by a [`DateLetter`], and [`date_slots`] turns a list
+//! of dates into slots holding the letters of the chosen readings, to be solved like
+//! any other puzzle.
+
+use crate::Slot;
+use anyhow::{Result, bail};
+use std::fmt;
+use std::str::FromStr;
+
+/// Names of the months, January first
+pub const MONTH_NAMES: [&str; 12] = [
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+
+/// Names of the weekdays, Monday first
+pub const WEEKDAY_NAMES: [&str; 7] = [
+    "monday",
+    "tuesday",
+    "wednesday",
+    "thursday",
+    "friday",
+    "saturday",
+    "sunday",
+];
+
+/// Returns `true` if `year` has a February 29 in the Gregorian calendar.
+fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+/// Returns the number of days in a month, counting February 29 when `leap`.
+fn days_in_month(month: u32, leap: bool) -> u32 {
+    match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Returns the letter numbered `number` from `a` for 1 to `z` for 26.
+fn ordinal_letter(number: u32) -> Option<char> {
+    (1..=26)
+        .contains(&number)
+        .then(|| char::from(b'a' + number as u8 - 1))
+}
+
+/// A day of the Gregorian calendar, with or without its year.
+///
+/// Parsed from `YYYY-MM-DD` or `MM-DD`, or from a month name or its first three
+/// letters with a day and an optional year, in either order, such as `March 5`,
+/// `5 Mar` or `Mar 5, 1923`.
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::{CalendarDate, DateLetter};
+///
+/// let date: CalendarDate = "1923-03-05".parse().unwrap();
+/// assert_eq!(date.month_name(), "march");
+/// assert_eq!(date.weekday_name(), Some("monday"));
+/// assert_eq!(date.day_of_year(), 64);
+/// assert_eq!(date.letter(DateLetter::DayOfMonth), Some('e'));
+/// assert_eq!("Mar 5".parse::<CalendarDate>().unwrap().weekday_name(), None);
+/// assert!("1923-02-29".parse::<CalendarDate>().is_err());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CalendarDate {
+    /// The year, if known
+    year: Option<i32>,
+    /// The month from 1 for January
+    month: u32,
+    /// The day of the month from 1
+    day: u32,
+}
+
+impl CalendarDate {
+    /// Creates a date, checking that the day exists in the month.
+    ///
+    /// Without a year, February 29 is allowed.
+    ///
+    /// # Parameters
+    ///
+    /// * `year` - The year, if known
+    /// * `month` - The month from 1 for January to 12
+    /// * `day` - The day of the month from 1
+    pub fn new(year: Option<i32>, month: u32, day: u32) -> Result<Self> {
+        if !(1..=12).contains(&month) {
+            bail!("Month {} is not between 1 and 12", month);
+        }
+        let leap = year.is_none_or(is_leap_year);
+        if day == 0 || day > days_in_month(month, leap) {
+            bail!(
+                "{} has no day {}{}",
+                title_case(MONTH_NAMES[month as usize - 1]),
+                day,
+                year.map(|year| format!(" in {}", year)).unwrap_or_default()
+            );
+        }
+        Ok(CalendarDate { year, month, day })
+    }
+
+    /// Returns the year, if known.
+    pub fn year(&self) -> Option<i32> {
+        self.year
+    }
+
+    /// Returns the month from 1 for January to 12.
+    pub fn month(&self) -> u32 {
+        self.month
+    }
+
+    /// Returns the day of the month from 1.
+    pub fn day(&self) -> u32 {
+        self.day
+    }
+
+    /// Returns the name of the month in lowercase.
+    pub fn month_name(&self) -> &'static str {
+        MONTH_NAMES[self.month as usize - 1]
+    }
+
+    /// Returns the day of the year from 1 for January 1.
+    ///
+    /// Without a year, a common year is assumed, so March 1 is day 60.
+    pub fn day_of_year(&self) -> u32 {
+        let leap = self.year.is_some_and(is_leap_year);
+        (1..self.month)
+            .map(|month| days_in_month(month, leap))
+            .sum::<u32>()
+            + self.day
+    }
+
+    /// Returns the day of the week from 0 for Monday to 6 for Sunday, if the year is
+    /// known.
+    pub fn weekday(&self) -> Option<u32> {
+        // Sakamoto's method, which counts from 0 for Sunday
+        const OFFSETS: [i32; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+        let year = self.year? - i32::from(self.month < 3);
+        let sunday_first = (year + year.div_euclid(4) - year.div_euclid(100)
+            + year.div_euclid(400)
+            + OFFSETS[self.month as usize - 1]
+            + self.day as i32)
+            .rem_euclid(7);
+        Some((sunday_first as u32 + 6) % 7)
+    }
+
+    /// Returns the name of the weekday in lowercase, if the year is known.
+    pub fn weekday_name(&self) -> Option<&'static str> {
+        self.weekday()
+            .map(|weekday| WEEKDAY_NAMES[weekday as usize])
+    }
+
+    /// Returns the letter the date stands for under one reading, if it has one.
+    pub fn letter(&self, reading: DateLetter) -> Option<char> {
+        let initial = |name: &str| name.chars().next();
+        match reading {
+            DateLetter::MonthInitial => initial(self.month_name()),
+            DateLetter::MonthNumber => ordinal_letter(self.month),
+            DateLetter::DayOfMonth => ordinal_letter(self.day),
+            DateLetter::DayOfYear => ordinal_letter((self.day_of_year() - 1) % 26 + 1),
+            DateLetter::WeekdayInitial => self.weekday_name().and_then(initial),
+        }
+    }
+}
+
+/// Returns a lowercase name with its first letter capitalized.
+fn title_case(name: &str) -> String {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+/// Returns the month numbered by a name or its first three letters, ignoring case.
+fn month_number(name: &str) -> Option<u32> {
+    let name = name.to_lowercase();
+    let name = name.trim_end_matches('.');
+    if name.len() < 3 {
+        return None;
+    }
+    MONTH_NAMES
+        .iter()
+        .position(|month| month.starts_with(name))
+        .map(|index| index as u32 + 1)
+}
+
+impl FromStr for CalendarDate {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let invalid = || {
+            format!(
+                "invalid date '{}', expected e.g. 1923-03-05, 03-05 or March 5",
+                s
+            )
+        };
+        let number = |part: &str| part.parse::<i64>().map_err(|_| invalid());
+
+        let parts: Vec<&str> = s
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|part| !part.is_empty())
+            .collect();
+        let (year, month, day) = match parts.as_slice() {
+            [numeric] => match numeric.split('-').collect::<Vec<_>>().as_slice() {
+                [year, month, day] => (Some(number(year)?), number(month)?, number(day)?),
+                [month, day] => (None, number(month)?, number(day)?),
+                _ => return Err(invalid()),
+            },
+            [first, second, rest @ ..] if rest.len() <= 1 => {
+                let year = rest.first().map(|year| number(year)).transpose()?;
+                match (month_number(first), month_number(second)) {
+                    (Some(month), None) => (year, i64::from(month), number(second)?),
+                    (None, Some(month)) => (year, i64::from(month), number(first)?),
+                    _ => return Err(invalid()),
+                }
+            }
+            _ => return Err(invalid()),
+        };
+
+        let year = year
+            .map(|year| i32::try_from(year).map_err(|_| invalid()))
+            .transpose()?;
+        let month = u32::try_from(month).map_err(|_| invalid())?;
+        let day = u32::try_from(day).map_err(|_| invalid())?;
+        CalendarDate::new(year, month, day).map_err(|error| error.to_string())
+    }
+}
+
+impl fmt::Display for CalendarDate {
+    /// Writes the date as `YYYY-MM-DD`, or `MM-DD` without a year.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(year) = self.year {
+            write!(f, "{:04}-", year)?;
+        }
+        write!(f, "{:02}-{:02}", self.month, self.day)
+    }
+}
+
+/// A way of reading a date as a letter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum DateLetter {
+    /// The initial of the month, such as `m` for March or May
+    MonthInitial,
+    /// The letter numbered by the month, such as `c` for March
+    MonthNumber,
+    /// The letter numbered by the day of the month, for days 1 to 26
+    DayOfMonth,
+    /// The letter numbered by the day of the year, counting on from `a` again after
+    /// each `z`, so day 27 is `a`
+    DayOfYear,
+    /// The initial of the weekday, for dates with a year
+    WeekdayInitial,
+}
+
+impl DateLetter {
+    /// Every reading, in the order they are listed.
+    pub const ALL: [DateLetter; 5] = [
+        DateLetter::MonthInitial,
+        DateLetter::MonthNumber,
+        DateLetter::DayOfMonth,
+        DateLetter::DayOfYear,
+        DateLetter::WeekdayInitial,
+    ];
+}
+
+impl FromStr for DateLetter {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "month-initial" => Ok(DateLetter::MonthInitial),
+            "month-number" => Ok(DateLetter::MonthNumber),
+            "day-of-month" => Ok(DateLetter::DayOfMonth),
+            "day-of-year" => Ok(DateLetter::DayOfYear),
+            "weekday-initial" => Ok(DateLetter::WeekdayInitial),
+            _ => Err(format!(
+                "unknown reading '{}', expected month-initial, month-number, day-of-month, \
+                 day-of-year or weekday-initial",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for DateLetter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DateLetter::MonthInitial => "month-initial",
+            DateLetter::MonthNumber => "month-number",
+            DateLetter::DayOfMonth => "day-of-month",
+            DateLetter::DayOfYear => "day-of-year",
+            DateLetter::WeekdayInitial => "weekday-initial",
+        })
+    }
+}
+
+/// Returns one slot per date, holding its letters under each of the readings.
+///
+/// When it is unclear how the dates encode the answer, giving several readings lets
+/// the dictionary decide between them.
+///
+/// # Returns
+///
+/// The slots, or an error naming a date none of the readings turn into a letter
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::{CalendarDate, DateLetter, Dictionary, LoadOptions};
+/// use gallry_puzzle_soulver::{WordGenerator, date_slots};
+///
+/// let dates: Vec<CalendarDate> = ["Mar 15", "Apr 1", "Jun 25"]
+///     .iter()
+///     .map(|date| date.parse().unwrap())
+///     .collect();
+/// let slots = date_slots(&dates, &[DateLetter::MonthInitial, DateLetter::DayOfMonth]).unwrap();
+/// assert_eq!(slots[0].options(), ['m', 'o']);
+///
+/// let dictionary = Dictionary::from_text("may\nmoa\noak\n", &LoadOptions::default());
+/// let generator = WordGenerator::with_dictionary(slots, dictionary);
+/// assert_eq!(generator.iter().collect::<Vec<_>>(), ["may"]);
+/// ```
+pub fn date_slots(dates: &[CalendarDate], readings: &[DateLetter]) -> Result<Vec<Slot>> {
+    dates
+        .iter()
+        .map(|date| {
+            let mut letters: Vec<char> = readings
+                .iter()
+                .filter_map(|&reading| date.letter(reading))
+                .collect();
+            letters.sort_unstable();
+            letters.dedup();
+            if letters.is_empty() {
+                bail!(
+                    "Date {} stands for no letter under the chosen readings",
+                    date
+                );
+            }
+            Ok(Slot::new(letters))
+        })
+        .collect()
+}
@@ -0,0 +1,116 @@
+//! The order and form in which a generator checks its constraints.
+//!
+//! Constraints are checked cheapest first. When several of them look for substrings,
+//! required texts from [`Contains`](Constraint::Contains) and forbidden letters from
+//! [`ExcludesLetters`](Constraint::ExcludesLetters), they are compiled into one
+//! Aho-Corasick automaton, so a candidate is scanned once rather than once for each.
+
+use crate::Constraint;
+use aho_corasick::AhoCorasick;
+use smallvec::SmallVec;
+
+/// Required texts tracked on the stack before the set spills to the heap
+const INLINE_REQUIRED: usize = 8;
+
+/// One step of checking a candidate against the constraints.
+#[derive(Clone, Debug)]
+pub(crate) enum Check {
+    /// Check the constraint at this index on its own
+    Constraint(usize),
+    /// Check every substring constraint at once
+    Substrings(SubstringSet),
+}
+
+/// Substring constraints compiled into one automaton.
+#[derive(Clone, Debug)]
+pub(crate) struct SubstringSet {
+    /// Matches the required texts, then the forbidden letters
+    automaton: AhoCorasick,
+    /// How many of the automaton's patterns are required texts
+    required: usize,
+}
+
+impl SubstringSet {
+    /// Returns `true` if `word` contains every required text and no forbidden letter.
+    pub(crate) fn accepts(&self, word: &str) -> bool {
+        let mut found: SmallVec<[bool; INLINE_REQUIRED]> =
+            SmallVec::from_elem(false, self.required);
+        let mut missing = self.required;
+        for found_match in self.automaton.find_overlapping_iter(word) {
+            let pattern = found_match.pattern().as_usize();
+            if pattern >= self.required {
+                return false;
+            }
+            if !found[pattern] {
+                found[pattern] = true;
+                missing -= 1;
+            }
+        }
+        missing == 0
+    }
+}
+
+/// Returns the checks for the constraints, cheapest first.
+///
+/// Constraints of equal cost keep the order they were added in, and substring
+/// constraints are combined when there are at least two of them.
+pub(crate) fn plan(constraints: &[Constraint]) -> Vec<Check> {
+    let is_substring = |constraint: &Constraint| {
+        matches!(
+            constraint,
+            Constraint::Contains(_) | Constraint::ExcludesLetters(_)
+        )
+    };
+    let substrings: Vec<&Constraint> = constraints.iter().filter(|c| is_substring(c)).collect();
+    let combined = substrings.len() >= 2;
+
+    let mut order: Vec<usize> = (0..constraints.len())
+        .filter(|&index| !(combined && is_substring(&constraints[index])))
+        .collect();
+    order.sort_by_key(|&index| constraints[index].cost());
+    let mut checks: Vec<(u32, Check)> = order
+        .into_iter()
+        .map(|index| (constraints[index].cost(), Check::Constraint(index)))
+        .collect();
+
+    if combined {
+        let cost = substrings
+            .iter()
+            .map(|c| c.cost())
+            .min()
+            .unwrap_or_default();
+        let position = checks.partition_point(|(other, _)| *other <= cost);
+        checks.insert(position, (cost, Check::Substrings(compile(&substrings))));
+    }
+    checks.into_iter().map(|(_, check)| check).collect()
+}
+
+/// Compiles substring constraints into one automaton.
+fn compile(constraints: &[&Constraint]) -> SubstringSet {
+    let mut required: Vec<&str> = Vec::new();
+    let mut forbidden: Vec<String> = Vec::new();
+    for constraint in constraints {
+        match constraint {
+            Constraint::Contains(text) if !required.contains(&text.as_str()) => {
+                required.push(text);
+            }
+            Constraint::ExcludesLetters(letters) => {
+                for letter in letters.chars().map(String::from) {
+                    if !forbidden.contains(&letter) {
+                        forbidden.push(letter);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let patterns = required
+        .iter()
+        .copied()
+        .chain(forbidden.iter().map(String::as_str));
+    SubstringSet {
+        automaton: AhoCorasick::new(patterns).expect("substring patterns are small"),
+        required: required.len(),
+    }
+}
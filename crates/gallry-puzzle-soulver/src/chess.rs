@@ -0,0 +1,319 @@
+//! Letters read off a board by chess coordinates or knight moves.
+//!
+//! Chess-flavored clues put a letter on each square of a board and then either name
+//! squares in algebraic notation, such as `e4`, or hint that the answer is spelled by
+//! a knight hopping from square to square. Named squares become slots, with `?` in a
+//! coordinate standing for any file or rank, and knight paths are searched against
+//! the dictionary directly, reporting the squares each word was read from.
+
+use crate::grid::word_prefixes;
+use crate::{Dictionary, LetterGrid, Slot};
+use anyhow::{Result, bail};
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+/// The eight moves of a knight, as file and rank steps
+pub const KNIGHT_MOVES: [(isize, isize); 8] = [
+    (1, 2),
+    (2, 1),
+    (2, -1),
+    (1, -2),
+    (-1, -2),
+    (-2, -1),
+    (-2, 1),
+    (-1, 2),
+];
+
+/// A square of a board in algebraic notation, such as `e4`.
+///
+/// Files are letters from `a` at the left and ranks numbers from `1` at the bottom,
+/// so on a [`LetterGrid`] rank 1 is the last row.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Square {
+    /// The column from 0 for file `a`
+    pub file: usize,
+    /// The row from 0 for rank 1, counting up the board
+    pub rank: usize,
+}
+
+impl Square {
+    /// Returns the grid row and column of the square, if it is on the grid.
+    fn cell(self, grid: &LetterGrid) -> Option<(usize, usize)> {
+        (self.file < grid.width() && self.rank < grid.height())
+            .then(|| (grid.height() - 1 - self.rank, self.file))
+    }
+
+    /// Returns the letter on the square, if it is on the grid.
+    pub fn letter(self, grid: &LetterGrid) -> Option<char> {
+        let (row, column) = self.cell(grid)?;
+        grid.get(row, column)
+    }
+
+    /// Returns the square a step away, if it is on the grid.
+    fn step(self, grid: &LetterGrid, (files, ranks): (isize, isize)) -> Option<Square> {
+        let square = Square {
+            file: self.file.checked_add_signed(files)?,
+            rank: self.rank.checked_add_signed(ranks)?,
+        };
+        square.cell(grid).map(|_| square)
+    }
+}
+
+impl FromStr for Square {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let invalid = || format!("invalid square '{}', expected e.g. e4", s);
+        let mut chars = s.chars();
+        let file = chars
+            .next()
+            .filter(char::is_ascii_alphabetic)
+            .ok_or_else(invalid)?;
+        let rank: usize = chars.as_str().parse().map_err(|_| invalid())?;
+        if rank == 0 {
+            return Err(invalid());
+        }
+        Ok(Square {
+            file: usize::from(file.to_ascii_lowercase() as u8 - b'a'),
+            rank: rank - 1,
+        })
+    }
+}
+
+impl fmt::Display for Square {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", char::from(b'a' + self.file as u8), self.rank + 1)
+    }
+}
+
+/// Returns one slot per coordinate, holding the letter on its square.
+///
+/// A `?` in place of the file or rank, as in `e?` or `?4`, stands for every square of
+/// that rank or file, so the slot holds each of their letters.
+///
+/// # Returns
+///
+/// The slots, or an error naming a coordinate that is not on the board
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::{LetterGrid, chess_slots};
+///
+/// let board = LetterGrid::from_text("kno\nwig\nhat\n").unwrap();
+/// let slots = chess_slots(&board, &["a1", "b2", "c3"]).unwrap();
+/// assert_eq!(slots[0].options(), ['h']);
+/// assert_eq!(slots[2].options(), ['o']);
+/// assert_eq!(chess_slots(&board, &["c?"]).unwrap()[0].options(), ['g', 'o', 't']);
+/// assert!(chess_slots(&board, &["d1"]).is_err());
+/// ```
+pub fn chess_slots<S: AsRef<str>>(grid: &LetterGrid, coordinates: &[S]) -> Result<Vec<Slot>> {
+    coordinates
+        .iter()
+        .map(|coordinate| {
+            let mut letters = Vec::new();
+            for square in squares_of(grid, coordinate.as_ref())? {
+                let Some(letter) = square.letter(grid) else {
+                    bail!(
+                        "Square {} is off the {}x{} board",
+                        square,
+                        grid.width(),
+                        grid.height()
+                    );
+                };
+                letters.push(letter);
+            }
+            letters.sort_unstable();
+            letters.dedup();
+            Ok(Slot::new(letters))
+        })
+        .collect()
+}
+
+/// Returns the squares a coordinate names, expanding a `?` file or rank to the whole
+/// board.
+fn squares_of(grid: &LetterGrid, coordinate: &str) -> Result<Vec<Square>> {
+    let invalid = || anyhow::anyhow!("Invalid square '{}', expected e.g. e4 or e?", coordinate);
+    let mut chars = coordinate.chars();
+    let files: Vec<usize> = match chars.next() {
+        Some('?') => (0..grid.width()).collect(),
+        Some(file) if file.is_ascii_alphabetic() => {
+            vec![usize::from(file.to_ascii_lowercase() as u8 - b'a')]
+        }
+        _ => return Err(invalid()),
+    };
+    let ranks: Vec<usize> = match chars.as_str() {
+        "?" => (0..grid.height()).collect(),
+        rank => match rank.parse::<usize>() {
+            Ok(rank) if rank > 0 => vec![rank - 1],
+            _ => return Err(invalid()),
+        },
+    };
+    Ok(files
+        .iter()
+        .flat_map(|&file| ranks.iter().map(move |&rank| Square { file, rank }))
+        .collect())
+}
+
+/// A word spelled by a knight's path over a board.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KnightPath {
+    /// The letters of the squares visited, in order
+    pub word: String,
+    /// The squares visited, starting square first
+    pub squares: Vec<Square>,
+}
+
+/// Finds the dictionary words of `length` letters a knight spells hopping over a
+/// board, never landing on a square twice.
+///
+/// Paths are cut short as soon as their letters stop being the start of a dictionary
+/// word, so long words on large boards stay quick to search.
+///
+/// # Parameters
+///
+/// * `grid` - The letters on the board
+/// * `start` - The square the knight starts on, or `None` to try every square
+/// * `length` - The number of letters, counting the starting square
+/// * `dictionary` - The words to look for, compared in lowercase
+///
+/// # Returns
+///
+/// Every path spelling a word, sorted by word and then by squares
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::{Dictionary, LetterGrid, LoadOptions, knight_words};
+///
+/// let board = LetterGrid::from_text("kno\nwig\nhat\n").unwrap();
+/// let dictionary = Dictionary::from_text("two\ntow\nhit\n", &LoadOptions::default());
+/// let paths = knight_words(&board, None, 3, &dictionary);
+/// assert_eq!(paths.len(), 1);
+/// assert_eq!(paths[0].word, "two");
+/// assert_eq!(paths[0].squares[1].to_string(), "a2");
+/// ```
+pub fn knight_words(
+    grid: &LetterGrid,
+    start: Option<Square>,
+    length: usize,
+    dictionary: &Dictionary,
+) -> Vec<KnightPath> {
+    let words: HashSet<String> = dictionary
+        .words_of_len(length)
+        .map(str::to_lowercase)
+        .collect();
+    let prefixes = word_prefixes(&words);
+
+    let mut search = KnightSearch {
+        grid,
+        length,
+        words: &words,
+        prefixes: &prefixes,
+        path: Vec::new(),
+        found: Vec::new(),
+    };
+    if length > 0 {
+        let starts = start.map_or_else(|| all_squares(grid), |square| vec![square]);
+        for square in starts {
+            if square.letter(grid).is_some() {
+                search.extend(square);
+            }
+        }
+    }
+    let mut found = search.found;
+    found.sort_by(|a, b| a.word.cmp(&b.word).then(a.squares.cmp(&b.squares)));
+    found
+}
+
+/// Returns every square of a board.
+fn all_squares(grid: &LetterGrid) -> Vec<Square> {
+    (0..grid.height())
+        .flat_map(|rank| (0..grid.width()).map(move |file| Square { file, rank }))
+        .collect()
+}
+
+/// The state of a search for words along knight's paths.
+struct KnightSearch<'a> {
+    /// The letters on the board
+    grid: &'a LetterGrid,
+    /// The number of squares in a path
+    length: usize,
+    /// Lowercase dictionary words of that length
+    words: &'a HashSet<String>,
+    /// Every proper beginning of those words
+    prefixes: &'a HashSet<&'a str>,
+    /// The squares visited so far
+    path: Vec<Square>,
+    /// Complete paths spelling a word
+    found: Vec<KnightPath>,
+}
+
+impl KnightSearch<'_> {
+    /// Adds a square to the path and follows every move from it, recording the
+    /// complete paths that spell a word.
+    fn extend(&mut self, square: Square) {
+        self.path.push(square);
+        let word: String = self
+            .path
+            .iter()
+            .filter_map(|square| square.letter(self.grid))
+            .collect();
+        if self.path.len() == self.length {
+            if self.words.contains(&word) {
+                self.found.push(KnightPath {
+                    word,
+                    squares: self.path.clone(),
+                });
+            }
+        } else if self.prefixes.contains(word.as_str()) {
+            for knight_move in KNIGHT_MOVES {
+                if let Some(next) = square.step(self.grid, knight_move)
+                    && !self.path.contains(&next)
+                {
+                    self.extend(next);
+                }
+            }
+        }
+        self.path.pop();
+    }
+}
+
+/// Returns slots for a knight's path of `length` squares, each holding the letters
+/// the knight could be on at that step.
+///
+/// The slots ignore which squares were already visited and how the steps join up, so
+/// the words they spell still need checking against the board, for instance with
+/// [`knight_words`].
+///
+/// # Parameters
+///
+/// * `grid` - The letters on the board
+/// * `start` - The square the knight starts on, or `None` for any square
+/// * `length` - The number of letters, counting the starting square
+pub fn knight_slots(grid: &LetterGrid, start: Option<Square>, length: usize) -> Vec<Slot> {
+    let mut squares: HashSet<Square> = match start {
+        Some(square) => HashSet::from([square]),
+        None => all_squares(grid).into_iter().collect(),
+    };
+    let mut slots = Vec::with_capacity(length);
+    for _ in 0..length {
+        let mut letters: Vec<char> = squares
+            .iter()
+            .filter_map(|square| square.letter(grid))
+            .collect();
+        letters.sort_unstable();
+        letters.dedup();
+        slots.push(Slot::new(letters));
+        squares = squares
+            .iter()
+            .flat_map(|square| {
+                KNIGHT_MOVES
+                    .iter()
+                    .filter_map(|&knight_move| square.step(grid, knight_move))
+            })
+            .collect();
+    }
+    slots
+}
@@ -0,0 +1,372 @@
+//! Breaking the shift ciphers found on notes and walls around the house.
+//!
+//! A Caesar cipher shifts every letter a fixed number of places along the alphabet,
+//! so there are only 26 keys to try. A Vigenère cipher shifts each letter by the next
+//! letter of a repeating key, which is broken one key length at a time by finding the
+//! best Caesar shift of every column of letters sharing a key letter. Each candidate
+//! plaintext is scored by how much of it the dictionary recognizes as words and by how
+//! closely its letters follow English letter frequencies, which still tells keys apart
+//! when the text has no spaces.
+
+use crate::Dictionary;
+use std::cmp::Ordering;
+
+/// Relative frequencies of `a` to `z` in English text, in percent
+pub const LETTER_FREQUENCIES: [f64; 26] = [
+    8.17, 1.49, 2.78, 4.25, 12.70, 2.23, 2.02, 6.09, 6.97, 0.15, 0.77, 4.03, 2.41, 6.75, 7.51,
+    1.93, 0.10, 5.99, 6.33, 9.06, 2.76, 0.98, 2.36, 0.15, 1.97, 0.07,
+];
+
+/// Shifts each letter of `text` forward `shift` places, wrapping from `z` to `a`.
+///
+/// Case is kept and other characters are left alone. Shifting by `26 - shift` undoes
+/// a shift, and a shift of 13 is ROT13, which undoes itself.
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::caesar_shift;
+///
+/// assert_eq!(caesar_shift("Blue Prince!", 3), "Eoxh Sulqfh!");
+/// assert_eq!(caesar_shift("Eoxh Sulqfh!", 23), "Blue Prince!");
+/// ```
+pub fn caesar_shift(text: &str, shift: u8) -> String {
+    text.chars().map(|c| shift_letter(c, shift)).collect()
+}
+
+/// Shifts one letter forward `shift` places, keeping its case.
+pub(crate) fn shift_letter(c: char, shift: u8) -> char {
+    let base = match c {
+        'a'..='z' => b'a',
+        'A'..='Z' => b'A',
+        _ => return c,
+    };
+    char::from(base + (c as u8 - base + shift % 26) % 26)
+}
+
+/// How English-like a candidate plaintext is, from [`score_plaintext`].
+///
+/// Candidates compare by coverage first, so real words win over a merely plausible
+/// spread of letters, and by letter fit when coverage ties.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PlaintextScore {
+    /// Share of the letters, from 0 to 1, that are in words the dictionary contains
+    pub coverage: f64,
+    /// Mean base-10 logarithm of the English frequency of each letter; around -1.2
+    /// for English text and lower for gibberish
+    pub letter_fit: f64,
+}
+
+impl PlaintextScore {
+    /// Orders scores from least to most English-like.
+    pub fn compare(&self, other: &Self) -> Ordering {
+        self.coverage
+            .total_cmp(&other.coverage)
+            .then(self.letter_fit.total_cmp(&other.letter_fit))
+    }
+}
+
+/// Scores how English-like a candidate plaintext is.
+///
+/// Words are runs of letters, looked up in lowercase. Text without letters scores zero
+/// coverage and the lowest possible fit.
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::{Dictionary, LoadOptions, score_plaintext};
+///
+/// let dictionary = Dictionary::from_text("the\nkey\n", &LoadOptions::default());
+/// let score = score_plaintext("The key is here", &dictionary);
+/// assert_eq!(score.coverage, 0.5);
+/// assert!(score.compare(&score_plaintext("Wkh nhb lv khuh", &dictionary)).is_gt());
+/// ```
+pub fn score_plaintext(text: &str, dictionary: &Dictionary) -> PlaintextScore {
+    let mut letters = 0;
+    let mut covered = 0;
+    for word in text.split(|c: char| !c.is_ascii_alphabetic()) {
+        if word.is_empty() {
+            continue;
+        }
+        letters += word.len();
+        if dictionary.contains(&word.to_ascii_lowercase()) {
+            covered += word.len();
+        }
+    }
+    PlaintextScore {
+        coverage: if letters == 0 {
+            0.0
+        } else {
+            covered as f64 / letters as f64
+        },
+        letter_fit: letter_fit(text),
+    }
+}
+
+/// Returns the mean base-10 logarithm of the English frequency of each letter in
+/// `text`, or negative infinity if it has no letters.
+pub(crate) fn letter_fit(text: &str) -> f64 {
+    let (sum, letters) = text
+        .chars()
+        .filter(char::is_ascii_alphabetic)
+        .map(|c| {
+            let index = usize::from(c.to_ascii_lowercase() as u8 - b'a');
+            (LETTER_FREQUENCIES[index] / 100.0).log10()
+        })
+        .fold((0.0, 0), |(sum, letters), log| (sum + log, letters + 1));
+    if letters == 0 {
+        f64::NEG_INFINITY
+    } else {
+        sum / f64::from(letters)
+    }
+}
+
+/// One way to read a Caesar ciphertext, from [`caesar_decryptions`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct CaesarDecryption {
+    /// Places each letter was shifted forward when enciphering
+    pub shift: u8,
+    /// The ciphertext shifted back by `shift`
+    pub plaintext: String,
+    /// How English-like the plaintext is
+    pub score: PlaintextScore,
+}
+
+/// Tries every Caesar shift of `ciphertext` and ranks the plaintexts.
+///
+/// # Returns
+///
+/// All 26 decryptions, most English-like first, with ties broken by smaller shift
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::{Dictionary, LoadOptions, caesar_decryptions};
+///
+/// let dictionary = Dictionary::from_text("look\nbehind\nthe\nclock\n", &LoadOptions::default());
+/// let best = &caesar_decryptions("Orrn ehklqg wkh forfn", &dictionary)[0];
+/// assert_eq!(best.shift, 3);
+/// assert_eq!(best.plaintext, "Look behind the clock");
+/// assert_eq!(best.score.coverage, 1.0);
+/// ```
+pub fn caesar_decryptions(ciphertext: &str, dictionary: &Dictionary) -> Vec<CaesarDecryption> {
+    let mut decryptions: Vec<CaesarDecryption> = (0..26)
+        .map(|shift| {
+            let plaintext = caesar_shift(ciphertext, 26 - shift);
+            CaesarDecryption {
+                shift,
+                score: score_plaintext(&plaintext, dictionary),
+                plaintext,
+            }
+        })
+        .collect();
+    decryptions.sort_by(|a, b| b.score.compare(&a.score).then(a.shift.cmp(&b.shift)));
+    decryptions
+}
+
+/// Options for [`vigenere_decryptions`].
+#[derive(Clone, Debug)]
+pub struct VigenereOptions {
+    /// Longest key to break by letter frequencies
+    pub max_key_len: usize,
+    /// Whether every dictionary word no longer than `max_key_len` is tried as a key too
+    ///
+    /// This finds short keys in texts too short for letter frequencies to reveal, at
+    /// the cost of a decryption per word.
+    pub word_keys: bool,
+    /// Most decryptions to return
+    pub max_results: usize,
+}
+
+impl Default for VigenereOptions {
+    fn default() -> Self {
+        VigenereOptions {
+            max_key_len: 8,
+            word_keys: false,
+            max_results: 10,
+        }
+    }
+}
+
+/// One way to read a Vigenère ciphertext, from [`vigenere_decryptions`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct VigenereDecryption {
+    /// The key, in lowercase letters
+    pub key: String,
+    /// The ciphertext deciphered with `key`
+    pub plaintext: String,
+    /// How English-like the plaintext is
+    pub score: PlaintextScore,
+}
+
+/// Returns the shifts of a key's letters, or `None` if it has no letters or a
+/// character outside `a` to `z`.
+fn key_shifts(key: &str) -> Option<Vec<u8>> {
+    let shifts: Option<Vec<u8>> = key
+        .chars()
+        .map(|c| {
+            c.is_ascii_alphabetic()
+                .then(|| c.to_ascii_lowercase() as u8 - b'a')
+        })
+        .collect();
+    shifts.filter(|shifts| !shifts.is_empty())
+}
+
+/// Shifts each letter of `text` by the next of `shifts`, forward or back.
+///
+/// Only letters use up a shift, so spaces and punctuation keep the key aligned with
+/// the letters as a person enciphering by hand would.
+fn apply_key(text: &str, shifts: &[u8], forward: bool) -> String {
+    let mut next = shifts.iter().cycle();
+    text.chars()
+        .map(|c| {
+            if !c.is_ascii_alphabetic() {
+                return c;
+            }
+            let shift = *next.next().expect("the key is never empty");
+            shift_letter(c, if forward { shift } else { 26 - shift })
+        })
+        .collect()
+}
+
+/// Enciphers `text` with a Vigenère key, shifting each letter forward by the next
+/// letter of the key, where `a` shifts by 0.
+///
+/// # Returns
+///
+/// The ciphertext, or `None` if the key is empty or has a character outside `a` to `z`
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::{vigenere_decrypt, vigenere_encrypt};
+///
+/// let ciphertext = vigenere_encrypt("Attack at dawn", "lemon").unwrap();
+/// assert_eq!(ciphertext, "Lxfopv ef rnhr");
+/// assert_eq!(vigenere_decrypt(&ciphertext, "LEMON").unwrap(), "Attack at dawn");
+/// assert_eq!(vigenere_encrypt("text", "no key"), None);
+/// ```
+pub fn vigenere_encrypt(text: &str, key: &str) -> Option<String> {
+    Some(apply_key(text, &key_shifts(key)?, true))
+}
+
+/// Deciphers `ciphertext` with a Vigenère key, undoing [`vigenere_encrypt`].
+///
+/// # Returns
+///
+/// The plaintext, or `None` if the key is empty or has a character outside `a` to `z`
+pub fn vigenere_decrypt(ciphertext: &str, key: &str) -> Option<String> {
+    Some(apply_key(ciphertext, &key_shifts(key)?, false))
+}
+
+/// Searches for the Vigenère keys that best decipher `ciphertext` and ranks the
+/// plaintexts.
+///
+/// For every key length up to [`max_key_len`](VigenereOptions::max_key_len), each key
+/// letter is chosen by the Caesar shift that gives its column of letters the most
+/// English-like frequencies. This needs a few dozen letters per column to be
+/// reliable; with [`word_keys`](VigenereOptions::word_keys) dictionary words are tried
+/// as keys as well. Keys that repeat a shorter key are left out.
+///
+/// # Returns
+///
+/// The best decryptions, most English-like first, with ties broken by shorter key
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::{
+///     Dictionary, LoadOptions, VigenereOptions, vigenere_decryptions, vigenere_encrypt,
+/// };
+///
+/// let words = "lamp\nthe\nkey\nis\nin\nvase\n";
+/// let dictionary = Dictionary::from_text(words, &LoadOptions::default());
+/// let ciphertext = vigenere_encrypt("The key is in the vase", "lamp").unwrap();
+/// let options = VigenereOptions {
+///     word_keys: true,
+///     ..Default::default()
+/// };
+/// let best = &vigenere_decryptions(&ciphertext, &dictionary, &options)[0];
+/// assert_eq!(best.key, "lamp");
+/// assert_eq!(best.plaintext, "The key is in the vase");
+/// ```
+pub fn vigenere_decryptions(
+    ciphertext: &str,
+    dictionary: &Dictionary,
+    options: &VigenereOptions,
+) -> Vec<VigenereDecryption> {
+    let letters: Vec<u8> = ciphertext
+        .chars()
+        .filter(char::is_ascii_alphabetic)
+        .map(|c| c.to_ascii_lowercase() as u8 - b'a')
+        .collect();
+    if letters.is_empty() {
+        return Vec::new();
+    }
+
+    let mut keys: Vec<Vec<u8>> = (1..=options.max_key_len.min(letters.len()))
+        .map(|len| {
+            (0..len)
+                .map(|column| best_shift(&letters, column, len))
+                .collect()
+        })
+        .collect();
+    if options.word_keys {
+        keys.extend(
+            dictionary
+                .iter()
+                .map(|(word, _)| word)
+                .filter(|word| word.len() <= options.max_key_len)
+                .filter_map(key_shifts),
+        );
+    }
+    keys.retain(|key| !repeats_shorter(key));
+    keys.sort();
+    keys.dedup();
+
+    let mut decryptions: Vec<VigenereDecryption> = keys
+        .into_iter()
+        .map(|shifts| {
+            let plaintext = apply_key(ciphertext, &shifts, false);
+            VigenereDecryption {
+                key: shifts
+                    .iter()
+                    .map(|&shift| char::from(b'a' + shift))
+                    .collect(),
+                score: score_plaintext(&plaintext, dictionary),
+                plaintext,
+            }
+        })
+        .collect();
+    decryptions.sort_by(|a, b| {
+        b.score
+            .compare(&a.score)
+            .then(a.key.len().cmp(&b.key.len()))
+            .then(a.key.cmp(&b.key))
+    });
+    decryptions.truncate(options.max_results);
+    decryptions
+}
+
+/// Returns the shift of the key letter at `column` of a key of `len` letters that
+/// makes that column's letters most English-like.
+fn best_shift(letters: &[u8], column: usize, len: usize) -> u8 {
+    let fit = |shift: u8| -> f64 {
+        letters
+            .iter()
+            .skip(column)
+            .step_by(len)
+            .map(|&letter| LETTER_FREQUENCIES[usize::from((letter + 26 - shift) % 26)].log10())
+            .sum()
+    };
+    (0..26)
+        .max_by(|&a, &b| fit(a).total_cmp(&fit(b)).then(b.cmp(&a)))
+        .unwrap_or(0)
+}
+
+/// Returns `true` if the key is a shorter key written out more than once.
+fn repeats_shorter(key: &[u8]) -> bool {
+    (1..key.len())
+        .filter(|len| key.len().is_multiple_of(*len))
+        .any(|len| key.chunks(len).all(|chunk| chunk == &key[..len]))
+}
@@ -0,0 +1,120 @@
+//! Terminal colors for text output, selected with `--color`.
+//!
+//! Colors are decided once per run and read wherever text is formatted. With `auto`
+//! they are used only when standard output is a terminal and `NO_COLOR` is unset.
+
+use std::fmt;
+use std::io::IsTerminal;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Letters from slots with several options
+const AMBIGUOUS: &str = "\x1b[1;33m";
+/// Annotations such as stems and dictionary names
+const MUTED: &str = "\x1b[2m";
+const RESET: &str = "\x1b[0m";
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// When to color output.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorChoice {
+    /// Color when writing to a terminal and `NO_COLOR` is unset
+    #[default]
+    Auto,
+    /// Always color
+    Always,
+    /// Never color
+    Never,
+}
+
+impl FromStr for ColorChoice {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(ColorChoice::Auto),
+            "always" => Ok(ColorChoice::Always),
+            "never" => Ok(ColorChoice::Never),
+            _ => Err(format!(
+                "unknown color choice '{}', expected auto, always or never",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ColorChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ColorChoice::Auto => "auto",
+            ColorChoice::Always => "always",
+            ColorChoice::Never => "never",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Decides whether this run's output is colored.
+pub fn init(choice: ColorChoice) {
+    let enabled = match choice {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => {
+            std::env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+                && std::io::stdout().is_terminal()
+        }
+    };
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns `true` if output should be colored.
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Colors the letters of `word` that came from ambiguous slots.
+///
+/// `ambiguous` holds one flag per slot. Words whose length does not match the slots,
+/// such as dictionary spellings that normalize differently, are returned unchanged.
+pub fn highlight(word: &str, ambiguous: &[bool]) -> String {
+    if !enabled() || word.chars().count() != ambiguous.len() {
+        return word.to_string();
+    }
+
+    let mut out = String::new();
+    for (c, &ambiguous) in word.chars().zip(ambiguous) {
+        if ambiguous {
+            out.push_str(AMBIGUOUS);
+            out.push(c);
+            out.push_str(RESET);
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Returns how many characters of `text` are visible, ignoring color codes.
+pub fn visible_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // Color codes run from the escape to the closing 'm'
+            chars.by_ref().find(|&c| c == 'm');
+        } else {
+            width += 1;
+        }
+    }
+    width
+}
+
+/// Dims an annotation printed next to a word.
+pub fn muted(text: &str) -> String {
+    if enabled() {
+        format!("{}{}{}", MUTED, text, RESET)
+    } else {
+        text.to_string()
+    }
+}
@@ -0,0 +1,58 @@
+//! The `a1z26` subcommand, reading letter numbers such as `12 9 2 18 1 18 25` as words.
+
+use super::load_dictionary;
+use anyhow::Result;
+use argh::{ArgsInfo, FromArgs};
+use gallry_puzzle_soulver::{a1z26_encode, a1z26_readings, a1z26_words};
+
+/// Read numbers as letters, 1 for a up to 26 for z, listing the words they can spell
+#[derive(ArgsInfo, FromArgs)]
+#[argh(
+    subcommand,
+    name = "a1z26",
+    example = "gallery-puzzle-soulver a1z26 12 9 2 18 1 18 25",
+    example = "gallery-puzzle-soulver a1z26 --all 1225",
+    example = "gallery-puzzle-soulver a1z26 --encode blue prince",
+    note = "Spacing between the numbers is ignored, so 1225 and '12 2 5' give the same \
+            words."
+)]
+pub struct A1z26Args {
+    /// the numbers to read, or the text to write with --encode
+    #[argh(positional)]
+    numbers: Vec<String>,
+
+    /// write text as letter numbers instead of reading numbers
+    #[argh(switch)]
+    encode: bool,
+
+    /// list every reading of the numbers, not only dictionary words
+    #[argh(switch)]
+    all: bool,
+
+    /// word list to check readings against instead of the embedded one
+    #[argh(option, short = 'w')]
+    word_list: Option<String>,
+}
+
+/// Runs the `a1z26` subcommand, returning how many readings were listed.
+pub fn run(args: A1z26Args) -> Result<usize> {
+    let input = args.numbers.join(" ");
+    if args.encode {
+        println!("{}", a1z26_encode(&input));
+        return Ok(1);
+    }
+
+    let readings = if args.all {
+        a1z26_readings(&input)?
+    } else {
+        let dictionary = load_dictionary(args.word_list.as_deref())?;
+        a1z26_words(&input, &dictionary)
+            .into_iter()
+            .map(String::from)
+            .collect()
+    };
+    for reading in &readings {
+        println!("{}", reading);
+    }
+    Ok(readings.len())
+}
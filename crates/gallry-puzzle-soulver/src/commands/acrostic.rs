@@ -0,0 +1,57 @@
+//! The `acrostic` subcommand, solving for the word spelled by a letter of each line.
+
+use super::load_dictionary;
+use anyhow::Result;
+use argh::{ArgsInfo, FromArgs};
+use gallry_puzzle_soulver::{Acrostic, AcrosticMode, WordGenerator};
+
+/// Take a letter from each word or line and list the dictionary words they spell
+#[derive(ArgsInfo, FromArgs)]
+#[argh(
+    subcommand,
+    name = "acrostic",
+    example = "gallery-puzzle-soulver acrostic candle atlas torch",
+    example = "gallery-puzzle-soulver acrostic --nth 2 --bag 'stone' 'ivory' 'ebony'",
+    note = "Only letters are counted, so '--nth 2' of 'a clock' is 'c'."
+)]
+pub struct AcrosticArgs {
+    /// the words or lines to take letters from
+    #[argh(positional)]
+    lines: Vec<String>,
+
+    /// which letter of each line to take, counting from 1 (default: 1)
+    #[argh(option, default = "1")]
+    nth: usize,
+
+    /// allow the letters in any order, for when the order of the lines is unknown
+    #[argh(switch)]
+    bag: bool,
+
+    /// word list to check answers against instead of the embedded one
+    #[argh(option, short = 'w')]
+    word_list: Option<String>,
+}
+
+/// Runs the `acrostic` subcommand, returning how many words were found.
+pub fn run(args: AcrosticArgs) -> Result<usize> {
+    let Some(position) = args.nth.checked_sub(1) else {
+        anyhow::bail!("--nth counts from 1");
+    };
+    let acrostic = Acrostic::extract(&args.lines, position)?;
+    let mode = if args.bag {
+        AcrosticMode::Bag
+    } else {
+        AcrosticMode::Sequence
+    };
+
+    let dictionary = load_dictionary(args.word_list.as_deref())?;
+    let mut generator = WordGenerator::with_dictionary(Vec::new(), dictionary);
+    acrostic.apply(&mut generator, mode);
+    println!("letters: {}", acrostic.letters());
+    let mut found = 0;
+    for word in generator.iter() {
+        println!("{}", word);
+        found += 1;
+    }
+    Ok(found)
+}
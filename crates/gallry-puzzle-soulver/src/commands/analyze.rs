@@ -0,0 +1,60 @@
+//! The `analyze` subcommand.
+
+use super::{char_sets, load_dictionary};
+use anyhow::Result;
+use argh::{ArgsInfo, FromArgs};
+use gallry_puzzle_soulver::{WordGenerator, parse_slots};
+
+/// Rank each slot's options by how often they occur at that position in the word list
+#[derive(ArgsInfo, FromArgs)]
+#[argh(subcommand, name = "analyze")]
+pub struct AnalyzeArgs {
+    /// character sets for each position (e.g., ABC DEF GHI), with ? for any letter,
+    /// [a-f] ranges, !xyz for any letter but these, and \ escapes
+    #[argh(positional)]
+    char_sets: Vec<String>,
+
+    /// read character sets from a file, one per line with # comments, or - for stdin
+    #[argh(option)]
+    slots_file: Option<String>,
+
+    /// separator between the sets when the puzzle is one argument, like cbr/aio/tse
+    #[argh(option, default = "'/'")]
+    separator: char,
+
+    /// word list to take statistics from instead of the embedded one
+    #[argh(option, short = 'w')]
+    word_list: Option<String>,
+}
+
+/// Runs the `analyze` subcommand.
+pub fn run(args: AnalyzeArgs) -> Result<()> {
+    let char_sets = char_sets(&args.char_sets, args.slots_file.as_deref(), args.separator)?;
+    if char_sets.is_empty() {
+        anyhow::bail!("You must provide at least one character set");
+    }
+
+    let generator = WordGenerator::with_dictionary(
+        parse_slots(&char_sets)?,
+        load_dictionary(args.word_list.as_deref())?,
+    );
+    let dictionary = &generator.dictionaries()[0];
+    let stats = dictionary.positional_frequencies(char_sets.len());
+    println!(
+        "{} words of length {} in {}",
+        stats.total_words(),
+        stats.word_len(),
+        dictionary.name()
+    );
+
+    // Wildcards are ranked over every letter of the word list
+    for (position, slot) in generator.slots().iter().enumerate() {
+        let ranked: Vec<String> = stats
+            .rank_options(position, slot.options())
+            .into_iter()
+            .map(|(c, frequency)| format!("{} {:.3}", c, frequency))
+            .collect();
+        println!("{}: {}", position + 1, ranked.join(", "));
+    }
+    Ok(())
+}
@@ -0,0 +1,95 @@
+//! The `bee` subcommand, solving Spelling Bee style puzzles.
+
+use super::load_dictionary;
+use anyhow::Result;
+use argh::{ArgsInfo, FromArgs};
+use gallry_puzzle_soulver::Constraint;
+use std::collections::BTreeSet;
+
+/// Bonus points for a word using every letter
+const PANGRAM_BONUS: usize = 7;
+
+/// List the words spelled from seven letters that use the center one, pangrams first
+#[derive(ArgsInfo, FromArgs)]
+#[argh(
+    subcommand,
+    name = "bee",
+    example = "gallery-puzzle-soulver bee t cnoeia"
+)]
+pub struct BeeArgs {
+    /// the center letter, which every word must use
+    #[argh(positional)]
+    center: char,
+
+    /// the other letters, each usable any number of times
+    #[argh(positional)]
+    letters: String,
+
+    /// shortest word accepted (default: 4)
+    #[argh(option, default = "4")]
+    min_length: usize,
+
+    /// word list to search instead of the embedded one
+    #[argh(option, short = 'w')]
+    word_list: Option<String>,
+}
+
+/// Runs the `bee` subcommand.
+pub fn run(args: BeeArgs) -> Result<()> {
+    let center = args.center.to_lowercase().next().unwrap_or(args.center);
+    let mut letters: BTreeSet<char> = args.letters.to_lowercase().chars().collect();
+    letters.insert(center);
+    if let Some(c) = letters.iter().find(|c| !c.is_alphabetic()) {
+        anyhow::bail!("'{}' is not a letter", c);
+    }
+
+    // The center letter is the same requirement solving expresses as a constraint
+    let required = Constraint::Contains(center.to_string());
+    let dictionary = load_dictionary(args.word_list.as_deref())?;
+    let mut words: Vec<(&str, usize, bool)> = dictionary
+        .iter()
+        .map(|(word, _)| word)
+        .filter(|word| word.chars().count() >= args.min_length && required.accepts(word))
+        .filter(|word| word.chars().all(|c| letters.contains(&c)))
+        .map(|word| {
+            let pangram = letters.iter().all(|&c| word.contains(c));
+            (word, points(word, pangram), pangram)
+        })
+        .collect();
+    words.sort_by(|a, b| {
+        b.2.cmp(&a.2)
+            .then_with(|| b.1.cmp(&a.1))
+            .then_with(|| a.0.cmp(b.0))
+    });
+
+    for (word, points, pangram) in &words {
+        if *pangram {
+            println!("{} {} (pangram)", word, points);
+        } else {
+            println!("{} {}", word, points);
+        }
+    }
+    let pangrams = words.iter().filter(|(_, _, pangram)| *pangram).count();
+    let total: usize = words.iter().map(|(_, points, _)| points).sum();
+    println!(
+        "{} words, {} pangrams, {} points",
+        words.len(),
+        pangrams,
+        total
+    );
+    Ok(())
+}
+
+/// Scores a word: one point for four letters, a point per letter beyond that, and a
+/// bonus for pangrams.
+fn points(word: &str, pangram: bool) -> usize {
+    let score = match word.chars().count() {
+        4 => 1,
+        length => length,
+    };
+    if pangram {
+        score + PANGRAM_BONUS
+    } else {
+        score
+    }
+}
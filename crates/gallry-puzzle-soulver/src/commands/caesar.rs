@@ -0,0 +1,58 @@
+//! The `caesar` subcommand, breaking Caesar and ROT-N ciphers by trying every shift.
+
+use super::load_dictionary;
+use anyhow::Result;
+use argh::{ArgsInfo, FromArgs};
+use gallry_puzzle_soulver::{caesar_decryptions, caesar_shift};
+
+/// Try every Caesar shift of a ciphertext and rank the plaintexts by how English-like
+/// they are
+#[derive(ArgsInfo, FromArgs)]
+#[argh(
+    subcommand,
+    name = "caesar",
+    example = "gallery-puzzle-soulver caesar 'Orrn ehklqg wkh forfn'",
+    example = "gallery-puzzle-soulver caesar --shift 13 'Ybbx oruvaq gur pybpx'",
+    note = "Plaintexts are ranked by the share of their letters in dictionary words, then \
+            by how closely their letters follow English letter frequencies."
+)]
+pub struct CaesarArgs {
+    /// the ciphertext
+    #[argh(positional)]
+    text: Vec<String>,
+
+    /// only shift the text forward this many places, e.g. 13 for ROT13
+    #[argh(option)]
+    shift: Option<u8>,
+
+    /// how many plaintexts to list (default: 5)
+    #[argh(option, default = "5")]
+    show: usize,
+
+    /// word list to score plaintexts against instead of the embedded one
+    #[argh(option, short = 'w')]
+    word_list: Option<String>,
+}
+
+/// Runs the `caesar` subcommand.
+pub fn run(args: CaesarArgs) -> Result<()> {
+    let text = args.text.join(" ");
+    if let Some(shift) = args.shift {
+        println!("{}", caesar_shift(&text, shift));
+        return Ok(());
+    }
+
+    let dictionary = load_dictionary(args.word_list.as_deref())?;
+    for decryption in caesar_decryptions(&text, &dictionary)
+        .iter()
+        .take(args.show)
+    {
+        println!(
+            "ROT-{:<2} {:>3.0}% words  {}",
+            decryption.shift,
+            decryption.score.coverage * 100.0,
+            decryption.plaintext
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,67 @@
+//! The `chess` subcommand, reading letters off a board by coordinates or knight moves.
+
+use super::load_dictionary;
+use anyhow::Result;
+use argh::{ArgsInfo, FromArgs};
+use gallry_puzzle_soulver::{LetterGrid, Square, WordGenerator, chess_slots, knight_words};
+
+/// Read words off a lettered board, by naming squares in chess notation or by
+/// following a knight's moves
+#[derive(ArgsInfo, FromArgs)]
+#[argh(
+    subcommand,
+    name = "chess",
+    example = "gallery-puzzle-soulver chess --board board.txt e4 d5 c?",
+    example = "gallery-puzzle-soulver chess --board board.txt --knight 5 --from b1",
+    note = "The board file holds one row of letters per line, top row first, so file a is \
+            the first column and rank 1 the last line. A ? in a coordinate stands for any \
+            file or rank."
+)]
+pub struct ChessArgs {
+    /// the squares to read, such as e4 d5
+    #[argh(positional)]
+    squares: Vec<String>,
+
+    /// file with the letters of the board, one row per line
+    #[argh(option)]
+    board: String,
+
+    /// find words of this many letters spelled by a knight's path instead
+    #[argh(option)]
+    knight: Option<usize>,
+
+    /// the square the knight starts on (default: any)
+    #[argh(option)]
+    from: Option<Square>,
+
+    /// word list to read against instead of the embedded one
+    #[argh(option, short = 'w')]
+    word_list: Option<String>,
+}
+
+/// Runs the `chess` subcommand, returning how many words were found.
+pub fn run(args: ChessArgs) -> Result<usize> {
+    let board = LetterGrid::from_path(&args.board)?;
+    let dictionary = load_dictionary(args.word_list.as_deref())?;
+
+    if let Some(length) = args.knight {
+        if !args.squares.is_empty() {
+            anyhow::bail!("--knight cannot be combined with squares to read");
+        }
+        let paths = knight_words(&board, args.from, length, &dictionary);
+        for path in &paths {
+            let squares: Vec<String> = path.squares.iter().map(Square::to_string).collect();
+            println!("{}  {}", path.word, squares.join(" "));
+        }
+        return Ok(paths.len());
+    }
+
+    if args.squares.is_empty() {
+        anyhow::bail!("Give the squares to read, such as e4 d5, or --knight LENGTH");
+    }
+    let slots = chess_slots(&board, &args.squares)?;
+    let generator = WordGenerator::with_dictionary(slots, dictionary);
+    let matches: Vec<String> = generator.iter().collect();
+    matches.iter().for_each(|word| println!("{}", word));
+    Ok(matches.len())
+}
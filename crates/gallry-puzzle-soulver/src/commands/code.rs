@@ -0,0 +1,73 @@
+//! The `code` subcommand, listing keypad and safe combinations that fit the clues.
+
+use anyhow::Result;
+use argh::{ArgsInfo, FromArgs};
+use gallry_puzzle_soulver::{Constraint, SequenceOrder, WordGenerator, parse_digit_slots};
+
+/// List the numeric codes allowed by each digit's candidates and clues about the whole
+#[derive(ArgsInfo, FromArgs)]
+#[argh(
+    subcommand,
+    name = "code",
+    example = "gallery-puzzle-soulver code 1357 ? [2-4] --sum 12 --order increasing",
+    example = "gallery-puzzle-soulver code --length 4 --even 1 --order strictly-decreasing"
+)]
+pub struct CodeArgs {
+    /// candidate digits for each position, such as 137, [2-5], !0 or ? for any digit
+    #[argh(positional)]
+    digits: Vec<String>,
+
+    /// number of digits when every one is unknown, instead of giving candidates
+    #[argh(option)]
+    length: Option<usize>,
+
+    /// only show codes whose digits add up to this
+    #[argh(option)]
+    sum: Option<u32>,
+
+    /// only show codes with exactly this many even digits
+    #[argh(option)]
+    even: Option<usize>,
+
+    /// only show codes whose digits are in this order: increasing, decreasing,
+    /// strictly-increasing or strictly-decreasing
+    #[argh(option)]
+    order: Option<SequenceOrder>,
+
+    /// how many codes to list (default: all)
+    #[argh(option)]
+    show: Option<usize>,
+}
+
+/// Runs the `code` subcommand.
+pub fn run(args: CodeArgs) -> Result<usize> {
+    let slots = match (args.digits.is_empty(), args.length) {
+        (false, None) => parse_digit_slots(&args.digits)?,
+        (true, Some(length)) => parse_digit_slots(&vec![String::from("?"); length])?,
+        (false, Some(_)) => anyhow::bail!("--length cannot be combined with candidate digits"),
+        (true, None) => anyhow::bail!("Give the candidate digits of each position, or --length"),
+    };
+
+    let mut generator = WordGenerator::with_no_filtering(slots);
+    let constraints = [
+        args.sum.map(Constraint::DigitSum),
+        args.even.map(Constraint::EvenDigits),
+        args.order.map(Constraint::Ordered),
+    ];
+    constraints
+        .into_iter()
+        .flatten()
+        .for_each(|constraint| generator.add_constraint(constraint));
+
+    let mut found = 0;
+    for code in generator.iter() {
+        if args.show.is_none_or(|show| found < show) {
+            println!("{}", code);
+        }
+        found += 1;
+    }
+    if args.show.is_some_and(|show| found > show) {
+        println!("... {} codes in all", found);
+    }
+    Ok(found)
+}
@@ -0,0 +1,88 @@
+//! The `combos` subcommand.
+
+use super::char_sets;
+use crate::color::{self, ColorChoice};
+use crate::defaults::UserDefaults;
+use crate::output::{self, OutputFormat, ResultOptions};
+use anyhow::Result;
+use argh::{ArgsInfo, FromArgs};
+use gallry_puzzle_soulver::PuzzleConfig;
+use std::cell::Cell;
+use std::time::Instant;
+
+/// Print every combination the slots can spell, without dictionary filtering
+#[derive(ArgsInfo, FromArgs)]
+#[argh(subcommand, name = "combos")]
+pub struct CombosArgs {
+    /// character sets for each position (e.g., ABC DEF GHI), with ? for any letter,
+    /// [a-f] ranges, !xyz for any letter but these, and \ escapes
+    #[argh(positional)]
+    char_sets: Vec<String>,
+
+    /// read character sets from a file, one per line with # comments, or - for stdin
+    #[argh(option)]
+    slots_file: Option<String>,
+
+    /// separator between the sets when the puzzle is one argument, like cbr/aio/tse
+    #[argh(option, default = "'/'")]
+    separator: char,
+
+    /// output format: text (default), json, or ndjson to stream one object per match
+    #[argh(option)]
+    output: Option<OutputFormat>,
+
+    /// stop after this many combinations, without generating the rest
+    #[argh(option)]
+    limit: Option<usize>,
+
+    /// lay text results out in columns across the terminal, like ls
+    #[argh(switch)]
+    columns: bool,
+
+    /// color text output: auto (default; off when NO_COLOR is set), always, or never
+    #[argh(option)]
+    color: Option<ColorChoice>,
+
+    /// end each result with NUL instead of a newline, for xargs -0
+    #[argh(switch, short = '0')]
+    print0: bool,
+
+    /// ignore the user defaults in ~/.config/gallry/config.toml (or $GALLRY_CONFIG)
+    #[argh(switch)]
+    no_config: bool,
+}
+
+/// Runs the `combos` subcommand, returning how many combinations were written.
+pub fn run(args: CombosArgs) -> Result<usize> {
+    let defaults = UserDefaults::load(args.no_config)?;
+    color::init(args.color.or(defaults.color()?).unwrap_or_default());
+    output::set_nul_terminated(args.print0);
+    let config = PuzzleConfig {
+        slots: char_sets(&args.char_sets, args.slots_file.as_deref(), args.separator)?,
+        all_combinations: true,
+        ..Default::default()
+    };
+    if config.slots.is_empty() {
+        anyhow::bail!("You must provide at least one character set");
+    }
+
+    let generator = config.to_generator()?;
+    let started = Instant::now();
+    let options = ResultOptions {
+        limit: args.limit,
+        ..Default::default()
+    };
+    let written = Cell::new(0);
+    let solutions =
+        output::select(&generator, &options)?.inspect(|_| written.set(written.get() + 1));
+    let format = args.output.or(defaults.output()?).unwrap_or_default();
+    if args.columns {
+        if format != OutputFormat::Text || args.print0 {
+            anyhow::bail!("--columns only applies to text output without --print0");
+        }
+        output::write_columns(&generator, solutions);
+    } else {
+        output::write_solutions(format, &generator, solutions, started)?;
+    }
+    Ok(written.get())
+}
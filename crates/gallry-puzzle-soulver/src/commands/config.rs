@@ -0,0 +1,64 @@
+//! The `config` subcommand, for working with puzzle definition files.
+
+use anyhow::Result;
+use argh::{ArgsInfo, FromArgs};
+use gallry_puzzle_soulver::{Constraint, PuzzleConfig};
+
+/// Create or inspect puzzle definition files
+#[derive(ArgsInfo, FromArgs)]
+#[argh(subcommand, name = "config")]
+pub struct ConfigArgs {
+    #[argh(subcommand)]
+    action: ConfigAction,
+}
+
+#[derive(ArgsInfo, FromArgs)]
+#[argh(subcommand)]
+enum ConfigAction {
+    Init(InitArgs),
+    Show(ShowArgs),
+}
+
+/// Print an example puzzle definition to start from
+#[derive(ArgsInfo, FromArgs)]
+#[argh(subcommand, name = "init")]
+struct InitArgs {
+    /// print JSON instead of TOML
+    #[argh(switch)]
+    json: bool,
+}
+
+/// Validate a puzzle definition and print it with every setting spelled out
+#[derive(ArgsInfo, FromArgs)]
+#[argh(subcommand, name = "show")]
+struct ShowArgs {
+    /// path to the puzzle definition
+    #[argh(positional)]
+    path: String,
+
+    /// print JSON instead of TOML
+    #[argh(switch)]
+    json: bool,
+}
+
+/// Runs the `config` subcommand.
+pub fn run(args: ConfigArgs) -> Result<()> {
+    let (config, json) = match args.action {
+        ConfigAction::Init(init) => {
+            let config = PuzzleConfig {
+                slots: vec!["cbr".to_string(), "aio".to_string(), "tse".to_string()],
+                constraints: vec![Constraint::Contains("a".to_string())],
+                ..Default::default()
+            };
+            (config, init.json)
+        }
+        ConfigAction::Show(show) => (PuzzleConfig::from_path(&show.path)?, show.json),
+    };
+
+    if json {
+        println!("{}", config.to_json_string()?);
+    } else {
+        print!("{}", config.to_toml_string()?);
+    }
+    Ok(())
+}
@@ -0,0 +1,75 @@
+//! The `cryptogram` subcommand, solving substitution ciphers against the dictionary.
+
+use super::load_dictionary;
+use anyhow::{Context, Result};
+use argh::{ArgsInfo, FromArgs};
+use gallry_puzzle_soulver::Cryptogram;
+
+/// Solve a substitution cipher, where each letter stands for another throughout, by
+/// fitting dictionary words to the pattern of each ciphertext word
+#[derive(ArgsInfo, FromArgs)]
+#[argh(
+    subcommand,
+    name = "cryptogram",
+    example = "gallery-puzzle-soulver cryptogram 'Qxxb iox bxz'",
+    example = "gallery-puzzle-soulver cryptogram --hint q=s --key 'Qxxb iox bxz'",
+    note = "Every word of the ciphertext must decode to a dictionary word. Add words \
+            missing from the word list with 'dict add', or pin letters with --hint."
+)]
+pub struct CryptogramArgs {
+    /// the ciphertext
+    #[argh(positional)]
+    text: Vec<String>,
+
+    /// a known letter as CIPHER=PLAIN, e.g. q=s; may be repeated
+    #[argh(option)]
+    hint: Vec<String>,
+
+    /// how many of the most English-like decryptions to list (default: 10)
+    #[argh(option, default = "10")]
+    show: usize,
+
+    /// print the key of each decryption below it
+    #[argh(switch)]
+    key: bool,
+
+    /// word list to solve against instead of the embedded one
+    #[argh(option, short = 'w')]
+    word_list: Option<String>,
+}
+
+/// Runs the `cryptogram` subcommand, returning how many decryptions were found.
+pub fn run(args: CryptogramArgs) -> Result<usize> {
+    let mut cryptogram = Cryptogram::new(&args.text.join(" "));
+    for hint in &args.hint {
+        let (cipher, plain) = hint
+            .split_once('=')
+            .and_then(|(cipher, plain)| Some((single(cipher)?, single(plain)?)))
+            .with_context(|| format!("Invalid hint '{}', expected e.g. q=s", hint))?;
+        cryptogram = cryptogram.with_hint(cipher, plain)?;
+    }
+
+    let dictionary = load_dictionary(args.word_list.as_deref())?;
+    let solutions = cryptogram.solve(&dictionary);
+    for solution in solutions.iter().take(args.show) {
+        println!("{}", solution.plaintext);
+        if args.key {
+            let pairs: Vec<String> = solution
+                .key
+                .iter()
+                .map(|(cipher, plain)| format!("{}={}", cipher, plain))
+                .collect();
+            println!("  {}", pairs.join(" "));
+        }
+    }
+    if solutions.len() > args.show {
+        println!("... {} decryptions found", solutions.len());
+    }
+    Ok(solutions.len())
+}
+
+/// Returns the only character of `text`, if it has exactly one.
+fn single(text: &str) -> Option<char> {
+    let mut chars = text.trim().chars();
+    chars.next().filter(|_| chars.next().is_none())
+}
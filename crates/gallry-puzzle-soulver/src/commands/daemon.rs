@@ -0,0 +1,508 @@
+//! The `daemon` subcommand, which keeps the word lists loaded between solves.
+//!
+//! Parsing the word lists dominates the run time of small puzzles, so a daemon loads
+//! them once and answers solve requests on a unix socket. While one is listening,
+//! `solve` hands it every puzzle it can answer identically, and falls back to solving
+//! on its own when the daemon is missing, busy with other word lists, or fails.
+//!
+//! Each connection carries one request line holding a [`Request`], answered with a
+//! [`Header`] line and then one [`WireMatch`] line per match.
+
+use anyhow::Result;
+use argh::{ArgsInfo, FromArgs};
+use gallry_puzzle_soulver::{PuzzleConfig, WordMatch};
+use serde::{Deserialize, Serialize};
+#[cfg(unix)]
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// How long either side of a connection waits to read or write before giving up, so a
+/// stalled client cannot hold up the daemon, nor a busy daemon the clients; a client
+/// that gives up solves on its own
+#[cfg_attr(not(unix), allow(dead_code))]
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Keep the word lists loaded and answer solves from other runs over a unix socket
+#[derive(ArgsInfo, FromArgs)]
+#[argh(subcommand, name = "daemon")]
+#[cfg_attr(not(unix), allow(dead_code))]
+pub struct DaemonArgs {
+    /// path of the socket to listen on, instead of GALLRY_SOCKET or the default
+    #[argh(option)]
+    socket: Option<String>,
+
+    /// optional path to a custom word list file (repeatable)
+    #[argh(option, short = 'w')]
+    word_list: Vec<String>,
+
+    /// ignore the user's defaults file
+    #[argh(switch)]
+    no_config: bool,
+}
+
+/// A puzzle to solve, sent by a client.
+#[derive(Serialize, Deserialize)]
+struct Request {
+    /// The puzzle, with word list and blocklist paths made absolute
+    puzzle: PuzzleConfig,
+    /// Most matches to send
+    limit: Option<usize>,
+}
+
+/// The first line of an answer.
+#[derive(Serialize, Deserialize)]
+struct Header {
+    /// Why the daemon cannot answer, in which case no matches follow
+    error: Option<String>,
+    /// The daemon's dictionaries, in registration order
+    dictionaries: Vec<WireDictionary>,
+    /// Options of every slot, with wildcards filled in from the daemon's alphabet
+    slots: Vec<Vec<char>>,
+}
+
+/// What a client needs to know of a loaded dictionary to describe it like the daemon.
+#[derive(Serialize, Deserialize)]
+struct WireDictionary {
+    name: String,
+    /// Whether it has no words, which leaves it out of JSON output
+    empty: bool,
+}
+
+/// A match as sent over the socket.
+#[derive(Serialize, Deserialize)]
+struct WireMatch {
+    word: String,
+    stem: Option<String>,
+    entry: Option<String>,
+    dictionaries: Vec<String>,
+    rank: Option<u32>,
+}
+
+impl From<WordMatch> for WireMatch {
+    fn from(word_match: WordMatch) -> Self {
+        Self {
+            word: word_match.word,
+            stem: word_match.stem,
+            entry: word_match.entry,
+            dictionaries: word_match.dictionaries,
+            rank: word_match.rank,
+        }
+    }
+}
+
+impl From<WireMatch> for WordMatch {
+    fn from(wire: WireMatch) -> Self {
+        Self {
+            word: wire.word,
+            stem: wire.stem,
+            entry: wire.entry,
+            dictionaries: wire.dictionaries,
+            rank: wire.rank,
+        }
+    }
+}
+
+/// A puzzle solved by the daemon.
+#[cfg_attr(not(unix), allow(dead_code))]
+pub struct Delegated {
+    /// Generator matching the daemon's slots and dictionary names, for formatting the
+    /// matches; its dictionaries are empty, so it must not be solved
+    pub generator: gallry_puzzle_soulver::WordGenerator,
+    pub matches: Vec<WordMatch>,
+}
+
+/// Returns the socket path: `GALLRY_SOCKET`, else `gallry.sock` in the runtime
+/// directory, else in the user's [private temporary directory](private_temp_dir).
+#[cfg(unix)]
+fn socket_path() -> PathBuf {
+    if let Some(path) = std::env::var_os("GALLRY_SOCKET") {
+        return PathBuf::from(path);
+    }
+    dirs::runtime_dir()
+        .unwrap_or_else(private_temp_dir)
+        .join("gallry.sock")
+}
+
+/// Returns the directory holding the socket when there is no runtime directory, named
+/// after the user's id; the daemon creates it so that only the user can enter it.
+#[cfg(unix)]
+fn private_temp_dir() -> PathBuf {
+    std::env::temp_dir().join(format!("gallry-{}", peer::current_uid()))
+}
+
+/// Who is at the other end of a socket, so that neither side talks to another user.
+#[cfg(unix)]
+mod peer {
+    use anyhow::Result;
+    use std::io;
+    use std::os::fd::AsRawFd;
+    use std::os::unix::net::UnixStream;
+
+    /// Returns the id of the user running this process.
+    pub fn current_uid() -> u32 {
+        // SAFETY: getuid has no preconditions and always succeeds
+        unsafe { libc::getuid() }
+    }
+
+    /// Fails unless the process at the other end of `stream` runs as this user.
+    pub fn check(stream: &UnixStream) -> Result<()> {
+        let uid = peer_uid(stream)?;
+        if uid != current_uid() {
+            anyhow::bail!("the other end of the socket belongs to user {}", uid);
+        }
+        Ok(())
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn peer_uid(stream: &UnixStream) -> io::Result<u32> {
+        let mut credentials = libc::ucred {
+            pid: 0,
+            uid: 0,
+            gid: 0,
+        };
+        let mut len = std::mem::size_of::<libc::ucred>() as libc::socklen_t;
+        // SAFETY: the pointers are valid for writes of `len` bytes for the whole call
+        let result = unsafe {
+            libc::getsockopt(
+                stream.as_raw_fd(),
+                libc::SOL_SOCKET,
+                libc::SO_PEERCRED,
+                (&raw mut credentials).cast(),
+                &mut len,
+            )
+        };
+        if result != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(credentials.uid)
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    fn peer_uid(stream: &UnixStream) -> io::Result<u32> {
+        let mut uid = 0;
+        let mut gid = 0;
+        // SAFETY: the pointers are valid for writes for the whole call
+        let result = unsafe { libc::getpeereid(stream.as_raw_fd(), &mut uid, &mut gid) };
+        if result != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(uid)
+    }
+}
+
+/// Runs the `daemon` subcommand.
+#[cfg(unix)]
+pub fn run(args: DaemonArgs) -> Result<()> {
+    server::run(args)
+}
+
+#[cfg(not(unix))]
+pub fn run(_args: DaemonArgs) -> Result<()> {
+    anyhow::bail!("the daemon needs unix sockets, which this platform lacks")
+}
+
+/// Solves a puzzle with a running daemon, if there is one that can answer it.
+///
+/// # Parameters
+///
+/// * `config` - The puzzle, with the user's defaults already applied
+/// * `limit` - Most matches wanted, letting the daemon stop early
+///
+/// # Returns
+///
+/// The daemon's answer, or `None` to solve locally
+#[cfg(unix)]
+pub fn delegate(config: &PuzzleConfig, limit: Option<usize>) -> Option<Delegated> {
+    match client::solve(config, limit) {
+        Ok(delegated) => delegated,
+        Err(error) => {
+            tracing::debug!("not using the daemon: {:#}", error);
+            None
+        }
+    }
+}
+
+#[cfg(not(unix))]
+pub fn delegate(_config: &PuzzleConfig, _limit: Option<usize>) -> Option<Delegated> {
+    None
+}
+
+/// Returns the puzzle with its word list and blocklist paths made absolute, so the
+/// daemon can tell whether the word lists are the ones it loaded, and opens the same
+/// blocklists, wherever either process was started.
+#[cfg(unix)]
+fn canonical_paths(mut config: PuzzleConfig) -> Result<PuzzleConfig> {
+    use anyhow::Context;
+
+    for path in &mut config.word_lists {
+        *path = std::fs::canonicalize(&*path)
+            .with_context(|| format!("Failed to resolve word list '{}'", path))?
+            .to_string_lossy()
+            .into_owned();
+    }
+    for path in &mut config.blocklists {
+        *path = std::fs::canonicalize(&*path)
+            .with_context(|| format!("Failed to resolve blocklist '{}'", path))?
+            .to_string_lossy()
+            .into_owned();
+    }
+    Ok(config)
+}
+
+#[cfg(unix)]
+mod client {
+    use super::{
+        Delegated, Header, Request, TIMEOUT, WireMatch, canonical_paths, peer, socket_path,
+    };
+    use anyhow::{Context, Result};
+    use gallry_puzzle_soulver::{Dictionary, PuzzleConfig, Slot, WordGenerator};
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::net::UnixStream;
+    use tracing::{debug, info, warn};
+
+    /// Sends the puzzle to the daemon, returning `None` when none is listening, or the
+    /// socket belongs to another user.
+    pub fn solve(config: &PuzzleConfig, limit: Option<usize>) -> Result<Option<Delegated>> {
+        let path = socket_path();
+        if !path.exists() {
+            return Ok(None);
+        }
+        let mut stream = UnixStream::connect(&path)
+            .with_context(|| format!("Failed to connect to '{}'", path.display()))?;
+        // Another user could otherwise answer with matches of their choosing
+        if let Err(error) = peer::check(&stream) {
+            warn!("not using the daemon at '{}': {:#}", path.display(), error);
+            return Ok(None);
+        }
+        stream.set_read_timeout(Some(TIMEOUT))?;
+        stream.set_write_timeout(Some(TIMEOUT))?;
+        debug!("asking the daemon at '{}'", path.display());
+
+        let request = Request {
+            puzzle: canonical_paths(config.clone())?,
+            limit,
+        };
+        writeln!(stream, "{}", serde_json::to_string(&request)?)?;
+
+        let mut lines = BufReader::new(&stream).lines();
+        let header = lines
+            .next()
+            .context("No answer")?
+            .context("The daemon did not answer in time")?;
+        let header: Header = serde_json::from_str(&header)?;
+        if let Some(error) = header.error {
+            anyhow::bail!(error);
+        }
+        // The daemon is solving this puzzle now, and closes the connection if it exits
+        stream.set_read_timeout(None)?;
+        let matches = lines
+            .map(|line| Ok(serde_json::from_str::<WireMatch>(&line?)?.into()))
+            .collect::<Result<Vec<_>>>()?;
+        info!("solved by the daemon at '{}'", path.display());
+
+        // Stand-ins named like the daemon's dictionaries make the output read the same
+        let mut dictionaries = header.dictionaries.into_iter().map(|wire| {
+            let mut dictionary = Dictionary::new().with_name(wire.name.clone());
+            if !wire.empty {
+                dictionary.insert(wire.name);
+            }
+            dictionary
+        });
+        let first = dictionaries.next().unwrap_or_else(Dictionary::new);
+        let mut generator = WordGenerator::with_dictionary(Vec::new(), first);
+        for dictionary in dictionaries {
+            generator.add_dictionary(dictionary);
+        }
+        config.configure(&mut generator)?;
+        generator.set_slots(header.slots.into_iter().map(Slot::new).collect());
+        Ok(Some(Delegated { generator, matches }))
+    }
+}
+
+#[cfg(unix)]
+mod server {
+    use super::{
+        DaemonArgs, Header, Request, TIMEOUT, WireDictionary, WireMatch, canonical_paths, peer,
+        private_temp_dir, socket_path,
+    };
+    use crate::commands::solve::build_generator;
+    use crate::commands::{check_preloaded, user_words_path};
+    use crate::defaults::UserDefaults;
+    use anyhow::{Context, Result};
+    use gallry_puzzle_soulver::{PuzzleConfig, WordGenerator};
+    use std::io::{BufRead, BufReader, BufWriter, Write};
+    use std::os::unix::fs::{DirBuilderExt, MetadataExt, PermissionsExt};
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::path::{Path, PathBuf};
+    use std::time::SystemTime;
+    use tracing::{debug, info, warn};
+
+    /// The loaded word lists and what they were loaded from.
+    struct Daemon {
+        /// Word list settings the generator was built with
+        config: PuzzleConfig,
+        generator: WordGenerator,
+        /// When the user word list last changed, to reload it after `dict add`
+        user_words_modified: Option<SystemTime>,
+    }
+
+    pub fn run(args: DaemonArgs) -> Result<()> {
+        let mut config = PuzzleConfig {
+            word_lists: args.word_list,
+            ..Default::default()
+        };
+        UserDefaults::load(args.no_config)?.apply(&mut config);
+        let config = canonical_paths(config)?;
+        let mut daemon = Daemon {
+            user_words_modified: user_words_modified(),
+            generator: build_generator(&config)?,
+            config,
+        };
+
+        let path = args.socket.map(PathBuf::from).unwrap_or_else(socket_path);
+        let listener = bind(&path)?;
+        eprintln!("Listening on '{}'", path.display());
+
+        for stream in listener.incoming() {
+            let result = stream
+                .context("Failed to accept a connection")
+                .and_then(|stream| daemon.answer(stream));
+            if let Err(error) = result {
+                warn!("{:#}", error);
+            }
+        }
+        Ok(())
+    }
+
+    /// Listens on `path`, replacing a socket left behind by a daemon that has exited.
+    ///
+    /// The default socket outside a runtime directory goes in a directory only the
+    /// user can enter, so that nobody else can connect before the socket's own
+    /// permissions are restricted, nor put a socket of their own in its place.
+    fn bind(path: &PathBuf) -> Result<UnixListener> {
+        if let Some(dir) = path.parent().filter(|&dir| dir == private_temp_dir()) {
+            private_dir(dir)?;
+        }
+        if path.exists() {
+            if UnixStream::connect(path).is_ok() {
+                anyhow::bail!("a daemon is already listening on '{}'", path.display());
+            }
+            std::fs::remove_file(path)
+                .with_context(|| format!("Failed to remove stale socket '{}'", path.display()))?;
+        }
+
+        let listener = UnixListener::bind(path)
+            .with_context(|| format!("Failed to listen on '{}'", path.display()))?;
+        // Only the user who started the daemon may use it
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))
+            .with_context(|| format!("Failed to restrict '{}'", path.display()))?;
+        Ok(listener)
+    }
+
+    /// Creates `dir` for the user alone if it is missing, and fails if it belongs to
+    /// someone else or others may enter it.
+    fn private_dir(dir: &Path) -> Result<()> {
+        if !dir.exists() {
+            std::fs::DirBuilder::new()
+                .mode(0o700)
+                .create(dir)
+                .with_context(|| format!("Failed to create '{}'", dir.display()))?;
+        }
+        let meta = std::fs::symlink_metadata(dir)
+            .with_context(|| format!("Failed to inspect '{}'", dir.display()))?;
+        if !meta.is_dir() || meta.uid() != peer::current_uid() || meta.mode() & 0o077 != 0 {
+            anyhow::bail!(
+                "'{}' must be a directory of your own that only you can access",
+                dir.display()
+            );
+        }
+        Ok(())
+    }
+
+    fn user_words_modified() -> Option<SystemTime> {
+        std::fs::metadata(user_words_path()?)
+            .and_then(|meta| meta.modified())
+            .ok()
+    }
+
+    impl Daemon {
+        /// Answers one connection.
+        fn answer(&mut self, stream: UnixStream) -> Result<()> {
+            peer::check(&stream).context("Refused a connection")?;
+            stream.set_read_timeout(Some(TIMEOUT))?;
+            stream.set_write_timeout(Some(TIMEOUT))?;
+            let mut line = String::new();
+            BufReader::new(&stream)
+                .read_line(&mut line)
+                .context("Failed to read request")?;
+            // Another daemon checking whether this one is alive sends nothing
+            if line.is_empty() {
+                return Ok(());
+            }
+            let request: Request = serde_json::from_str(&line).context("Invalid request")?;
+            let mut out = BufWriter::new(&stream);
+
+            if let Err(error) = self.prepare(&request.puzzle) {
+                debug!("declining request: {:#}", error);
+                let header = Header {
+                    error: Some(format!("{:#}", error)),
+                    dictionaries: Vec::new(),
+                    slots: Vec::new(),
+                };
+                writeln!(out, "{}", serde_json::to_string(&header)?)?;
+                return Ok(out.flush()?);
+            }
+
+            let header = Header {
+                error: None,
+                dictionaries: self
+                    .generator
+                    .dictionaries()
+                    .iter()
+                    .map(|dictionary| WireDictionary {
+                        name: dictionary.name().to_string(),
+                        empty: dictionary.is_empty(),
+                    })
+                    .collect(),
+                slots: self
+                    .generator
+                    .slots()
+                    .iter()
+                    .map(|slot| slot.options().to_vec())
+                    .collect(),
+            };
+            writeln!(out, "{}", serde_json::to_string(&header)?)?;
+            // Let the client stop waiting for the header while the matches are found
+            out.flush()?;
+            let limit = request.limit.unwrap_or(usize::MAX);
+            for word_match in self.generator.iter_matches().take(limit) {
+                writeln!(
+                    out,
+                    "{}",
+                    serde_json::to_string(&WireMatch::from(word_match))?
+                )?;
+            }
+            Ok(out.flush()?)
+        }
+
+        /// Configures the generator for a puzzle, if it can be solved with the loaded
+        /// word lists.
+        fn prepare(&mut self, puzzle: &PuzzleConfig) -> Result<()> {
+            if puzzle.word_lists != self.config.word_lists {
+                anyhow::bail!("the daemon loaded other word lists");
+            }
+            check_preloaded(&PuzzleConfig {
+                word_lists: Vec::new(),
+                ..puzzle.clone()
+            })?;
+
+            let modified = user_words_modified();
+            if self.config.word_lists.is_empty() && modified != self.user_words_modified {
+                info!("user word list changed; reloading");
+                self.generator = build_generator(&self.config)?;
+                self.user_words_modified = modified;
+            }
+            puzzle.configure(&mut self.generator)
+        }
+    }
+}
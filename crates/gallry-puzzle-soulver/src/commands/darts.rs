@@ -0,0 +1,79 @@
+//! The `darts` subcommand, scoring dartboard throws or finding throws for a number.
+
+use anyhow::Result;
+use argh::{ArgsInfo, FromArgs};
+use gallry_puzzle_soulver::{DARTBOARD_SEGMENTS, DartOp, DartThrow, score_throws, throws_for};
+
+/// Compute the number dartboard throws give, or with --target find throws giving one
+#[derive(ArgsInfo, FromArgs)]
+#[argh(
+    subcommand,
+    name = "darts",
+    example = "gallery-puzzle-soulver darts blue:7 pink:3 yellow:1",
+    example = "gallery-puzzle-soulver darts --target 42 --throws 2",
+    note = "Rings are blue (+), yellow (-), pink (*) and purple (/), applied in order \
+            starting from zero; divisions must be even."
+)]
+pub struct DartsArgs {
+    /// throws in order, each a ring and a segment like blue:7 or an operation like +7
+    #[argh(positional)]
+    throws: Vec<DartThrow>,
+
+    /// number to find throws for, instead of scoring throws
+    #[argh(option)]
+    target: Option<i64>,
+
+    /// most throws in a sequence found for --target (default: 3)
+    #[argh(option, default = "3")]
+    max_throws: usize,
+
+    /// operations the rings allow when searching, e.g. '+*' (default: all four)
+    #[argh(option)]
+    ops: Option<String>,
+
+    /// how many sequences to list for --target (default: 20)
+    #[argh(option, default = "20")]
+    show: usize,
+}
+
+/// Runs the `darts` subcommand.
+pub fn run(args: DartsArgs) -> Result<()> {
+    let Some(target) = args.target else {
+        if args.throws.is_empty() {
+            anyhow::bail!("Give the throws to score, or a number to reach with --target");
+        }
+        match score_throws(&args.throws) {
+            Some(total) => println!("{}", total),
+            None => anyhow::bail!("A division is uneven, or the total overflows"),
+        }
+        return Ok(());
+    };
+    if !args.throws.is_empty() {
+        anyhow::bail!("--target cannot be combined with throws to score");
+    }
+
+    let ops = match &args.ops {
+        Some(ops) => ops
+            .chars()
+            .map(|op| op.to_string().parse())
+            .collect::<std::result::Result<Vec<DartOp>, String>>()
+            .map_err(anyhow::Error::msg)?,
+        None => DartOp::ALL.to_vec(),
+    };
+    let found = throws_for(target, args.max_throws, &DARTBOARD_SEGMENTS, &ops);
+    if found.is_empty() {
+        anyhow::bail!(
+            "No sequence of at most {} throws gives {}",
+            args.max_throws,
+            target
+        );
+    }
+    for throws in found.iter().take(args.show) {
+        let written: Vec<String> = throws.iter().map(ToString::to_string).collect();
+        println!("{}", written.join(" "));
+    }
+    if found.len() > args.show {
+        println!("... {} sequences in all", found.len());
+    }
+    Ok(())
+}
@@ -0,0 +1,58 @@
+//! The `dates` subcommand, solving for the word a list of dates spells.
+
+use super::load_dictionary;
+use anyhow::Result;
+use argh::{ArgsInfo, FromArgs};
+use gallry_puzzle_soulver::{CalendarDate, DateLetter, WordGenerator, date_slots};
+
+/// Read each date as a letter and list the dictionary words the dates spell
+#[derive(ArgsInfo, FromArgs)]
+#[argh(
+    subcommand,
+    name = "dates",
+    example = "gallery-puzzle-soulver dates 'Mar 15' 04-01 'Jun 25'",
+    example = "gallery-puzzle-soulver dates --read weekday-initial 1923-03-05 1923-03-10",
+    note = "Readings are month-initial, month-number, day-of-month, day-of-year and \
+            weekday-initial. Without --read, each date may stand for its letter under any \
+            of them."
+)]
+pub struct DatesArgs {
+    /// the dates, such as 1923-03-05, 03-05 or 'March 5'
+    #[argh(positional)]
+    dates: Vec<CalendarDate>,
+
+    /// how the dates stand for letters; may be repeated
+    #[argh(option)]
+    read: Vec<DateLetter>,
+
+    /// word list to check answers against instead of the embedded one
+    #[argh(option, short = 'w')]
+    word_list: Option<String>,
+}
+
+/// Runs the `dates` subcommand, returning how many words were found.
+pub fn run(args: DatesArgs) -> Result<usize> {
+    if args.dates.is_empty() {
+        anyhow::bail!("Give the dates to read, such as 1923-03-05 or 'March 5'");
+    }
+    let readings = if args.read.is_empty() {
+        DateLetter::ALL.to_vec()
+    } else {
+        args.read
+    };
+
+    let slots = date_slots(&args.dates, &readings)?;
+    for (date, slot) in args.dates.iter().zip(&slots) {
+        let letters: String = slot.options().iter().collect();
+        println!("{}: {}", date, letters);
+    }
+
+    let dictionary = load_dictionary(args.word_list.as_deref())?;
+    let generator = WordGenerator::with_dictionary(slots, dictionary);
+    let mut found = 0;
+    for word in generator.iter() {
+        println!("{}", word);
+        found += 1;
+    }
+    Ok(found)
+}
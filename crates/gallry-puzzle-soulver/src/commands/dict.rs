@@ -0,0 +1,193 @@
+//! The `dict` subcommand, for querying a word list.
+
+use super::{load_dictionary, read_user_words, user_words_path, write_user_words};
+use anyhow::Result;
+use argh::{ArgsInfo, FromArgs};
+use gallry_puzzle_soulver::Dictionary;
+
+/// Query a word list or manage the persistent user word list
+#[derive(ArgsInfo, FromArgs)]
+#[argh(subcommand, name = "dict")]
+pub struct DictArgs {
+    /// word list to query instead of the embedded one merged with the user word list
+    #[argh(option, short = 'w')]
+    word_list: Option<String>,
+
+    #[argh(subcommand)]
+    action: DictAction,
+}
+
+#[derive(ArgsInfo, FromArgs)]
+#[argh(subcommand)]
+enum DictAction {
+    Check(CheckArgs),
+    Add(AddArgs),
+    Remove(RemoveArgs),
+    Info(InfoArgs),
+    Length(LengthArgs),
+    Search(SearchArgs),
+    Suggest(SuggestArgs),
+    Sample(SampleArgs),
+}
+
+/// Report whether a word is in the word list, and whether it came from the user list
+#[derive(ArgsInfo, FromArgs)]
+#[argh(subcommand, name = "check")]
+struct CheckArgs {
+    /// the word to look up
+    #[argh(positional)]
+    word: String,
+}
+
+/// Add a confirmed answer to the user word list
+#[derive(ArgsInfo, FromArgs)]
+#[argh(subcommand, name = "add")]
+struct AddArgs {
+    /// the words to add
+    #[argh(positional)]
+    words: Vec<String>,
+}
+
+/// Remove a word from the user word list
+#[derive(ArgsInfo, FromArgs)]
+#[argh(subcommand, name = "remove")]
+struct RemoveArgs {
+    /// the words to remove
+    #[argh(positional)]
+    words: Vec<String>,
+}
+
+/// Summarize the word list
+#[derive(ArgsInfo, FromArgs)]
+#[argh(subcommand, name = "info")]
+struct InfoArgs {}
+
+/// List the words with a given number of characters
+#[derive(ArgsInfo, FromArgs)]
+#[argh(subcommand, name = "length")]
+struct LengthArgs {
+    /// the length in characters
+    #[argh(positional)]
+    len: usize,
+}
+
+/// List the words containing a substring
+#[derive(ArgsInfo, FromArgs)]
+#[argh(subcommand, name = "search")]
+struct SearchArgs {
+    /// the text the words must contain
+    #[argh(positional)]
+    substring: String,
+}
+
+/// Suggest words within a few edits of a possibly misspelled word
+#[derive(ArgsInfo, FromArgs)]
+#[argh(subcommand, name = "suggest")]
+struct SuggestArgs {
+    /// the word to correct
+    #[argh(positional)]
+    word: String,
+
+    /// largest edit distance to report (default: 2)
+    #[argh(option, default = "2")]
+    distance: usize,
+}
+
+/// Print random words from the list
+#[derive(ArgsInfo, FromArgs)]
+#[argh(subcommand, name = "sample")]
+struct SampleArgs {
+    /// how many words to print
+    #[argh(positional)]
+    count: usize,
+
+    /// only pick words with this many characters
+    #[argh(option)]
+    len: Option<usize>,
+}
+
+/// Runs the `dict` subcommand.
+pub fn run(args: DictArgs) -> Result<()> {
+    let word_list = args.word_list.as_deref();
+    match args.action {
+        DictAction::Check(check) => check_word(word_list, &check.word)?,
+        DictAction::Add(add) => update_user_words(add.words, true)?,
+        DictAction::Remove(remove) => update_user_words(remove.words, false)?,
+        DictAction::Info(_) => print_info(&load_dictionary(word_list)?),
+        DictAction::Length(length) => {
+            print_words(load_dictionary(word_list)?.words_of_len(length.len))
+        }
+        DictAction::Search(search) => {
+            print_words(load_dictionary(word_list)?.containing(&search.substring))
+        }
+        DictAction::Suggest(suggest) => {
+            let dictionary = load_dictionary(word_list)?;
+            for (word, distance) in dictionary.suggest(&suggest.word, suggest.distance) {
+                println!("{} ({})", word, distance);
+            }
+        }
+        DictAction::Sample(sample) => {
+            print_words(load_dictionary(word_list)?.sample(sample.count, sample.len))
+        }
+    }
+    Ok(())
+}
+
+/// Prints where a word was found, exiting with status 1 if it was not.
+fn check_word(word_list: Option<&str>, word: &str) -> Result<()> {
+    let dictionary = load_dictionary(word_list)?;
+    let Some(membership) = dictionary.contains_detailed(word) else {
+        println!("{}: not found", word);
+        std::process::exit(1);
+    };
+
+    if word_list.is_none() && read_user_words()?.contains(membership.entry) {
+        println!("{}: found in the user word list", word);
+    } else {
+        println!("{}: found in {}", word, membership.dictionary);
+    }
+    Ok(())
+}
+
+/// Adds words to, or removes them from, the user word list.
+fn update_user_words(words: Vec<String>, add: bool) -> Result<()> {
+    if words.is_empty() {
+        anyhow::bail!("You must provide at least one word");
+    }
+
+    let mut user_words = read_user_words()?;
+    for word in words {
+        let changed = if add {
+            user_words.insert(word.clone())
+        } else {
+            user_words.remove(&word)
+        };
+        match (add, changed) {
+            (true, true) => println!("added {}", word),
+            (true, false) => println!("{} is already in the user word list", word),
+            (false, true) => println!("removed {}", word),
+            (false, false) => println!("{} is not in the user word list", word),
+        }
+    }
+    write_user_words(&user_words)?;
+
+    if let Some(path) = user_words_path() {
+        println!("user word list: {}", path.display());
+    }
+    Ok(())
+}
+
+fn print_info(dictionary: &Dictionary) {
+    println!("name: {}", dictionary.name());
+    println!("entries: {}", dictionary.len());
+    println!(
+        "alphabet: {}",
+        dictionary.alphabet().iter().collect::<String>()
+    );
+}
+
+fn print_words<'a>(words: impl IntoIterator<Item = &'a str>) {
+    for word in words {
+        println!("{}", word);
+    }
+}
@@ -0,0 +1,40 @@
+//! The `match` subcommand, listing the dictionary words that fit a glob pattern.
+
+use super::load_dictionary;
+use anyhow::Result;
+use argh::{ArgsInfo, FromArgs};
+
+/// List the dictionary words matching a pattern with * for any run of letters and ? for
+/// one, for answers whose length is not known
+#[derive(ArgsInfo, FromArgs)]
+#[argh(
+    subcommand,
+    name = "match",
+    example = "gallery-puzzle-soulver match 'c*t?'",
+    example = "gallery-puzzle-soulver match '*ward' --limit 20",
+    note = "Quote the pattern so the shell does not expand * and ? into file names."
+)]
+pub struct MatchArgs {
+    /// the pattern, such as c*t?
+    #[argh(positional)]
+    pattern: String,
+
+    /// stop after this many words
+    #[argh(option)]
+    limit: Option<usize>,
+
+    /// word list to match against instead of the embedded one
+    #[argh(option, short = 'w')]
+    word_list: Option<String>,
+}
+
+/// Runs the `match` subcommand, returning how many words matched.
+pub fn run(args: MatchArgs) -> Result<usize> {
+    let dictionary = load_dictionary(args.word_list.as_deref())?;
+    let words = dictionary.glob(&args.pattern);
+    let shown = args.limit.unwrap_or(words.len()).min(words.len());
+    for word in &words[..shown] {
+        println!("{}", word);
+    }
+    Ok(words.len())
+}
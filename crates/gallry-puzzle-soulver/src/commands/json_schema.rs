@@ -0,0 +1,403 @@
+//! The `json-schema` subcommand, describing the file and output formats.
+//!
+//! The schemas are written out by hand next to the types they describe, so a field
+//! added to [`PuzzleConfig`](gallry_puzzle_soulver::PuzzleConfig) or to the JSON
+//! output needs a matching entry here.
+
+use anyhow::Result;
+use argh::{ArgsInfo, FromArgs};
+use serde_json::{Value, json};
+use std::fmt;
+use std::str::FromStr;
+
+const DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Print a JSON Schema for puzzle files or for the JSON and NDJSON output
+#[derive(ArgsInfo, FromArgs)]
+#[argh(subcommand, name = "json-schema")]
+pub struct JsonSchemaArgs {
+    /// the document to describe: config (default), json for --output json, or ndjson
+    /// for one line of --output ndjson
+    #[argh(positional, default = "SchemaKind::Config")]
+    kind: SchemaKind,
+}
+
+/// The documents a schema can be printed for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SchemaKind {
+    /// Puzzle definition files, in JSON or the equivalent TOML
+    Config,
+    /// The document written by `--output json`
+    Json,
+    /// One line written by `--output ndjson`
+    Ndjson,
+}
+
+impl FromStr for SchemaKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "config" => Ok(SchemaKind::Config),
+            "json" => Ok(SchemaKind::Json),
+            "ndjson" => Ok(SchemaKind::Ndjson),
+            _ => Err(format!(
+                "unknown schema '{}', expected config, json or ndjson",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for SchemaKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            SchemaKind::Config => "config",
+            SchemaKind::Json => "json",
+            SchemaKind::Ndjson => "ndjson",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Runs the `json-schema` subcommand.
+pub fn run(args: JsonSchemaArgs) -> Result<()> {
+    let schema = match args.kind {
+        SchemaKind::Config => config_schema(),
+        SchemaKind::Json => json_output_schema(),
+        SchemaKind::Ndjson => ndjson_output_schema(),
+    };
+    println!("{}", serde_json::to_string_pretty(&schema)?);
+    Ok(())
+}
+
+/// Describes puzzle definition files.
+fn config_schema() -> Value {
+    let strings = json!({ "type": "array", "items": { "type": "string" } });
+    let part_of_speech = json!({
+        "enum": [
+            "noun", "n", "verb", "v", "adjective", "adj", "a", "adverb", "adv", "r",
+            "pronoun", "pron", "preposition", "prep", "conjunction", "conj",
+            "interjection", "intj"
+        ]
+    });
+    let constraint = |name: &str, value: Value, description: &str| {
+        json!({
+            "type": "object",
+            "description": description,
+            "properties": { name: value },
+            "required": [name],
+            "additionalProperties": false
+        })
+    };
+    let text = json!({ "type": "string" });
+    let number = json!({
+        "oneOf": [{ "type": "integer", "minimum": 0 }, { "const": "any" }]
+    });
+
+    json!({
+        "$schema": DRAFT,
+        "title": "Puzzle definition",
+        "description": "A reproducible puzzle for gallery-puzzle-soulver, in JSON or TOML",
+        "type": "object",
+        "additionalProperties": false,
+        "properties": {
+            "version": {
+                "description": "Version of the definition format; 1 when absent",
+                "type": "integer",
+                "minimum": 1,
+                "maximum": 1
+            },
+            "slots": {
+                "description": "Character options for each position, one set per slot, \
+                    or the whole puzzle as one '/'-separated string such as \"cbr/aio/tse\"",
+                "oneOf": [strings, { "type": "string" }]
+            },
+            "constraints": {
+                "description": "Conditions on the whole word",
+                "type": "array",
+                "items": {
+                    "oneOf": [
+                        constraint("contains", text.clone(), "The word contains this text"),
+                        constraint("starts-with", text.clone(), "The word starts with this text"),
+                        constraint("ends-with", text.clone(), "The word ends with this text"),
+                        constraint(
+                            "excludes-letters",
+                            text.clone(),
+                            "The word contains none of these characters"
+                        ),
+                        constraint(
+                            "regex",
+                            json!({ "type": "string", "format": "regex" }),
+                            "The word matches this regular expression"
+                        ),
+                        constraint("rhymes-with", text.clone(), "The word rhymes with this word"),
+                        constraint(
+                            "syllables",
+                            json!({ "type": "integer", "minimum": 0 }),
+                            "The word has this many syllables"
+                        ),
+                        constraint(
+                            "digit-sum",
+                            json!({ "type": "integer", "minimum": 0 }),
+                            "The digits of the word add up to this"
+                        ),
+                        constraint(
+                            "even-digits",
+                            json!({ "type": "integer", "minimum": 0 }),
+                            "Exactly this many characters of the word are even digits"
+                        ),
+                        constraint(
+                            "ordered",
+                            json!({
+                                "enum": [
+                                    "increasing",
+                                    "decreasing",
+                                    "strictly-increasing",
+                                    "strictly-decreasing"
+                                ]
+                            }),
+                            "Each character compares to the one before it in this order"
+                        ),
+                        constraint(
+                            "anagram-of",
+                            text.clone(),
+                            "The word uses exactly these characters, in any order"
+                        ),
+                        constraint(
+                            "spelled-number",
+                            number.clone(),
+                            "The word is a spelled-out number of this value, or any"
+                        ),
+                        constraint(
+                            "roman-numeral",
+                            number,
+                            "The word is a Roman numeral of this value, or any"
+                        ),
+                        constraint(
+                            "morse",
+                            json!({ "type": "string", "pattern": "^[.\\-? ]*$" }),
+                            "The word spells this Morse code; spaces fall between letters"
+                        ),
+                        constraint(
+                            "repeat-pattern",
+                            text.clone(),
+                            "The word's letters repeat where this text's characters do"
+                        )
+                    ]
+                }
+            },
+            "word_lists": {
+                "description": "Paths of word lists to filter against; the embedded list \
+                    is used when empty",
+                "type": "array",
+                "items": { "type": "string" }
+            },
+            "exclude_word_lists": {
+                "description": "Paths of word lists whose words are removed from the word \
+                    lists before solving",
+                "type": "array",
+                "items": { "type": "string" }
+            },
+            "policy": {
+                "description": "Whether words must appear in any or all word lists",
+                "enum": ["any", "all"]
+            },
+            "all_combinations": {
+                "description": "Yield every combination without dictionary filtering",
+                "type": "boolean"
+            },
+            "normalize": {
+                "description": "Unicode normalization applied to the word lists",
+                "enum": ["none", "nfc", "strip-diacritics"]
+            },
+            "expand_inflections": {
+                "description": "Expand the word lists with plurals and verb inflections",
+                "type": "boolean"
+            },
+            "ignore_case": {
+                "description": "Ignore case when matching against the word lists",
+                "type": "boolean"
+            },
+            "stem": {
+                "description": "Also match words whose stem is in the word lists",
+                "type": "boolean"
+            },
+            "proper_nouns": {
+                "description": "Whether capitalized proper nouns match",
+                "enum": ["include", "exclude", "only"]
+            },
+            "pos": {
+                "description": "Only match words tagged with one of these parts of speech",
+                "type": "array",
+                "items": part_of_speech
+            },
+            "exclude_pos": {
+                "description": "Never match words tagged with one of these parts of speech",
+                "type": "array",
+                "items": part_of_speech
+            },
+            "family_friendly": {
+                "description": "Suppress offensive words using the embedded blocklist",
+                "type": "boolean"
+            },
+            "blocklists": {
+                "description": "Paths of files of words to suppress",
+                "type": "array",
+                "items": { "type": "string" }
+            },
+            "definitions": {
+                "description": "Path of a gloss database used to annotate results",
+                "type": ["string", "null"]
+            },
+            "pronunciations": {
+                "description": "Path of a CMUdict-style pronunciation table",
+                "type": ["string", "null"]
+            },
+            "output": {
+                "description": "Preferred output format",
+                "enum": ["text", "json", "ndjson", null]
+            },
+            "report": {
+                "description": "Preferred report format",
+                "enum": ["md", "markdown", "html", null]
+            },
+            "metadata": {
+                "description": "Descriptive information, which does not affect solving",
+                "type": "object",
+                "additionalProperties": false,
+                "properties": {
+                    "name": { "type": "string" },
+                    "description": { "type": "string" },
+                    "source": {
+                        "description": "Where the puzzle came from, such as a URL",
+                        "type": "string"
+                    }
+                }
+            }
+        }
+    })
+}
+
+/// Describes the document written by `--output json`.
+fn json_output_schema() -> Value {
+    let report = json!({
+        "type": "object",
+        "required": ["puzzle", "matches", "stats"],
+        "properties": {
+            "name": {
+                "description": "Name of the puzzle when solving a batch",
+                "type": "string"
+            },
+            "puzzle": {
+                "description": "Echo of the puzzle that was solved",
+                "type": "object",
+                "required": ["slots", "dictionaries", "policy"],
+                "properties": {
+                    "slots": {
+                        "description": "Options of every slot, in position order",
+                        "type": "array",
+                        "items": { "type": "string" }
+                    },
+                    "dictionaries": {
+                        "description": "Names of the dictionaries used for filtering",
+                        "type": "array",
+                        "items": { "type": "string" }
+                    },
+                    "policy": { "enum": ["any", "all"] }
+                }
+            },
+            "matches": { "type": "array", "items": { "$ref": "#/$defs/match" } },
+            "stats": {
+                "type": "object",
+                "required": ["combinations", "matches", "elapsed_ms"],
+                "properties": {
+                    "combinations": {
+                        "description": "Combinations the slots produce before filtering",
+                        "type": "integer"
+                    },
+                    "matches": { "description": "Number of matches reported", "type": "integer" },
+                    "elapsed_ms": {
+                        "description": "Wall-clock time spent solving, in milliseconds",
+                        "type": "integer"
+                    }
+                }
+            }
+        }
+    });
+    let batch = json!({
+        "description": "Reports for every puzzle of a --batch run",
+        "type": "object",
+        "required": ["puzzles"],
+        "properties": { "puzzles": { "type": "array", "items": report } }
+    });
+
+    json!({
+        "$schema": DRAFT,
+        "title": "gallery-puzzle-soulver JSON output",
+        "description": "A solve report, a batch of reports, or a --count summary",
+        "oneOf": [report, batch, { "$ref": "#/$defs/count" }],
+        "$defs": { "match": match_schema(), "count": count_schema() }
+    })
+}
+
+/// Describes one line written by `--output ndjson`.
+fn ndjson_output_schema() -> Value {
+    json!({
+        "$schema": DRAFT,
+        "title": "gallery-puzzle-soulver NDJSON output line",
+        "description": "One match per line, or a single --count summary",
+        "oneOf": [{ "$ref": "#/$defs/match" }, { "$ref": "#/$defs/count" }],
+        "$defs": { "match": match_schema(), "count": count_schema() }
+    })
+}
+
+/// Describes a single match and how it was formed.
+fn match_schema() -> Value {
+    let optional_string = json!({ "type": ["string", "null"] });
+    json!({
+        "type": "object",
+        "required": ["word", "entry", "stem", "dictionaries", "rank", "choices", "definition"],
+        "properties": {
+            "puzzle": {
+                "description": "Name of the puzzle when solving a batch",
+                "type": "string"
+            },
+            "word": { "type": "string" },
+            "entry": optional_string,
+            "stem": optional_string,
+            "dictionaries": { "type": "array", "items": { "type": "string" } },
+            "rank": { "type": ["integer", "null"], "minimum": 1 },
+            "score": {
+                "description": "Positional letter frequency score, when matches were ranked",
+                "type": "number"
+            },
+            "choices": {
+                "description": "Index of the option chosen in each slot",
+                "type": "array",
+                "items": { "type": "integer", "minimum": 0 }
+            },
+            "definition": optional_string
+        }
+    })
+}
+
+/// Describes the object written by `--count`.
+fn count_schema() -> Value {
+    json!({
+        "type": "object",
+        "required": ["matches", "combinations", "pruned_percent"],
+        "additionalProperties": false,
+        "properties": {
+            "matches": { "type": "integer" },
+            "combinations": {
+                "description": "Combinations the slots produce before filtering",
+                "type": "integer"
+            },
+            "pruned_percent": {
+                "description": "Share of the combinations rejected, from 0 to 100",
+                "type": "number"
+            }
+        }
+    })
+}
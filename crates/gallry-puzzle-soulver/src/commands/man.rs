@@ -0,0 +1,184 @@
+//! The `man` subcommand, which writes a roff manual page.
+//!
+//! The page is built from the help text argh generates for every subcommand, so it
+//! always documents exactly the flags the binary accepts.
+
+use crate::Cli;
+use anyhow::Result;
+use argh::{ArgsInfo, FromArgs};
+
+const PROGRAM: &str = "gallery-puzzle-soulver";
+
+/// Print a manual page in roff format, e.g. for `man -l -` or packaging
+#[derive(ArgsInfo, FromArgs)]
+#[argh(subcommand, name = "man")]
+pub struct ManArgs {}
+
+/// Help text split into its parts.
+#[derive(Default)]
+struct Help {
+    usage: String,
+    description: String,
+    /// Headed lists such as "Options", each holding entry names and descriptions
+    sections: Vec<(String, Vec<(String, String)>)>,
+}
+
+/// Runs the `man` subcommand.
+pub fn run(_args: ManArgs) -> Result<()> {
+    print!("{}", render());
+    Ok(())
+}
+
+/// Renders the whole manual page.
+fn render() -> String {
+    let top = help(&[]);
+    let mut page = format!(
+        ".TH {} 1 \"\" \"{} {}\" \"User Commands\"\n",
+        PROGRAM.to_uppercase(),
+        PROGRAM,
+        env!("CARGO_PKG_VERSION")
+    );
+    page.push_str(&format!(
+        ".SH NAME\n{} \\- {}\n",
+        escape(PROGRAM),
+        escape(&top.description)
+    ));
+    page.push_str(&format!(".SH SYNOPSIS\n{}\n", escape(&top.usage)));
+    page.push_str(&format!(
+        ".PP\nArguments without a subcommand are solved as with \\fB{} solve\\fR.\n",
+        escape(PROGRAM)
+    ));
+    for (title, entries) in &top.sections {
+        if title != "Commands" {
+            page.push_str(&format!(".SH {}\n", title.to_uppercase()));
+            push_entries(&mut page, entries);
+        }
+    }
+
+    page.push_str(".SH COMMANDS\n");
+    let mut pending: Vec<Vec<String>> = subcommands(&top)
+        .into_iter()
+        .rev()
+        .map(|name| vec![name])
+        .collect();
+    while let Some(path) = pending.pop() {
+        let path_args: Vec<&str> = path.iter().map(String::as_str).collect();
+        let help = help(&path_args);
+        page.push_str(&format!(
+            ".SS \"{} {}\"\n",
+            escape(PROGRAM),
+            escape(&path.join(" "))
+        ));
+        page.push_str(&format!(
+            "{}\n.PP\n{}\n",
+            escape(&help.usage),
+            escape(&help.description)
+        ));
+        for (title, entries) in &help.sections {
+            page.push_str(&format!(".PP\n\\fB{}:\\fR\n", escape(title)));
+            push_entries(&mut page, entries);
+        }
+
+        // Nested subcommands follow their parent, in the order they are listed
+        for name in subcommands(&help).into_iter().rev() {
+            let mut nested = path.clone();
+            nested.push(name);
+            pending.push(nested);
+        }
+    }
+    page
+}
+
+/// Appends a tagged paragraph per entry.
+fn push_entries(page: &mut String, entries: &[(String, String)]) {
+    for (name, description) in entries {
+        page.push_str(&format!(
+            ".TP\n\\fB{}\\fR\n{}\n",
+            escape(name),
+            escape(description)
+        ));
+    }
+}
+
+/// Returns the names listed under "Commands" in a help text.
+fn subcommands(help: &Help) -> Vec<String> {
+    help.sections
+        .iter()
+        .filter(|(title, _)| title == "Commands")
+        .flat_map(|(_, entries)| entries.iter().map(|(name, _)| name.clone()))
+        .collect()
+}
+
+/// Returns the parsed help text of the subcommand at `path`.
+fn help(path: &[&str]) -> Help {
+    let mut args = path.to_vec();
+    args.push("--help");
+    match Cli::from_args(&[PROGRAM], &args) {
+        Err(early_exit) => parse_help(&early_exit.output),
+        Ok(_) => Help::default(),
+    }
+}
+
+/// Splits argh's help text into usage, description and entry lists.
+fn parse_help(text: &str) -> Help {
+    let mut help = Help::default();
+    let mut paragraphs = text.split("\n\n");
+
+    if let Some(usage) = paragraphs.next() {
+        help.usage = usage.trim_start_matches("Usage: ").trim().to_string();
+    }
+    for paragraph in paragraphs {
+        let paragraph = paragraph.trim_end();
+        let mut lines = paragraph.lines();
+        let Some(first) = lines.next() else {
+            continue;
+        };
+
+        match first.strip_suffix(':') {
+            Some(title) if !first.starts_with(' ') => {
+                let mut entries: Vec<(String, String)> = Vec::new();
+                for line in lines {
+                    let indent = line.len() - line.trim_start().len();
+                    let line = line.trim();
+                    match entries.last_mut() {
+                        // Descriptions wrap onto lines indented past the names
+                        Some((_, description)) if indent > 2 => {
+                            if !description.is_empty() {
+                                description.push(' ');
+                            }
+                            description.push_str(line);
+                        }
+                        _ => {
+                            let (name, description) = line.split_once("  ").unwrap_or((line, ""));
+                            entries.push((name.to_string(), description.trim().to_string()));
+                        }
+                    }
+                }
+                help.sections.push((title.to_string(), entries));
+            }
+            _ => {
+                if !help.description.is_empty() {
+                    help.description.push(' ');
+                }
+                help.description.push_str(
+                    &paragraph
+                        .lines()
+                        .map(str::trim)
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                );
+            }
+        }
+    }
+    help
+}
+
+/// Escapes text so roff prints it literally.
+fn escape(text: &str) -> String {
+    let escaped = text.replace('\\', "\\e").replace('-', "\\-");
+    if escaped.starts_with(['.', '\'']) {
+        format!("\\&{}", escaped)
+    } else {
+        escaped
+    }
+}
@@ -0,0 +1,186 @@
+//! Subcommands of the command-line interface.
+
+use anyhow::{Context, Result};
+use gallry_puzzle_soulver::{Dictionary, LoadOptions, PuzzleConfig, split_sets};
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+pub mod a1z26;
+pub mod acrostic;
+pub mod analyze;
+pub mod bee;
+pub mod caesar;
+pub mod chess;
+pub mod code;
+pub mod combos;
+pub mod config;
+pub mod cryptogram;
+pub mod daemon;
+pub mod darts;
+pub mod dates;
+pub mod dict;
+pub mod glob;
+pub mod json_schema;
+pub mod man;
+pub mod morse;
+pub mod narrow;
+pub mod parlor;
+pub mod repl;
+pub mod rpc;
+pub mod serve;
+pub mod solve;
+pub mod symbols;
+pub mod t9;
+pub mod tui;
+pub mod verify;
+pub mod vigenere;
+pub mod wheel;
+pub mod wordle;
+pub mod wordsearch;
+
+/// Returns the character sets given as arguments or read with `--slots-file`.
+///
+/// A single argument holds the whole puzzle in compact form, such as `cbr/aio/tse`,
+/// with the sets joined by `separator`.
+///
+/// # Parameters
+///
+/// * `positional` - Character sets given as positional arguments
+/// * `slots_file` - Path of a file of character sets, or `-` for standard input
+/// * `separator` - Separator between the sets of a compact puzzle
+pub fn char_sets(
+    positional: &[String],
+    slots_file: Option<&str>,
+    separator: char,
+) -> Result<Vec<String>> {
+    match (slots_file, positional) {
+        (Some(_), [_, ..]) => {
+            anyhow::bail!("character sets cannot be given both as arguments and with --slots-file")
+        }
+        (Some(path), []) => read_char_sets(path),
+        (None, [puzzle]) => Ok(split_sets(puzzle, separator)),
+        (None, sets) => Ok(sets.to_vec()),
+    }
+}
+
+/// Rejects settings of a puzzle that only take effect when the word lists are loaded.
+///
+/// Long-running commands load their word lists once at startup and then
+/// [configure](PuzzleConfig::configure) the same generator for every puzzle they are
+/// sent, so these settings would otherwise be silently ignored.
+pub fn check_preloaded(config: &PuzzleConfig) -> Result<()> {
+    let load_time = [
+        ("word_lists", !config.word_lists.is_empty()),
+        ("exclude_word_lists", !config.exclude_word_lists.is_empty()),
+        ("definitions", config.definitions.is_some()),
+        ("pronunciations", config.pronunciations.is_some()),
+        ("all_combinations", config.all_combinations),
+        ("normalize", config.normalize != Default::default()),
+        ("expand_inflections", config.expand_inflections),
+    ];
+    match load_time.iter().find(|(_, set)| *set) {
+        Some((name, _)) => anyhow::bail!(
+            "'{}' cannot be changed after startup; the word lists are already loaded",
+            name
+        ),
+        None => Ok(()),
+    }
+}
+
+/// Loads the word list at `path`, or the embedded list merged with the user word list
+/// when no path is given.
+pub fn load_dictionary(path: Option<&str>) -> Result<Dictionary> {
+    match path {
+        Some(path) => Dictionary::from_path(path, &LoadOptions::default())
+            .with_context(|| format!("Failed to load word list from '{}'", path)),
+        None => {
+            let mut dictionary = Dictionary::embedded();
+            add_user_words(&mut dictionary)?;
+            Ok(dictionary)
+        }
+    }
+}
+
+/// Returns the path of the persistent user word list.
+///
+/// This is `gallry/words.txt` in the platform's data directory, e.g.
+/// `~/.local/share/gallry/words.txt` on Linux, unless `GALLRY_USER_WORDS` names a file.
+pub fn user_words_path() -> Option<PathBuf> {
+    match std::env::var_os("GALLRY_USER_WORDS") {
+        Some(path) => Some(PathBuf::from(path)),
+        None => dirs::data_dir().map(|dir| dir.join("gallry").join("words.txt")),
+    }
+}
+
+/// Reads the user word list, which is empty until a word is first added.
+pub fn read_user_words() -> Result<BTreeSet<String>> {
+    let Some(path) = user_words_path() else {
+        return Ok(BTreeSet::new());
+    };
+    if !path.exists() {
+        return Ok(BTreeSet::new());
+    }
+
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read user word list '{}'", path.display()))?;
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect())
+}
+
+/// Replaces the user word list with `words`, creating its directory if needed.
+pub fn write_user_words(words: &BTreeSet<String>) -> Result<()> {
+    let path = user_words_path().context("No data directory for the user word list")?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create '{}'", parent.display()))?;
+    }
+
+    let content: String = words.iter().map(|word| format!("{}\n", word)).collect();
+    std::fs::write(&path, content)
+        .with_context(|| format!("Failed to write user word list '{}'", path.display()))
+}
+
+/// Adds the words of the user word list to a dictionary.
+pub fn add_user_words(dictionary: &mut Dictionary) -> Result<()> {
+    let words = read_user_words()?;
+    if !words.is_empty() {
+        dictionary.merge(words.into_iter().collect());
+    }
+    Ok(())
+}
+
+/// Reads one character set per line from a file, or from standard input for `-`.
+///
+/// Comments run from a `#` at the start of a line or after whitespace to the end of
+/// the line. Surrounding whitespace is trimmed and blank lines are skipped.
+pub fn read_char_sets(path: &str) -> Result<Vec<String>> {
+    let content = if path == "-" {
+        std::io::read_to_string(std::io::stdin()).context("Failed to read slots from stdin")?
+    } else {
+        std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read slots from '{}'", path))?
+    };
+
+    Ok(content
+        .lines()
+        .map(|line| strip_comment(line).trim())
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// Removes a trailing `#` comment, leaving a `#` inside a character set untouched.
+fn strip_comment(line: &str) -> &str {
+    let mut previous = None;
+    for (index, c) in line.char_indices() {
+        if c == '#' && previous.is_none_or(char::is_whitespace) {
+            return &line[..index];
+        }
+        previous = Some(c);
+    }
+    line
+}
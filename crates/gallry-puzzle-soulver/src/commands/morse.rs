@@ -0,0 +1,93 @@
+//! The `morse` subcommand, reading Morse code as dictionary words.
+
+use super::load_dictionary;
+use anyhow::{Context, Result};
+use argh::{ArgsInfo, FromArgs};
+use gallry_puzzle_soulver::{MorseCode, MorseSpacing, WordGenerator, morse_encode};
+
+/// Read Morse code as dictionary words, searching the split points when the gaps
+/// between letters were lost
+#[derive(ArgsInfo, FromArgs)]
+#[argh(
+    subcommand,
+    name = "morse",
+    example = "gallery-puzzle-soulver morse '.-- . ... -'",
+    example = "gallery-puzzle-soulver morse --runs '...---...'",
+    example = "gallery-puzzle-soulver morse --encode 'west wing'",
+    note = "Letters are separated by spaces and words by /. A ? group is an unknown letter. \
+            With --runs, each group may hold several letters and every way of splitting it \
+            is tried."
+)]
+pub struct MorseArgs {
+    /// the dots and dashes, such as '... --- ...'
+    #[argh(positional)]
+    code: Vec<String>,
+
+    /// the gaps between some letters are missing, so a group may hold several letters
+    #[argh(switch)]
+    runs: bool,
+
+    /// only read words of this many letters
+    #[argh(option)]
+    length: Option<usize>,
+
+    /// write text in Morse code instead
+    #[argh(switch)]
+    encode: bool,
+
+    /// word list to read against instead of the embedded one
+    #[argh(option, short = 'w')]
+    word_list: Option<String>,
+}
+
+/// Runs the `morse` subcommand, returning how many words were found in all.
+pub fn run(args: MorseArgs) -> Result<usize> {
+    let text = args.code.join(" ");
+    if args.encode {
+        println!("{}", morse_encode(&text));
+        return Ok(1);
+    }
+
+    let spacing = if args.runs {
+        MorseSpacing::Runs
+    } else {
+        MorseSpacing::Letters
+    };
+    let words: Vec<&str> = text
+        .split('/')
+        .map(str::trim)
+        .filter(|word| !word.is_empty())
+        .collect();
+    if words.is_empty() {
+        anyhow::bail!("Give the dots and dashes, such as '... --- ...'");
+    }
+    let codes = words
+        .iter()
+        .map(|word| {
+            MorseCode::parse(word, spacing).with_context(|| format!("Invalid code '{}'", word))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let dictionary = load_dictionary(args.word_list.as_deref())?;
+    let mut generator = WordGenerator::with_dictionary(Vec::new(), dictionary);
+    let mut found = 0;
+    for (word, code) in words.iter().zip(&codes) {
+        let mut matches: Vec<String> = Vec::new();
+        for length in code.lengths() {
+            if args.length.is_some_and(|wanted| wanted != length) {
+                continue;
+            }
+            generator.clear_constraints();
+            if code.apply(&mut generator, length) {
+                matches.extend(generator.iter());
+            }
+        }
+        found += matches.len();
+        if words.len() == 1 {
+            matches.iter().for_each(|word| println!("{}", word));
+        } else {
+            println!("{}: {}", word, matches.join(", "));
+        }
+    }
+    Ok(found)
+}
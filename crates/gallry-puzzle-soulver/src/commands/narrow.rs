@@ -0,0 +1,239 @@
+//! The `narrow` subcommand, a twenty-questions game over the matches of a puzzle.
+
+use super::char_sets;
+use super::solve::build_generator;
+use crate::defaults::UserDefaults;
+use anyhow::{Context, Result};
+use argh::{ArgsInfo, FromArgs};
+use gallry_puzzle_soulver::{Constraint, PuzzleConfig, Slot, WordGenerator};
+use std::collections::BTreeSet;
+use std::io::{BufRead, Write};
+
+const VOWELS: &str = "aeiou";
+
+/// Narrow the matches down by answering yes/no questions, best split first
+#[derive(ArgsInfo, FromArgs)]
+#[argh(subcommand, name = "narrow")]
+pub struct NarrowArgs {
+    /// character sets for each position (e.g., ABC DEF GHI), with ? for any letter,
+    /// [a-f] ranges, !xyz for any letter but these, and \ escapes
+    #[argh(positional)]
+    char_sets: Vec<String>,
+
+    /// read character sets from a file, one per line with # comments, or - for stdin
+    #[argh(option)]
+    slots_file: Option<String>,
+
+    /// separator between the sets when the puzzle is one argument, like cbr/aio/tse
+    #[argh(option, default = "'/'")]
+    separator: char,
+
+    /// puzzle definition to start from (TOML, or JSON with a .json extension)
+    #[argh(option)]
+    puzzle: Option<String>,
+
+    /// optional path to a custom word list file (repeatable)
+    #[argh(option, short = 'w')]
+    word_list: Vec<String>,
+
+    /// ignore the user defaults in ~/.config/gallry/config.toml (or $GALLRY_CONFIG)
+    #[argh(switch)]
+    no_config: bool,
+}
+
+/// A yes/no question about the answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Question {
+    /// Whether the word contains this letter anywhere
+    Contains(char),
+    /// Whether the slot at this zero-based position holds a vowel
+    Vowel(usize),
+    /// Whether the slot at this zero-based position holds this letter
+    Letter(usize, char),
+}
+
+impl Question {
+    /// Returns `true` if the answer for `word` is yes.
+    fn holds(&self, word: &str) -> bool {
+        match *self {
+            Question::Contains(letter) => word.contains(letter),
+            Question::Vowel(position) => word
+                .chars()
+                .nth(position)
+                .is_some_and(|c| VOWELS.contains(c)),
+            Question::Letter(position, letter) => word.chars().nth(position) == Some(letter),
+        }
+    }
+
+    fn prompt(&self) -> String {
+        match self {
+            Question::Contains(letter) => format!("Does the word contain '{}'?", letter),
+            Question::Vowel(position) => format!("Is slot {} a vowel?", position + 1),
+            Question::Letter(position, letter) => {
+                format!("Is slot {} '{}'?", position + 1, letter)
+            }
+        }
+    }
+}
+
+/// The puzzle as narrowed by the answers so far.
+struct Game {
+    /// Options of every slot, in position order
+    slots: Vec<Vec<char>>,
+    /// Generator holding the loaded dictionaries and the constraints learned so far
+    generator: WordGenerator,
+    /// Words matching every answer so far
+    candidates: Vec<String>,
+    /// Questions answered with "skip", which are not asked again
+    skipped: Vec<Question>,
+}
+
+/// Runs the `narrow` subcommand.
+pub fn run(args: NarrowArgs) -> Result<()> {
+    let defaults = UserDefaults::load(args.no_config)?;
+    let mut config = match &args.puzzle {
+        Some(path) => PuzzleConfig::from_path(path)?,
+        None => PuzzleConfig::default(),
+    };
+    let char_sets = char_sets(&args.char_sets, args.slots_file.as_deref(), args.separator)?;
+    if !char_sets.is_empty() {
+        config.slots = char_sets;
+    }
+    config.word_lists.extend(args.word_list);
+    defaults.apply(&mut config);
+    if config.slots.is_empty() {
+        anyhow::bail!("You must provide at least one character set");
+    }
+
+    let generator = build_generator(&config)?;
+    let mut game = Game {
+        slots: generator
+            .slots()
+            .iter()
+            .map(|slot| slot.options().to_vec())
+            .collect(),
+        generator,
+        candidates: Vec::new(),
+        skipped: Vec::new(),
+    };
+    game.solve();
+    println!(
+        "{} candidates. Answer y(es), n(o), s(kip) or q(uit).",
+        game.candidates.len()
+    );
+
+    let stdin = std::io::stdin();
+    let mut lines = stdin.lock().lines();
+    while game.candidates.len() > 1 {
+        let Some(question) = game.best_question() else {
+            println!("No question tells the remaining candidates apart.");
+            break;
+        };
+        print!("{} [y/n/s/q] ", question.prompt());
+        std::io::stdout().flush()?;
+        let Some(line) = lines.next() else {
+            println!();
+            break;
+        };
+        let line = line.context("Failed to read from standard input")?;
+
+        match line.trim().to_lowercase().as_str() {
+            "y" | "yes" => game.answer(question, true),
+            "n" | "no" => game.answer(question, false),
+            "s" | "skip" | "?" => game.skipped.push(question),
+            "q" | "quit" | "exit" => break,
+            _ => {
+                println!("Please answer y, n, s or q.");
+                continue;
+            }
+        }
+        match game.candidates.len() {
+            1 => println!("1 candidate left"),
+            count => println!("{} candidates left", count),
+        }
+    }
+
+    match game.candidates.as_slice() {
+        [] => println!("No word matches every answer."),
+        [answer] => println!("The answer is '{}'.", answer),
+        candidates => println!("Remaining: {}", candidates.join(", ")),
+    }
+    Ok(())
+}
+
+impl Game {
+    /// Re-solves the puzzle with the current slots and constraints.
+    fn solve(&mut self) {
+        let slots = self
+            .slots
+            .iter()
+            .map(|options| Slot::new(options.clone()))
+            .collect();
+        self.generator.set_slots(slots);
+        self.candidates = self
+            .generator
+            .iter_matches()
+            .map(|word_match| word_match.word)
+            .collect();
+    }
+
+    /// Returns the question splitting the candidates most evenly, if any splits them.
+    ///
+    /// Questions about the whole word come first among equally good splits, as they
+    /// are the easiest to answer from a partly read painting.
+    fn best_question(&self) -> Option<Question> {
+        let mut letters: BTreeSet<char> = BTreeSet::new();
+        for word in &self.candidates {
+            letters.extend(word.chars());
+        }
+
+        let mut questions: Vec<Question> = letters.iter().map(|&c| Question::Contains(c)).collect();
+        for (position, options) in self.slots.iter().enumerate() {
+            questions.push(Question::Vowel(position));
+            questions.extend(options.iter().map(|&c| Question::Letter(position, c)));
+        }
+
+        questions
+            .into_iter()
+            .filter(|question| !self.skipped.contains(question))
+            .map(|question| {
+                let yes = self
+                    .candidates
+                    .iter()
+                    .filter(|word| question.holds(word))
+                    .count();
+                (question, yes.min(self.candidates.len() - yes))
+            })
+            .filter(|&(_, smaller)| smaller > 0)
+            // The first of the best splits wins, keeping the preferred order
+            .fold(
+                None,
+                |best: Option<(Question, usize)>, (question, smaller)| match best {
+                    Some((_, best_smaller)) if best_smaller >= smaller => best,
+                    _ => Some((question, smaller)),
+                },
+            )
+            .map(|(question, _)| question)
+    }
+
+    /// Records an answer as a constraint or narrower slot, then re-solves.
+    fn answer(&mut self, question: Question, yes: bool) {
+        match question {
+            Question::Contains(letter) if yes => {
+                self.generator
+                    .add_constraint(Constraint::Contains(letter.to_string()));
+            }
+            Question::Contains(letter) => {
+                self.generator
+                    .add_constraint(Constraint::ExcludesLetters(letter.to_string()));
+            }
+            Question::Vowel(position) => {
+                self.slots[position].retain(|&c| VOWELS.contains(c) == yes);
+            }
+            Question::Letter(position, letter) => {
+                self.slots[position].retain(|&c| (c == letter) == yes);
+            }
+        }
+        self.solve();
+    }
+}
@@ -0,0 +1,73 @@
+//! The `parlor` subcommand, solving the logic puzzles of boxes bearing statements.
+
+use anyhow::Result;
+use argh::{ArgsInfo, FromArgs};
+use gallry_puzzle_soulver::ParlorPuzzle;
+
+/// Find which box holds the prize, given the true and false statements on each
+#[derive(ArgsInfo, FromArgs)]
+#[argh(
+    subcommand,
+    name = "parlor",
+    example = "gallery-puzzle-soulver parlor 'blue: in this' 'white: empty black' \
+               'black: in white or in blue'",
+    note = "Statements are 'in BOX', 'empty BOX', 'true BOX' (all its statements are \
+            true) and 'false BOX', where BOX is a name or 'this', combined with not, and, \
+            or and parentheses. Separate several statements on one box with ';'."
+)]
+pub struct ParlorArgs {
+    /// each box as NAME: STATEMENTS, e.g. 'white: empty this; true blue'
+    #[argh(positional)]
+    boxes: Vec<String>,
+
+    /// fewest boxes whose statements are all true (default: 1)
+    #[argh(option, default = "1")]
+    min_true: usize,
+
+    /// fewest boxes whose statements are all false (default: 1)
+    #[argh(option, default = "1")]
+    min_false: usize,
+}
+
+/// Runs the `parlor` subcommand.
+pub fn run(args: ParlorArgs) -> Result<()> {
+    let puzzle = ParlorPuzzle::parse(&args.boxes)?.with_rules(args.min_true, args.min_false);
+    let solutions = puzzle.solutions()?;
+    if solutions.is_empty() {
+        anyhow::bail!("No placement of the prize is consistent with the statements");
+    }
+
+    let boxes = puzzle.boxes();
+    for solution in &solutions {
+        let verdicts: Vec<String> = boxes
+            .iter()
+            .enumerate()
+            .map(|(index, prize_box)| {
+                let verdict = match solution.box_truth(index) {
+                    Some(true) => "true",
+                    Some(false) => "false",
+                    None => "mixed",
+                };
+                format!("{} {}", prize_box.name, verdict)
+            })
+            .collect();
+        println!(
+            "prize in {}: {}",
+            boxes[solution.prize].name,
+            verdicts.join(", ")
+        );
+    }
+
+    match puzzle.answer() {
+        Some(answer) => println!("The prize must be in the {} box.", boxes[answer].name),
+        None => {
+            let mut possible: Vec<&str> = solutions
+                .iter()
+                .map(|solution| boxes[solution.prize].name.as_str())
+                .collect();
+            possible.dedup();
+            println!("The prize could be in any of: {}", possible.join(", "));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,195 @@
+//! The `repl` subcommand, an interactive session that keeps the dictionary loaded.
+
+use super::solve::build_generator;
+use crate::output;
+use anyhow::{Context, Result};
+use argh::{ArgsInfo, FromArgs};
+use gallry_puzzle_soulver::{Constraint, PuzzleConfig, WordGenerator, parse_slots};
+use std::io::{BufRead, IsTerminal, Write};
+
+const HELP: &str = "\
+commands:
+  slots <set>...           replace every slot, e.g. slots cbr ? [a-e] !xz
+  set <n> <options>        replace the options of slot n
+  add <n> <options>        add options to slot n
+  remove <n> <options>     remove options from slot n
+  constrain <kind> <text>  add a constraint: contains, starts-with, ends-with,
+                           excludes-letters, regex, rhymes-with or syllables
+  clear                    remove every constraint
+  show                     print the slots and constraints
+  solve                    print the matches (an empty line does the same)
+  help                     print this help
+  quit                     leave the session";
+
+/// Define and tweak slots interactively, re-solving without reloading the word lists
+#[derive(ArgsInfo, FromArgs)]
+#[argh(subcommand, name = "repl")]
+pub struct ReplArgs {
+    /// puzzle definition to start from (TOML, or JSON with a .json extension)
+    #[argh(option)]
+    puzzle: Option<String>,
+
+    /// optional path to a custom word list file (repeatable)
+    #[argh(option, short = 'w')]
+    word_list: Vec<String>,
+
+    /// ignore case when matching against the word list
+    #[argh(switch, short = 'i')]
+    ignore_case: bool,
+}
+
+/// The puzzle being edited in the session.
+struct Session {
+    /// Options of every slot, in position order
+    slots: Vec<String>,
+    constraints: Vec<Constraint>,
+    /// Generator holding the loaded dictionaries, reused for every solve
+    generator: WordGenerator,
+}
+
+/// Runs the `repl` subcommand.
+pub fn run(args: ReplArgs) -> Result<()> {
+    let mut config = match &args.puzzle {
+        Some(path) => PuzzleConfig::from_path(path)?,
+        None => PuzzleConfig::default(),
+    };
+    config.word_lists.extend(args.word_list);
+    config.ignore_case |= args.ignore_case;
+
+    let mut session = Session {
+        slots: config.slots.clone(),
+        constraints: config.constraints.clone(),
+        generator: build_generator(&config)?,
+    };
+
+    // Only prompt when a person is typing, so scripted sessions print just results
+    let interactive = std::io::stdin().is_terminal();
+    if interactive {
+        println!("Type help for a list of commands.");
+    }
+
+    let stdin = std::io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        if interactive {
+            print!("> ");
+            std::io::stdout().flush()?;
+        }
+        let Some(line) = lines.next() else {
+            break;
+        };
+        let line = line.context("Failed to read from standard input")?;
+
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.as_slice() {
+            ["quit" | "exit"] => break,
+            words => {
+                if let Err(error) = session.execute(words) {
+                    println!("error: {:#}", error);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Parses a constraint the way puzzle files spell it, e.g. `ends-with t`.
+fn parse_constraint(kind: &str, text: &str) -> Result<Constraint> {
+    // Numeric constraints such as syllables take a number rather than text
+    let value = match text.parse::<usize>() {
+        Ok(number) if kind == "syllables" => serde_json::json!({ kind: number }),
+        _ => serde_json::json!({ kind: text }),
+    };
+    serde_json::from_value(value).with_context(|| format!("invalid constraint '{}'", kind))
+}
+
+impl Session {
+    /// Executes one command line, already split into words.
+    fn execute(&mut self, words: &[&str]) -> Result<()> {
+        match words {
+            [] | ["solve"] => self.solve()?,
+            ["help"] => println!("{}", HELP),
+            ["show"] => self.show(),
+            ["slots", sets @ ..] => {
+                self.slots = sets.iter().map(|set| set.to_string()).collect();
+                self.show();
+            }
+            ["set", position, options] => {
+                *self.slot_mut(position)? = options.to_string();
+                self.show();
+            }
+            ["add", position, options] => {
+                let slot = self.slot_mut(position)?;
+                for c in options.chars() {
+                    if !slot.contains(c) {
+                        slot.push(c);
+                    }
+                }
+                self.show();
+            }
+            ["remove", position, options] => {
+                self.slot_mut(position)?.retain(|c| !options.contains(c));
+                self.show();
+            }
+            ["constrain", kind, text] => {
+                self.constraints.push(parse_constraint(kind, text)?);
+                self.show();
+            }
+            ["clear"] => {
+                self.constraints.clear();
+                self.show();
+            }
+            [command, ..] => anyhow::bail!("unknown command '{}', type help for a list", command),
+        }
+        Ok(())
+    }
+
+    /// Returns the options of the slot at a 1-based position given by the user.
+    fn slot_mut(&mut self, position: &str) -> Result<&mut String> {
+        let len = self.slots.len();
+        position
+            .parse::<usize>()
+            .ok()
+            .and_then(|position| position.checked_sub(1))
+            .and_then(|index| self.slots.get_mut(index))
+            .with_context(|| {
+                format!(
+                    "no slot '{}', expected a number from 1 to {}",
+                    position, len
+                )
+            })
+    }
+
+    fn show(&self) {
+        for (position, options) in self.slots.iter().enumerate() {
+            println!("{}: {}", position + 1, options);
+        }
+        for constraint in &self.constraints {
+            println!(
+                "constraint: {}",
+                serde_json::to_string(constraint).unwrap_or_default()
+            );
+        }
+    }
+
+    fn solve(&mut self) -> Result<()> {
+        if self.slots.is_empty() {
+            println!("no slots defined, type help for a list of commands");
+            return Ok(());
+        }
+
+        self.generator.set_slots(parse_slots(&self.slots)?);
+        self.generator.clear_constraints();
+        for constraint in &self.constraints {
+            self.generator.add_constraint(constraint.clone());
+        }
+
+        let mut count = 0;
+        for solution in output::solve(&self.generator) {
+            println!("{}", output::text_line(&self.generator, &solution));
+            count += 1;
+        }
+        println!("{} match{}", count, if count == 1 { "" } else { "es" });
+        Ok(())
+    }
+}
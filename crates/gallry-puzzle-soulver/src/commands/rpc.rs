@@ -0,0 +1,324 @@
+//! The `rpc` subcommand, a JSON-RPC 2.0 protocol over standard input and output.
+//!
+//! Editors, bots and graphical front ends can keep one process running and drive it
+//! with one request object per line, so the word lists are parsed only once:
+//!
+//! ```text
+//! > {"jsonrpc":"2.0","id":1,"method":"define","params":{"slots":"cbr/aio/tse"}}
+//! < {"jsonrpc":"2.0","id":1,"result":{"combinations":27}}
+//! > {"jsonrpc":"2.0","id":2,"method":"next","params":{"count":2}}
+//! < {"jsonrpc":"2.0","id":2,"result":{"matches":[...],"done":false,"examined":4,...}}
+//! > {"jsonrpc":"2.0","id":3,"method":"update_slot","params":{"slot":1,"options":"bh"}}
+//! ```
+//!
+//! `define` takes a puzzle definition in the JSON form of puzzle files, `update_slot`
+//! replaces the options of one slot, counted from 1, and `next` returns up to `count`
+//! further matches. Both `define` and `update_slot` start the solve over. `cancel`
+//! stops a `next` that is still running, which then answers with the matches found so
+//! far and `"cancelled": true`.
+
+use super::check_preloaded;
+use super::solve::build_generator;
+use crate::defaults::UserDefaults;
+use crate::output::{annotate, json_value};
+use anyhow::{Context, Result};
+use argh::{ArgsInfo, FromArgs};
+use gallry_puzzle_soulver::{Cursor, PuzzleConfig, WordGenerator};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::io::{BufRead, Write};
+use std::sync::mpsc::{self, Receiver};
+use tracing::debug;
+
+/// Combinations examined between checks for a `cancel` request
+const BATCH_COMBINATIONS: u128 = 100_000;
+
+/// Invalid JSON was received
+const PARSE_ERROR: i64 = -32700;
+/// The JSON was not a valid request object
+const INVALID_REQUEST: i64 = -32600;
+/// The method does not exist
+const METHOD_NOT_FOUND: i64 = -32601;
+/// The parameters were missing or malformed, or described an invalid puzzle
+const INVALID_PARAMS: i64 = -32602;
+/// `next` or `update_slot` was called before `define`
+const NO_PUZZLE: i64 = -32000;
+
+/// Drive a long-lived solver with JSON-RPC requests on stdin, one per line
+#[derive(ArgsInfo, FromArgs)]
+#[argh(subcommand, name = "rpc")]
+pub struct RpcArgs {
+    /// optional path to a custom word list file (repeatable)
+    #[argh(option, short = 'w')]
+    word_list: Vec<String>,
+
+    /// ignore the user's defaults file
+    #[argh(switch)]
+    no_config: bool,
+}
+
+/// A JSON-RPC request or notification.
+#[derive(Deserialize)]
+struct Request {
+    /// Absent for notifications, which get no response
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+/// Parameters of `update_slot`.
+#[derive(Deserialize)]
+struct UpdateSlot {
+    /// Position of the slot, counted from 1
+    slot: usize,
+    /// New options, in the notation of the command line
+    options: String,
+}
+
+/// Parameters of `next`.
+#[derive(Deserialize)]
+struct Next {
+    count: usize,
+}
+
+/// The result of a successful request.
+#[derive(Serialize)]
+#[serde(untagged)]
+enum Outcome {
+    /// A puzzle was defined or changed
+    Puzzle {
+        /// Combinations the slots produce before filtering
+        combinations: u128,
+    },
+    /// Matches returned by `next`
+    Matches {
+        /// Matches in the format of `--output ndjson`
+        matches: Vec<Value>,
+        /// Whether every combination has been examined
+        done: bool,
+        /// Combinations examined since the puzzle was defined or changed
+        examined: u128,
+        /// Whether a `cancel` stopped the solve early
+        cancelled: bool,
+    },
+    /// The answer to `cancel`
+    Cancel { cancelled: bool },
+}
+
+/// A failed request, reported as a JSON-RPC error object.
+#[derive(Serialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+/// A JSON-RPC response.
+#[derive(Serialize)]
+struct Response {
+    jsonrpc: &'static str,
+    id: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Outcome>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<RpcError>,
+}
+
+impl Response {
+    fn new(id: Value, result: Result<Outcome, RpcError>) -> Self {
+        let (result, error) = match result {
+            Ok(outcome) => (Some(outcome), None),
+            Err(error) => (None, Some(error)),
+        };
+        Self {
+            jsonrpc: "2.0",
+            id,
+            result,
+            error,
+        }
+    }
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    /// Reports an invalid puzzle or parameter, with the causes of the error.
+    fn invalid(error: impl Into<anyhow::Error>) -> Self {
+        Self::new(INVALID_PARAMS, format!("{:#}", error.into()))
+    }
+}
+
+/// A solver process answering requests.
+struct Session {
+    /// Generator holding the loaded word lists, configured for the current puzzle
+    generator: WordGenerator,
+    /// The current puzzle, or `None` until one is defined
+    puzzle: Option<PuzzleConfig>,
+    cursor: Cursor,
+    /// Lines received while a `next` was running, answered once it finishes
+    queued: VecDeque<String>,
+    /// Answers to `cancel` requests that stopped a `next`, sent after its response
+    cancellations: Vec<Response>,
+}
+
+/// Runs the `rpc` subcommand.
+pub fn run(args: RpcArgs) -> Result<()> {
+    let mut config = PuzzleConfig {
+        word_lists: args.word_list,
+        ..Default::default()
+    };
+    UserDefaults::load(args.no_config)?.apply(&mut config);
+    let mut session = Session {
+        generator: build_generator(&config)?,
+        puzzle: None,
+        cursor: Cursor::new(),
+        queued: VecDeque::new(),
+        cancellations: Vec::new(),
+    };
+
+    // Lines are read on their own thread, so a cancel can arrive during a solve
+    let (sender, lines) = mpsc::channel();
+    std::thread::spawn(move || {
+        for line in std::io::stdin().lock().lines() {
+            let Ok(line) = line else { break };
+            if sender.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut stdout = std::io::stdout().lock();
+    while let Some(line) = session.queued.pop_front().or_else(|| lines.recv().ok()) {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = session.answer(&line, &lines);
+        for response in response.into_iter().chain(session.cancellations.drain(..)) {
+            writeln!(stdout, "{}", serde_json::to_string(&response)?)
+                .and_then(|()| stdout.flush())
+                .context("Failed to write response")?;
+        }
+    }
+    Ok(())
+}
+
+impl Session {
+    /// Answers one line, returning the response unless it was a notification.
+    fn answer(&mut self, line: &str, lines: &Receiver<String>) -> Option<Response> {
+        let request: Request = match serde_json::from_str::<Value>(line) {
+            Err(error) => {
+                let error = RpcError::new(PARSE_ERROR, error.to_string());
+                return Some(Response::new(Value::Null, Err(error)));
+            }
+            Ok(value) => match serde_json::from_value(value) {
+                Ok(request) => request,
+                Err(error) => {
+                    let error = RpcError::new(INVALID_REQUEST, error.to_string());
+                    return Some(Response::new(Value::Null, Err(error)));
+                }
+            },
+        };
+        debug!("rpc request '{}'", request.method);
+
+        let result = match request.method.as_str() {
+            "define" => self.define(request.params),
+            "update_slot" => self.update_slot(request.params),
+            "next" => self.next(request.params, lines),
+            // Nothing is running between requests, so there is nothing to stop
+            "cancel" => Ok(Outcome::Cancel { cancelled: false }),
+            method => Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                format!("unknown method '{}'", method),
+            )),
+        };
+        Some(Response::new(request.id?, result))
+    }
+
+    /// Replaces the puzzle with the definition given as parameters.
+    fn define(&mut self, params: Value) -> Result<Outcome, RpcError> {
+        let config: PuzzleConfig = serde_json::from_value(params).map_err(RpcError::invalid)?;
+        self.start(config)
+    }
+
+    /// Replaces the options of one slot of the current puzzle.
+    fn update_slot(&mut self, params: Value) -> Result<Outcome, RpcError> {
+        let params: UpdateSlot = serde_json::from_value(params).map_err(RpcError::invalid)?;
+        let mut config = self.puzzle.clone().ok_or_else(no_puzzle)?;
+        let count = config.slots.len();
+        let slot = params
+            .slot
+            .checked_sub(1)
+            .and_then(|index| config.slots.get_mut(index))
+            .ok_or_else(|| {
+                let message = format!("no slot {}; the puzzle has {}", params.slot, count);
+                RpcError::new(INVALID_PARAMS, message)
+            })?;
+        *slot = params.options;
+        self.start(config)
+    }
+
+    /// Configures the generator for a puzzle and starts solving it over.
+    fn start(&mut self, config: PuzzleConfig) -> Result<Outcome, RpcError> {
+        check_preloaded(&config)
+            .and_then(|()| config.configure(&mut self.generator))
+            .map_err(RpcError::invalid)?;
+        self.puzzle = Some(config);
+        self.cursor = Cursor::new();
+        Ok(Outcome::Puzzle {
+            combinations: self.generator.combination_count(),
+        })
+    }
+
+    /// Solves on until `count` more matches are found, the puzzle is exhausted or a
+    /// `cancel` request arrives.
+    fn next(&mut self, params: Value, lines: &Receiver<String>) -> Result<Outcome, RpcError> {
+        let Next { count } = serde_json::from_value(params).map_err(RpcError::invalid)?;
+        if self.puzzle.is_none() {
+            return Err(no_puzzle());
+        }
+
+        let mut matches = Vec::new();
+        let mut cancelled = false;
+        while !cancelled && matches.len() < count && !self.cursor.is_done(&self.generator) {
+            let wanted = count - matches.len();
+            let found = self
+                .generator
+                .advance(&mut self.cursor, wanted, BATCH_COMBINATIONS);
+            for word_match in found {
+                let solution = annotate(&self.generator, word_match);
+                matches.push(json_value(&self.generator, &solution).map_err(RpcError::invalid)?);
+            }
+
+            // Look for a cancel among the waiting lines, keeping the rest for later
+            while let Ok(line) = lines.try_recv() {
+                match serde_json::from_str::<Request>(&line) {
+                    Ok(request) if request.method == "cancel" => {
+                        cancelled = true;
+                        if let Some(id) = request.id {
+                            let outcome = Ok(Outcome::Cancel { cancelled: true });
+                            self.cancellations.push(Response::new(id, outcome));
+                        }
+                    }
+                    _ => self.queued.push_back(line),
+                }
+            }
+        }
+
+        Ok(Outcome::Matches {
+            matches,
+            done: self.cursor.is_done(&self.generator),
+            examined: self.cursor.position(),
+            cancelled,
+        })
+    }
+}
+
+fn no_puzzle() -> RpcError {
+    RpcError::new(NO_PUZZLE, "no puzzle defined; call 'define' first")
+}
@@ -0,0 +1,325 @@
+//! The `serve` subcommand, an HTTP server that streams matches as NDJSON.
+//!
+//! The word lists are loaded once at startup and shared by every request, so other
+//! devices, such as phones on the same network, can solve puzzles without a copy of
+//! the binary or its word lists:
+//!
+//! ```text
+//! GET  /                              a small page for entering slots
+//! GET  /solve?slots=cbr/aio/tse       matches of a compact puzzle
+//! POST /solve                         matches of a puzzle definition sent as JSON
+//! ```
+//!
+//! Both `/solve` forms accept `limit=N` in the query. Matches are written one JSON
+//! object per line, in the format of `--output ndjson`, with chunked transfer encoding
+//! so they arrive while the solve is still running.
+
+use anyhow::Result;
+use argh::{ArgsInfo, FromArgs};
+
+/// Serve an HTTP API that streams the matches of submitted puzzles
+#[derive(ArgsInfo, FromArgs)]
+#[argh(subcommand, name = "serve")]
+#[cfg_attr(not(feature = "server"), allow(dead_code))]
+pub struct ServeArgs {
+    /// port to listen on
+    #[argh(option, default = "8080")]
+    port: u16,
+
+    /// address to listen on; use 0.0.0.0 to accept other devices on the network
+    #[argh(option, default = "String::from(\"127.0.0.1\")")]
+    host: String,
+
+    /// optional path to a custom word list file (repeatable)
+    #[argh(option, short = 'w')]
+    word_list: Vec<String>,
+
+    /// number of requests solved at the same time
+    #[argh(option, default = "4")]
+    workers: usize,
+
+    /// most matches written per request, whatever limit the request asks for
+    #[argh(option, default = "10_000")]
+    max_matches: usize,
+
+    /// ignore the user's defaults file
+    #[argh(switch)]
+    no_config: bool,
+}
+
+/// Runs the `serve` subcommand.
+#[cfg(feature = "server")]
+pub fn run(args: ServeArgs) -> Result<()> {
+    use super::solve::build_generator;
+    use crate::defaults::UserDefaults;
+    use anyhow::Context;
+    use gallry_puzzle_soulver::PuzzleConfig;
+    use tracing::info;
+
+    if args.workers == 0 {
+        anyhow::bail!("--workers must be at least 1");
+    }
+
+    let mut config = PuzzleConfig {
+        word_lists: args.word_list,
+        ..Default::default()
+    };
+    UserDefaults::load(args.no_config)?.apply(&mut config);
+    let generator = build_generator(&config)?;
+
+    let address = format!("{}:{}", args.host, args.port);
+    let server = tiny_http::Server::http(&address)
+        .map_err(|error| anyhow::anyhow!(error))
+        .with_context(|| format!("Failed to listen on {}", address))?;
+    info!("listening on http://{}", address);
+    eprintln!("Serving on http://{}", address);
+
+    std::thread::scope(|scope| {
+        for _ in 0..args.workers {
+            let mut generator = http::worker_generator(&generator);
+            let server = &server;
+            scope.spawn(move || {
+                while let Ok(request) = server.recv() {
+                    http::handle(request, &mut generator, args.max_matches);
+                }
+            });
+        }
+    });
+    Ok(())
+}
+
+#[cfg(not(feature = "server"))]
+pub fn run(_args: ServeArgs) -> Result<()> {
+    anyhow::bail!("the HTTP server requires building with the 'server' feature")
+}
+
+#[cfg(feature = "server")]
+mod http {
+    use crate::commands::check_preloaded;
+    use crate::output::{annotate, ndjson_line};
+    use anyhow::{Context, Result};
+    use gallry_puzzle_soulver::{
+        Cursor, Dictionary, PuzzleConfig, WordGenerator, WordMatch, split_sets,
+    };
+    use std::io::Read;
+    use tiny_http::{Header, Method, Request, Response, StatusCode};
+    use tracing::debug;
+
+    /// Largest puzzle definition accepted in a request body
+    const MAX_BODY_BYTES: u64 = 64 * 1024;
+    /// Combinations examined between checks for matches to send
+    const BATCH_COMBINATIONS: u128 = 100_000;
+    /// Matches found before they are handed to the connection
+    const BATCH_MATCHES: usize = 64;
+
+    /// The page served at `/`, for solving from a browser.
+    const INDEX: &str = include_str!("serve.html");
+
+    /// Returns a generator with its own copy of the loaded word lists.
+    pub fn worker_generator(loaded: &WordGenerator) -> WordGenerator {
+        let mut dictionaries = loaded.dictionaries().iter().cloned();
+        let first = dictionaries.next().unwrap_or_else(Dictionary::new);
+        let mut generator = WordGenerator::with_dictionary(Vec::new(), first);
+        for dictionary in dictionaries {
+            generator.add_dictionary(dictionary);
+        }
+        generator
+    }
+
+    /// Answers one request, logging failures instead of stopping the server.
+    pub fn handle(mut request: Request, generator: &mut WordGenerator, max_matches: usize) {
+        debug!("{} {}", request.method(), request.url());
+        let url = request.url().to_string();
+        let (path, query) = url.split_once('?').unwrap_or((&url, ""));
+
+        let response = match (request.method(), path) {
+            (Method::Get, "/") => request.respond(html(INDEX)),
+            (Method::Get | Method::Post, "/solve") => {
+                let puzzle = if *request.method() == Method::Get {
+                    query_config(query)
+                } else {
+                    read_config(&mut request)
+                };
+                let streamed = puzzle
+                    .and_then(|config| Ok((config, query_limit(query)?)))
+                    .map_err(|error| bad_request(&format!("{:#}", error)))
+                    .and_then(|(config, limit)| stream(generator, &config, limit, max_matches));
+                match streamed {
+                    Ok(response) => request.respond(response),
+                    Err(response) => request.respond(response),
+                }
+            }
+            (_, "/" | "/solve") => request.respond(error(405, "method not allowed")),
+            _ => request.respond(error(404, "not found")),
+        };
+        // A client hanging up mid-stream is routine, e.g. after reading enough matches
+        if let Err(error) = response {
+            debug!("failed to send response: {}", error);
+        }
+    }
+
+    /// A response whose body is produced while it is sent.
+    type Streamed<'a> = Response<MatchStream<'a>>;
+    /// A response with a body known up front.
+    type Complete = Response<std::io::Cursor<Vec<u8>>>;
+
+    /// Configures the generator for a puzzle and returns a response streaming its matches.
+    fn stream<'a>(
+        generator: &'a mut WordGenerator,
+        config: &PuzzleConfig,
+        limit: Option<usize>,
+        max_matches: usize,
+    ) -> Result<Streamed<'a>, Complete> {
+        check_remote(config)
+            .and_then(|()| config.configure(generator))
+            .map_err(|error| bad_request(&format!("{:#}", error)))?;
+        generator.reset_stats();
+
+        let stream = MatchStream {
+            generator,
+            cursor: Cursor::new(),
+            remaining: limit.unwrap_or(max_matches).min(max_matches),
+            buffer: Vec::new(),
+            offset: 0,
+        };
+        let header = Header::from_bytes("Content-Type", "application/x-ndjson").unwrap();
+        Ok(Response::new(
+            StatusCode(200),
+            vec![header],
+            stream,
+            None,
+            None,
+        ))
+    }
+
+    /// Rejects settings that would read files on the server or reload its word lists.
+    fn check_remote(config: &PuzzleConfig) -> Result<()> {
+        if !config.blocklists.is_empty() {
+            anyhow::bail!("'blocklists' is not accepted by the server");
+        }
+        check_preloaded(config)
+    }
+
+    /// Reads a JSON puzzle definition from the request body.
+    fn read_config(request: &mut Request) -> Result<PuzzleConfig> {
+        let mut body = String::new();
+        request
+            .as_reader()
+            .take(MAX_BODY_BYTES)
+            .read_to_string(&mut body)
+            .context("Failed to read request body")?;
+        PuzzleConfig::from_json_str(&body)
+    }
+
+    /// Builds a puzzle from the compact `slots` parameter, such as `cbr/aio/tse`.
+    fn query_config(query: &str) -> Result<PuzzleConfig> {
+        let slots = parameter(query, "slots").context("Missing 'slots' parameter")?;
+        Ok(PuzzleConfig {
+            slots: split_sets(&slots, '/'),
+            ..Default::default()
+        })
+    }
+
+    /// Returns the `limit` parameter, if given.
+    fn query_limit(query: &str) -> Result<Option<usize>> {
+        parameter(query, "limit")
+            .map(|limit| limit.parse().context("'limit' must be a number"))
+            .transpose()
+    }
+
+    /// Returns the decoded value of a query parameter.
+    fn parameter(query: &str, name: &str) -> Option<String> {
+        query
+            .split('&')
+            .filter_map(|pair| pair.split_once('=').or(Some((pair, ""))))
+            .find(|(key, _)| decode(key) == name)
+            .map(|(_, value)| decode(value))
+    }
+
+    /// Decodes `+` and `%XX` escapes of a query component.
+    fn decode(text: &str) -> String {
+        let mut bytes = Vec::with_capacity(text.len());
+        let mut rest = text.as_bytes();
+        while let Some((&byte, tail)) = rest.split_first() {
+            let escaped = tail
+                .get(..2)
+                .and_then(|hex| std::str::from_utf8(hex).ok())
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+            match (byte, escaped) {
+                (b'%', Some(decoded)) => {
+                    bytes.push(decoded);
+                    rest = &tail[2..];
+                }
+                _ => {
+                    bytes.push(if byte == b'+' { b' ' } else { byte });
+                    rest = tail;
+                }
+            }
+        }
+        String::from_utf8_lossy(&bytes).into_owned()
+    }
+
+    fn html(page: &str) -> Complete {
+        let header = Header::from_bytes("Content-Type", "text/html; charset=utf-8").unwrap();
+        Response::from_string(page).with_header(header)
+    }
+
+    fn bad_request(message: &str) -> Complete {
+        error(400, message)
+    }
+
+    fn error(status: u16, message: &str) -> Complete {
+        Response::from_string(format!("{}\n", message)).with_status_code(status)
+    }
+
+    /// NDJSON lines of a puzzle's matches, solved as the connection reads them.
+    struct MatchStream<'a> {
+        generator: &'a WordGenerator,
+        cursor: Cursor,
+        /// Matches still to be written before stopping
+        remaining: usize,
+        /// Encoded lines not yet read
+        buffer: Vec<u8>,
+        offset: usize,
+    }
+
+    impl MatchStream<'_> {
+        /// Solves on until there are matches to encode, returning `false` when done.
+        fn refill(&mut self) -> Result<bool> {
+            let mut matches: Vec<WordMatch> = Vec::new();
+            while matches.is_empty() {
+                if self.remaining == 0 || self.cursor.is_done(self.generator) {
+                    return Ok(false);
+                }
+                matches = self.generator.advance(
+                    &mut self.cursor,
+                    self.remaining.min(BATCH_MATCHES),
+                    BATCH_COMBINATIONS,
+                );
+            }
+
+            self.remaining -= matches.len();
+            self.buffer.clear();
+            self.offset = 0;
+            for word_match in matches {
+                let solution = annotate(self.generator, word_match);
+                self.buffer
+                    .extend_from_slice(ndjson_line(self.generator, &solution)?.as_bytes());
+                self.buffer.push(b'\n');
+            }
+            Ok(true)
+        }
+    }
+
+    impl Read for MatchStream<'_> {
+        fn read(&mut self, out: &mut [u8]) -> std::io::Result<usize> {
+            if self.offset == self.buffer.len() && !self.refill().map_err(std::io::Error::other)? {
+                return Ok(0);
+            }
+            let count = out.len().min(self.buffer.len() - self.offset);
+            out[..count].copy_from_slice(&self.buffer[self.offset..self.offset + count]);
+            self.offset += count;
+            Ok(count)
+        }
+    }
+}
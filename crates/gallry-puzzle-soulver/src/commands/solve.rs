@@ -0,0 +1,694 @@
+//! The `solve` subcommand, which also handles the bare positional form.
+
+use super::{add_user_words, char_sets, daemon, read_char_sets};
+use crate::color::{self, ColorChoice};
+use crate::defaults::UserDefaults;
+use crate::filter::FilterCommand;
+use crate::output::{self, GroupBy, OutputFormat, ResultOptions, Solution};
+use crate::report::{self, ReportFormat};
+use crate::template::Template;
+use anyhow::{Context, Result};
+use argh::{ArgsInfo, FromArgs};
+use gallry_puzzle_soulver::{
+    Constraint, DictionaryPolicy, Interner, Normalization, NumberTarget, PartOfSpeech, Pattern,
+    ProperNouns, PuzzleConfig, Strategy, WordGenerator, confusable_substitutions,
+};
+use std::cell::Cell;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tracing::{debug, info};
+
+/// Find the words the slots can spell (the default when no subcommand is given)
+#[derive(ArgsInfo, FromArgs)]
+#[argh(
+    subcommand,
+    name = "solve",
+    error_code(1, "no matches were found"),
+    error_code(2, "invalid arguments, slots, puzzle files or settings"),
+    error_code(3, "reading or writing a file, or running a command, failed")
+)]
+pub struct SolveArgs {
+    /// character sets for each position (e.g., ABC DEF GHI), with ? for any letter,
+    /// [a-f] ranges, !xyz for any letter but these, and \ escapes
+    #[argh(positional)]
+    char_sets: Vec<String>,
+
+    /// read character sets from a file, one per line with # comments, or - for stdin
+    #[argh(option)]
+    slots_file: Option<String>,
+
+    /// separator between the sets when the puzzle is one argument, like cbr/aio/tse
+    #[argh(option, default = "'/'")]
+    separator: char,
+
+    /// path to a puzzle definition (TOML, or JSON with a .json extension); flags add to it
+    #[argh(option)]
+    puzzle: Option<String>,
+
+    /// solve a puzzle definition shipped with the program, by name; flags add to it
+    #[argh(option)]
+    preset: Option<String>,
+
+    /// solve every puzzle file in a directory, or listed one per line in a manifest file
+    #[argh(option)]
+    batch: Option<String>,
+
+    /// optional path to a custom word list file (repeatable, results show which lists match)
+    #[argh(option, short = 'w')]
+    word_list: Vec<String>,
+
+    /// remove the words of this file from the word lists before solving, e.g. answers
+    /// already used or known to be wrong (repeatable)
+    #[argh(option)]
+    exclude_wordlist: Vec<String>,
+
+    /// only show words found in every word list given with -w
+    #[argh(switch)]
+    require_all: bool,
+
+    /// ignore case when matching against the word list
+    #[argh(switch, short = 'i')]
+    ignore_case: bool,
+
+    /// also match words whose stem is in the word list, showing the stem
+    #[argh(switch)]
+    stem: bool,
+
+    /// path to a gloss database (word<TAB>definition lines) used to annotate results
+    #[argh(option)]
+    definitions: Option<String>,
+
+    /// unicode normalization of the custom word list: none, nfc (default) or strip-diacritics
+    #[argh(option)]
+    normalize: Option<Normalization>,
+
+    /// expand the custom word list with plurals and verb inflections
+    #[argh(switch)]
+    expand_inflections: bool,
+
+    /// show all combinations, even those not in the word list
+    #[argh(switch, short = 'a')]
+    all_combinations: bool,
+
+    /// whether capitalized proper nouns match: include, exclude or only (default: include)
+    #[argh(option)]
+    proper_nouns: Option<ProperNouns>,
+
+    /// only match words tagged with this part of speech (repeatable, needs word list metadata)
+    #[argh(option)]
+    pos: Vec<PartOfSpeech>,
+
+    /// never match words tagged with this part of speech (repeatable)
+    #[argh(option)]
+    exclude_pos: Vec<PartOfSpeech>,
+
+    /// suppress offensive words using the embedded blocklist
+    #[argh(switch)]
+    family_friendly: bool,
+
+    /// path to a file of words to suppress from output (repeatable)
+    #[argh(option)]
+    blocklist: Vec<String>,
+
+    /// only show words matching this regular expression (repeatable, anchor with ^ and $)
+    #[argh(option)]
+    regex: Vec<Pattern>,
+
+    /// output format: text (default), json, or ndjson to stream one object per match
+    #[argh(option)]
+    output: Option<OutputFormat>,
+
+    /// write a formatted report instead of the plain results: md or html
+    #[argh(option)]
+    report: Option<ReportFormat>,
+
+    /// stop after this many results, without generating the rest
+    #[argh(option)]
+    limit: Option<usize>,
+
+    /// only show words rhyming with this word (repeatable)
+    #[argh(option)]
+    rhyme: Vec<String>,
+
+    /// only show words with this many syllables, estimated from spelling
+    #[argh(option)]
+    syllables: Option<usize>,
+
+    /// only show words that are spelled-out numbers, such as seven, of this value or any
+    #[argh(option)]
+    spelled_number: Option<NumberTarget>,
+
+    /// only show words that are Roman numerals, such as mix, of this value or any
+    #[argh(option)]
+    roman_numeral: Option<NumberTarget>,
+
+    /// path to a CMUdict-style pronunciation table for judging --rhyme and --syllables
+    #[argh(option)]
+    pronunciations: Option<String>,
+
+    /// shell command deciding which results to keep: it gets each word on stdin and
+    /// keeps it by exiting 0
+    #[argh(option)]
+    filter_cmd: Option<String>,
+
+    /// run --filter-cmd once with every word on stdin, keeping the words it prints
+    #[argh(switch)]
+    filter_batch: bool,
+
+    /// threads to solve on (default: available parallelism)
+    #[argh(option)]
+    threads: Option<usize>,
+
+    /// force a solving algorithm, for comparing them: enumerate, dict-scan or
+    /// positional-index (default: chosen per puzzle)
+    #[argh(option)]
+    strategy: Option<Strategy>,
+
+    /// print only the number of matches, the combination count and how much was pruned
+    #[argh(switch)]
+    count: bool,
+
+    /// print only the N best scoring matches, best first, with their positional letter
+    /// frequency scores
+    #[argh(option)]
+    top: Option<usize>,
+
+    /// print only N matches chosen at random, e.g. to eyeball a huge result set
+    #[argh(option)]
+    sample: Option<usize>,
+
+    /// seed for --sample, so the same matches are chosen on every run
+    #[argh(option)]
+    seed: Option<u64>,
+
+    /// print each match with a template like "{word}\t{score}\t{indices}"; placeholders
+    /// are word, entry, stem, dictionaries, rank, score, indices and definition
+    #[argh(option)]
+    format: Option<Template>,
+
+    /// show which option of each slot every match used, as in c[1] a[0] t[2]
+    #[argh(switch)]
+    explain: bool,
+
+    /// instead of the matches, print the options of each slot that some match uses and
+    /// those eliminated
+    #[argh(switch)]
+    prune: bool,
+
+    /// lay text results out in columns across the terminal, like ls
+    #[argh(switch)]
+    columns: bool,
+
+    /// print text results under a header per group, with counts: first-letter, length,
+    /// or score-band
+    #[argh(option)]
+    group_by: Option<GroupBy>,
+
+    /// print a summary of the work done to stderr: combinations examined, dictionary hits,
+    /// elapsed time and peak memory
+    #[argh(switch)]
+    stats: bool,
+
+    /// when nothing matches, retry with easily misread letters (i/l/1, o/0, n/m, u/v)
+    /// and report on stderr which reading found matches
+    #[argh(switch)]
+    retry_confusables: bool,
+
+    /// color text output: auto (default; off when NO_COLOR is set), always, or never
+    #[argh(option)]
+    color: Option<ColorChoice>,
+
+    /// end each result with NUL instead of a newline, for xargs -0
+    #[argh(switch, short = '0')]
+    print0: bool,
+
+    /// ignore the user defaults in ~/.config/gallry/config.toml (or $GALLRY_CONFIG)
+    #[argh(switch)]
+    no_config: bool,
+
+    /// solve in this process even when a `daemon` is running
+    #[argh(switch)]
+    no_daemon: bool,
+}
+
+/// Runs the `solve` subcommand, returning how many matches were written.
+pub fn run(args: SolveArgs) -> Result<usize> {
+    let defaults = UserDefaults::load(args.no_config)?;
+    color::init(args.color.or(defaults.color()?).unwrap_or_default());
+    output::set_nul_terminated(args.print0);
+    if let Some(path) = &args.batch {
+        return solve_batch(path, &args, &defaults);
+    }
+
+    let mut config = match (&args.puzzle, &args.preset) {
+        (Some(_), Some(_)) => anyhow::bail!("--puzzle cannot be combined with --preset"),
+        (Some(path), None) => {
+            debug!("loading puzzle '{}'", path);
+            PuzzleConfig::from_path(path)?
+        }
+        (None, Some(name)) => {
+            debug!("loading preset '{}'", name);
+            preset(name)?
+        }
+        (None, None) => PuzzleConfig::default(),
+    };
+    apply_args(&mut config, &args)?;
+    defaults.apply(&mut config);
+
+    if config.slots.is_empty() {
+        anyhow::bail!("You must provide at least one character set");
+    }
+
+    let started = Instant::now();
+    let options = result_options(&args, &defaults)?;
+    if let Some(delegated) = delegate(&args, &config, &options) {
+        let written = Cell::new(0);
+        let generator = &delegated.generator;
+        let solutions = delegated
+            .matches
+            .into_iter()
+            .map(|word_match| output::annotate(generator, word_match));
+        let solutions = output::select_from(generator, Box::new(solutions), &options)?
+            .inspect(|_| written.set(written.get() + 1));
+        write_results(&args, &config, generator, Box::new(solutions), started)?;
+        options.finish()?;
+        info!(
+            "wrote {} matches in {:.1?}",
+            written.get(),
+            started.elapsed()
+        );
+        return Ok(written.get());
+    }
+
+    let mut generator = build_generator(&config)?;
+    generator.set_strategy(args.strategy);
+    if args.retry_confusables {
+        retry_confusables(&mut generator);
+    }
+
+    // Generate and display the words
+    let written = Cell::new(0);
+    let solutions =
+        output::select(&generator, &options)?.inspect(|_| written.set(written.get() + 1));
+    write_results(&args, &config, &generator, Box::new(solutions), started)?;
+    options.finish()?;
+
+    let stats = generator.stats();
+    info!(
+        "examined {} of {} combinations with {} dictionary hits; wrote {} matches in {:.1?}",
+        stats.examined,
+        generator.combination_count(),
+        stats.dictionary_hits,
+        written.get(),
+        started.elapsed()
+    );
+    if args.stats {
+        output::write_stats(&generator, started);
+    }
+    Ok(written.get())
+}
+
+/// Hands the puzzle to a running `daemon`, unless the output needs this process's own
+/// word lists, definitions or solver statistics.
+///
+/// The daemon only sends the matches, so reports, JSON documents and definitions, which
+/// describe the dictionaries and the work done, are always solved here.
+fn delegate(
+    args: &SolveArgs,
+    config: &PuzzleConfig,
+    options: &ResultOptions,
+) -> Option<daemon::Delegated> {
+    let output = match args.output {
+        Some(format) => Some(format),
+        None => config
+            .output
+            .as_deref()
+            .and_then(|format| format.parse().ok()),
+    };
+    if args.no_daemon
+        || args.strategy.is_some()
+        || args.retry_confusables
+        || args.stats
+        || args.explain
+        || args.prune
+        || args.report.is_some()
+        || config.report.is_some()
+        || output == Some(OutputFormat::Json)
+        || config.definitions.is_some()
+        || options.top.is_some()
+        || options.score
+    {
+        return None;
+    }
+    // Filtering and sampling need every match; otherwise the daemon can stop early
+    let limit = options
+        .limit
+        .filter(|_| options.filter.is_none() && options.sample.is_none());
+    daemon::delegate(config, limit)
+}
+
+/// Widens the slots with misread letters when the puzzle as given has no matches.
+///
+/// Each substitution is tried on its own and reported on stderr along with how many
+/// matches it finds; the slots then take every substitution that found any.
+fn retry_confusables(generator: &mut WordGenerator) {
+    if generator.iter_matches().next().is_some() {
+        return;
+    }
+
+    let slots = generator.slots().into_owned();
+    let mut widened = slots.clone();
+    let mut found = false;
+    for substitution in confusable_substitutions(&slots) {
+        let mut trial = slots.clone();
+        substitution.apply(&mut trial);
+        generator.set_slots(trial);
+        let matches = generator.count_matches();
+        debug!("reading {} finds {} matches", substitution, matches);
+        if matches > 0 {
+            eprintln!(
+                "No matches as given; reading {} finds {}",
+                substitution, matches
+            );
+            substitution.apply(&mut widened);
+            found = true;
+        }
+    }
+    if !found {
+        eprintln!("No matches as given, nor with any misread letter substituted");
+    }
+    generator.set_slots(widened);
+    generator.reset_stats();
+}
+
+/// Writes the solutions in the form the flags and puzzle file ask for.
+fn write_results(
+    args: &SolveArgs,
+    config: &PuzzleConfig,
+    generator: &WordGenerator,
+    solutions: Box<dyn Iterator<Item = Solution> + '_>,
+    started: Instant,
+) -> Result<()> {
+    if args.count {
+        return output::write_count(args.output.unwrap_or_default(), generator, solutions);
+    }
+    if args.prune {
+        if args
+            .output
+            .is_some_and(|format| format != OutputFormat::Text)
+            || args.format.is_some()
+            || args.report.is_some()
+            || args.columns
+            || args.group_by.is_some()
+            || args.explain
+        {
+            anyhow::bail!(
+                "--prune prints text and cannot be combined with --format, --report, \
+                 --columns, --group-by or --explain"
+            );
+        }
+        output::write_pruned(generator, solutions);
+        return Ok(());
+    }
+
+    if let Some(template) = &args.format {
+        if args.output.is_some() || args.report.is_some() || args.columns || args.group_by.is_some()
+        {
+            anyhow::bail!(
+                "--format cannot be combined with --output, --report, --columns or --group-by"
+            );
+        }
+        for solution in solutions {
+            output::write_line(&template.render(generator, &solution));
+        }
+        return Ok(());
+    }
+
+    let report = match args.report {
+        Some(format) => Some(format),
+        None => config.report.as_deref().map(parse_setting).transpose()?,
+    };
+    if let Some(format) = report {
+        print!("{}", report::render(format, generator, solutions, started));
+        return Ok(());
+    }
+
+    let format = match args.output {
+        Some(format) => format,
+        None => config
+            .output
+            .as_deref()
+            .map(parse_setting)
+            .transpose()?
+            .unwrap_or_default(),
+    };
+    if let Some(group_by) = args.group_by {
+        if format != OutputFormat::Text || args.explain || args.columns {
+            anyhow::bail!("--group-by only applies to text output without --explain or --columns");
+        }
+        output::write_groups(generator, solutions, group_by);
+        return Ok(());
+    }
+    if args.columns {
+        if format != OutputFormat::Text || args.explain || args.print0 {
+            anyhow::bail!("--columns only applies to text output without --explain or --print0");
+        }
+        output::write_columns(generator, solutions);
+        return Ok(());
+    }
+    if args.explain {
+        if format != OutputFormat::Text {
+            anyhow::bail!("--explain only applies to text output; JSON includes choices");
+        }
+        for solution in solutions {
+            let line = output::text_line(generator, &solution);
+            match output::explanation(generator, &solution.word_match.word) {
+                Some(explanation) => output::write_line(&format!("{}  {}", line, explanation)),
+                None => output::write_line(&line),
+            }
+        }
+        return Ok(());
+    }
+    output::write_solutions(format, generator, solutions, started)
+}
+
+/// Builds the generator for a puzzle, adding the user word list to the embedded one.
+pub fn build_generator(config: &PuzzleConfig) -> Result<WordGenerator> {
+    load_generator(config, None)
+}
+
+/// Builds the generator for a puzzle like [`build_generator`], sharing word list
+/// entries with the other generators built with `interner`.
+pub fn build_generator_with(config: &PuzzleConfig, interner: &Interner) -> Result<WordGenerator> {
+    load_generator(config, Some(interner))
+}
+
+fn load_generator(config: &PuzzleConfig, interner: Option<&Interner>) -> Result<WordGenerator> {
+    let loading = Instant::now();
+    let mut generator = match interner {
+        Some(interner) => config.to_generator_interned(interner)?,
+        None => config.to_generator()?,
+    };
+    if config.word_lists.is_empty()
+        && !config.all_combinations
+        && let Some(dictionary) = generator.dictionary_mut()
+    {
+        add_user_words(dictionary)?;
+        // User words are layered on afterwards, so exclusions must be applied again
+        if !config.exclude_word_lists.is_empty() {
+            dictionary.subtract(&config.excluded_words()?);
+        }
+    }
+
+    info!(
+        "loaded {} word lists in {:.1?}",
+        generator.dictionaries().len(),
+        loading.elapsed()
+    );
+    for dictionary in generator.dictionaries() {
+        debug!(
+            "word list '{}' has {} words",
+            dictionary.name(),
+            dictionary.len()
+        );
+    }
+    if !generator.constraints().is_empty() {
+        debug!(
+            "checking {} constraints per combination",
+            generator.constraints().len()
+        );
+    }
+    Ok(generator)
+}
+
+/// Loads a shipped preset, or explains how to enable them when they are not built in.
+#[cfg(feature = "presets")]
+fn preset(name: &str) -> Result<PuzzleConfig> {
+    PuzzleConfig::preset(name)
+}
+
+#[cfg(not(feature = "presets"))]
+fn preset(_name: &str) -> Result<PuzzleConfig> {
+    anyhow::bail!("presets require building with the 'presets' feature")
+}
+
+/// Solves every puzzle of a batch, applying the command-line flags to each.
+fn solve_batch(path: &str, args: &SolveArgs, defaults: &UserDefaults) -> Result<usize> {
+    if !args.char_sets.is_empty()
+        || args.slots_file.is_some()
+        || args.puzzle.is_some()
+        || args.preset.is_some()
+    {
+        anyhow::bail!(
+            "--batch cannot be combined with character sets, --slots-file, --puzzle or --preset"
+        );
+    }
+    if args.report.is_some()
+        || args.count
+        || args.format.is_some()
+        || args.columns
+        || args.group_by.is_some()
+        || args.retry_confusables
+    {
+        anyhow::bail!(
+            "--batch cannot be combined with --report, --count, --format, --columns, \
+             --group-by or --retry-confusables"
+        );
+    }
+
+    // Puzzles of a batch often load the same word lists, so their entries are shared
+    let interner = Interner::new();
+    let puzzles = batch_paths(Path::new(path))?.into_iter().map(|path| {
+        let name = path.file_stem().map_or_else(
+            || path.display().to_string(),
+            |stem| stem.to_string_lossy().into(),
+        );
+        let mut config = PuzzleConfig::from_path(&path.to_string_lossy())?;
+        apply_args(&mut config, args)?;
+        defaults.apply(&mut config);
+        let mut generator = build_generator_with(&config, &interner)?;
+        generator.set_strategy(args.strategy);
+        Ok((name, generator))
+    });
+    let options = result_options(args, defaults)?;
+    let format = args.output.or(defaults.output()?).unwrap_or_default();
+    output::write_batch(format, puzzles, &options)
+}
+
+/// Lists the puzzle files of a batch.
+///
+/// A directory contributes its `.toml` and `.json` files in name order; any other file
+/// is read as a manifest of puzzle paths, one per line with `#` comments, relative to
+/// the manifest's directory.
+fn batch_paths(path: &Path) -> Result<Vec<PathBuf>> {
+    if path.is_dir() {
+        let mut paths = Vec::new();
+        for entry in std::fs::read_dir(path)
+            .with_context(|| format!("Failed to read batch directory '{}'", path.display()))?
+        {
+            let entry_path = entry?.path();
+            if entry_path
+                .extension()
+                .is_some_and(|extension| extension == "toml" || extension == "json")
+            {
+                paths.push(entry_path);
+            }
+        }
+        paths.sort();
+        return Ok(paths);
+    }
+
+    let base = path.parent().unwrap_or(Path::new(""));
+    Ok(read_char_sets(&path.to_string_lossy())?
+        .into_iter()
+        .map(|line| base.join(line))
+        .collect())
+}
+
+/// Merges the command-line arguments into a puzzle definition.
+///
+/// Character sets given on the command line replace those of the puzzle file, options
+/// given on the command line override it, and switches and repeatable flags add to it.
+fn apply_args(config: &mut PuzzleConfig, args: &SolveArgs) -> Result<()> {
+    let char_sets = char_sets(&args.char_sets, args.slots_file.as_deref(), args.separator)?;
+    if !char_sets.is_empty() {
+        config.slots = char_sets;
+    }
+
+    config.word_lists.extend(args.word_list.iter().cloned());
+    config
+        .exclude_word_lists
+        .extend(args.exclude_wordlist.iter().cloned());
+    if args.require_all {
+        config.policy = DictionaryPolicy::All;
+    }
+    config.all_combinations |= args.all_combinations;
+    config.expand_inflections |= args.expand_inflections;
+    config.ignore_case |= args.ignore_case;
+    config.stem |= args.stem;
+    config.family_friendly |= args.family_friendly;
+    if let Some(normalize) = args.normalize {
+        config.normalize = normalize;
+    }
+    if let Some(proper_nouns) = args.proper_nouns {
+        config.proper_nouns = proper_nouns;
+    }
+    config.pos.extend(args.pos.iter().copied());
+    config.exclude_pos.extend(args.exclude_pos.iter().copied());
+    config.blocklists.extend(args.blocklist.iter().cloned());
+    config
+        .constraints
+        .extend(args.regex.iter().cloned().map(Constraint::Regex));
+    config
+        .constraints
+        .extend(args.rhyme.iter().cloned().map(Constraint::RhymesWith));
+    config
+        .constraints
+        .extend(args.syllables.map(Constraint::Syllables));
+    config
+        .constraints
+        .extend(args.spelled_number.map(Constraint::SpelledNumber));
+    config
+        .constraints
+        .extend(args.roman_numeral.map(Constraint::RomanNumeral));
+    if args.definitions.is_some() {
+        config.definitions = args.definitions.clone();
+    }
+    if args.pronunciations.is_some() {
+        config.pronunciations = args.pronunciations.clone();
+    }
+    Ok(())
+}
+
+/// Collects the options applied to the matches before they are written.
+fn result_options(args: &SolveArgs, defaults: &UserDefaults) -> Result<ResultOptions> {
+    if args.filter_batch && args.filter_cmd.is_none() {
+        anyhow::bail!("--filter-batch requires --filter-cmd");
+    }
+    if args.seed.is_some() && args.sample.is_none() {
+        anyhow::bail!("--seed requires --sample");
+    }
+    if args.sample.is_some() && args.top.is_some() {
+        anyhow::bail!("--sample cannot be combined with --top");
+    }
+
+    Ok(ResultOptions {
+        filter: args
+            .filter_cmd
+            .clone()
+            .map(|command| FilterCommand::new(command, args.filter_batch)),
+        limit: args.limit,
+        top: args.top,
+        sample: args.sample,
+        seed: args.seed,
+        score: args.format.as_ref().is_some_and(Template::uses_score),
+        threads: args.threads.or(defaults.threads).unwrap_or_else(|| {
+            std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get)
+        }),
+    })
+}
+
+/// Parses a format name taken from a puzzle file.
+fn parse_setting<T: std::str::FromStr<Err = String>>(value: &str) -> Result<T> {
+    value.parse().map_err(anyhow::Error::msg)
+}
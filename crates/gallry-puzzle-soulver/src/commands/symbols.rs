@@ -0,0 +1,82 @@
+//! The `symbols` subcommand, reading messages written in a symbol alphabet.
+
+use super::load_dictionary;
+use anyhow::{Context, Result};
+use argh::{ArgsInfo, FromArgs};
+use gallry_puzzle_soulver::{SymbolTable, WordGenerator};
+
+/// Read a message written in symbols, such as pigpen or carved sigils, through a table
+/// of the letters each symbol may stand for
+#[derive(ArgsInfo, FromArgs)]
+#[argh(
+    subcommand,
+    name = "symbols",
+    example = "gallery-puzzle-soulver symbols --table sigils.txt sun moon star",
+    example = "gallery-puzzle-soulver symbols --map sun=s --map moon=ae sun moon star / moon \
+               sun",
+    note = "Symbols are names of your choosing, separated by spaces, with / between words. \
+            Table files hold one 'symbol = letters' entry per line, where several letters \
+            mean the symbol is one of them and ? that it is unknown. Symbols missing from \
+            the table may be any letter not taken by another symbol."
+)]
+pub struct SymbolsArgs {
+    /// the symbols of the message, such as sun moon star
+    #[argh(positional)]
+    symbols: Vec<String>,
+
+    /// file of symbol = letters entries
+    #[argh(option)]
+    table: Option<String>,
+
+    /// the letters of a symbol as SYMBOL=LETTERS, e.g. moon=ae; may be repeated and
+    /// overrides the table
+    #[argh(option)]
+    map: Vec<String>,
+
+    /// word list to read against instead of the embedded one
+    #[argh(option, short = 'w')]
+    word_list: Option<String>,
+}
+
+/// Runs the `symbols` subcommand, returning how many words were found in all.
+pub fn run(args: SymbolsArgs) -> Result<usize> {
+    let mut table = match &args.table {
+        Some(path) => SymbolTable::from_path(path)?,
+        None => SymbolTable::new(),
+    };
+    for entry in &args.map {
+        let (symbol, letters) = entry
+            .split_once('=')
+            .with_context(|| format!("Invalid mapping '{}', expected e.g. moon=ae", entry))?;
+        table.insert(symbol, letters)?;
+    }
+
+    let symbols: Vec<&str> = args
+        .symbols
+        .iter()
+        .flat_map(|symbols| symbols.split_whitespace())
+        .collect();
+    let words: Vec<&[&str]> = symbols
+        .split(|symbol| *symbol == "/")
+        .filter(|word| !word.is_empty())
+        .collect();
+    if words.is_empty() {
+        anyhow::bail!("Give the symbols of the message, such as sun moon star");
+    }
+
+    let dictionary = load_dictionary(args.word_list.as_deref())?;
+    let mut generator = WordGenerator::with_dictionary(Vec::new(), dictionary);
+    let mut found = 0;
+    for symbols in &words {
+        generator.clear_constraints();
+        table.apply(&mut generator, symbols);
+        let matches: Vec<String> = generator.iter().collect();
+        found += matches.len();
+        if words.len() == 1 {
+            matches.iter().for_each(|word| println!("{}", word));
+        } else {
+            println!("{}: {}", symbols.join(" "), matches.join(", "));
+        }
+    }
+    Ok(found)
+}
@@ -0,0 +1,55 @@
+//! The `t9` subcommand, decoding words typed on a phone keypad.
+
+use super::load_dictionary;
+use anyhow::{Context, Result};
+use argh::{ArgsInfo, FromArgs};
+use gallry_puzzle_soulver::{WordGenerator, parse_keypad_slots};
+
+/// Decode key presses on a phone keypad, one digit per letter, into dictionary words
+#[derive(ArgsInfo, FromArgs)]
+#[argh(
+    subcommand,
+    name = "t9",
+    example = "gallery-puzzle-soulver t9 4663",
+    example = "gallery-puzzle-soulver t9 843 0 2?5",
+    note = "Keys 2 to 9 carry the letters printed on them and ? is an unknown key. Words \
+            are separated by spaces or the 0 key."
+)]
+pub struct T9Args {
+    /// the keys pressed, such as 4663
+    #[argh(positional)]
+    keys: Vec<String>,
+
+    /// word list to decode against instead of the embedded one
+    #[argh(option, short = 'w')]
+    word_list: Option<String>,
+}
+
+/// Runs the `t9` subcommand, returning how many words were found in all.
+pub fn run(args: T9Args) -> Result<usize> {
+    let words: Vec<&str> = args
+        .keys
+        .iter()
+        .flat_map(|keys| keys.split(|c: char| c == '0' || c.is_whitespace()))
+        .filter(|keys| !keys.is_empty())
+        .collect();
+    if words.is_empty() {
+        anyhow::bail!("Give the keys pressed, such as 4663");
+    }
+
+    let dictionary = load_dictionary(args.word_list.as_deref())?;
+    let mut generator = WordGenerator::with_dictionary(Vec::new(), dictionary);
+    let mut found = 0;
+    for keys in &words {
+        let slots = parse_keypad_slots(keys).with_context(|| format!("Invalid keys '{}'", keys))?;
+        generator.set_slots(slots);
+        let matches: Vec<String> = generator.iter().collect();
+        found += matches.len();
+        if words.len() == 1 {
+            matches.iter().for_each(|word| println!("{}", word));
+        } else {
+            println!("{}: {}", keys, matches.join(", "));
+        }
+    }
+    Ok(found)
+}
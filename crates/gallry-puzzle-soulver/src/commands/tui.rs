@@ -0,0 +1,399 @@
+//! The `tui` subcommand, a full-screen grid of slots with live-updating matches.
+
+use anyhow::Result;
+use argh::{ArgsInfo, FromArgs};
+
+/// Edit the slots in a full-screen grid while the matching words update live
+#[derive(ArgsInfo, FromArgs)]
+#[argh(subcommand, name = "tui")]
+#[cfg_attr(not(feature = "tui"), allow(dead_code))]
+pub struct TuiArgs {
+    /// character sets for each position (e.g., ABC DEF GHI), with ? for any letter,
+    /// [a-f] ranges, !xyz for any letter but these, and \ escapes
+    #[argh(positional)]
+    char_sets: Vec<String>,
+
+    /// read character sets from a file, one per line with # comments, or - for stdin
+    #[argh(option)]
+    slots_file: Option<String>,
+
+    /// separator between the sets when the puzzle is one argument, like cbr/aio/tse
+    #[argh(option, default = "'/'")]
+    separator: char,
+
+    /// optional path to a custom word list file (repeatable)
+    #[argh(option, short = 'w')]
+    word_list: Vec<String>,
+
+    /// ignore case when matching against the word list
+    #[argh(switch, short = 'i')]
+    ignore_case: bool,
+}
+
+/// Runs the `tui` subcommand.
+#[cfg(feature = "tui")]
+pub fn run(args: TuiArgs) -> Result<()> {
+    use super::char_sets;
+    use super::solve::build_generator;
+    use gallry_puzzle_soulver::PuzzleConfig;
+
+    let config = PuzzleConfig {
+        slots: char_sets(&args.char_sets, args.slots_file.as_deref(), args.separator)?,
+        word_lists: args.word_list,
+        ignore_case: args.ignore_case,
+        ..Default::default()
+    };
+    let mut app = app::App::new(build_generator(&config)?);
+
+    let mut terminal = ratatui::init();
+    let result = app.run(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+#[cfg(not(feature = "tui"))]
+pub fn run(_args: TuiArgs) -> Result<()> {
+    anyhow::bail!("the terminal interface requires building with the 'tui' feature")
+}
+
+#[cfg(feature = "tui")]
+mod app {
+    use crate::output;
+    use anyhow::Result;
+    use gallry_puzzle_soulver::{Slot, WordGenerator};
+    use ratatui::DefaultTerminal;
+    use ratatui::Frame;
+    use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+    use ratatui::layout::{Constraint, Layout, Rect};
+    use ratatui::style::{Modifier, Style, Stylize};
+    use ratatui::text::{Line, Span};
+    use ratatui::widgets::{Block, List, Paragraph};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::mpsc::{self, Receiver, Sender};
+    use std::time::Duration;
+
+    const KEYS: &str = "←→ slot  ↑↓ option  space toggle  a-z add  backspace remove option  \
+                        enter new slot  delete remove slot  esc quit";
+
+    /// How long to wait for a key press before checking for new matches
+    const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+    /// A puzzle for the solver thread, numbered so that stale answers can be dropped.
+    struct Request {
+        id: u64,
+        slots: Vec<Slot>,
+        /// Most matches to find, as many as the list can show
+        limit: usize,
+    }
+
+    /// The solver thread's answer to a request.
+    struct Answer {
+        id: u64,
+        matches: Vec<String>,
+        /// Whether there were more matches than the limit
+        more: bool,
+        combinations: u128,
+    }
+
+    /// A character option that can be switched off without forgetting it.
+    struct Choice {
+        c: char,
+        enabled: bool,
+    }
+
+    /// The grid being edited and the matches for its current state.
+    pub struct App {
+        /// Options of every slot, in position order
+        slots: Vec<Vec<Choice>>,
+        /// Selected slot and option within it
+        cursor: (usize, usize),
+        /// Puzzles for the solver thread, which owns the generator
+        requests: Sender<Request>,
+        answers: Receiver<Answer>,
+        /// Number of the newest request, which the solver stops any older search for
+        latest: Arc<AtomicU64>,
+        /// Whether the newest request is still being solved
+        solving: bool,
+        matches: Vec<String>,
+        more: bool,
+        combinations: u128,
+        /// Rows the match list showed when last drawn
+        visible: usize,
+    }
+
+    impl App {
+        /// Starts editing the generator's slots, with wildcards expanded to their letters.
+        ///
+        /// The generator moves to a solver thread, so that typing never waits for a
+        /// search to finish.
+        pub fn new(generator: WordGenerator) -> Self {
+            let slots = generator
+                .slots()
+                .iter()
+                .map(|slot| {
+                    slot.options()
+                        .iter()
+                        .map(|&c| Choice { c, enabled: true })
+                        .collect()
+                })
+                .collect();
+            let (requests, receiver) = mpsc::channel();
+            let (sender, answers) = mpsc::channel();
+            let latest = Arc::new(AtomicU64::new(0));
+            let solver_latest = Arc::clone(&latest);
+            std::thread::spawn(move || solve_requests(generator, receiver, sender, solver_latest));
+            Self {
+                slots,
+                cursor: (0, 0),
+                requests,
+                answers,
+                latest,
+                solving: false,
+                matches: Vec::new(),
+                more: false,
+                combinations: 0,
+                visible: 0,
+            }
+        }
+
+        /// Draws the interface and handles key presses until the user quits.
+        pub fn run(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+            // The first draw measures how many matches fit
+            terminal.draw(|frame| self.draw(frame))?;
+            self.update();
+            loop {
+                while let Ok(answer) = self.answers.try_recv() {
+                    if answer.id == self.latest.load(Ordering::Relaxed) {
+                        self.solving = false;
+                        self.matches = answer.matches;
+                        self.more = answer.more;
+                        self.combinations = answer.combinations;
+                    }
+                }
+                terminal.draw(|frame| self.draw(frame))?;
+                // A taller list has room for matches beyond the last limit
+                if !self.solving && self.more && self.visible > self.matches.len() {
+                    self.update();
+                }
+
+                if !event::poll(POLL_INTERVAL)? {
+                    continue;
+                }
+                let Event::Key(key) = event::read()? else {
+                    continue;
+                };
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+
+                let (slot, option) = self.cursor;
+                match key.code {
+                    KeyCode::Esc => return Ok(()),
+                    KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        return Ok(());
+                    }
+                    KeyCode::Left => self.move_to(slot.saturating_sub(1), option),
+                    KeyCode::Right => self.move_to(slot + 1, option),
+                    KeyCode::Up => self.move_to(slot, option.saturating_sub(1)),
+                    KeyCode::Down => self.move_to(slot, option + 1),
+                    KeyCode::Char(' ') => {
+                        if let Some(choice) = self.choice_mut() {
+                            choice.enabled = !choice.enabled;
+                            self.update();
+                        }
+                    }
+                    KeyCode::Char(c) => self.add_option(c),
+                    KeyCode::Backspace if self.choice_mut().is_some() => {
+                        self.slots[slot].remove(option);
+                        self.move_to(slot, option);
+                        self.update();
+                    }
+                    KeyCode::Enter => {
+                        let index = if self.slots.is_empty() { 0 } else { slot + 1 };
+                        self.slots.insert(index, Vec::new());
+                        self.move_to(index, 0);
+                        self.update();
+                    }
+                    KeyCode::Delete if !self.slots.is_empty() => {
+                        self.slots.remove(slot);
+                        self.move_to(slot, option);
+                        self.update();
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        /// Moves the cursor, keeping it inside the grid.
+        fn move_to(&mut self, slot: usize, option: usize) {
+            let slot = slot.min(self.slots.len().saturating_sub(1));
+            let options = self.slots.get(slot).map_or(0, Vec::len);
+            self.cursor = (slot, option.min(options.saturating_sub(1)));
+        }
+
+        fn choice_mut(&mut self) -> Option<&mut Choice> {
+            let (slot, option) = self.cursor;
+            self.slots.get_mut(slot)?.get_mut(option)
+        }
+
+        /// Adds an option to the selected slot, or re-enables it if already present.
+        fn add_option(&mut self, c: char) {
+            if self.slots.is_empty() {
+                self.slots.push(Vec::new());
+            }
+            let (slot, _) = self.cursor;
+            let options = &mut self.slots[slot];
+            let option = match options.iter().position(|choice| choice.c == c) {
+                Some(option) => {
+                    options[option].enabled = true;
+                    option
+                }
+                None => {
+                    options.push(Choice { c, enabled: true });
+                    options.len() - 1
+                }
+            };
+            self.move_to(slot, option);
+            self.update();
+        }
+
+        /// Asks the solver thread for the matches of the enabled options, in place of
+        /// any earlier request.
+        fn update(&mut self) {
+            let slots: Vec<Slot> = self
+                .slots
+                .iter()
+                .map(|options| {
+                    Slot::new(
+                        options
+                            .iter()
+                            .filter(|choice| choice.enabled)
+                            .map(|choice| choice.c)
+                            .collect(),
+                    )
+                })
+                .collect();
+
+            let id = self.latest.fetch_add(1, Ordering::Relaxed) + 1;
+            // A slot with every option switched off cannot spell anything
+            if slots.is_empty() || slots.iter().any(|slot| slot.options().is_empty()) {
+                self.solving = false;
+                self.matches.clear();
+                self.more = false;
+                self.combinations = 0;
+                return;
+            }
+
+            self.solving = true;
+            let limit = self.visible.max(1);
+            // The solver only stops when the app has quit
+            self.requests.send(Request { id, slots, limit }).ok();
+        }
+
+        fn draw(&mut self, frame: &mut Frame) {
+            let [main, help] =
+                Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+            let [grid, matches] =
+                Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+                    .areas(main);
+
+            self.draw_grid(frame, grid);
+
+            // Inside the list's border
+            self.visible = usize::from(matches.height.saturating_sub(2));
+            let title = if self.solving {
+                " solving… ".to_string()
+            } else if self.more {
+                format!(
+                    " first {} matches of {} combinations ",
+                    self.matches.len(),
+                    self.combinations
+                )
+            } else {
+                format!(
+                    " {} matches of {} combinations ",
+                    self.matches.len(),
+                    self.combinations
+                )
+            };
+            let list = List::new(self.matches.iter().map(String::as_str))
+                .block(Block::bordered().title(title));
+            frame.render_widget(list, matches);
+            frame.render_widget(Paragraph::new(KEYS).dim(), help);
+        }
+
+        /// Draws one column per slot, with disabled options struck through.
+        fn draw_grid(&self, frame: &mut Frame, area: Rect) {
+            let rows = self.slots.iter().map(Vec::len).max().unwrap_or(0);
+            let header: Vec<Span> = (1..=self.slots.len())
+                .map(|position| Span::from(format!("{:>3}", position)).bold())
+                .collect();
+
+            let mut lines = vec![Line::from(header)];
+            for row in 0..rows {
+                let cells: Vec<Span> = self
+                    .slots
+                    .iter()
+                    .enumerate()
+                    .map(|(slot, options)| {
+                        let Some(choice) = options.get(row) else {
+                            return Span::from("   ");
+                        };
+                        let mut style = Style::default();
+                        if !choice.enabled {
+                            style = style.add_modifier(Modifier::CROSSED_OUT | Modifier::DIM);
+                        }
+                        if self.cursor == (slot, row) {
+                            style = style.add_modifier(Modifier::REVERSED);
+                        }
+                        Span::styled(format!("{:>3}", choice.c), style)
+                    })
+                    .collect();
+                lines.push(Line::from(cells));
+            }
+
+            let grid = Paragraph::new(lines).block(Block::bordered().title(" Slots "));
+            frame.render_widget(grid, area);
+        }
+    }
+
+    /// Solves requests on the solver thread until the app drops its end of the channel.
+    ///
+    /// Only the newest waiting request is solved, and a search stops at its next match
+    /// once a newer request arrives.
+    fn solve_requests(
+        mut generator: WordGenerator,
+        requests: Receiver<Request>,
+        answers: Sender<Answer>,
+        latest: Arc<AtomicU64>,
+    ) {
+        'requests: while let Ok(mut request) = requests.recv() {
+            while let Ok(newer) = requests.try_recv() {
+                request = newer;
+            }
+            generator.set_slots(request.slots);
+            let mut matches = Vec::new();
+            let mut more = false;
+            for solution in output::solve(&generator) {
+                if latest.load(Ordering::Relaxed) != request.id {
+                    continue 'requests;
+                }
+                if matches.len() == request.limit {
+                    more = true;
+                    break;
+                }
+                matches.push(output::text_line(&generator, &solution));
+            }
+            let answer = Answer {
+                id: request.id,
+                matches,
+                more,
+                combinations: generator.combination_count(),
+            };
+            if answers.send(answer).is_err() {
+                return;
+            }
+        }
+    }
+}
@@ -0,0 +1,77 @@
+//! The `verify` subcommand.
+
+use super::solve::build_generator;
+use crate::defaults::UserDefaults;
+use anyhow::{Context, Result};
+use argh::{ArgsInfo, FromArgs};
+use gallry_puzzle_soulver::PuzzleConfig;
+use tracing::info;
+
+/// Check candidate answers against a puzzle, naming the first check each one fails
+#[derive(ArgsInfo, FromArgs)]
+#[argh(subcommand, name = "verify")]
+pub struct VerifyArgs {
+    /// puzzle definition file (TOML or JSON) the candidates should solve
+    #[argh(option)]
+    puzzle: String,
+
+    /// file of candidate answers, one per line with # comments; read from stdin when
+    /// omitted
+    #[argh(positional)]
+    candidates: Option<String>,
+
+    /// ignore the user defaults in ~/.config/gallry/config.toml (or $GALLRY_CONFIG)
+    #[argh(switch)]
+    no_config: bool,
+}
+
+/// Runs the `verify` subcommand, returning how many candidates are solutions.
+pub fn run(args: VerifyArgs) -> Result<usize> {
+    let defaults = UserDefaults::load(args.no_config)?;
+    let mut config = PuzzleConfig::from_path(&args.puzzle)?;
+    defaults.apply(&mut config);
+    let generator = build_generator(&config)?;
+
+    let candidates = read_candidates(args.candidates.as_deref())?;
+    let mut solutions = 0;
+    for candidate in &candidates {
+        match generator.verify(candidate) {
+            Ok(word_match) if word_match.dictionaries.is_empty() => {
+                println!("{}: ok", candidate);
+                solutions += 1;
+            }
+            Ok(word_match) => {
+                println!(
+                    "{}: ok, in {}",
+                    candidate,
+                    word_match.dictionaries.join(", ")
+                );
+                solutions += 1;
+            }
+            Err(rejection) => println!("{}: {}", candidate, rejection),
+        }
+    }
+    info!(
+        "{} of {} candidates solve the puzzle",
+        solutions,
+        candidates.len()
+    );
+    Ok(solutions)
+}
+
+/// Reads candidate answers, skipping blank lines and `#` comments.
+fn read_candidates(path: Option<&str>) -> Result<Vec<String>> {
+    let content = match path {
+        Some(path) => std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read candidates from '{}'", path))?,
+        None => std::io::read_to_string(std::io::stdin())
+            .context("Failed to read candidates from stdin")?,
+    };
+
+    Ok(content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
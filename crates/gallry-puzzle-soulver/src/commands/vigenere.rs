@@ -0,0 +1,87 @@
+//! The `vigenere` subcommand, breaking or applying Vigenère ciphers.
+
+use super::load_dictionary;
+use anyhow::Result;
+use argh::{ArgsInfo, FromArgs};
+use gallry_puzzle_soulver::{
+    VigenereOptions, vigenere_decrypt, vigenere_decryptions, vigenere_encrypt,
+};
+
+/// Search for the key of a Vigenère cipher and rank the plaintexts, or apply a known key
+#[derive(ArgsInfo, FromArgs)]
+#[argh(
+    subcommand,
+    name = "vigenere",
+    example = "gallery-puzzle-soulver vigenere --word-keys 'Ehq zpy uh tn fwp vmhp'",
+    example = "gallery-puzzle-soulver vigenere --key lamp 'Ehq zpy uh tn fwp vmhp'",
+    example = "gallery-puzzle-soulver vigenere --key lamp --encrypt 'The key is in the vase'",
+    note = "Without --word-keys, keys are found from letter frequencies, which needs a few \
+            dozen letters of ciphertext for each letter of the key."
+)]
+pub struct VigenereArgs {
+    /// the ciphertext, or the plaintext with --encrypt
+    #[argh(positional)]
+    text: Vec<String>,
+
+    /// decipher with this key instead of searching for one
+    #[argh(option)]
+    key: Option<String>,
+
+    /// encipher the text with --key instead of deciphering it
+    #[argh(switch)]
+    encrypt: bool,
+
+    /// longest key to search for (default: 8)
+    #[argh(option, default = "8")]
+    max_key_len: usize,
+
+    /// also try every dictionary word as a key
+    #[argh(switch)]
+    word_keys: bool,
+
+    /// how many plaintexts to list (default: 5)
+    #[argh(option, default = "5")]
+    show: usize,
+
+    /// word list to score plaintexts against instead of the embedded one
+    #[argh(option, short = 'w')]
+    word_list: Option<String>,
+}
+
+/// Runs the `vigenere` subcommand.
+pub fn run(args: VigenereArgs) -> Result<()> {
+    let text = args.text.join(" ");
+    match (args.key.as_deref(), args.encrypt) {
+        (Some(key), encrypt) => {
+            let apply = if encrypt {
+                vigenere_encrypt
+            } else {
+                vigenere_decrypt
+            };
+            let Some(output) = apply(&text, key) else {
+                anyhow::bail!("Key '{}' must be letters from a to z", key);
+            };
+            println!("{}", output);
+            return Ok(());
+        }
+        (None, true) => anyhow::bail!("--encrypt requires --key"),
+        (None, false) => {}
+    }
+
+    let dictionary = load_dictionary(args.word_list.as_deref())?;
+    let options = VigenereOptions {
+        max_key_len: args.max_key_len,
+        word_keys: args.word_keys,
+        max_results: args.show,
+    };
+    for decryption in vigenere_decryptions(&text, &dictionary, &options) {
+        println!(
+            "{:<width$} {:>3.0}% words  {}",
+            decryption.key,
+            decryption.score.coverage * 100.0,
+            decryption.plaintext,
+            width = args.max_key_len
+        );
+    }
+    Ok(())
+}
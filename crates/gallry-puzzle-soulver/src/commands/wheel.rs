@@ -0,0 +1,81 @@
+//! The `wheel` subcommand, finding dictionary words read by stepping around a wheel.
+
+use super::load_dictionary;
+use anyhow::Result;
+use argh::{ArgsInfo, FromArgs};
+use gallry_puzzle_soulver::{Rotation, WheelOptions, wheel_words};
+
+/// Find the dictionary words read by stepping around a circle of letters, a fixed or
+/// varying number of letters at a time, in either direction
+#[derive(ArgsInfo, FromArgs)]
+#[argh(
+    subcommand,
+    name = "wheel",
+    example = "gallery-puzzle-soulver wheel tsean",
+    example = "gallery-puzzle-soulver wheel t s e a n --stride 1 --stride 2 --variable",
+    note = "Letters are given clockwise. Each word is reported with the position of its \
+            first letter, counting from 1, the way round it reads and the stride of its \
+            steps, or of each step if they differ."
+)]
+pub struct WheelArgs {
+    /// the letters around the wheel, clockwise
+    #[argh(positional)]
+    letters: Vec<String>,
+
+    /// shortest word to report (default: 3)
+    #[argh(option, default = "3")]
+    min_len: usize,
+
+    /// step this many letters at a time (repeatable; default: every stride)
+    #[argh(option)]
+    stride: Vec<usize>,
+
+    /// let each step use a different one of the strides
+    #[argh(switch)]
+    variable: bool,
+
+    /// only read clockwise or counterclockwise
+    #[argh(option)]
+    rotation: Option<Rotation>,
+
+    /// word list to search for instead of the embedded one
+    #[argh(option, short = 'w')]
+    word_list: Option<String>,
+}
+
+/// Runs the `wheel` subcommand, returning how many words were found.
+pub fn run(args: WheelArgs) -> Result<usize> {
+    let letters = args.letters.concat();
+    if letters.trim().is_empty() {
+        anyhow::bail!("Give the letters around the wheel, such as tsean");
+    }
+    let mut options = WheelOptions {
+        min_len: args.min_len,
+        strides: args.stride.clone(),
+        variable: args.variable,
+        ..WheelOptions::default()
+    };
+    if let Some(rotation) = args.rotation {
+        options.rotations = vec![rotation];
+    }
+
+    let dictionary = load_dictionary(args.word_list.as_deref())?;
+    let found = wheel_words(&letters, &dictionary, &options);
+    for word in &found {
+        let strides = match word.stride() {
+            Some(stride) => format!("stride {}", stride),
+            None => {
+                let strides: Vec<String> = word.strides.iter().map(usize::to_string).collect();
+                format!("strides {}", strides.join(","))
+            }
+        };
+        println!(
+            "{}  {} {} {}",
+            word.word,
+            word.start + 1,
+            word.rotation,
+            strides
+        );
+    }
+    Ok(found.len())
+}
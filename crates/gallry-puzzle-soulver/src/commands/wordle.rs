@@ -0,0 +1,132 @@
+//! The `wordle` subcommand.
+
+use super::load_dictionary;
+use anyhow::{Context, Result};
+use argh::{ArgsInfo, FromArgs};
+use gallry_puzzle_soulver::{Feedback, best_guess};
+use std::str::FromStr;
+use tracing::info;
+
+/// Candidates scored against when recommending a guess; larger lists are sampled evenly
+const SCORED_CANDIDATES: usize = 2000;
+
+/// Filter Wordle answers by the feedback so far and recommend the next guess
+#[derive(ArgsInfo, FromArgs)]
+#[argh(
+    subcommand,
+    name = "wordle",
+    example = "gallery-puzzle-soulver wordle crane:..y.g slept:.g..g"
+)]
+pub struct WordleArgs {
+    /// rounds played so far, each a guess and its feedback like crane:..y.g, with g for
+    /// green, y for yellow and . (or b or -) for gray
+    #[argh(positional)]
+    rounds: Vec<Round>,
+
+    /// length of the answer, when no round has been played yet (default: 5)
+    #[argh(option)]
+    length: Option<usize>,
+
+    /// word list to take answers and guesses from instead of the embedded one
+    #[argh(option, short = 'w')]
+    word_list: Option<String>,
+
+    /// recommend only guesses that could still be the answer, as hard mode requires
+    #[argh(switch)]
+    hard: bool,
+
+    /// how many of the remaining candidates to list (default: 20)
+    #[argh(option, default = "20")]
+    show: usize,
+}
+
+/// A guess and the feedback the game showed for it.
+struct Round {
+    guess: String,
+    feedback: Feedback,
+}
+
+impl FromStr for Round {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let (guess, feedback) = s
+            .split_once(':')
+            .ok_or_else(|| format!("invalid round '{}', expected guess:feedback", s))?;
+        let feedback: Feedback = feedback.parse()?;
+        if feedback.marks().len() != guess.chars().count() {
+            return Err(format!(
+                "invalid round '{}', the feedback needs one mark per letter",
+                s
+            ));
+        }
+        Ok(Round {
+            guess: guess.to_lowercase(),
+            feedback,
+        })
+    }
+}
+
+/// Runs the `wordle` subcommand.
+pub fn run(args: WordleArgs) -> Result<()> {
+    let length = match (args.rounds.first(), args.length) {
+        (Some(round), _) => round.guess.chars().count(),
+        (None, length) => length.unwrap_or(5),
+    };
+    if let Some(round) = args
+        .rounds
+        .iter()
+        .find(|round| round.guess.chars().count() != length)
+    {
+        anyhow::bail!("Guess '{}' does not have {} letters", round.guess, length);
+    }
+
+    let dictionary = load_dictionary(args.word_list.as_deref())?;
+    let words: Vec<&str> = dictionary
+        .words_of_len(length)
+        .filter(|word| word.chars().all(|c| c.is_ascii_lowercase()))
+        .collect();
+    let candidates: Vec<&str> = words
+        .iter()
+        .copied()
+        .filter(|word| {
+            args.rounds
+                .iter()
+                .all(|round| Feedback::score(&round.guess, word) == round.feedback)
+        })
+        .collect();
+    info!(
+        "{} of {} words of {} letters fit {} rounds",
+        candidates.len(),
+        words.len(),
+        length,
+        args.rounds.len()
+    );
+
+    match candidates.as_slice() {
+        [] => anyhow::bail!("No word of the word list fits every round"),
+        [answer] => {
+            println!("The answer is '{}'.", answer);
+            return Ok(());
+        }
+        _ => {}
+    }
+    println!("{} candidates", candidates.len());
+    for word in candidates.iter().take(args.show) {
+        println!("  {}", word);
+    }
+    if candidates.len() > args.show {
+        println!("  ...");
+    }
+
+    let guesses = if args.hard { &candidates } else { &words };
+    let step = candidates.len().div_ceil(SCORED_CANDIDATES);
+    let scored: Vec<&str> = candidates.iter().copied().step_by(step).collect();
+    let (guess, information) =
+        best_guess(guesses, &scored).context("No guess is available from the word list")?;
+    println!(
+        "Next guess: {} (expected {:.2} bits of information)",
+        guess, information
+    );
+    Ok(())
+}
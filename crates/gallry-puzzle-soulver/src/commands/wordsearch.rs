@@ -0,0 +1,70 @@
+//! The `wordsearch` subcommand, finding dictionary words hidden in a letter grid.
+
+use super::load_dictionary;
+use anyhow::Result;
+use argh::{ArgsInfo, FromArgs};
+use gallry_puzzle_soulver::{Direction, LetterGrid, WordSearchOptions, word_search};
+
+/// Find the dictionary words written along the rows, columns and diagonals of a letter
+/// grid, forwards or backwards
+#[derive(ArgsInfo, FromArgs)]
+#[argh(
+    subcommand,
+    name = "wordsearch",
+    example = "gallery-puzzle-soulver wordsearch cat oxo wet",
+    example = "gallery-puzzle-soulver wordsearch --grid grid.txt --min-len 5",
+    note = "Positions are given as row:column of the first letter, counting from 1 at the \
+            top left, followed by the direction the word reads in."
+)]
+pub struct WordSearchArgs {
+    /// the rows of the grid, top first
+    #[argh(positional)]
+    rows: Vec<String>,
+
+    /// file with the grid, one row per line
+    #[argh(option)]
+    grid: Option<String>,
+
+    /// shortest word to report (default: 3)
+    #[argh(option, default = "3")]
+    min_len: usize,
+
+    /// only read in this direction, such as right or down-left (repeatable)
+    #[argh(option)]
+    direction: Vec<Direction>,
+
+    /// word list to search for instead of the embedded one
+    #[argh(option, short = 'w')]
+    word_list: Option<String>,
+}
+
+/// Runs the `wordsearch` subcommand, returning how many words were found.
+pub fn run(args: WordSearchArgs) -> Result<usize> {
+    let grid = match &args.grid {
+        Some(_) if !args.rows.is_empty() => {
+            anyhow::bail!("--grid cannot be combined with rows on the command line")
+        }
+        Some(path) => LetterGrid::from_path(path)?,
+        None => LetterGrid::from_rows(&args.rows)?,
+    };
+    let mut options = WordSearchOptions {
+        min_len: args.min_len,
+        ..WordSearchOptions::default()
+    };
+    if !args.direction.is_empty() {
+        options.directions = args.direction.clone();
+    }
+
+    let dictionary = load_dictionary(args.word_list.as_deref())?;
+    let found = word_search(&grid, &dictionary, &options);
+    for word in &found {
+        println!(
+            "{}  {}:{} {}",
+            word.word,
+            word.row + 1,
+            word.column + 1,
+            word.direction
+        );
+    }
+    Ok(found.len())
+}
@@ -0,0 +1,580 @@
+//! Puzzle definitions that can be saved to and loaded from TOML or JSON files.
+//!
+//! A [`PuzzleConfig`] declares everything needed to reproduce a solve: the slots,
+//! constraints, word lists and matching options, plus the preferred output format and
+//! descriptive metadata. It is the one representation of a puzzle shared by the
+//! command line, the server, the RPC protocol and the WebAssembly bindings.
+//!
+//! Files carry the [`SchemaVersion`] they were written for, so later releases can tell
+//! old definitions apart; files without one are read as version 1.
+//!
+//! ```toml
+//! version = 1
+//! slots = ["cbr", "aio", "tse"]
+//! word_lists = ["words.txt"]
+//! ignore_case = true
+//! pos = ["noun"]
+//!
+//! [[constraints]]
+//! ends-with = "t"
+//!
+//! [[constraints]]
+//! regex = "^[^aeiou]"
+//!
+//! [metadata]
+//! name = "west wing portraits"
+//! ```
+
+use crate::{
+    Blocklist, Constraint, Dictionary, DictionaryPolicy, InflectionRules, Interner, LoadOptions,
+    MatchOptions, Normalization, PartOfSpeech, ProperNouns, WordGenerator, parse_slots, split_sets,
+};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::num::NonZeroUsize;
+use std::path::Path;
+
+// Embed the shipped gallery presets at compile time, by name, as
+// `("name", include_str!("../data/presets/name.toml"))`
+#[cfg(feature = "presets")]
+const PRESETS: &[(&str, &str)] = &[];
+
+/// Serializes option types through their `Display` and `FromStr` implementations, so
+/// files accept exactly the same spellings as the command line.
+macro_rules! serde_via_str {
+    ($($ty:ty),*) => {
+        $(
+            impl Serialize for $ty {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.collect_str(self)
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $ty {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    String::deserialize(deserializer)?
+                        .parse()
+                        .map_err(serde::de::Error::custom)
+                }
+            }
+        )*
+    };
+}
+
+serde_via_str!(DictionaryPolicy, Normalization, PartOfSpeech, ProperNouns);
+
+/// Reads slots written either as a list of sets or as one `/`-separated string.
+fn deserialize_slots<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Slots {
+        List(Vec<String>),
+        Compact(String),
+    }
+
+    Ok(match Slots::deserialize(deserializer)? {
+        Slots::List(sets) => sets,
+        Slots::Compact(puzzle) => split_sets(&puzzle, '/'),
+    })
+}
+
+/// The version of the puzzle definition format a file was written for.
+///
+/// Reading a definition newer than [`SchemaVersion::CURRENT`] fails rather than
+/// silently ignoring what this release does not understand.
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::{PuzzleConfig, SchemaVersion};
+///
+/// let config = PuzzleConfig::from_toml_str("slots = \"ab/c\"").unwrap();
+/// assert_eq!(config.version, SchemaVersion::CURRENT);
+///
+/// let error = PuzzleConfig::from_toml_str("version = 99").unwrap_err();
+/// assert!(format!("{:#}", error).contains("version 99 is newer"));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SchemaVersion(u32);
+
+impl SchemaVersion {
+    /// The version written by this release
+    pub const CURRENT: Self = Self(1);
+
+    /// Returns the version number.
+    pub fn get(self) -> u32 {
+        self.0
+    }
+}
+
+impl Default for SchemaVersion {
+    fn default() -> Self {
+        Self::CURRENT
+    }
+}
+
+impl fmt::Display for SchemaVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Serialize for SchemaVersion {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u32(self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for SchemaVersion {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let version = Self(u32::deserialize(deserializer)?);
+        if version.0 == 0 {
+            return Err(serde::de::Error::custom(
+                "puzzle definition versions start at 1",
+            ));
+        }
+        if version > Self::CURRENT {
+            return Err(serde::de::Error::custom(format!(
+                "puzzle definition version {} is newer than the supported version {}",
+                version,
+                Self::CURRENT
+            )));
+        }
+        Ok(version)
+    }
+}
+
+/// Descriptive information about a puzzle, which does not affect solving.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PuzzleMetadata {
+    /// Short name, such as the room or painting the puzzle was found in
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Free-form notes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Where the puzzle came from, such as a URL or a screenshot's path
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+impl PuzzleMetadata {
+    /// Returns `true` if no field is set.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// A complete, reproducible puzzle definition.
+///
+/// Every field is optional in files; missing fields take their default values.
+/// Relative paths are resolved against the directory of the file they were read from.
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::PuzzleConfig;
+///
+/// let config = PuzzleConfig::from_toml_str(
+///     r#"
+///     slots = ["cb", "a", "t"]
+///
+///     [[constraints]]
+///     starts-with = "c"
+///     "#,
+/// )
+/// .unwrap();
+///
+/// let words: Vec<String> = config.to_generator().unwrap().iter().collect();
+/// assert_eq!(words, vec!["cat"]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PuzzleConfig {
+    /// Version of the definition format; see [`SchemaVersion`]
+    pub version: SchemaVersion,
+    /// Character options for each position, one string per slot in the notation
+    /// accepted by [`Slot::from_str`](crate::Slot#impl-FromStr-for-Slot); files may
+    /// also give the whole puzzle as one string such as `"cbr/aio/tse"`
+    #[serde(deserialize_with = "deserialize_slots")]
+    pub slots: Vec<String>,
+    /// Conditions on the whole word
+    pub constraints: Vec<Constraint>,
+    /// Paths of word lists to filter against; the embedded list is used when empty
+    pub word_lists: Vec<String>,
+    /// Paths of word lists whose words are removed from the word lists before solving
+    pub exclude_word_lists: Vec<String>,
+    /// Whether words must appear in any or all word lists
+    pub policy: DictionaryPolicy,
+    /// Yield every combination without dictionary filtering
+    pub all_combinations: bool,
+    /// Unicode normalization applied to the word lists
+    pub normalize: Normalization,
+    /// Expand the word lists with plurals and verb inflections
+    pub expand_inflections: bool,
+    /// Ignore case when matching against the word lists
+    pub ignore_case: bool,
+    /// Also match words whose stem is in the word lists
+    pub stem: bool,
+    /// Whether capitalized proper nouns match
+    pub proper_nouns: ProperNouns,
+    /// Only match words tagged with one of these parts of speech
+    pub pos: Vec<PartOfSpeech>,
+    /// Never match words tagged with one of these parts of speech
+    pub exclude_pos: Vec<PartOfSpeech>,
+    /// Suppress offensive words using the embedded blocklist
+    pub family_friendly: bool,
+    /// Paths of files of words to suppress
+    pub blocklists: Vec<String>,
+    /// Path of a gloss database used to annotate results
+    pub definitions: Option<String>,
+    /// Path of a CMUdict-style pronunciation table used to judge rhymes
+    pub pronunciations: Option<String>,
+    /// Preferred output format, as accepted by the command line's `--output`
+    pub output: Option<String>,
+    /// Preferred report format, as accepted by the command line's `--report`
+    pub report: Option<String>,
+    /// Descriptive information, such as the puzzle's name
+    #[serde(skip_serializing_if = "PuzzleMetadata::is_empty")]
+    pub metadata: PuzzleMetadata,
+}
+
+impl PuzzleConfig {
+    /// Parses a puzzle definition written in TOML.
+    pub fn from_toml_str(text: &str) -> Result<Self> {
+        toml::from_str(text).context("Invalid puzzle definition")
+    }
+
+    /// Parses a puzzle definition written in JSON.
+    pub fn from_json_str(text: &str) -> Result<Self> {
+        serde_json::from_str(text).context("Invalid puzzle definition")
+    }
+
+    /// Returns a puzzle definition shipped with the crate.
+    ///
+    /// Presets cover gallery paintings whose letters are already known, and are
+    /// embedded at compile time so they work without any files at hand.
+    ///
+    /// # Parameters
+    ///
+    /// * `name` - Name of the preset, one of [`preset_names`](Self::preset_names)
+    ///
+    /// # Returns
+    ///
+    /// The definition, or an error listing the available presets if there is none by
+    /// that name
+    #[cfg(feature = "presets")]
+    pub fn preset(name: &str) -> Result<Self> {
+        let Some((_, text)) = PRESETS.iter().find(|(preset, _)| *preset == name) else {
+            if PRESETS.is_empty() {
+                anyhow::bail!("Unknown preset '{}', this build ships no presets", name);
+            }
+            anyhow::bail!(
+                "Unknown preset '{}', expected one of: {}",
+                name,
+                Self::preset_names().join(", ")
+            );
+        };
+        Self::from_toml_str(text).with_context(|| format!("Failed to parse preset '{}'", name))
+    }
+
+    /// Returns the names of the shipped presets, in alphabetical order.
+    #[cfg(feature = "presets")]
+    pub fn preset_names() -> Vec<&'static str> {
+        let mut names: Vec<&str> = PRESETS.iter().map(|(name, _)| *name).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Reads a puzzle definition file.
+    ///
+    /// Files ending in `.json` are parsed as JSON, anything else as TOML. Relative
+    /// paths inside the file are resolved against the file's directory.
+    ///
+    /// # Parameters
+    ///
+    /// * `path` - Path to the puzzle file
+    ///
+    /// # Returns
+    ///
+    /// The parsed definition, or an error if the file could not be read or parsed
+    pub fn from_path(path: &str) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read puzzle from '{}'", path))?;
+
+        let path = Path::new(path);
+        let mut config = if path
+            .extension()
+            .is_some_and(|extension| extension == "json")
+        {
+            Self::from_json_str(&content)
+        } else {
+            Self::from_toml_str(&content)
+        }
+        .with_context(|| format!("Failed to parse puzzle '{}'", path.display()))?;
+
+        if let Some(base) = path.parent() {
+            config.resolve_paths(base);
+        }
+        Ok(config)
+    }
+
+    /// Writes the definition in TOML.
+    pub fn to_toml_string(&self) -> Result<String> {
+        toml::to_string_pretty(self).context("Failed to write puzzle definition as TOML")
+    }
+
+    /// Writes the definition in JSON.
+    pub fn to_json_string(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).context("Failed to write puzzle definition as JSON")
+    }
+
+    /// Writes the definition to a file, the inverse of [`from_path`](Self::from_path).
+    ///
+    /// Paths ending in `.json` are written as JSON, anything else as TOML. Paths inside
+    /// the definition that lie under the file's directory are written relative to it,
+    /// so the file reads back the same from wherever it is loaded.
+    ///
+    /// # Parameters
+    ///
+    /// * `path` - Path of the file to create or replace
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gallry_puzzle_soulver::PuzzleConfig;
+    ///
+    /// let dir = std::env::temp_dir().join(format!("gallry-save-doc-{}", std::process::id()));
+    /// std::fs::create_dir_all(&dir).unwrap();
+    /// let path = dir.join("puzzle.toml");
+    ///
+    /// let mut config = PuzzleConfig::from_toml_str("slots = \"cb/ao/t\"").unwrap();
+    /// config.word_lists = vec![dir.join("words.txt").to_string_lossy().into_owned()];
+    /// config.save(path.to_str().unwrap()).unwrap();
+    ///
+    /// let saved = std::fs::read_to_string(&path).unwrap();
+    /// assert!(saved.contains("word_lists = [\"words.txt\"]"));
+    /// assert_eq!(PuzzleConfig::from_path(path.to_str().unwrap()).unwrap(), config);
+    /// # std::fs::remove_dir_all(&dir).unwrap();
+    /// ```
+    pub fn save(&self, path: &str) -> Result<()> {
+        let path = Path::new(path);
+        let mut config = self.clone();
+        if let Some(base) = path.parent() {
+            config.relativize_paths(base);
+        }
+
+        let content = if path
+            .extension()
+            .is_some_and(|extension| extension == "json")
+        {
+            config.to_json_string()?
+        } else {
+            config.to_toml_string()?
+        };
+        std::fs::write(path, content)
+            .with_context(|| format!("Failed to write puzzle to '{}'", path.display()))
+    }
+
+    /// Makes every relative path in the definition relative to `base` instead.
+    fn resolve_paths(&mut self, base: &Path) {
+        self.for_each_path(|path| {
+            if Path::new(path.as_str()).is_relative() {
+                *path = base.join(path.as_str()).to_string_lossy().into_owned();
+            }
+        });
+    }
+
+    /// Makes every path under `base` relative to it, undoing `resolve_paths`.
+    fn relativize_paths(&mut self, base: &Path) {
+        if base.as_os_str().is_empty() {
+            return;
+        }
+        self.for_each_path(|path| {
+            if let Ok(relative) = Path::new(path.as_str()).strip_prefix(base) {
+                *path = relative.to_string_lossy().into_owned();
+            }
+        });
+    }
+
+    /// Applies `change` to every file path in the definition.
+    fn for_each_path(&mut self, mut change: impl FnMut(&mut String)) {
+        self.word_lists.iter_mut().for_each(&mut change);
+        self.exclude_word_lists.iter_mut().for_each(&mut change);
+        self.blocklists.iter_mut().for_each(&mut change);
+        self.definitions.iter_mut().for_each(&mut change);
+        self.pronunciations.iter_mut().for_each(&mut change);
+    }
+
+    /// Builds a generator that solves this puzzle, loading every referenced file.
+    ///
+    /// # Returns
+    ///
+    /// The configured generator, or an error if a file could not be loaded or an
+    /// option needs a feature this build lacks
+    pub fn to_generator(&self) -> Result<WordGenerator> {
+        self.load(None)
+    }
+
+    /// Builds a generator like [`to_generator`](Self::to_generator), storing the
+    /// entries of its word lists in `interner`.
+    ///
+    /// Generators built for several puzzles with one interner share the spelling of
+    /// every word their lists have in common.
+    ///
+    /// # Parameters
+    ///
+    /// * `interner` - Strings shared with other generators built with it
+    pub fn to_generator_interned(&self, interner: &Interner) -> Result<WordGenerator> {
+        self.load(Some(interner))
+    }
+
+    /// Builds the generator, loading word lists through `interner` if given.
+    #[cfg_attr(
+        feature = "instrument",
+        tracing::instrument(level = "debug", skip_all, fields(slots = self.slots.len()))
+    )]
+    fn load(&self, interner: Option<&Interner>) -> Result<WordGenerator> {
+        let slots = parse_slots(&self.slots)?;
+
+        let mut generator = if self.all_combinations {
+            WordGenerator::with_no_filtering(slots)
+        } else if self.word_lists.is_empty() {
+            WordGenerator::with_slots(slots)
+        } else {
+            let load_options = LoadOptions {
+                inflections: self.expand_inflections.then(InflectionRules::default),
+                normalization: self.normalize,
+                interner: interner.cloned(),
+                threads: std::thread::available_parallelism().map_or(1, NonZeroUsize::get),
+                ..Default::default()
+            };
+            let mut generator = WordGenerator::with_no_filtering(slots);
+            for (index, path) in self.word_lists.iter().enumerate() {
+                let dictionary = Dictionary::from_path(path, &load_options)
+                    .with_context(|| format!("Failed to load word list from '{}'", path))?;
+                if index == 0 {
+                    generator.set_dictionary(dictionary);
+                } else {
+                    generator.add_dictionary(dictionary);
+                }
+            }
+            generator
+        };
+        if !self.exclude_word_lists.is_empty() {
+            generator.subtract_words(&self.excluded_words()?);
+        }
+
+        if let Some(path) = &self.definitions {
+            attach_definitions(&mut generator, path)?;
+        }
+        if let Some(path) = &self.pronunciations {
+            attach_pronunciations(&mut generator, path)?;
+        }
+
+        self.configure(&mut generator)?;
+        Ok(generator)
+    }
+
+    /// Applies the slots, constraints, policy, matching options and blocklists to a
+    /// generator, keeping the dictionaries it already holds.
+    ///
+    /// This lets a long-running process load its word lists once and solve many
+    /// puzzles with them. The word lists, exclusions, definitions and pronunciations
+    /// named by the definition are not loaded, nor are the load-time options
+    /// `all_combinations`, `normalize` and `expand_inflections` applied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gallry_puzzle_soulver::{Dictionary, LoadOptions, PuzzleConfig, Slot, WordGenerator};
+    ///
+    /// let dictionary = Dictionary::from_text("cat\ncot\n", &LoadOptions::default());
+    /// let mut generator = WordGenerator::with_dictionary(vec![Slot::new(vec!['x'])], dictionary);
+    ///
+    /// let config = PuzzleConfig::from_toml_str("slots = \"c/ao/t\"").unwrap();
+    /// config.configure(&mut generator).unwrap();
+    /// assert_eq!(generator.iter().collect::<Vec<_>>(), vec!["cat", "cot"]);
+    /// ```
+    pub fn configure(&self, generator: &mut WordGenerator) -> Result<()> {
+        generator.set_slots(parse_slots(&self.slots)?);
+        generator.set_dictionary_policy(self.policy);
+
+        if self.stem && !cfg!(feature = "stemming") {
+            anyhow::bail!("stem-aware matching requires building with the 'stemming' feature");
+        }
+        generator.set_match_options(MatchOptions {
+            proper_nouns: self.proper_nouns,
+            include_pos: self.pos.clone(),
+            exclude_pos: self.exclude_pos.clone(),
+            case_insensitive: self.ignore_case,
+            stemming: self.stem,
+        });
+
+        let mut blocklist = Blocklist::new();
+        if self.family_friendly {
+            blocklist.merge(embedded_blocklist()?);
+        }
+        for path in &self.blocklists {
+            blocklist.merge(Blocklist::from_path(path)?);
+        }
+        generator.set_blocklist(blocklist);
+
+        generator.clear_constraints();
+        for constraint in &self.constraints {
+            generator.add_constraint(constraint.clone());
+        }
+        Ok(())
+    }
+
+    /// Loads every word of the excluded word lists into one dictionary.
+    pub fn excluded_words(&self) -> Result<Dictionary> {
+        let mut excluded = Dictionary::new();
+        for path in &self.exclude_word_lists {
+            let words = Dictionary::from_path(path, &LoadOptions::default())
+                .with_context(|| format!("Failed to load excluded words from '{}'", path))?;
+            excluded.merge(words);
+        }
+        Ok(excluded)
+    }
+}
+
+#[cfg(feature = "profanity-filter")]
+fn embedded_blocklist() -> Result<Blocklist> {
+    Ok(Blocklist::embedded())
+}
+
+#[cfg(not(feature = "profanity-filter"))]
+fn embedded_blocklist() -> Result<Blocklist> {
+    anyhow::bail!("family-friendly output requires building with the 'profanity-filter' feature")
+}
+
+#[cfg(feature = "definitions")]
+fn attach_definitions(generator: &mut WordGenerator, path: &str) -> Result<()> {
+    let glossary = crate::Glossary::from_path(path)?;
+    if let Some(dictionary) = generator.dictionary_mut() {
+        dictionary.set_glossary(glossary);
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "definitions"))]
+fn attach_definitions(_generator: &mut WordGenerator, _path: &str) -> Result<()> {
+    anyhow::bail!("definitions require building with the 'definitions' feature")
+}
+
+#[cfg(feature = "pronunciation")]
+fn attach_pronunciations(generator: &mut WordGenerator, path: &str) -> Result<()> {
+    let pronunciations = crate::Pronunciations::from_path(path)?;
+    if let Some(dictionary) = generator.dictionary_mut() {
+        dictionary.set_pronunciations(pronunciations);
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "pronunciation"))]
+fn attach_pronunciations(_generator: &mut WordGenerator, _path: &str) -> Result<()> {
+    anyhow::bail!("pronunciation tables require building with the 'pronunciation' feature")
+}
@@ -0,0 +1,103 @@
+//! Letters that are easily misread for one another.
+//!
+//! Puzzle lettering is often painted or worn, so a slot that yields nothing may simply
+//! hold a misread letter. Substitutions widen a slot with the letter it may really be.
+
+use crate::Slot;
+use std::fmt;
+
+/// Pairs of characters commonly mistaken for each other, in either direction
+pub const CONFUSABLE_PAIRS: &[(char, char)] = &[
+    ('i', 'l'),
+    ('i', '1'),
+    ('l', '1'),
+    ('o', '0'),
+    ('n', 'm'),
+    ('u', 'v'),
+];
+
+/// An alternative reading of one option of one slot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Substitution {
+    /// Zero-based position of the slot
+    pub position: usize,
+    /// The option as it was read
+    pub read: char,
+    /// The character it may have been instead
+    pub alternative: char,
+}
+
+impl Substitution {
+    /// Adds the alternative to the options of its slot, keeping the letter as read.
+    pub fn apply(&self, slots: &mut [Slot]) {
+        if let Some(slot) = slots.get_mut(self.position) {
+            slot.add_option(self.alternative);
+        }
+    }
+}
+
+impl fmt::Display for Substitution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "'{}' in slot {} as '{}'",
+            self.read,
+            self.position + 1,
+            self.alternative
+        )
+    }
+}
+
+/// Lists every confusable substitution the slots allow, slot by slot.
+///
+/// Wildcard slots already accept any letter, and alternatives a slot already offers
+/// are skipped.
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::{Slot, Substitution, confusable_substitutions};
+///
+/// let slots = vec![Slot::new(vec!['c']), Slot::new(vec!['u', 'v'])];
+/// assert!(confusable_substitutions(&slots).is_empty());
+///
+/// let mut slots = vec![Slot::new(vec!['c']), Slot::new(vec!['o']), Slot::new(vec!['n'])];
+/// let substitutions = confusable_substitutions(&slots);
+/// assert_eq!(
+///     substitutions,
+///     vec![
+///         Substitution { position: 1, read: 'o', alternative: '0' },
+///         Substitution { position: 2, read: 'n', alternative: 'm' },
+///     ]
+/// );
+///
+/// substitutions[1].apply(&mut slots);
+/// assert_eq!(slots[2].options(), &['n', 'm']);
+/// ```
+pub fn confusable_substitutions(slots: &[Slot]) -> Vec<Substitution> {
+    let mut substitutions = Vec::new();
+    for (position, slot) in slots.iter().enumerate() {
+        if slot.is_wildcard() {
+            continue;
+        }
+        for &read in slot.options() {
+            for &(a, b) in CONFUSABLE_PAIRS {
+                let alternative = match read {
+                    _ if read == a => b,
+                    _ if read == b => a,
+                    _ => continue,
+                };
+                let substitution = Substitution {
+                    position,
+                    read,
+                    alternative,
+                };
+                if !slot.options().contains(&alternative) && !substitutions.contains(&substitution)
+                {
+                    substitutions.push(substitution);
+                }
+            }
+        }
+    }
+    substitutions
+}
@@ -0,0 +1,366 @@
+//! Extra conditions a generated word must satisfy beyond its slots.
+//!
+//! Constraints encode clues that apply to the word as a whole, such as "the answer
+//! contains ART somewhere", and are checked before any dictionary lookup.
+
+use regex::Regex;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// A condition on the whole word.
+///
+/// In puzzle files each constraint is written as a single key, e.g.
+/// `{ contains = "art" }` in TOML or `{"contains": "art"}` in JSON.
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::Constraint;
+///
+/// assert!(Constraint::Contains("art".to_string()).accepts("party"));
+/// assert!(!Constraint::ExcludesLetters("xyz".to_string()).accepts("zebra"));
+/// assert!(Constraint::Regex("^c.t$".parse().unwrap()).accepts("cat"));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Constraint {
+    /// The word must contain this text somewhere
+    Contains(String),
+    /// The word must start with this text
+    StartsWith(String),
+    /// The word must end with this text
+    EndsWith(String),
+    /// The word must not contain any of these characters
+    ExcludesLetters(String),
+    /// The word must match this regular expression somewhere; anchor it with `^` and `$`
+    /// to match the whole word
+    Regex(Pattern),
+    /// The word must rhyme with this word
+    ///
+    /// Rhymes are judged by spelling (see [`rhymes`](crate::rhymes)), or by the
+    /// dictionary's pronunciation table when the `pronunciation` feature is enabled
+    /// and both words are in it.
+    RhymesWith(String),
+    /// The word must have this many syllables
+    ///
+    /// Syllables are estimated from spelling (see
+    /// [`syllable_count`](crate::syllable_count)), or counted from the dictionary's
+    /// pronunciation table when the `pronunciation` feature is enabled and the word is
+    /// in it.
+    Syllables(usize),
+    /// The digits of the word must add up to this; other characters are ignored
+    DigitSum(u32),
+    /// Exactly this many characters of the word must be even digits
+    EvenDigits(usize),
+    /// Each character of the word must compare to the one before it in this order,
+    /// so digits are ordered by value
+    Ordered(SequenceOrder),
+    /// The word must use exactly these characters, each as often, in any order;
+    /// letters are compared ignoring case
+    AnagramOf(String),
+    /// The word must be a spelled-out number, such as "seven" or "twentyone", of this
+    /// value if one is given (see [`spelled_number_value`](crate::spelled_number_value))
+    SpelledNumber(NumberTarget),
+    /// The word must be a Roman numeral in the standard form, such as "mix" for 1009,
+    /// of this value if one is given (see
+    /// [`roman_numeral_value`](crate::roman_numeral_value))
+    RomanNumeral(NumberTarget),
+    /// The word must spell this Morse code, whose spaces fall between letters and
+    /// whose runs of dots and dashes may hold several letters (see
+    /// [`MorseCode`](crate::MorseCode))
+    Morse(String),
+    /// The word's letters must repeat where this text's characters repeat and differ
+    /// where they differ, so `abca` accepts "that"; compared ignoring case
+    RepeatPattern(String),
+}
+
+/// Returns the values of the decimal digits in `word`.
+fn digits(word: &str) -> impl Iterator<Item = u32> + '_ {
+    word.chars().filter_map(|c| c.to_digit(10))
+}
+
+/// Returns the characters of `text` in lowercase and sorted, which anagrams share.
+fn sorted_lowercase(text: &str) -> Vec<char> {
+    let mut chars: Vec<char> = text.chars().flat_map(char::to_lowercase).collect();
+    chars.sort_unstable();
+    chars
+}
+
+/// The value a number read from a word must have.
+///
+/// In puzzle files and on the command line it is written as the value, or as `"any"`
+/// for any number at all.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum NumberTarget {
+    /// Any value
+    #[default]
+    Any,
+    /// Exactly this value
+    Value(u64),
+}
+
+impl NumberTarget {
+    /// Returns `true` if a number read from a word meets the target.
+    pub fn allows(self, value: Option<u64>) -> bool {
+        match self {
+            NumberTarget::Any => value.is_some(),
+            NumberTarget::Value(target) => value == Some(target),
+        }
+    }
+}
+
+impl FromStr for NumberTarget {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "any" {
+            return Ok(NumberTarget::Any);
+        }
+        s.parse()
+            .map(NumberTarget::Value)
+            .map_err(|_| format!("invalid number '{}', expected a value or any", s))
+    }
+}
+
+impl fmt::Display for NumberTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NumberTarget::Any => f.write_str("any"),
+            NumberTarget::Value(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl Serialize for NumberTarget {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            NumberTarget::Any => serializer.serialize_str("any"),
+            NumberTarget::Value(value) => serializer.serialize_u64(*value),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for NumberTarget {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        /// A value as written in a file, where "any" has to be a string
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Written {
+            Value(u64),
+            Text(String),
+        }
+
+        match Written::deserialize(deserializer)? {
+            Written::Value(value) => Ok(NumberTarget::Value(value)),
+            Written::Text(text) => text.parse().map_err(serde::de::Error::custom),
+        }
+    }
+}
+
+/// How each character of a word must compare to the one before it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SequenceOrder {
+    /// Never smaller than the one before
+    Increasing,
+    /// Never larger than the one before
+    Decreasing,
+    /// Larger than the one before
+    StrictlyIncreasing,
+    /// Smaller than the one before
+    StrictlyDecreasing,
+}
+
+impl SequenceOrder {
+    /// Returns `true` if `next` may follow `previous`.
+    fn allows(self, previous: char, next: char) -> bool {
+        match self {
+            SequenceOrder::Increasing => previous <= next,
+            SequenceOrder::Decreasing => previous >= next,
+            SequenceOrder::StrictlyIncreasing => previous < next,
+            SequenceOrder::StrictlyDecreasing => previous > next,
+        }
+    }
+}
+
+impl FromStr for SequenceOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "increasing" => Ok(SequenceOrder::Increasing),
+            "decreasing" => Ok(SequenceOrder::Decreasing),
+            "strictly-increasing" => Ok(SequenceOrder::StrictlyIncreasing),
+            "strictly-decreasing" => Ok(SequenceOrder::StrictlyDecreasing),
+            _ => Err(format!(
+                "unknown order '{}', expected increasing, decreasing, strictly-increasing or \
+                 strictly-decreasing",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for SequenceOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            SequenceOrder::Increasing => "increasing",
+            SequenceOrder::Decreasing => "decreasing",
+            SequenceOrder::StrictlyIncreasing => "strictly-increasing",
+            SequenceOrder::StrictlyDecreasing => "strictly-decreasing",
+        })
+    }
+}
+
+impl Constraint {
+    /// Returns `true` if `word` satisfies the constraint.
+    ///
+    /// Rhymes and syllables are judged by spelling here; a
+    /// [`WordGenerator`](crate::WordGenerator) also consults its dictionary's
+    /// pronunciation table.
+    pub fn accepts(&self, word: &str) -> bool {
+        match self {
+            Constraint::Contains(text) => word.contains(text.as_str()),
+            Constraint::StartsWith(text) => word.starts_with(text.as_str()),
+            Constraint::EndsWith(text) => word.ends_with(text.as_str()),
+            Constraint::ExcludesLetters(letters) => !word.chars().any(|c| letters.contains(c)),
+            Constraint::Regex(pattern) => pattern.0.is_match(word),
+            Constraint::RhymesWith(target) => crate::rhymes(word, target),
+            Constraint::Syllables(count) => crate::syllable_count(word) == *count,
+            Constraint::DigitSum(sum) => digits(word).sum::<u32>() == *sum,
+            Constraint::EvenDigits(count) => digits(word).filter(|d| d % 2 == 0).count() == *count,
+            Constraint::Ordered(order) => {
+                let mut chars = word.chars().peekable();
+                while let (Some(previous), Some(&next)) = (chars.next(), chars.peek()) {
+                    if !order.allows(previous, next) {
+                        return false;
+                    }
+                }
+                true
+            }
+            Constraint::AnagramOf(letters) => sorted_lowercase(word) == sorted_lowercase(letters),
+            Constraint::SpelledNumber(target) => target.allows(crate::spelled_number_value(word)),
+            Constraint::RomanNumeral(target) => {
+                target.allows(crate::roman_numeral_value(word).map(u64::from))
+            }
+            Constraint::Morse(code) => crate::morse::spells_morse(word, code),
+            Constraint::RepeatPattern(pattern) => {
+                crate::cryptogram::pattern(&word.to_lowercase())
+                    == crate::cryptogram::pattern(&pattern.to_lowercase())
+            }
+        }
+    }
+
+    /// Returns a rough relative cost of checking the constraint against one word.
+    ///
+    /// A [`WordGenerator`](crate::WordGenerator) checks its constraints cheapest first
+    /// and stops at the first that fails, so comparing the ends of a word comes before
+    /// scanning it, and regular expressions come last.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gallry_puzzle_soulver::Constraint;
+    ///
+    /// let prefix = Constraint::StartsWith("un".to_string());
+    /// let pattern = Constraint::Regex("^un.*ly$".parse().unwrap());
+    /// assert!(prefix.cost() < pattern.cost());
+    /// ```
+    pub fn cost(&self) -> u32 {
+        match self {
+            Constraint::StartsWith(_) | Constraint::EndsWith(_) => 1,
+            Constraint::ExcludesLetters(_)
+            | Constraint::DigitSum(_)
+            | Constraint::EvenDigits(_)
+            | Constraint::Ordered(_)
+            | Constraint::AnagramOf(_)
+            | Constraint::RomanNumeral(_)
+            | Constraint::Morse(_)
+            | Constraint::RepeatPattern(_) => 2,
+            Constraint::Contains(_) => 3,
+            // Number names sharing a beginning are tried in turn
+            Constraint::SpelledNumber(_) => 4,
+            Constraint::Syllables(_) => 4,
+            // Two rhyme keys are built for every word
+            Constraint::RhymesWith(_) => 5,
+            Constraint::Regex(_) => 8,
+        }
+    }
+}
+
+impl fmt::Display for Constraint {
+    /// Writes the constraint as it is spelled in TOML puzzle files, e.g. `contains = "art"`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Constraint::Contains(text) => write!(f, "contains = {:?}", text),
+            Constraint::StartsWith(text) => write!(f, "starts-with = {:?}", text),
+            Constraint::EndsWith(text) => write!(f, "ends-with = {:?}", text),
+            Constraint::ExcludesLetters(letters) => write!(f, "excludes-letters = {:?}", letters),
+            Constraint::Regex(pattern) => write!(f, "regex = {:?}", pattern.as_str()),
+            Constraint::RhymesWith(word) => write!(f, "rhymes-with = {:?}", word),
+            Constraint::Syllables(count) => write!(f, "syllables = {}", count),
+            Constraint::DigitSum(sum) => write!(f, "digit-sum = {}", sum),
+            Constraint::EvenDigits(count) => write!(f, "even-digits = {}", count),
+            Constraint::Ordered(order) => write!(f, "ordered = \"{}\"", order),
+            Constraint::AnagramOf(letters) => write!(f, "anagram-of = {:?}", letters),
+            Constraint::SpelledNumber(NumberTarget::Any) => f.write_str("spelled-number = \"any\""),
+            Constraint::SpelledNumber(target) => write!(f, "spelled-number = {}", target),
+            Constraint::RomanNumeral(NumberTarget::Any) => f.write_str("roman-numeral = \"any\""),
+            Constraint::RomanNumeral(target) => write!(f, "roman-numeral = {}", target),
+            Constraint::Morse(code) => write!(f, "morse = {:?}", code),
+            Constraint::RepeatPattern(pattern) => write!(f, "repeat-pattern = {:?}", pattern),
+        }
+    }
+}
+
+/// A compiled regular expression that is written as its source text in puzzle files.
+///
+/// Two patterns are equal when their source text is.
+#[derive(Clone, Debug)]
+pub struct Pattern(Regex);
+
+impl Pattern {
+    /// Returns the source text of the pattern.
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl FromStr for Pattern {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Regex::new(s)
+            .map(Pattern)
+            .map_err(|error| format!("invalid regex '{}': {}", s, error))
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl PartialEq for Pattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for Pattern {}
+
+impl Serialize for Pattern {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Pattern {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
@@ -0,0 +1,332 @@
+//! Solving substitution cryptograms, where each letter stands for another throughout.
+//!
+//! A substitution keeps the pattern of repeated letters within each word, so `xqq`
+//! can only be a word with its last two letters alike, such as "see" or "all". The
+//! solver lists the dictionary words matching each ciphertext word's pattern, then
+//! assigns words one at a time, always taking the ciphertext word with the fewest
+//! candidates consistent with the letters fixed so far, and backtracks at dead ends.
+
+use crate::cipher::letter_fit;
+use crate::{Dictionary, PlaintextScore, score_plaintext};
+use anyhow::{Result, bail};
+use std::collections::{BTreeMap, HashMap};
+
+/// Most words the solver tries before giving up on finding further solutions
+pub const MAX_CRYPTOGRAM_STEPS: u64 = 1 << 20;
+
+/// Letters of the alphabet, each mapped to at most one other
+type Mapping = [Option<u8>; 26];
+
+/// A substitution cipher to solve against a dictionary.
+///
+/// Every run of letters in the ciphertext is a word that must decode to a dictionary
+/// word, compared in lowercase. Other characters are kept as they are.
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::{Cryptogram, Dictionary, LoadOptions};
+///
+/// let dictionary = Dictionary::from_text("seek\nthe\nkey\nmap\n", &LoadOptions::default());
+/// let solutions = Cryptogram::new("Qxxb iox bxz").solve(&dictionary);
+/// assert_eq!(solutions[0].plaintext, "Seek the key");
+/// assert_eq!(solutions[0].key[&'q'], 's');
+/// ```
+#[derive(Clone, Debug)]
+pub struct Cryptogram {
+    /// The ciphertext as given
+    ciphertext: String,
+    /// Distinct lowercase words of the ciphertext, in order of appearance
+    words: Vec<String>,
+    /// Letters known before solving
+    hints: Mapping,
+    /// Most solutions to find
+    max_solutions: usize,
+}
+
+/// One decryption of a [`Cryptogram`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct CryptogramSolution {
+    /// The plaintext letter each ciphertext letter stands for
+    pub key: BTreeMap<char, char>,
+    /// The ciphertext with every letter replaced, keeping its case
+    pub plaintext: String,
+    /// How English-like the plaintext is
+    pub score: PlaintextScore,
+}
+
+impl Cryptogram {
+    /// Creates a cryptogram for the ciphertext, finding at most 1000 solutions.
+    pub fn new(ciphertext: &str) -> Self {
+        let mut words: Vec<String> = Vec::new();
+        for word in ciphertext.split(|c: char| !c.is_ascii_alphabetic()) {
+            let word = word.to_ascii_lowercase();
+            if !word.is_empty() && !words.contains(&word) {
+                words.push(word);
+            }
+        }
+        Cryptogram {
+            ciphertext: ciphertext.to_string(),
+            words,
+            hints: [None; 26],
+            max_solutions: 1000,
+        }
+    }
+
+    /// Sets the most solutions to find before stopping.
+    pub fn with_max_solutions(mut self, max_solutions: usize) -> Self {
+        self.max_solutions = max_solutions;
+        self
+    }
+
+    /// Fixes the plaintext letter of a ciphertext letter before solving.
+    ///
+    /// # Returns
+    ///
+    /// The cryptogram, or an error if either is not a letter from `a` to `z` or the
+    /// hint contradicts an earlier one
+    pub fn with_hint(mut self, cipher: char, plain: char) -> Result<Self> {
+        let (Some(from), Some(to)) = (letter_index(cipher), letter_index(plain)) else {
+            bail!("Hint '{}={}' must pair two letters", cipher, plain);
+        };
+        let taken = self
+            .hints
+            .iter()
+            .position(|&hint| hint == Some(to))
+            .filter(|&other| other != usize::from(from));
+        if self.hints[usize::from(from)].is_some_and(|hint| hint != to) || taken.is_some() {
+            bail!("Hint '{}={}' contradicts an earlier hint", cipher, plain);
+        }
+        self.hints[usize::from(from)] = Some(to);
+        Ok(self)
+    }
+
+    /// Finds the decryptions whose every word is in the dictionary.
+    ///
+    /// The search stops after the configured number of solutions or
+    /// [`MAX_CRYPTOGRAM_STEPS`] tried words. Candidates are tried commonest first when
+    /// the dictionary has frequency ranks, so the solutions found first tend to be the
+    /// likely ones.
+    ///
+    /// # Returns
+    ///
+    /// The solutions found, most English-like first
+    pub fn solve(&self, dictionary: &Dictionary) -> Vec<CryptogramSolution> {
+        let mut search = Search {
+            candidates: self
+                .words
+                .iter()
+                .map(|word| candidates(word, dictionary))
+                .collect(),
+            words: &self.words,
+            forward: self.hints,
+            backward: [None; 26],
+            solved: vec![false; self.words.len()],
+            found: Vec::new(),
+            max_solutions: self.max_solutions,
+            steps: 0,
+        };
+        for (from, to) in self.hints.iter().enumerate() {
+            if let Some(to) = to {
+                search.backward[usize::from(*to)] = Some(from as u8);
+            }
+        }
+        if self.max_solutions > 0 {
+            search.run();
+        }
+        trace_event!(
+            debug,
+            solutions = search.found.len(),
+            steps = search.steps,
+            "solved a cryptogram"
+        );
+
+        let mut solutions: Vec<CryptogramSolution> = search
+            .found
+            .iter()
+            .map(|mapping| self.solution(mapping, dictionary))
+            .collect();
+        solutions.sort_by(|a, b| {
+            b.score
+                .compare(&a.score)
+                .then(a.plaintext.cmp(&b.plaintext))
+        });
+        solutions
+    }
+
+    /// Decrypts the ciphertext with a complete mapping.
+    fn solution(&self, mapping: &Mapping, dictionary: &Dictionary) -> CryptogramSolution {
+        let plaintext: String = self
+            .ciphertext
+            .chars()
+            .map(|c| match (letter_index(c), c.is_ascii_uppercase()) {
+                (Some(from), upper) => match mapping[usize::from(from)] {
+                    Some(to) if upper => char::from(b'A' + to),
+                    Some(to) => char::from(b'a' + to),
+                    None => c,
+                },
+                (None, _) => c,
+            })
+            .collect();
+        let key = mapping
+            .iter()
+            .enumerate()
+            .filter_map(|(from, to)| {
+                Some((char::from(b'a' + from as u8), char::from(b'a' + (*to)?)))
+            })
+            .filter(|(from, _)| self.words.iter().any(|word| word.contains(*from)))
+            .collect();
+        CryptogramSolution {
+            key,
+            score: score_plaintext(&plaintext, dictionary),
+            plaintext,
+        }
+    }
+}
+
+/// Returns the index of a letter in the alphabet, ignoring case.
+fn letter_index(c: char) -> Option<u8> {
+    c.is_ascii_alphabetic()
+        .then(|| c.to_ascii_lowercase() as u8 - b'a')
+}
+
+/// Returns the pattern of repeated letters in a word, numbering each new letter.
+pub(crate) fn pattern(word: &str) -> Vec<usize> {
+    let mut seen: HashMap<char, usize> = HashMap::new();
+    word.chars()
+        .map(|c| {
+            let next = seen.len();
+            *seen.entry(c).or_insert(next)
+        })
+        .collect()
+}
+
+/// Returns the lowercase dictionary words with the same pattern as a ciphertext word,
+/// commonest first, or with the most English-like letters first when unranked.
+fn candidates(word: &str, dictionary: &Dictionary) -> Vec<String> {
+    let wanted = pattern(word);
+    let mut found: Vec<(Option<u32>, f64, String)> = dictionary
+        .words_of_len(word.len())
+        .filter(|entry| entry.chars().all(|c| c.is_ascii_alphabetic()))
+        .map(|entry| {
+            let rank = dictionary.get(entry).and_then(|info| info.rank);
+            (rank, letter_fit(entry), entry.to_ascii_lowercase())
+        })
+        .filter(|(_, _, entry)| pattern(entry) == wanted)
+        .collect();
+    found.sort_by(|(a_rank, a_fit, a), (b_rank, b_fit, b)| {
+        (a_rank.is_none(), a_rank)
+            .cmp(&(b_rank.is_none(), b_rank))
+            .then(b_fit.total_cmp(a_fit))
+            .then(a.cmp(b))
+    });
+    let mut candidates: Vec<String> = Vec::with_capacity(found.len());
+    for (_, _, entry) in found {
+        if !candidates.contains(&entry) {
+            candidates.push(entry);
+        }
+    }
+    candidates
+}
+
+/// The state of a backtracking search for cryptogram solutions.
+struct Search<'a> {
+    /// Dictionary words fitting the pattern of each ciphertext word
+    candidates: Vec<Vec<String>>,
+    /// The ciphertext words
+    words: &'a [String],
+    /// The plaintext letter of each ciphertext letter fixed so far
+    forward: Mapping,
+    /// The ciphertext letter of each plaintext letter fixed so far
+    backward: Mapping,
+    /// Whether each ciphertext word has been assigned a word
+    solved: Vec<bool>,
+    /// Complete mappings found
+    found: Vec<Mapping>,
+    /// Most complete mappings to find
+    max_solutions: usize,
+    /// Candidate words tried so far
+    steps: u64,
+}
+
+impl Search<'_> {
+    /// Assigns the remaining words in every consistent way, until told to stop.
+    ///
+    /// Returns `false` once the search should stop.
+    fn run(&mut self) -> bool {
+        // The unsolved word with the fewest fitting candidates is the most constrained
+        let next = (0..self.words.len())
+            .filter(|&index| !self.solved[index])
+            .map(|index| {
+                let fitting = self.candidates[index]
+                    .iter()
+                    .filter(|candidate| self.fits(&self.words[index], candidate))
+                    .count();
+                (fitting, index)
+            })
+            .min();
+        let Some((fitting, index)) = next else {
+            self.found.push(self.forward);
+            return self.found.len() < self.max_solutions;
+        };
+        if fitting == 0 {
+            return true;
+        }
+
+        self.solved[index] = true;
+        for candidate in 0..self.candidates[index].len() {
+            let word = &self.words[index];
+            let plain = &self.candidates[index][candidate];
+            if !self.fits(word, plain) {
+                continue;
+            }
+            self.steps += 1;
+            if self.steps > MAX_CRYPTOGRAM_STEPS {
+                return false;
+            }
+
+            let assigned = self.assign(index, candidate);
+            let go_on = self.run();
+            for from in assigned {
+                let to = self.forward[usize::from(from)].take();
+                if let Some(to) = to {
+                    self.backward[usize::from(to)] = None;
+                }
+            }
+            if !go_on {
+                return false;
+            }
+        }
+        self.solved[index] = false;
+        true
+    }
+
+    /// Returns `true` if a ciphertext word can stand for `plain` given the fixed letters.
+    fn fits(&self, word: &str, plain: &str) -> bool {
+        word.bytes().zip(plain.bytes()).all(|(from, to)| {
+            let (from, to) = (from - b'a', to - b'a');
+            match self.forward[usize::from(from)] {
+                Some(fixed) => fixed == to,
+                None => self.backward[usize::from(to)].is_none(),
+            }
+        })
+    }
+
+    /// Fixes the letters of a word's candidate, returning the ciphertext letters newly
+    /// fixed so they can be released.
+    fn assign(&mut self, index: usize, candidate: usize) -> Vec<u8> {
+        let mut assigned = Vec::new();
+        let pairs = self.words[index]
+            .bytes()
+            .zip(self.candidates[index][candidate].bytes());
+        for (from, to) in pairs {
+            let (from, to) = (from - b'a', to - b'a');
+            if self.forward[usize::from(from)].is_none() {
+                self.forward[usize::from(from)] = Some(to);
+                self.backward[usize::from(to)] = Some(from);
+                assigned.push(from);
+            }
+        }
+        assigned
+    }
+}
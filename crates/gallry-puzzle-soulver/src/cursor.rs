@@ -0,0 +1,97 @@
+//! Resumable solving for callers that cannot hold a borrowing iterator.
+//!
+//! A [`Cursor`] is a plain combination number, so it can live next to its
+//! [`WordGenerator`] in one struct, cross an FFI boundary, or be stored between
+//! requests, where [`WordGenerator::iter_matches`] would borrow the generator.
+
+use crate::combination::{decode, increment, spell_into};
+use crate::{SolveStats, WordGenerator, WordMatch};
+
+/// How far a solve has got, counted in combinations.
+///
+/// Combinations are numbered in the order [`WordGenerator::iter`] produces them. A
+/// cursor is only meaningful for the slots it was advanced with; start a new one after
+/// [`WordGenerator::set_slots`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Cursor {
+    /// Number of the next combination to examine
+    position: u128,
+}
+
+impl Cursor {
+    /// Creates a cursor at the first combination.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns how many combinations have been examined.
+    pub fn position(&self) -> u128 {
+        self.position
+    }
+
+    /// Returns `true` once every combination of `generator` has been examined.
+    pub fn is_done(&self, generator: &WordGenerator) -> bool {
+        self.position >= generator.combination_count()
+    }
+}
+
+impl WordGenerator {
+    /// Solves on from `cursor`, returning the matches found along the way.
+    ///
+    /// Solving stops after `max_matches` matches or `max_combinations` combinations,
+    /// whichever comes first, and the cursor is moved past what was examined. Calling
+    /// this until the cursor [is done](Cursor::is_done) yields the same matches, in the
+    /// same order, as [`iter_matches`](Self::iter_matches), while bounding the work done
+    /// per call, e.g. to keep a browser or an async executor responsive.
+    ///
+    /// # Parameters
+    ///
+    /// * `cursor` - Where to resume; updated to where solving stopped
+    /// * `max_matches` - Most matches to return
+    /// * `max_combinations` - Most combinations to examine
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gallry_puzzle_soulver::{Cursor, Slot, WordGenerator};
+    ///
+    /// let generator = WordGenerator::with_slots(vec![
+    ///     Slot::new(vec!['c', 'b', 'r']),
+    ///     Slot::new(vec!['a', 'i', 'o']),
+    ///     Slot::new(vec!['t', 's', 'e']),
+    /// ]);
+    ///
+    /// let mut cursor = Cursor::new();
+    /// let mut matches = Vec::new();
+    /// while !cursor.is_done(&generator) {
+    ///     matches.extend(generator.advance(&mut cursor, 2, 5));
+    /// }
+    /// assert_eq!(matches, generator.iter_matches().collect::<Vec<_>>());
+    /// ```
+    pub fn advance(
+        &self,
+        cursor: &mut Cursor,
+        max_matches: usize,
+        max_combinations: u128,
+    ) -> Vec<WordMatch> {
+        let slots = self.resolved_slots();
+        let total = self.combination_count();
+        let end = total.min(cursor.position.saturating_add(max_combinations));
+        if cursor.position >= end || max_matches == 0 {
+            return Vec::new();
+        }
+
+        let mut indices = decode(&slots, cursor.position);
+        let mut matches = Vec::new();
+        let mut stats = SolveStats::default();
+        let mut word = String::new();
+        while cursor.position < end && matches.len() < max_matches {
+            spell_into(&slots, &indices, &mut word);
+            matches.extend(self.evaluate(&word, &mut stats));
+            increment(&slots, &mut indices);
+            cursor.position += 1;
+        }
+        self.stats.record(&stats);
+        matches
+    }
+}
@@ -0,0 +1,221 @@
+//! Arithmetic of the billiard room's dartboard puzzles.
+//!
+//! Each throw lands on a numbered segment, and the color of the ring it hits says
+//! what to do with that number: blue adds it, yellow subtracts it, pink multiplies by
+//! it and purple divides by it. Starting from zero, the throws are applied in order to
+//! give the number the puzzle asks for. [`score_throws`] computes that number, and
+//! [`throws_for`] searches for the throws that give a wanted one.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// The numbered segments of a dartboard.
+pub const DARTBOARD_SEGMENTS: [i64; 20] = [
+    1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20,
+];
+
+/// What a ring does with the number of the segment hit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum DartOp {
+    /// Adds the number; the blue ring
+    Add,
+    /// Subtracts the number; the yellow ring
+    Subtract,
+    /// Multiplies by the number; the pink ring
+    Multiply,
+    /// Divides by the number, which must divide evenly; the purple ring
+    Divide,
+}
+
+impl DartOp {
+    /// Every operation, in the order searches try them.
+    pub const ALL: [DartOp; 4] = [
+        DartOp::Add,
+        DartOp::Subtract,
+        DartOp::Multiply,
+        DartOp::Divide,
+    ];
+
+    /// Applies the operation, or returns `None` on overflow or an uneven division.
+    pub fn apply(self, total: i64, value: i64) -> Option<i64> {
+        match self {
+            DartOp::Add => total.checked_add(value),
+            DartOp::Subtract => total.checked_sub(value),
+            DartOp::Multiply => total.checked_mul(value),
+            DartOp::Divide => match total.checked_rem(value) {
+                Some(0) => total.checked_div(value),
+                _ => None,
+            },
+        }
+    }
+
+    /// Returns the arithmetic symbol of the operation.
+    fn symbol(self) -> char {
+        match self {
+            DartOp::Add => '+',
+            DartOp::Subtract => '-',
+            DartOp::Multiply => '*',
+            DartOp::Divide => '/',
+        }
+    }
+}
+
+impl FromStr for DartOp {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "+" | "blue" | "add" => Ok(DartOp::Add),
+            "-" | "yellow" | "subtract" => Ok(DartOp::Subtract),
+            "*" | "x" | "pink" | "multiply" => Ok(DartOp::Multiply),
+            "/" | "purple" | "divide" => Ok(DartOp::Divide),
+            _ => Err(format!(
+                "unknown operation '{}', expected +, -, *, / or blue, yellow, pink, purple",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for DartOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+/// One dart: the number of the segment hit and the operation of its ring.
+///
+/// Written as the operation followed by the number, either as a symbol such as `*3`
+/// or as a ring color and a colon such as `pink:3`.
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::{DartOp, DartThrow};
+///
+/// let throw: DartThrow = "pink:3".parse().unwrap();
+/// assert_eq!(throw, DartThrow { op: DartOp::Multiply, value: 3 });
+/// assert_eq!(throw.to_string(), "*3");
+/// assert_eq!("-12".parse::<DartThrow>().unwrap().op, DartOp::Subtract);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DartThrow {
+    /// What to do with the number
+    pub op: DartOp,
+    /// The number of the segment hit
+    pub value: i64,
+}
+
+impl FromStr for DartThrow {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let s = s.trim();
+        let (op, value) = match s.split_once(':') {
+            Some((op, value)) => (op, value),
+            None => s.split_at(s.chars().next().map_or(0, char::len_utf8)),
+        };
+        let value = value
+            .trim()
+            .parse()
+            .map_err(|_| format!("invalid throw '{}', expected e.g. +5 or blue:5", s))?;
+        Ok(DartThrow {
+            op: op.trim().parse()?,
+            value,
+        })
+    }
+}
+
+impl fmt::Display for DartThrow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.op, self.value)
+    }
+}
+
+/// Returns the number the throws give, applying each in order starting from zero.
+///
+/// # Returns
+///
+/// The result, or `None` if a division is uneven or the arithmetic overflows
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::score_throws;
+///
+/// let throws = ["+7", "*3", "-1", "/4"].map(|throw| throw.parse().unwrap());
+/// assert_eq!(score_throws(&throws), Some(5));
+/// assert_eq!(score_throws(&["+7".parse().unwrap(), "/2".parse().unwrap()]), None);
+/// ```
+pub fn score_throws(throws: &[DartThrow]) -> Option<i64> {
+    throws
+        .iter()
+        .try_fold(0, |total, throw| throw.op.apply(total, throw.value))
+}
+
+/// Returns every sequence of at most `max_throws` throws that gives `target`.
+///
+/// Shorter sequences come first. Sequences of one length are ordered throw by throw,
+/// by operation in the order of `ops` and then by segment in the order of `segments`.
+///
+/// # Parameters
+///
+/// * `target` - The number the throws must give
+/// * `max_throws` - Most throws in a sequence
+/// * `segments` - Numbers a dart can hit, such as [`DARTBOARD_SEGMENTS`]
+/// * `ops` - Operations of the rings that can be hit, such as [`DartOp::ALL`]
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::{DartOp, score_throws, throws_for};
+///
+/// let found = throws_for(12, 2, &[3, 4], &[DartOp::Add, DartOp::Multiply]);
+/// let written: Vec<String> = found
+///     .iter()
+///     .map(|throws| throws.iter().map(ToString::to_string).collect::<Vec<_>>().join(" "))
+///     .collect();
+/// assert_eq!(written, ["+3 *4", "+4 *3"]);
+/// assert!(found.iter().all(|throws| score_throws(throws) == Some(12)));
+/// ```
+pub fn throws_for(
+    target: i64,
+    max_throws: usize,
+    segments: &[i64],
+    ops: &[DartOp],
+) -> Vec<Vec<DartThrow>> {
+    let mut found = Vec::new();
+    for len in 1..=max_throws {
+        let mut throws = Vec::with_capacity(len);
+        search(target, len, 0, segments, ops, &mut throws, &mut found);
+    }
+    found
+}
+
+/// Extends `throws` to `len` throws in every way, keeping those that give `target`.
+fn search(
+    target: i64,
+    len: usize,
+    total: i64,
+    segments: &[i64],
+    ops: &[DartOp],
+    throws: &mut Vec<DartThrow>,
+    found: &mut Vec<Vec<DartThrow>>,
+) {
+    if throws.len() == len {
+        if total == target {
+            found.push(throws.clone());
+        }
+        return;
+    }
+    for &op in ops {
+        for &value in segments {
+            let Some(next) = op.apply(total, value) else {
+                continue;
+            };
+            throws.push(DartThrow { op, value });
+            search(target, len, next, segments, ops, throws, found);
+            throws.pop();
+        }
+    }
+}
@@ -0,0 +1,104 @@
+//! Persistent defaults read from the user's configuration file.
+//!
+//! The file is `gallry/config.toml` in the platform's configuration directory, e.g.
+//! `~/.config/gallry/config.toml` on Linux, unless `GALLRY_CONFIG` names another file:
+//!
+//! ```toml
+//! word_lists = ["words/english.txt"]
+//! output = "ndjson"
+//! color = "never"
+//! threads = 4
+//! ```
+//!
+//! Defaults apply only where neither the command line nor a puzzle file chose a value,
+//! and `--no-config` ignores the file entirely.
+
+use crate::color::ColorChoice;
+use crate::output::OutputFormat;
+use anyhow::{Context, Result};
+use gallry_puzzle_soulver::PuzzleConfig;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// Settings applied to every run unless overridden.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UserDefaults {
+    /// Word lists used when a puzzle names none, relative to the configuration file
+    word_lists: Vec<String>,
+    /// Output format, as accepted by `--output`
+    output: Option<String>,
+    /// When to color output, as accepted by `--color`
+    color: Option<String>,
+    /// Threads to solve on, as accepted by `--threads`
+    pub threads: Option<usize>,
+}
+
+impl UserDefaults {
+    /// Reads the user's defaults, or returns empty defaults when there is no file.
+    ///
+    /// # Parameters
+    ///
+    /// * `skip` - Ignore the file, as asked with `--no-config`
+    pub fn load(skip: bool) -> Result<Self> {
+        let Some(path) = config_path().filter(|path| !skip && path.exists()) else {
+            return Ok(Self::default());
+        };
+
+        debug!("reading user defaults from '{}'", path.display());
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read defaults from '{}'", path.display()))?;
+        let mut defaults: Self = toml::from_str(&content)
+            .with_context(|| format!("Invalid defaults in '{}'", path.display()))?;
+
+        let base = path.parent().unwrap_or(Path::new(""));
+        for word_list in &mut defaults.word_lists {
+            *word_list = base.join(word_list.as_str()).to_string_lossy().into_owned();
+        }
+        // Check the settings now, so a typo is reported even when a flag overrides it
+        defaults
+            .output()
+            .and_then(|_| defaults.color())
+            .with_context(|| format!("Invalid defaults in '{}'", path.display()))?;
+        Ok(defaults)
+    }
+
+    /// Fills in the settings a puzzle definition leaves unset.
+    ///
+    /// Word lists are added only to puzzles that filter against the embedded list.
+    pub fn apply(&self, config: &mut PuzzleConfig) {
+        if config.word_lists.is_empty() && !config.all_combinations {
+            config.word_lists = self.word_lists.clone();
+        }
+        if config.output.is_none() {
+            config.output = self.output.clone();
+        }
+    }
+
+    /// Returns the default output format, if one is set.
+    pub fn output(&self) -> Result<Option<OutputFormat>> {
+        parse(self.output.as_deref(), "output")
+    }
+
+    /// Returns the default color choice, if one is set.
+    pub fn color(&self) -> Result<Option<ColorChoice>> {
+        parse(self.color.as_deref(), "color")
+    }
+}
+
+/// Parses an optional setting with the command line's spelling.
+fn parse<T: std::str::FromStr<Err = String>>(value: Option<&str>, name: &str) -> Result<Option<T>> {
+    value
+        .map(|value| value.parse().map_err(anyhow::Error::msg))
+        .transpose()
+        .with_context(|| format!("Invalid '{}' setting", name))
+}
+
+/// Returns the path of the user's defaults file.
+fn config_path() -> Option<PathBuf> {
+    match std::env::var_os("GALLRY_CONFIG") {
+        Some(path) => Some(PathBuf::from(path)),
+        None => dirs::config_dir().map(|dir| dir.join("gallry").join("config.toml")),
+    }
+}
@@ -0,0 +1,102 @@
+//! Offline word definitions for annotating results.
+//!
+//! A [`Glossary`] is a compact gloss database loaded from text with one
+//! `word<TAB>definition` entry per line, such as a list extracted from WordNet.
+//! Words with several senses may appear on several lines.
+
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+
+/// A mapping from words to their short definitions.
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::Glossary;
+///
+/// let glossary = Glossary::from_text("cat\tsmall domesticated feline\ncat\ta jazz musician\n");
+/// assert_eq!(glossary.definitions("cat").len(), 2);
+/// assert_eq!(glossary.definition("cat"), Some("small domesticated feline"));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Glossary {
+    /// Definitions keyed by word, in the order they were loaded
+    glosses: HashMap<String, Vec<String>>,
+}
+
+impl Glossary {
+    /// Creates an empty glossary.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses a gloss database with one `word<TAB>definition` entry per line.
+    ///
+    /// Blank lines, lines starting with `#` and lines without a tab are ignored.
+    pub fn from_text(text: &str) -> Self {
+        let mut glossary = Self::new();
+        glossary.extend_from_text(text);
+        glossary
+    }
+
+    /// Reads and parses a gloss database file.
+    ///
+    /// # Parameters
+    ///
+    /// * `path` - Path to the gloss database file
+    ///
+    /// # Returns
+    ///
+    /// The parsed glossary, or an error if the file could not be read
+    pub fn from_path(path: &str) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .context(format!("Failed to read definitions from {}", path))?;
+        Ok(Self::from_text(&content))
+    }
+
+    /// Adds the entries from gloss database text to this glossary.
+    pub fn extend_from_text(&mut self, text: &str) {
+        for line in text.lines() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some((word, gloss)) = line.split_once('\t') {
+                self.insert(word.trim(), gloss.trim());
+            }
+        }
+    }
+
+    /// Adds a single definition for a word.
+    pub fn insert(&mut self, word: &str, gloss: &str) {
+        if !word.is_empty() && !gloss.is_empty() {
+            self.glosses
+                .entry(word.to_string())
+                .or_default()
+                .push(gloss.to_string());
+        }
+    }
+
+    /// Returns the first definition of a word, if any.
+    pub fn definition(&self, word: &str) -> Option<&str> {
+        self.definitions(word).first().map(String::as_str)
+    }
+
+    /// Returns every definition of a word, in load order.
+    pub fn definitions(&self, word: &str) -> &[String] {
+        self.glosses
+            .get(word)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Returns the number of defined words.
+    pub fn len(&self) -> usize {
+        self.glosses.len()
+    }
+
+    /// Returns `true` if no words are defined.
+    pub fn is_empty(&self) -> bool {
+        self.glosses.is_empty()
+    }
+}
@@ -0,0 +1,1365 @@
+//! Word lists and the per-word metadata used when filtering generated words.
+//!
+//! A [`Dictionary`] maps each word to a [`WordInfo`] record. Metadata is derived
+//! while loading (for example, capitalized entries are flagged as proper nouns) and
+//! can then be used by [`MatchOptions`] to decide which entries count as matches.
+//!
+//! ## Word list format
+//!
+//! Word lists contain one entry per line. An entry may be followed by tab-separated
+//! `key=value` metadata columns; unknown keys are ignored:
+//!
+//! ```text
+//! cat<TAB>pos=noun,verb
+//! nasa<TAB>pos=abbreviation
+//! ```
+//!
+//! Supported keys:
+//!
+//! - `pos` - comma-separated parts of speech (see [`PartOfSpeech`])
+//! - `rank` - frequency rank, where 1 is the most common word
+
+#[cfg(feature = "definitions")]
+use crate::Glossary;
+#[cfg(feature = "pronunciation")]
+use crate::Pronunciations;
+use crate::intern::{Entry, Interner};
+use crate::short::ShortString;
+use crate::substring::SubstringIndex;
+use crate::{BkTree, InflectionRules, Normalization};
+use anyhow::{Context, Result};
+use rand::Rng;
+use std::borrow::Cow;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt;
+use std::io::Read;
+use std::ops::{BitOr, BitOrAssign};
+use std::str::FromStr;
+use std::sync::{Arc, OnceLock};
+
+// Embed the wordlist at compile time
+const EMBEDDED_WORDLIST: &str = include_str!("../data/words.txt");
+
+/// Bytes of word list text each thread parses at least, when parsing in parallel
+const PARALLEL_MIN_BYTES: usize = 1 << 16;
+
+/// Entries from which the length index is built on several threads
+const PARALLEL_MIN_ENTRIES: usize = 1 << 14;
+
+/// Size from which word list files are mapped into memory rather than read
+#[cfg(feature = "mmap")]
+const MAP_THRESHOLD: u64 = 1 << 20;
+
+/// A set of boolean properties attached to a dictionary entry.
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::WordFlags;
+///
+/// let flags = WordFlags::PROPER_NOUN;
+/// assert!(flags.contains(WordFlags::PROPER_NOUN));
+/// assert!(!WordFlags::NONE.contains(WordFlags::PROPER_NOUN));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct WordFlags(u32);
+
+impl WordFlags {
+    /// No flags set.
+    pub const NONE: Self = Self(0);
+    /// The entry is a capitalized proper noun, such as a name or a place.
+    pub const PROPER_NOUN: Self = Self(1 << 0);
+    /// The entry was generated from another entry by inflection expansion.
+    pub const INFLECTED: Self = Self(1 << 1);
+
+    /// Returns `true` if every flag in `other` is also set in `self`.
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Sets every flag in `other`.
+    pub fn insert(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+
+    /// Returns `true` if no flags are set.
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl BitOr for WordFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for WordFlags {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.insert(rhs);
+    }
+}
+
+/// A grammatical category attached to a dictionary entry through metadata.
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::PartOfSpeech;
+///
+/// assert_eq!("noun".parse::<PartOfSpeech>(), Ok(PartOfSpeech::Noun));
+/// assert_eq!("abbr".parse::<PartOfSpeech>(), Ok(PartOfSpeech::Abbreviation));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum PartOfSpeech {
+    Noun,
+    Verb,
+    Adjective,
+    Adverb,
+    Pronoun,
+    Preposition,
+    Conjunction,
+    Interjection,
+    Determiner,
+    Numeral,
+    Abbreviation,
+}
+
+impl PartOfSpeech {
+    /// Returns the canonical lowercase name used in word list metadata.
+    pub fn name(self) -> &'static str {
+        match self {
+            PartOfSpeech::Noun => "noun",
+            PartOfSpeech::Verb => "verb",
+            PartOfSpeech::Adjective => "adjective",
+            PartOfSpeech::Adverb => "adverb",
+            PartOfSpeech::Pronoun => "pronoun",
+            PartOfSpeech::Preposition => "preposition",
+            PartOfSpeech::Conjunction => "conjunction",
+            PartOfSpeech::Interjection => "interjection",
+            PartOfSpeech::Determiner => "determiner",
+            PartOfSpeech::Numeral => "numeral",
+            PartOfSpeech::Abbreviation => "abbreviation",
+        }
+    }
+}
+
+impl FromStr for PartOfSpeech {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "noun" | "n" => Ok(PartOfSpeech::Noun),
+            "verb" | "v" => Ok(PartOfSpeech::Verb),
+            "adjective" | "adj" | "a" => Ok(PartOfSpeech::Adjective),
+            "adverb" | "adv" | "r" => Ok(PartOfSpeech::Adverb),
+            "pronoun" | "pron" => Ok(PartOfSpeech::Pronoun),
+            "preposition" | "prep" => Ok(PartOfSpeech::Preposition),
+            "conjunction" | "conj" => Ok(PartOfSpeech::Conjunction),
+            "interjection" | "intj" => Ok(PartOfSpeech::Interjection),
+            "determiner" | "det" => Ok(PartOfSpeech::Determiner),
+            "numeral" | "num" => Ok(PartOfSpeech::Numeral),
+            "abbreviation" | "abbr" => Ok(PartOfSpeech::Abbreviation),
+            _ => Err(format!("unknown part of speech '{}'", s)),
+        }
+    }
+}
+
+impl fmt::Display for PartOfSpeech {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Metadata recorded for a single dictionary entry.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WordInfo {
+    /// Flags derived while loading the entry
+    pub flags: WordFlags,
+    /// Parts of speech from the word list metadata, empty when unknown
+    pub parts_of_speech: Vec<PartOfSpeech>,
+    /// Frequency rank where 1 is the most common word, if known
+    pub rank: Option<u32>,
+}
+
+impl WordInfo {
+    /// Derives the metadata for a raw entry as it appears in a word list.
+    fn from_entry(word: &str) -> Self {
+        let mut flags = WordFlags::NONE;
+        if word.chars().next().is_some_and(char::is_uppercase) {
+            flags.insert(WordFlags::PROPER_NOUN);
+        }
+        Self {
+            flags,
+            parts_of_speech: Vec::new(),
+            rank: None,
+        }
+    }
+
+    /// Applies a single `key=value` metadata column, ignoring unknown keys.
+    fn apply_metadata(&mut self, column: &str) {
+        let Some((key, value)) = column.split_once('=') else {
+            return;
+        };
+
+        match key.trim() {
+            "pos" => {
+                for pos in value.split(',').filter_map(|pos| pos.trim().parse().ok()) {
+                    if !self.parts_of_speech.contains(&pos) {
+                        self.parts_of_speech.push(pos);
+                    }
+                }
+            }
+            "rank" => self.rank = value.trim().parse().ok().or(self.rank),
+            _ => {}
+        }
+    }
+
+    /// Merges the metadata of a duplicate entry into this one.
+    fn merge(&mut self, other: WordInfo) {
+        self.flags.insert(other.flags);
+        self.rank = match (self.rank, other.rank) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        for pos in other.parts_of_speech {
+            if !self.parts_of_speech.contains(&pos) {
+                self.parts_of_speech.push(pos);
+            }
+        }
+    }
+}
+
+/// Controls whether capitalized proper nouns take part in matching.
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::ProperNouns;
+///
+/// let policy: ProperNouns = "exclude".parse().unwrap();
+/// assert_eq!(policy, ProperNouns::Exclude);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ProperNouns {
+    /// Proper nouns are treated like any other word
+    #[default]
+    Include,
+    /// Proper nouns never match
+    Exclude,
+    /// Only proper nouns match
+    Only,
+}
+
+impl ProperNouns {
+    /// Returns `true` if an entry with the given metadata passes this policy.
+    pub fn accepts(self, info: &WordInfo) -> bool {
+        let proper = info.flags.contains(WordFlags::PROPER_NOUN);
+        match self {
+            ProperNouns::Include => true,
+            ProperNouns::Exclude => !proper,
+            ProperNouns::Only => proper,
+        }
+    }
+}
+
+impl FromStr for ProperNouns {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "include" => Ok(ProperNouns::Include),
+            "exclude" => Ok(ProperNouns::Exclude),
+            "only" => Ok(ProperNouns::Only),
+            _ => Err(format!(
+                "unknown proper noun policy '{}', expected include, exclude or only",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ProperNouns {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ProperNouns::Include => "include",
+            ProperNouns::Exclude => "exclude",
+            ProperNouns::Only => "only",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Options applied while a word list is being loaded.
+///
+/// Entries rejected at load time are never stored, which keeps the resulting
+/// [`Dictionary`] small when whole classes of words are known to be unwanted.
+#[derive(Clone, Debug, Default)]
+pub struct LoadOptions {
+    /// Which capitalized entries to keep
+    pub proper_nouns: ProperNouns,
+    /// If set, the loaded list is expanded with inflected forms of every entry
+    pub inflections: Option<InflectionRules>,
+    /// Unicode normalization applied to entries, and later to looked up candidates
+    pub normalization: Normalization,
+    /// Whether the list is sorted most common first, so line order gives the rank
+    ///
+    /// Explicit `rank` metadata takes precedence over the line order.
+    pub rank_by_line_order: bool,
+    /// If set, entries are stored in this interner and shared with every other
+    /// dictionary loaded through it
+    pub interner: Option<Interner>,
+    /// Threads used to parse a large list and later to build its length index; `0` or
+    /// `1` does all the work on the calling thread
+    pub threads: usize,
+}
+
+/// Which of several registered dictionaries a word must appear in to match.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DictionaryPolicy {
+    /// The word must appear in at least one dictionary
+    #[default]
+    Any,
+    /// The word must appear in every dictionary
+    All,
+}
+
+impl FromStr for DictionaryPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "any" => Ok(DictionaryPolicy::Any),
+            "all" => Ok(DictionaryPolicy::All),
+            _ => Err(format!(
+                "unknown dictionary policy '{}', expected any or all",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for DictionaryPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DictionaryPolicy::Any => "any",
+            DictionaryPolicy::All => "all",
+        })
+    }
+}
+
+/// Options applied when a candidate word is looked up in a [`Dictionary`].
+#[derive(Clone, Debug, Default)]
+pub struct MatchOptions {
+    /// Which capitalized entries count as matches
+    pub proper_nouns: ProperNouns,
+    /// If non-empty, entries must have at least one of these parts of speech
+    ///
+    /// Entries without part-of-speech metadata are not affected.
+    pub include_pos: Vec<PartOfSpeech>,
+    /// Entries with any of these parts of speech never match
+    pub exclude_pos: Vec<PartOfSpeech>,
+    /// Whether case is ignored on both sides when looking up candidates
+    ///
+    /// Entries keep their original spelling, so `"paris"` finds the entry `"Paris"`.
+    pub case_insensitive: bool,
+    /// Whether candidates missing from the dictionary may match through their stem
+    ///
+    /// Requires the `stemming` feature and is ignored without it.
+    pub stemming: bool,
+}
+
+impl MatchOptions {
+    /// Returns `true` if an entry with the given metadata passes every option.
+    pub fn accepts(&self, info: &WordInfo) -> bool {
+        self.proper_nouns.accepts(info) && self.accepts_pos(info)
+    }
+
+    /// Applies the part-of-speech filters, which only affect tagged entries.
+    fn accepts_pos(&self, info: &WordInfo) -> bool {
+        let tags = &info.parts_of_speech;
+        if tags.is_empty() {
+            return true;
+        }
+
+        let included =
+            self.include_pos.is_empty() || tags.iter().any(|pos| self.include_pos.contains(pos));
+        let excluded = tags.iter().any(|pos| self.exclude_pos.contains(pos));
+        included && !excluded
+    }
+}
+
+/// Details about a word found by [`Dictionary::contains_detailed`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Membership<'a> {
+    /// Name of the dictionary containing the word, empty if unnamed
+    pub dictionary: &'a str,
+    /// The entry as spelled in the dictionary
+    pub entry: &'a str,
+    /// The entry's metadata, including flags and frequency rank
+    pub info: &'a WordInfo,
+}
+
+impl Membership<'_> {
+    /// Returns the frequency rank of the entry, if known.
+    pub fn rank(&self) -> Option<u32> {
+        self.info.rank
+    }
+
+    /// Returns the flags of the entry.
+    pub fn flags(&self) -> WordFlags {
+        self.info.flags
+    }
+}
+
+/// A word list with per-word metadata.
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::{Dictionary, LoadOptions, MatchOptions, ProperNouns};
+///
+/// let dictionary = Dictionary::from_text("cat\nParis\n", &LoadOptions::default());
+/// assert!(dictionary.contains("Paris"));
+///
+/// let options = MatchOptions {
+///     proper_nouns: ProperNouns::Exclude,
+///     ..Default::default()
+/// };
+/// assert!(dictionary.lookup("cat", &options).is_some());
+/// assert!(dictionary.lookup("Paris", &options).is_none());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Dictionary {
+    /// Name used to attribute matches, e.g. the file the list was loaded from
+    name: String,
+    /// Entries keyed by the word as it appears in the list, shared by copies of the
+    /// dictionary until one of them changes
+    words: Arc<HashMap<Entry, WordInfo>>,
+    /// Indexes of the entries, shared along with them
+    indexes: Arc<Indexes>,
+    /// Normalization applied to entries on insertion and to candidates on lookup
+    normalization: Normalization,
+    /// Threads used to build the length index of a large dictionary
+    threads: usize,
+    /// Definitions used to annotate results
+    #[cfg(feature = "definitions")]
+    glossary: Glossary,
+    /// Pronunciations used to judge rhymes
+    #[cfg(feature = "pronunciation")]
+    pronunciations: Pronunciations,
+}
+
+/// Indexes of a dictionary's entries, each built on first use.
+#[derive(Debug, Default)]
+struct Indexes {
+    /// Lowercased spellings mapped to the entries sharing them
+    folded: OnceLock<HashMap<String, Vec<Entry>>>,
+    /// Entries bucketed by length in characters and sorted
+    by_length: OnceLock<HashMap<usize, Vec<Entry>>>,
+    /// Edit-distance index used for spelling suggestions
+    bk_tree: OnceLock<BkTree>,
+    /// Suffix array used for substring queries
+    substrings: OnceLock<SubstringIndex>,
+    /// Every character used by the entries
+    alphabet: OnceLock<BTreeSet<char>>,
+}
+
+/// Returns `true` if all of `word` matches a glob pattern of `*` and `?` wildcards.
+///
+/// After a mismatch the last `*` is made to cover one more character, which finds a
+/// match whenever there is one without trying every split.
+fn glob_matches(pattern: &[char], word: &str) -> bool {
+    let word: Vec<char> = word.chars().collect();
+    let (mut at_pattern, mut at_word) = (0, 0);
+    let mut last_star: Option<(usize, usize)> = None;
+    while at_word < word.len() {
+        match pattern.get(at_pattern) {
+            Some('*') => {
+                last_star = Some((at_pattern, at_word));
+                at_pattern += 1;
+            }
+            Some(&c) if c == '?' || c == word[at_word] => {
+                at_pattern += 1;
+                at_word += 1;
+            }
+            _ => match last_star {
+                Some((star, covered)) => {
+                    last_star = Some((star, covered + 1));
+                    at_pattern = star + 1;
+                    at_word = covered + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[at_pattern..].iter().all(|&c| c == '*')
+}
+
+/// Parses one line of a word list into its normalized entry and metadata, skipping
+/// blank lines.
+fn parse_line<'t>(line: &'t str, options: &LoadOptions) -> Option<(Cow<'t, str>, WordInfo)> {
+    let mut columns = line.trim().split('\t');
+    let word = options
+        .normalization
+        .apply(columns.next().unwrap_or_default().trim());
+    if word.is_empty() {
+        return None;
+    }
+
+    let mut info = WordInfo::from_entry(&word);
+    for column in columns {
+        info.apply_metadata(column);
+    }
+    Some((word, info))
+}
+
+/// Splits text into at most `parts` runs of whole lines of roughly equal size.
+fn split_lines(text: &str, parts: usize) -> Vec<&str> {
+    let mut chunks = Vec::with_capacity(parts);
+    let mut rest = text;
+    for remaining in (2..=parts).rev() {
+        let target = rest.len() / remaining;
+        // A newline byte never falls inside a multi-byte character
+        let Some(newline) = rest.as_bytes()[target..].iter().position(|&b| b == b'\n') else {
+            break;
+        };
+        let (chunk, tail) = rest.split_at(target + newline + 1);
+        chunks.push(chunk);
+        rest = tail;
+    }
+    chunks.push(rest);
+    chunks
+}
+
+impl Dictionary {
+    /// Creates an empty dictionary.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the word list embedded in the crate at compile time.
+    ///
+    /// The list is parsed on the first call only, and every dictionary returned shares
+    /// the parsed entries and their indexes, so building many generators stays cheap.
+    /// A dictionary copies the entries only when it is changed, e.g. by [`insert`]
+    /// or [`merge`]. The entries borrow their spelling from the text compiled into the
+    /// binary, so neither parsing nor copying allocates a string per word.
+    ///
+    /// [`insert`]: Self::insert
+    /// [`merge`]: Self::merge
+    pub fn embedded() -> Self {
+        static EMBEDDED: OnceLock<Dictionary> = OnceLock::new();
+        EMBEDDED
+            .get_or_init(|| {
+                let keep = |word| match word {
+                    Cow::Borrowed(word) => Entry::Static(word),
+                    Cow::Owned(word) => Entry::from(word),
+                };
+                Self::parse(EMBEDDED_WORDLIST, &LoadOptions::default(), keep).with_name("embedded")
+            })
+            .clone()
+    }
+
+    /// Parses a word list with one entry per line.
+    ///
+    /// Surrounding whitespace is trimmed and blank lines are skipped. Metadata columns
+    /// are parsed as described in the [module documentation](self), and duplicate
+    /// entries have their metadata merged.
+    ///
+    /// # Parameters
+    ///
+    /// * `text` - The contents of the word list
+    /// * `options` - Options controlling which entries are kept
+    pub fn from_text(text: &str, options: &LoadOptions) -> Self {
+        match &options.interner {
+            Some(interner) => {
+                Self::parse(text, options, |word| Entry::Shared(interner.intern(&word)))
+            }
+            None => Self::parse(text, options, |word| Entry::from(word.into_owned())),
+        }
+    }
+
+    /// Parses a word list, storing each entry's normalized spelling as `keep` makes it,
+    /// which may borrow from `text` when it lives for the whole program.
+    fn parse<'t>(
+        text: &'t str,
+        options: &LoadOptions,
+        keep: impl Fn(Cow<'t, str>) -> Entry + Sync,
+    ) -> Self {
+        let mut dictionary = Self {
+            normalization: options.normalization,
+            threads: options.threads,
+            ..Self::new()
+        };
+        // Each non-blank line yields its entry, or `None` when it is rejected but still
+        // counts towards the ranks of the lines after it
+        let parse = |line| {
+            let (word, info) = parse_line(line, options)?;
+            Some(
+                options
+                    .proper_nouns
+                    .accepts(&info)
+                    .then(|| (keep(word), info)),
+            )
+        };
+
+        let parts = options.threads.min(text.len() / PARALLEL_MIN_BYTES);
+        if parts > 1 {
+            // Lines are parsed in parallel, then stored in list order so duplicates merge
+            // and ranks count exactly as on one thread
+            let chunks: Vec<Vec<_>> = std::thread::scope(|scope| {
+                let workers: Vec<_> = split_lines(text, parts)
+                    .into_iter()
+                    .map(|chunk| scope.spawn(|| chunk.lines().filter_map(parse).collect()))
+                    .collect();
+                workers
+                    .into_iter()
+                    .map(|worker| worker.join().expect("word list parser panicked"))
+                    .collect()
+            });
+            trace_event!(debug, threads = parts, "parsed word list lines in parallel");
+            dictionary
+                .words_mut()
+                .reserve(chunks.iter().map(Vec::len).sum());
+            dictionary.store(chunks.into_iter().flatten(), options);
+        } else {
+            dictionary.store(text.lines().filter_map(parse), options);
+        }
+
+        if let Some(rules) = &options.inflections {
+            dictionary.expand_inflections(rules);
+        }
+        trace_event!(debug, words = dictionary.len(), "parsed word list");
+        dictionary
+    }
+
+    /// Stores the parsed lines of a word list in order, merging duplicate entries.
+    fn store(
+        &mut self,
+        lines: impl Iterator<Item = Option<(Entry, WordInfo)>>,
+        options: &LoadOptions,
+    ) {
+        let words = self.words_mut();
+        for (line_rank, line) in (1..).zip(lines) {
+            let Some((word, mut info)) = line else {
+                continue;
+            };
+            if options.rank_by_line_order && info.rank.is_none() {
+                info.rank = Some(line_rank);
+            }
+            match words.get_mut(&word) {
+                Some(existing) => existing.merge(info),
+                None => {
+                    words.insert(word, info);
+                }
+            }
+        }
+    }
+
+    /// Reads and parses a word list file with one entry per line.
+    ///
+    /// The dictionary is named after the path. With the `mmap` feature, files of a
+    /// megabyte or more are mapped into memory and parsed a line at a time, so only
+    /// the entries are copied onto the heap rather than the whole file. The file must
+    /// not be truncated while it loads.
+    ///
+    /// # Parameters
+    ///
+    /// * `path` - Path to the word list file
+    /// * `options` - Options controlling which entries are kept
+    ///
+    /// # Returns
+    ///
+    /// The parsed dictionary, or an error if the file could not be read
+    #[cfg_attr(
+        feature = "instrument",
+        tracing::instrument(level = "debug", skip(options))
+    )]
+    pub fn from_path(path: &str, options: &LoadOptions) -> Result<Self> {
+        let context = || format!("Failed to read word list from {}", path);
+        let mut file = std::fs::File::open(path).with_context(context)?;
+        #[cfg(feature = "mmap")]
+        if file.metadata().with_context(context)?.len() >= MAP_THRESHOLD {
+            // SAFETY: the map is only read while parsing and dropped before returning.
+            // Truncating the file meanwhile would fault, as documented above
+            let map = unsafe { memmap2::Mmap::map(&file) }.with_context(context)?;
+            let content = std::str::from_utf8(&map).with_context(context)?;
+            return Ok(Self::from_text(content, options).with_name(path));
+        }
+
+        let mut content = String::new();
+        file.read_to_string(&mut content).with_context(context)?;
+        Ok(Self::from_text(&content, options).with_name(path))
+    }
+
+    /// Returns the dictionary with its name set, for match attribution.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Returns the name used to attribute matches, empty if unnamed.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Adds a word, deriving its metadata from its spelling.
+    ///
+    /// The word is normalized with the dictionary's [`Normalization`] first.
+    pub fn insert(&mut self, word: impl Into<String>) {
+        let word = word.into();
+        let word = match self.normalization.apply(&word) {
+            Cow::Borrowed(_) => word,
+            Cow::Owned(normalized) => normalized,
+        };
+        let info = WordInfo::from_entry(&word);
+        self.words_mut().insert(Entry::from(word), info);
+    }
+
+    /// Adds every entry of `other` to this dictionary, merging the metadata of shared
+    /// entries.
+    ///
+    /// This layers a personal word list over a base list such as the embedded one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gallry_puzzle_soulver::{Dictionary, LoadOptions};
+    ///
+    /// let mut dictionary = Dictionary::from_text("cat\n", &LoadOptions::default());
+    /// dictionary.merge(Dictionary::from_text("cat\nzorp\n", &LoadOptions::default()));
+    ///
+    /// assert_eq!(dictionary.len(), 2);
+    /// assert!(dictionary.contains("zorp"));
+    /// ```
+    pub fn merge(&mut self, other: Dictionary) {
+        if other.is_empty() {
+            return;
+        }
+        let normalization = self.normalization;
+        let words = self.words_mut();
+        for (word, info) in Arc::unwrap_or_clone(other.words) {
+            let word = match normalization.apply(&word) {
+                Cow::Borrowed(_) => word,
+                Cow::Owned(normalized) => Entry::from(normalized),
+            };
+            match words.get_mut(&word) {
+                Some(existing) => existing.merge(info),
+                None => {
+                    words.insert(word, info);
+                }
+            }
+        }
+    }
+
+    /// Removes every entry of `other` from this dictionary.
+    ///
+    /// This takes known-wrong or already-used answers out of a list for one solve.
+    /// Entries are matched exactly, after this dictionary's [`Normalization`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gallry_puzzle_soulver::{Dictionary, LoadOptions};
+    ///
+    /// let mut dictionary = Dictionary::from_text("cat\nbat\ncot\n", &LoadOptions::default());
+    /// dictionary.subtract(&Dictionary::from_text("bat\nzorp\n", &LoadOptions::default()));
+    ///
+    /// assert_eq!(dictionary.len(), 2);
+    /// assert!(!dictionary.contains("bat"));
+    /// ```
+    pub fn subtract(&mut self, other: &Dictionary) {
+        let normalization = self.normalization;
+        let words = self.words_mut();
+        for word in other.words.keys() {
+            words.remove(normalization.apply(word).as_ref());
+        }
+    }
+
+    /// Adds the inflected forms of every entry, such as plurals and verb forms.
+    ///
+    /// Generated entries inherit the part-of-speech metadata of their lemma and are
+    /// flagged with [`WordFlags::INFLECTED`]. Proper nouns are not inflected, and
+    /// entries already present are left untouched.
+    ///
+    /// # Parameters
+    ///
+    /// * `rules` - The rules used to produce inflected forms
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gallry_puzzle_soulver::{Dictionary, InflectionRules, LoadOptions, WordFlags};
+    ///
+    /// let mut dictionary = Dictionary::from_text("cat\nrun\n", &LoadOptions::default());
+    /// dictionary.expand_inflections(&InflectionRules::default());
+    ///
+    /// assert!(dictionary.contains("cats"));
+    /// assert!(dictionary.contains("ran"));
+    /// assert!(dictionary.get("running").unwrap().flags.contains(WordFlags::INFLECTED));
+    /// ```
+    pub fn expand_inflections(&mut self, rules: &InflectionRules) {
+        let mut generated = Vec::new();
+        for (lemma, info) in self.words.iter() {
+            if info.flags.contains(WordFlags::PROPER_NOUN) {
+                continue;
+            }
+
+            for form in rules.inflect(lemma) {
+                if !self.words.contains_key(form.as_str()) {
+                    let mut form_info = info.clone();
+                    form_info.flags.insert(WordFlags::INFLECTED);
+                    generated.push((form, form_info));
+                }
+            }
+        }
+
+        let words = self.words_mut();
+        for (form, info) in generated {
+            words.entry(Entry::from(form)).or_insert(info);
+        }
+    }
+
+    /// Returns the entries for changing, copying them first if another dictionary
+    /// shares them, and discards the indexes built from them.
+    fn words_mut(&mut self) -> &mut HashMap<Entry, WordInfo> {
+        match Arc::get_mut(&mut self.indexes) {
+            Some(indexes) => *indexes = Indexes::default(),
+            None => self.indexes = Arc::default(),
+        }
+        Arc::make_mut(&mut self.words)
+    }
+
+    /// Returns the case-folded index, building it on first use.
+    fn folded_index(&self) -> &HashMap<String, Vec<Entry>> {
+        self.indexes.folded.get_or_init(|| {
+            let mut index: HashMap<String, Vec<Entry>> = HashMap::new();
+            for word in self.words.keys() {
+                index
+                    .entry(word.to_lowercase())
+                    .or_default()
+                    .push(word.clone());
+            }
+            // Keep the preferred spelling deterministic when several entries fold together
+            for entries in index.values_mut() {
+                entries.sort();
+            }
+            index
+        })
+    }
+
+    /// Returns the length index, building it on first use.
+    fn length_index(&self) -> &HashMap<usize, Vec<Entry>> {
+        self.indexes.by_length.get_or_init(|| {
+            if self.threads > 1 && self.words.len() >= PARALLEL_MIN_ENTRIES {
+                return self.par_length_index();
+            }
+            let mut index: HashMap<usize, Vec<Entry>> = HashMap::new();
+            for word in self.words.keys() {
+                index
+                    .entry(word.chars().count())
+                    .or_default()
+                    .push(word.clone());
+            }
+            for words in index.values_mut() {
+                words.sort();
+            }
+            index
+        })
+    }
+
+    /// Builds the length index on several threads, each bucketing and then sorting a
+    /// share of the entries.
+    fn par_length_index(&self) -> HashMap<usize, Vec<Entry>> {
+        let entries: Vec<&Entry> = self.words.keys().collect();
+        let share = entries.len().div_ceil(self.threads);
+        let mut index: HashMap<usize, Vec<Entry>> = HashMap::new();
+        std::thread::scope(|scope| {
+            let workers: Vec<_> = entries
+                .chunks(share)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        let mut index: HashMap<usize, Vec<Entry>> = HashMap::new();
+                        for &word in chunk {
+                            index
+                                .entry(word.chars().count())
+                                .or_default()
+                                .push(word.clone());
+                        }
+                        index
+                    })
+                })
+                .collect();
+            for worker in workers {
+                for (len, words) in worker.join().expect("length indexer panicked") {
+                    index.entry(len).or_default().extend(words);
+                }
+            }
+
+            // Buckets are sorted largest first, each by the least loaded thread
+            let mut buckets: Vec<&mut Vec<Entry>> = index.values_mut().collect();
+            buckets.sort_by_key(|words| std::cmp::Reverse(words.len()));
+            let mut shares: Vec<(usize, Vec<&mut Vec<Entry>>)> =
+                (0..self.threads).map(|_| (0, Vec::new())).collect();
+            for words in buckets {
+                let (load, share) = shares.iter_mut().min_by_key(|(load, _)| *load).unwrap();
+                *load += words.len();
+                share.push(words);
+            }
+            for (_, share) in shares {
+                scope.spawn(move || share.into_iter().for_each(|words| words.sort()));
+            }
+        });
+        trace_event!(
+            debug,
+            threads = self.threads,
+            "built length index in parallel"
+        );
+        index
+    }
+
+    /// Returns every entry with exactly `len` characters, in sorted order.
+    ///
+    /// The entries are bucketed by length the first time this is called, so repeated
+    /// queries do not re-scan the whole dictionary.
+    ///
+    /// # Parameters
+    ///
+    /// * `len` - The length in characters
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gallry_puzzle_soulver::{Dictionary, LoadOptions};
+    ///
+    /// let dictionary = Dictionary::from_text("cat\nhorse\nbat\n", &LoadOptions::default());
+    /// let words: Vec<&str> = dictionary.words_of_len(3).collect();
+    /// assert_eq!(words, vec!["bat", "cat"]);
+    /// ```
+    pub fn words_of_len(&self, len: usize) -> impl Iterator<Item = &str> {
+        self.length_index()
+            .get(&len)
+            .into_iter()
+            .flatten()
+            .map(|word| word.as_ref())
+    }
+
+    /// Returns the set of letters that occur at `position` among entries of `word_len`.
+    ///
+    /// This answers whether a letter is possible at a position at all before a puzzle
+    /// is built. Positions are zero-based and counted in characters; a position outside
+    /// the word length yields an empty set.
+    ///
+    /// # Parameters
+    ///
+    /// * `position` - The zero-based character position
+    /// * `word_len` - The length of the words to consider
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gallry_puzzle_soulver::{Dictionary, LoadOptions};
+    ///
+    /// let dictionary = Dictionary::from_text("cat\ncot\ndog\n", &LoadOptions::default());
+    /// let letters = dictionary.letters_at(1, 3);
+    /// assert!(letters.contains(&'a') && letters.contains(&'o'));
+    /// assert_eq!(letters.len(), 2);
+    /// ```
+    pub fn letters_at(&self, position: usize, word_len: usize) -> HashSet<char> {
+        if position >= word_len {
+            return HashSet::new();
+        }
+
+        self.words_of_len(word_len)
+            .filter_map(|word| word.chars().nth(position))
+            .collect()
+    }
+
+    /// Returns the set of characters used by the entries, in sorted order.
+    ///
+    /// Wildcard slots such as [`Slot::any`](crate::Slot::any) draw their options from
+    /// this alphabet, so lists with accented letters, digits or symbols work without
+    /// a hard-coded `a`–`z` range. The set is computed the first time it is requested.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gallry_puzzle_soulver::{Dictionary, LoadOptions};
+    ///
+    /// let dictionary = Dictionary::from_text("café\nr2d2\n", &LoadOptions::default());
+    /// let alphabet: String = dictionary.alphabet().iter().collect();
+    /// assert_eq!(alphabet, "2acdfré");
+    /// ```
+    pub fn alphabet(&self) -> &BTreeSet<char> {
+        self.indexes
+            .alphabet
+            .get_or_init(|| self.words.keys().flat_map(|word| word.chars()).collect())
+    }
+
+    /// Returns up to `n` distinct entries chosen at random.
+    ///
+    /// This is useful for generating practice puzzles and for statistical baselines
+    /// when evaluating scoring heuristics. Fewer than `n` entries are returned when
+    /// the dictionary does not hold enough candidates.
+    ///
+    /// # Parameters
+    ///
+    /// * `n` - The number of entries to pick
+    /// * `len` - If set, only entries with exactly this many characters are picked
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gallry_puzzle_soulver::Dictionary;
+    ///
+    /// let dictionary = Dictionary::embedded();
+    /// let words = dictionary.sample(5, Some(4));
+    /// assert_eq!(words.len(), 5);
+    /// assert!(words.iter().all(|word| word.chars().count() == 4));
+    /// ```
+    pub fn sample(&self, n: usize, len: Option<usize>) -> Vec<&str> {
+        self.sample_with(&mut rand::rng(), n, len)
+    }
+
+    /// Like [`Dictionary::sample`], but draws from the given random number generator.
+    ///
+    /// Candidates are considered in sorted order, so a seeded generator yields the same
+    /// sample on every run.
+    pub fn sample_with<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+        n: usize,
+        len: Option<usize>,
+    ) -> Vec<&str> {
+        let candidates: Vec<&str> = match len {
+            Some(len) => self.words_of_len(len).collect(),
+            None => {
+                let mut words: Vec<&str> = self.words.keys().map(|word| word.as_ref()).collect();
+                words.sort_unstable();
+                words
+            }
+        };
+
+        let amount = n.min(candidates.len());
+        rand::seq::index::sample(rng, candidates.len(), amount)
+            .into_iter()
+            .map(|index| candidates[index])
+            .collect()
+    }
+
+    /// Returns every entry containing `substring`, in sorted order.
+    ///
+    /// This answers clues such as "the answer contains ART somewhere". The suffix
+    /// array backing this query is built the first time it is called, after which
+    /// each query costs a binary search rather than a scan of the whole dictionary.
+    ///
+    /// # Parameters
+    ///
+    /// * `substring` - The text the entries must contain
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gallry_puzzle_soulver::{Dictionary, LoadOptions};
+    ///
+    /// let text = "start\nparty\ncart\ndog\n";
+    /// let dictionary = Dictionary::from_text(text, &LoadOptions::default());
+    /// assert_eq!(dictionary.containing("art"), vec!["cart", "party", "start"]);
+    /// ```
+    pub fn containing(&self, substring: &str) -> Vec<&str> {
+        let index = self
+            .indexes
+            .substrings
+            .get_or_init(|| SubstringIndex::new(self.words.keys().map(|word| word.as_ref())));
+        index.containing(self.normalization.apply(substring).as_ref())
+    }
+
+    /// Returns every entry matching a glob pattern, in sorted order.
+    ///
+    /// `*` stands for any run of characters, including none, and `?` for exactly one,
+    /// so `c*t?` matches "cats" and "coats" alike. This answers clues where not even
+    /// the answer's length is known; with no `*` in the pattern, only entries of its
+    /// length are scanned.
+    ///
+    /// # Parameters
+    ///
+    /// * `pattern` - The pattern the entries must match in full
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gallry_puzzle_soulver::{Dictionary, LoadOptions};
+    ///
+    /// let text = "cats\ncoats\ncat\ncartel\ndog\n";
+    /// let dictionary = Dictionary::from_text(text, &LoadOptions::default());
+    /// assert_eq!(dictionary.glob("c*t?"), vec!["cats", "coats"]);
+    /// assert_eq!(dictionary.glob("c?t"), vec!["cat"]);
+    /// assert_eq!(dictionary.glob("*"), vec!["cartel", "cat", "cats", "coats", "dog"]);
+    /// ```
+    pub fn glob(&self, pattern: &str) -> Vec<&str> {
+        let pattern: Vec<char> = self.normalization.apply(pattern).chars().collect();
+        let fixed = pattern.iter().filter(|&&c| c != '*').count();
+        let open = pattern.contains(&'*');
+        let mut matches: Vec<&str> = self
+            .length_index()
+            .iter()
+            .filter(|&(&len, _)| len == fixed || (open && len > fixed))
+            .flat_map(|(_, words)| words.iter().map(|word| word.as_ref()))
+            .filter(|word| glob_matches(&pattern, word))
+            .collect();
+        matches.sort_unstable();
+        matches
+    }
+
+    /// Returns the entries closest to `word` by edit distance, for "did you mean" hints.
+    ///
+    /// Results are sorted by distance, then alphabetically, and include `word` itself
+    /// at distance 0 if it is an entry. The BK-tree backing this query is built the
+    /// first time it is called.
+    ///
+    /// # Parameters
+    ///
+    /// * `word` - The possibly misspelled word
+    /// * `max_distance` - The largest edit distance to report
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gallry_puzzle_soulver::{Dictionary, LoadOptions};
+    ///
+    /// let text = "gallery\ngalley\nvalley\n";
+    /// let dictionary = Dictionary::from_text(text, &LoadOptions::default());
+    /// let suggestions = dictionary.suggest("gallary", 1);
+    /// assert_eq!(suggestions, vec![("gallery", 1)]);
+    /// ```
+    pub fn suggest(&self, word: &str, max_distance: usize) -> Vec<(&str, usize)> {
+        let tree = self
+            .indexes
+            .bk_tree
+            .get_or_init(|| self.words.keys().map(|word| word.as_ref()).collect());
+        tree.find(self.normalization.apply(word).as_ref(), max_distance)
+    }
+
+    /// Returns the normalization applied to entries and looked up words.
+    pub fn normalization(&self) -> Normalization {
+        self.normalization
+    }
+
+    /// Returns `true` if the word is present, ignoring any match options.
+    pub fn contains(&self, word: &str) -> bool {
+        self.words
+            .contains_key(self.normalization.apply(word).as_ref())
+    }
+
+    /// Returns the metadata for a word, ignoring any match options.
+    pub fn get(&self, word: &str) -> Option<&WordInfo> {
+        self.words.get(self.normalization.apply(word).as_ref())
+    }
+
+    /// Looks up a candidate word, returning its metadata if it counts as a match.
+    ///
+    /// # Parameters
+    ///
+    /// * `word` - The candidate word
+    /// * `options` - Options deciding which entries count as matches
+    pub fn lookup(&self, word: &str, options: &MatchOptions) -> Option<&WordInfo> {
+        self.find(word, options).map(|(_, info)| info)
+    }
+
+    /// Looks up a word, returning which list contains it along with its metadata.
+    ///
+    /// This is the detailed counterpart of [`contains`](Self::contains): the word is
+    /// normalized, then matched exactly, falling back to entries that differ only in
+    /// case.
+    ///
+    /// # Parameters
+    ///
+    /// * `word` - The word to look up
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gallry_puzzle_soulver::{Dictionary, LoadOptions, WordFlags};
+    ///
+    /// let text = "the\tpos=det\trank=1\nParis\trank=900\n";
+    /// let dictionary = Dictionary::from_text(text, &LoadOptions::default()).with_name("common");
+    ///
+    /// let membership = dictionary.contains_detailed("paris").unwrap();
+    /// assert_eq!(membership.dictionary, "common");
+    /// assert_eq!(membership.entry, "Paris");
+    /// assert_eq!(membership.rank(), Some(900));
+    /// assert!(membership.flags().contains(WordFlags::PROPER_NOUN));
+    /// assert!(dictionary.contains_detailed("london").is_none());
+    /// ```
+    pub fn contains_detailed(&self, word: &str) -> Option<Membership<'_>> {
+        let options = MatchOptions {
+            case_insensitive: true,
+            ..Default::default()
+        };
+        let (entry, info) = self.find(word, &options)?;
+        Some(Membership {
+            dictionary: &self.name,
+            entry,
+            info,
+        })
+    }
+
+    /// Looks up a candidate word, returning the matching entry as spelled in the list.
+    ///
+    /// The candidate is normalized like the entries were. An exact match is then
+    /// preferred; with [`MatchOptions::case_insensitive`] set, entries differing only
+    /// in case are tried next.
+    ///
+    /// # Parameters
+    ///
+    /// * `word` - The candidate word
+    /// * `options` - Options deciding which entries count as matches
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gallry_puzzle_soulver::{Dictionary, LoadOptions, MatchOptions};
+    ///
+    /// let dictionary = Dictionary::from_text("McCoy\n", &LoadOptions::default());
+    /// let options = MatchOptions {
+    ///     case_insensitive: true,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let (entry, _) = dictionary.find("mccoy", &options).unwrap();
+    /// assert_eq!(entry, "McCoy");
+    /// ```
+    pub fn find(&self, word: &str, options: &MatchOptions) -> Option<(&str, &WordInfo)> {
+        let word = self.normalization.apply(word);
+        if let Some((entry, info)) = self.words.get_key_value(word.as_ref())
+            && options.accepts(info)
+        {
+            return Some((entry.as_ref(), info));
+        }
+
+        if !options.case_insensitive {
+            return None;
+        }
+
+        self.folded_index()
+            .get(&*ShortString::lowercase(&word))?
+            .iter()
+            .find_map(|entry| {
+                let (entry, info) = self.words.get_key_value(entry)?;
+                options.accepts(info).then_some((entry.as_ref(), info))
+            })
+    }
+
+    /// Looks up the stem of a candidate word, for stem-aware matching.
+    ///
+    /// Each base word proposed by [`stem_candidates`](crate::stem_candidates) is tried
+    /// in turn, and the first one that counts as a match is returned along with its
+    /// metadata. The candidate itself is not looked up.
+    ///
+    /// # Parameters
+    ///
+    /// * `word` - The candidate word
+    /// * `options` - Options deciding which entries count as matches
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gallry_puzzle_soulver::{Dictionary, LoadOptions, MatchOptions};
+    ///
+    /// let dictionary = Dictionary::from_text("run\n", &LoadOptions::default());
+    /// let (stem, _) = dictionary.lookup_stem("running", &MatchOptions::default()).unwrap();
+    /// assert_eq!(stem, "run");
+    /// ```
+    #[cfg(feature = "stemming")]
+    pub fn lookup_stem(&self, word: &str, options: &MatchOptions) -> Option<(&str, &WordInfo)> {
+        crate::stem_candidates(word)
+            .into_iter()
+            .find_map(|stem| self.find(&stem, options))
+    }
+
+    /// Attaches a gloss database used by [`definition`](Self::definition).
+    ///
+    /// # Parameters
+    ///
+    /// * `glossary` - The definitions to attach
+    #[cfg(feature = "definitions")]
+    pub fn set_glossary(&mut self, glossary: Glossary) {
+        self.glossary = glossary;
+    }
+
+    /// Returns the attached gloss database.
+    #[cfg(feature = "definitions")]
+    pub fn glossary(&self) -> &Glossary {
+        &self.glossary
+    }
+
+    /// Attaches a pronunciation table used to judge rhyme constraints.
+    ///
+    /// # Parameters
+    ///
+    /// * `pronunciations` - The pronunciations to attach
+    #[cfg(feature = "pronunciation")]
+    pub fn set_pronunciations(&mut self, pronunciations: Pronunciations) {
+        self.pronunciations = pronunciations;
+    }
+
+    /// Returns the attached pronunciation table.
+    #[cfg(feature = "pronunciation")]
+    pub fn pronunciations(&self) -> &Pronunciations {
+        &self.pronunciations
+    }
+
+    /// Returns the first definition of a word from the attached gloss database.
+    ///
+    /// The word does not need to be a dictionary entry itself.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gallry_puzzle_soulver::{Dictionary, Glossary, LoadOptions};
+    ///
+    /// let mut dictionary = Dictionary::from_text("cat\n", &LoadOptions::default());
+    /// dictionary.set_glossary(Glossary::from_text("cat\tsmall domesticated feline\n"));
+    ///
+    /// assert_eq!(dictionary.definition("cat"), Some("small domesticated feline"));
+    /// assert_eq!(dictionary.definition("dog"), None);
+    /// ```
+    #[cfg(feature = "definitions")]
+    pub fn definition(&self, word: &str) -> Option<&str> {
+        self.glossary.definition(word)
+    }
+
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Returns `true` if the dictionary has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Returns an iterator over every entry and its metadata, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &WordInfo)> {
+        self.words.iter().map(|(word, info)| (word.as_ref(), info))
+    }
+}
+
+impl From<HashSet<String>> for Dictionary {
+    fn from(words: HashSet<String>) -> Self {
+        words.into_iter().collect()
+    }
+}
+
+impl FromIterator<String> for Dictionary {
+    fn from_iter<I: IntoIterator<Item = String>>(iter: I) -> Self {
+        let mut dictionary = Self::new();
+        for word in iter {
+            dictionary.insert(word);
+        }
+        dictionary
+    }
+}
@@ -0,0 +1,83 @@
+//! Comparison of two versions of a word list.
+
+use crate::{Dictionary, WordGenerator};
+
+/// The differences between an old and a new [`Dictionary`].
+///
+/// Words are compared by their stored spelling; metadata changes are not reported.
+/// The `added` and `removed` streams are produced lazily, in arbitrary order.
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::{Dictionary, LoadOptions};
+///
+/// let old = Dictionary::from_text("cat\nbat\n", &LoadOptions::default());
+/// let new = Dictionary::from_text("cat\nrat\n", &LoadOptions::default());
+/// let diff = old.diff(&new);
+///
+/// assert_eq!(diff.added().collect::<Vec<_>>(), vec!["rat"]);
+/// assert_eq!(diff.removed().collect::<Vec<_>>(), vec!["bat"]);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct DictionaryDiff<'a> {
+    old: &'a Dictionary,
+    new: &'a Dictionary,
+}
+
+impl<'a> DictionaryDiff<'a> {
+    /// Returns the words present in the new dictionary but not the old one.
+    pub fn added(&self) -> impl Iterator<Item = &'a str> + use<'a> {
+        let old = self.old;
+        self.new
+            .iter()
+            .map(|(word, _)| word)
+            .filter(move |word| !old.contains(word))
+    }
+
+    /// Returns the words present in the old dictionary but not the new one.
+    pub fn removed(&self) -> impl Iterator<Item = &'a str> + use<'a> {
+        let new = self.new;
+        self.old
+            .iter()
+            .map(|(word, _)| word)
+            .filter(move |word| !new.contains(word))
+    }
+
+    /// Returns `true` if both dictionaries contain the same words.
+    pub fn is_empty(&self) -> bool {
+        self.old.len() == self.new.len() && self.added().next().is_none()
+    }
+
+    /// Returns the changed words a puzzle could generate, sorted.
+    ///
+    /// A non-empty result means the puzzle's matches may differ between the two
+    /// versions of the word list.
+    ///
+    /// # Parameters
+    ///
+    /// * `generator` - The puzzle to check
+    pub fn affected_words(&self, generator: &WordGenerator) -> Vec<&'a str> {
+        let mut affected: Vec<&str> = self
+            .added()
+            .chain(self.removed())
+            .filter(|word| generator.can_generate(word))
+            .collect();
+        affected.sort_unstable();
+        affected
+    }
+}
+
+impl Dictionary {
+    /// Compares this dictionary, as the old version, against a new version.
+    ///
+    /// # Parameters
+    ///
+    /// * `other` - The new version of the word list
+    pub fn diff<'a>(&'a self, other: &'a Dictionary) -> DictionaryDiff<'a> {
+        DictionaryDiff {
+            old: self,
+            new: other,
+        }
+    }
+}
@@ -0,0 +1,159 @@
+//! Filtering of results through an external program selected with `--filter-cmd`.
+//!
+//! The command is run by the system shell. By default it runs once per candidate with
+//! the word on standard input, and the word is kept if the command exits successfully.
+//! In batch mode it runs once with every candidate on standard input, one per line,
+//! and the words it echoes back on standard output are kept.
+
+use crate::output::Solution;
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use tracing::{debug, trace};
+
+/// An external program that decides which candidates to keep.
+pub struct FilterCommand {
+    /// Shell command line to run
+    pub command: String,
+    /// Run the command once for all candidates instead of once per candidate
+    pub batch: bool,
+    /// Why a per-candidate filter stopped early, reported by [`finish`](Self::finish)
+    failure: Mutex<Option<anyhow::Error>>,
+}
+
+impl FilterCommand {
+    /// Creates a filter running `command` once per candidate, or once for all of them
+    /// with `batch`.
+    pub fn new(command: String, batch: bool) -> Self {
+        Self {
+            command,
+            batch,
+            failure: Mutex::new(None),
+        }
+    }
+
+    /// Keeps the solutions the command accepts, preserving their order.
+    ///
+    /// In per-candidate mode the solutions are filtered lazily, so output streams as
+    /// before; batch mode has to collect every candidate first. Either way the command
+    /// is first run here, so one that cannot be started fails before any output.
+    pub fn apply<'a>(
+        &'a self,
+        solutions: impl Iterator<Item = Solution> + 'a,
+    ) -> Result<Box<dyn Iterator<Item = Solution> + 'a>> {
+        if !self.batch {
+            let mut solutions = solutions.peekable();
+            let mut first = match solutions.peek() {
+                Some(solution) => Some(self.accepts(&solution.word_match.word)?),
+                None => None,
+            };
+            // A later failure ends the results early, and `finish` reports it
+            let kept = solutions.map_while(move |solution| {
+                let accepted = match first.take() {
+                    Some(accepted) => accepted,
+                    None => match self.accepts(&solution.word_match.word) {
+                        Ok(accepted) => accepted,
+                        Err(error) => {
+                            *self.failure.lock().unwrap() = Some(error);
+                            return None;
+                        }
+                    },
+                };
+                Some(accepted.then_some(solution))
+            });
+            return Ok(Box::new(kept.flatten()));
+        }
+
+        let solutions: Vec<Solution> = solutions.collect();
+        let words: Vec<&str> = solutions
+            .iter()
+            .map(|solution| solution.word_match.word.as_str())
+            .collect();
+        let kept = self.run_batch(&words)?;
+        debug!(
+            "filter command kept {} of {} words",
+            kept.len(),
+            words.len()
+        );
+        Ok(Box::new(solutions.into_iter().filter(move |solution| {
+            kept.contains(&solution.word_match.word)
+        })))
+    }
+
+    /// Returns the error that ended per-candidate filtering early, if any.
+    ///
+    /// Call this once the filtered solutions have been written, since the filter
+    /// cannot fail the iterator it returned.
+    pub fn finish(&self) -> Result<()> {
+        match self.failure.lock().unwrap().take() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    /// Runs the command for a single word, returning whether it exited successfully.
+    fn accepts(&self, word: &str) -> Result<bool> {
+        let status = shell(&self.command)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .spawn()
+            .and_then(|mut child| {
+                if let Some(mut stdin) = child.stdin.take() {
+                    // The command may exit without reading its input
+                    let _ = writeln!(stdin, "{}", word);
+                }
+                child.wait()
+            })
+            .with_context(|| format!("Failed to run filter command '{}'", self.command))?;
+        trace!("filter command {} '{}'", status, word);
+        Ok(status.success())
+    }
+
+    /// Runs the command once for all words, returning the words it echoed back.
+    fn run_batch(&self, words: &[&str]) -> Result<HashSet<String>> {
+        let mut child = shell(&self.command)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .with_context(|| format!("Failed to run filter command '{}'", self.command))?;
+
+        let input: String = words.iter().map(|word| format!("{}\n", word)).collect();
+        let mut stdin = child.stdin.take().context("Filter command has no input")?;
+        // Write from another thread so a command that streams its output cannot deadlock
+        let writer = std::thread::spawn(move || stdin.write_all(input.as_bytes()));
+
+        let stdout = child
+            .stdout
+            .take()
+            .context("Filter command has no output")?;
+        let mut kept = HashSet::new();
+        for line in BufReader::new(stdout).lines() {
+            let line = line.context("Failed to read the filter command's output")?;
+            kept.insert(line.trim().to_string());
+        }
+
+        // A command that exits without reading all of its input is not an error
+        let _ = writer.join();
+        let status = child.wait()?;
+        if !status.success() {
+            anyhow::bail!("Filter command '{}' failed with {}", self.command, status);
+        }
+        Ok(kept)
+    }
+}
+
+#[cfg(unix)]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("sh");
+    shell.arg("-c").arg(command);
+    shell
+}
+
+#[cfg(windows)]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("cmd");
+    shell.arg("/C").arg(command);
+    shell
+}
@@ -0,0 +1,93 @@
+//! Rectangular grids of letters, such as boards and word search puzzles.
+
+use anyhow::{Context, Result, bail};
+use std::collections::HashSet;
+
+/// A rectangle of letters, read row by row from the top.
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::LetterGrid;
+///
+/// let grid = LetterGrid::from_text("c a t\nd o g\n").unwrap();
+/// assert_eq!((grid.height(), grid.width()), (2, 3));
+/// assert_eq!(grid.get(1, 2), Some('g'));
+/// assert!(LetterGrid::from_text("cat\ndo\n").is_err());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LetterGrid {
+    /// The cells of each row, in lowercase
+    rows: Vec<Vec<char>>,
+}
+
+impl LetterGrid {
+    /// Creates a grid from its rows, top first.
+    ///
+    /// Whitespace within a row is ignored, so `c a t` and `cat` are the same row.
+    ///
+    /// # Returns
+    ///
+    /// The grid, or an error if there are no rows or they differ in length
+    pub fn from_rows<S: AsRef<str>>(rows: &[S]) -> Result<Self> {
+        let rows: Vec<Vec<char>> = rows
+            .iter()
+            .map(|row| {
+                row.as_ref()
+                    .chars()
+                    .filter(|c| !c.is_whitespace())
+                    .flat_map(char::to_lowercase)
+                    .collect::<Vec<_>>()
+            })
+            .filter(|row| !row.is_empty())
+            .collect();
+        let Some(width) = rows.first().map(Vec::len) else {
+            bail!("The grid has no rows");
+        };
+        if let Some(number) = rows.iter().position(|row| row.len() != width) {
+            bail!(
+                "Row {} has {} letters, but the first row has {}",
+                number + 1,
+                rows[number].len(),
+                width
+            );
+        }
+        Ok(LetterGrid { rows })
+    }
+
+    /// Parses a grid with one row per line; blank lines are skipped.
+    pub fn from_text(text: &str) -> Result<Self> {
+        Self::from_rows(&text.lines().collect::<Vec<_>>())
+    }
+
+    /// Reads a grid file in the format of [`from_text`](Self::from_text).
+    pub fn from_path(path: &str) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read grid from '{}'", path))?;
+        Self::from_text(&content).with_context(|| format!("Failed to parse grid '{}'", path))
+    }
+
+    /// Returns the number of rows.
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns the number of letters in each row.
+    pub fn width(&self) -> usize {
+        self.rows[0].len()
+    }
+
+    /// Returns the letter at a row and column counted from zero at the top left.
+    pub fn get(&self, row: usize, column: usize) -> Option<char> {
+        self.rows.get(row)?.get(column).copied()
+    }
+}
+
+/// Returns every proper beginning of the words, so a search along a grid can stop as
+/// soon as its letters no longer lead to a word.
+pub(crate) fn word_prefixes(words: &HashSet<String>) -> HashSet<&str> {
+    words
+        .iter()
+        .flat_map(|word| word.char_indices().map(move |(end, _)| &word[..end]))
+        .collect()
+}
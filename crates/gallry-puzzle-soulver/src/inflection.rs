@@ -0,0 +1,254 @@
+//! Generation of regular and irregular inflected forms from base words.
+//!
+//! Custom word lists are often lemma-only ("cat", "run"), while puzzle answers may be
+//! inflected ("cats", "ran"). [`InflectionRules`] produces the likely forms of a lemma
+//! so a [`Dictionary`](crate::Dictionary) can be expanded at load time.
+
+use std::collections::HashMap;
+
+/// Irregular forms included by [`InflectionRules::default`], as `lemma form...` lines.
+const DEFAULT_IRREGULARS: &str = "\
+be am is are was were been being
+begin began begun
+break broke broken
+bring brought
+buy bought
+catch caught
+child children
+choose chose chosen
+come came
+do does did done
+draw drew drawn
+drink drank drunk
+drive drove driven
+eat ate eaten
+fall fell fallen
+feel felt
+find found
+fly flew flown flies
+foot feet
+forget forgot forgotten
+get got gotten
+give gave given
+go goes went gone
+goose geese
+grow grew grown
+have has had
+hear heard
+hold held
+keep kept
+know knew known
+leave left
+lose lost
+make made
+man men
+mean meant
+meet met
+mouse mice
+ox oxen
+pay paid
+person people
+ride rode ridden
+ring rang rung
+rise rose risen
+run ran
+say said
+see saw seen
+sell sold
+send sent
+sing sang sung
+sit sat
+sleep slept
+speak spoke spoken
+stand stood
+steal stole stolen
+swim swam swum
+take took taken
+teach taught
+tell told
+think thought
+throw threw thrown
+tooth teeth
+wear wore worn
+win won
+woman women
+write wrote written
+";
+
+/// Rules for producing inflected forms from a lemma.
+///
+/// Regular plurals and verb forms follow common English spelling rules; irregular
+/// forms come from a lookup table that can be extended.
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::InflectionRules;
+///
+/// let rules = InflectionRules::default();
+/// assert!(rules.inflect("cat").contains(&"cats".to_string()));
+/// assert!(rules.inflect("run").contains(&"ran".to_string()));
+/// assert!(rules.inflect("bake").contains(&"baking".to_string()));
+/// ```
+#[derive(Clone, Debug)]
+pub struct InflectionRules {
+    /// Whether to generate regular plurals ("cat" -> "cats")
+    pub plurals: bool,
+    /// Whether to generate regular verb forms ("walk" -> "walks", "walked", "walking")
+    pub verb_forms: bool,
+    /// Irregular forms keyed by lemma
+    irregulars: HashMap<String, Vec<String>>,
+}
+
+impl Default for InflectionRules {
+    fn default() -> Self {
+        let mut rules = Self::regular_only();
+        rules.add_irregulars_from_text(DEFAULT_IRREGULARS);
+        rules
+    }
+}
+
+impl InflectionRules {
+    /// Creates rules that generate regular forms only, without any irregular table.
+    pub fn regular_only() -> Self {
+        Self {
+            plurals: true,
+            verb_forms: true,
+            irregulars: HashMap::new(),
+        }
+    }
+
+    /// Registers irregular forms for a lemma.
+    ///
+    /// # Parameters
+    ///
+    /// * `lemma` - The base word
+    /// * `forms` - Additional forms produced for the lemma
+    pub fn add_irregular<I, S>(&mut self, lemma: &str, forms: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.irregulars
+            .entry(lemma.to_string())
+            .or_default()
+            .extend(forms.into_iter().map(Into::into));
+    }
+
+    /// Registers irregular forms from text with one `lemma form...` entry per line.
+    ///
+    /// Blank lines and lines starting with `#` are ignored.
+    pub fn add_irregulars_from_text(&mut self, text: &str) {
+        for line in text.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut words = line.split_whitespace();
+            if let Some(lemma) = words.next() {
+                self.add_irregular(lemma, words);
+            }
+        }
+    }
+
+    /// Returns the inflected forms of a lemma, not including the lemma itself.
+    ///
+    /// Forms may contain duplicates when a regular and an irregular rule agree.
+    pub fn inflect(&self, lemma: &str) -> Vec<String> {
+        let mut forms = Vec::new();
+        if lemma.chars().count() < 2 || !lemma.chars().all(char::is_alphabetic) {
+            return forms;
+        }
+
+        if let Some(irregular) = self.irregulars.get(lemma) {
+            forms.extend(irregular.iter().cloned());
+        }
+
+        if self.plurals || self.verb_forms {
+            forms.push(add_s(lemma));
+        }
+        if self.verb_forms {
+            forms.push(add_ed(lemma));
+            forms.push(add_ing(lemma));
+        }
+
+        forms.retain(|form| form != lemma);
+        forms
+    }
+}
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u')
+}
+
+/// Returns `true` if the word ends consonant-vowel-consonant with a single vowel
+/// group, where English doubles the final consonant ("run" -> "running").
+fn doubles_final_consonant(word: &str) -> bool {
+    let chars: Vec<char> = word.chars().collect();
+    let [.., a, b, c] = chars[..] else {
+        return false;
+    };
+
+    // The word ends in a consonant, so every vowel group is followed by one
+    let vowel_groups = chars
+        .windows(2)
+        .filter(|pair| is_vowel(pair[0]) && !is_vowel(pair[1]))
+        .count();
+
+    !is_vowel(a)
+        && is_vowel(b)
+        && !is_vowel(c)
+        && !matches!(c, 'w' | 'x' | 'y')
+        && vowel_groups == 1
+}
+
+/// Replaces a trailing consonant + `y` with `replacement`, if present.
+fn replace_consonant_y(word: &str, replacement: &str) -> Option<String> {
+    let stem = word.strip_suffix('y')?;
+    let before = stem.chars().last()?;
+    (!is_vowel(before)).then(|| format!("{}{}", stem, replacement))
+}
+
+fn add_s(word: &str) -> String {
+    if let Some(form) = replace_consonant_y(word, "ies") {
+        return form;
+    }
+    if ["s", "x", "z", "ch", "sh"]
+        .iter()
+        .any(|suffix| word.ends_with(suffix))
+    {
+        return format!("{}es", word);
+    }
+    format!("{}s", word)
+}
+
+fn add_ed(word: &str) -> String {
+    if let Some(form) = replace_consonant_y(word, "ied") {
+        return form;
+    }
+    if word.ends_with('e') {
+        return format!("{}d", word);
+    }
+    if doubles_final_consonant(word) {
+        let last = word.chars().last().unwrap_or_default();
+        return format!("{}{}ed", word, last);
+    }
+    format!("{}ed", word)
+}
+
+fn add_ing(word: &str) -> String {
+    if let Some(stem) = word.strip_suffix("ie") {
+        return format!("{}ying", stem);
+    }
+    if let Some(stem) = word.strip_suffix('e')
+        && !stem.ends_with('e')
+    {
+        return format!("{}ing", stem);
+    }
+    if doubles_final_consonant(word) {
+        let last = word.chars().last().unwrap_or_default();
+        return format!("{}{}ing", word, last);
+    }
+    format!("{}ing", word)
+}
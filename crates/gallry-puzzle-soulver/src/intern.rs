@@ -0,0 +1,147 @@
+//! Sharing the spelling of dictionary entries between dictionaries.
+//!
+//! A batch of puzzles, or a long session over JSON-RPC, loads the same word lists
+//! into one generator after another. Dictionaries loaded with the same [`Interner`]
+//! store each distinct spelling once and point at it, so the copies cost a pointer per
+//! entry instead of a string.
+
+use std::borrow::Borrow;
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// The spelling of a dictionary entry.
+///
+/// Entries of the embedded list borrow from the text compiled into the binary; the
+/// rest are reference counted so an [`Interner`] can share them. Either way an entry
+/// compares, hashes and sorts as the string it spells.
+#[derive(Clone)]
+pub(crate) enum Entry {
+    /// Borrowed from text that lives as long as the program
+    Static(&'static str),
+    /// Shared with other dictionaries or owned outright
+    Shared(Arc<str>),
+}
+
+impl Deref for Entry {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        match self {
+            Entry::Static(word) => word,
+            Entry::Shared(word) => word,
+        }
+    }
+}
+
+impl Borrow<str> for Entry {
+    fn borrow(&self) -> &str {
+        self
+    }
+}
+
+impl AsRef<str> for Entry {
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl From<String> for Entry {
+    fn from(word: String) -> Self {
+        Entry::Shared(word.into())
+    }
+}
+
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl Eq for Entry {}
+
+impl Hash for Entry {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (**self).hash(state);
+    }
+}
+
+impl PartialOrd for Entry {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Entry {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (**self).cmp(&**other)
+    }
+}
+
+impl fmt::Debug for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+/// A set of spellings shared by every dictionary loaded with it.
+///
+/// Set [`LoadOptions::interner`](crate::LoadOptions::interner) to load dictionaries
+/// through an interner. Clones share the same set, and strings stay in it for as long
+/// as any clone does, so drop it with the session it served.
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::{Dictionary, Interner, LoadOptions};
+///
+/// let options = LoadOptions {
+///     interner: Some(Interner::new()),
+///     ..Default::default()
+/// };
+/// let first = Dictionary::from_text("cat\ndog\n", &options);
+/// let second = Dictionary::from_text("cat\ncow\n", &options);
+///
+/// // "cat" is stored once for both dictionaries
+/// assert_eq!(options.interner.unwrap().len(), 3);
+/// assert!(first.contains("cat") && second.contains("cat"));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Interner {
+    strings: Arc<Mutex<HashSet<Arc<str>>>>,
+}
+
+impl Interner {
+    /// Creates an empty interner.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of distinct strings stored.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Returns `true` if no strings are stored.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Returns the stored copy of `word`, storing it first if it is new.
+    pub(crate) fn intern(&self, word: &str) -> Arc<str> {
+        let mut strings = self.lock();
+        if let Some(stored) = strings.get(word) {
+            return Arc::clone(stored);
+        }
+        let stored: Arc<str> = word.into();
+        strings.insert(Arc::clone(&stored));
+        stored
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashSet<Arc<str>>> {
+        // The set is never left half-updated, so a panic elsewhere does not spoil it
+        self.strings.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
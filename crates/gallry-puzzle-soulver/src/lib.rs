@@ -15,6 +15,82 @@
 //! - Filter generated words against an embedded word list
 //! - Support for custom word lists
 //! - Efficient HashSet-based lookups for word filtering
+//! - Multi-threaded solving with results in the same order as sequential solving
+//! - Collection of matches bounded by count and memory, marking truncated results
+//! - Scanning of the dictionary instead of enumeration when a puzzle has far more
+//!   combinations than the dictionary has words
+//! - A choice of solving strategy that can be forced for benchmarks and comparisons,
+//!   and a profile timing each strategy on a puzzle
+//! - Optional blocklists, including an embedded offensive word list, to suppress output
+//! - Per-word metadata flags, such as proper nouns, for finer-grained filtering
+//! - Part-of-speech filtering for word lists that carry metadata
+//! - Expansion of lemma-only word lists with plurals and verb inflections
+//! - Case-insensitive matching that preserves the dictionary's spelling for display
+//! - Unicode normalization, optionally stripping diacritics, of entries and lookups
+//! - Wildcard and negated slots drawing on the alphabet of the loaded word list
+//! - A slot notation with `?` wildcards, `[a-f]` ranges, `!xyz` negation and escapes,
+//!   and a compact form for whole puzzles such as `cbr/aio/tse`
+//! - Substring search over the dictionary, backed by a lazily built suffix array
+//! - Spelling suggestions from the dictionary, backed by a lazily built BK-tree
+//! - Several dictionaries at once, with per-match attribution and any/all policies
+//! - An optional interner that stores the entries shared by several loaded word lists
+//!   once, for batches of puzzles over the same lists
+//! - Scoring of the billiard room's dartboard throws, and a search for the throws that
+//!   give a wanted number
+//! - The A1Z26 letter-number cipher, with the readings of run-together numbers
+//!   narrowed to dictionary words
+//! - Brute-force decryption of Caesar and ROT-N ciphers, ranking plaintexts by dictionary
+//!   words and English letter frequencies
+//! - Vigenère decryption that breaks short keys by letter frequencies, or tries
+//!   dictionary words as keys
+//! - A solver for substitution cryptograms, matching the pattern of repeated letters in
+//!   each word against the dictionary
+//! - A solver for the parlor's logic puzzles of boxes bearing true and false statements
+//! - Diffing of word list versions, including which puzzles a change affects
+//! - Acrostics, solving for the word spelled by a letter of each line, in order or in
+//!   any order
+//! - Positional letter statistics for judging ambiguous interpretations
+//! - Whole-word constraints such as required substrings, prefixes, suffixes, regexes
+//!   and anagrams
+//! - Rhyme and syllable count constraints by spelling, or by a pronunciation table
+//!   behind the `pronunciation` feature
+//! - Numeric codes for keypads and safes, with digit sum, even digit and ordering
+//!   constraints
+//! - Glob patterns with `*` and `?` matched against the dictionary, for answers of
+//!   unknown length
+//! - Letter wheels read by stepping round the circle with a fixed or varying stride,
+//!   in either direction
+//! - Word searches finding dictionary words along the rows, columns and diagonals of
+//!   a letter grid
+//! - Letters read off a board by chess coordinates, or along a knight's path
+//! - Pictographic ciphers such as pigpen read through tables of symbols to letters,
+//!   some of which may be only partly known
+//! - Morse code read as slots, searching the split points of runs whose letter gaps
+//!   were lost
+//! - Spelled-out numbers and Roman numerals read as values, with constraints keeping
+//!   the words that are numbers, optionally of a given value
+//! - Dates read as letters, by month or weekday initial or by the number of the
+//!   month or day, as slots
+//! - Decoding of words typed on a phone keypad, T9 style, against the dictionary
+//! - Puzzle definitions loaded from TOML or JSON files
+//! - Named puzzle definitions embedded behind the `presets` feature, for contributed
+//!   definitions of known gallery paintings
+//! - Memory-mapped loading of very large word list files behind the `mmap` feature
+//! - Parsing and length indexing of large word lists on several threads
+//! - Stem-aware matching behind the `stemming` feature
+//! - Offline definitions for annotating results behind the `definitions` feature
+//! - `tracing` spans and events for word list loading and solving behind the
+//!   `instrument` feature
+//! - Asynchronous streams of matches that yield to the executor behind the `async`
+//!   feature
+//! - Slots and combination numbering from the `no_std` crate `gallry-core`, for
+//!   embedded and WebAssembly targets without the standard library
+//! - `proptest` strategies for random slots, constraints and puzzle definitions
+//!   behind the `proptest-support` feature
+//!
+//! The default `cli` feature builds the command-line program and its logging;
+//! libraries that only solve can turn default features off to leave out their
+//! dependencies.
 //!
 //! ## Example
 //!
@@ -37,98 +113,135 @@
 //! }
 //! ```
 
-use anyhow::{Context, Result};
-use std::collections::HashSet;
-use std::ops::Deref;
-
-// Embed the wordlist at compile time
-const EMBEDDED_WORDLIST: &str = include_str!("../data/words.txt");
-
-/// A character position with multiple possible character options.
-///
-/// Each `Slot` represents a single position in a word, with a set of possible characters
-/// that could appear in that position. It also implements `Iterator` to allow iterating
-/// through all possible characters in the slot.
-///
-/// # Examples
-///
-/// ```
-/// use gallry_puzzle_soulver::Slot;
-///
-/// // Create a slot with three possible characters
-/// let slot = Slot::new(vec!['a', 'b', 'c']);
-///
-/// // Use it as a character via deref coercion (defaults to first option)
-/// assert_eq!(*slot, 'a');
-///
-/// // Convert to string
-/// assert_eq!(slot.to_string(), "a");
-///
-/// // Iterate through all options
-/// let chars: Vec<char> = slot.collect();
-/// assert_eq!(chars, vec!['a', 'b', 'c']);
-/// ```
-#[derive(Clone, Debug)]
-pub struct Slot {
-    /// All possible characters for this position
-    options: Vec<char>,
-    /// Current index when iterating
-    current: usize,
-}
-
-impl Slot {
-    /// Creates a new Slot with the given character options.
-    ///
-    /// # Parameters
-    ///
-    /// * `options` - A vector of possible characters for this position
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use gallry_puzzle_soulver::Slot;
-    ///
-    /// let slot = Slot::new(vec!['a', 'b', 'c']);
-    /// ```
-    pub fn new(options: Vec<char>) -> Self {
-        Self {
-            options,
-            current: 0,
-        }
-    }
-}
+use anyhow::Result;
+use std::borrow::Cow;
+use std::collections::{BTreeSet, HashSet};
+use std::sync::OnceLock;
 
-impl From<Slot> for String {
-    /// Converts the slot to a String, using the currently selected character.
-    fn from(val: Slot) -> Self {
-        val.options[val.current].to_string()
-    }
+/// Emits a `tracing` event when the `instrument` feature is enabled, and nothing
+/// otherwise, so solving costs nothing extra for embedders that do not trace.
+macro_rules! trace_event {
+    ($level:ident, $($arg:tt)+) => {
+        #[cfg(feature = "instrument")]
+        tracing::$level!($($arg)+);
+    };
 }
 
-impl Deref for Slot {
-    type Target = char;
+/// Combinations examined between progress events of a traced solve
+#[cfg(feature = "instrument")]
+const TRACE_MILESTONE: u64 = 1 << 20;
 
-    /// Dereferences to the currently selected character.
-    /// By default, this is the first character in the options list.
-    fn deref(&self) -> &Self::Target {
-        &self.options[self.current]
-    }
-}
+mod a1z26;
+mod acrostic;
+mod analysis;
+#[cfg(feature = "proptest-support")]
+pub mod arbitrary;
+mod bktree;
+mod blocklist;
+mod bounded;
+mod calendar;
+mod checks;
+mod chess;
+mod cipher;
+mod config;
+mod confusable;
+mod constraint;
+mod cryptogram;
+mod cursor;
+mod darts;
+#[cfg(feature = "definitions")]
+mod definitions;
+mod dictionary;
+mod diff;
+mod grid;
+mod inflection;
+mod intern;
+mod morse;
+mod normalize;
+mod notation;
+mod numerals;
+mod parallel;
+mod parlor;
+mod profile;
+#[cfg(feature = "pronunciation")]
+mod pronunciation;
+mod rhyme;
+mod scan;
+mod short;
+mod slot_index;
+mod stats;
+#[cfg(feature = "stemming")]
+mod stem;
+mod strategy;
+#[cfg(feature = "async")]
+mod stream;
+mod substring;
+mod syllables;
+mod symbols;
+mod verify;
+mod wheel;
+mod wordle;
+mod wordsearch;
 
-impl Iterator for Slot {
-    type Item = char;
+pub use a1z26::{MAX_READING_DIGITS, a1z26_decode, a1z26_encode, a1z26_readings, a1z26_words};
+pub use acrostic::{Acrostic, AcrosticMode};
+pub use analysis::PositionalFrequencies;
+pub use bktree::{BkTree, edit_distance};
+pub use blocklist::Blocklist;
+pub use bounded::{BoundedMatches, Truncation};
+pub use calendar::{CalendarDate, DateLetter, MONTH_NAMES, WEEKDAY_NAMES, date_slots};
+pub use chess::{KNIGHT_MOVES, KnightPath, Square, chess_slots, knight_slots, knight_words};
+pub use cipher::{
+    CaesarDecryption, LETTER_FREQUENCIES, PlaintextScore, VigenereDecryption, VigenereOptions,
+    caesar_decryptions, caesar_shift, score_plaintext, vigenere_decrypt, vigenere_decryptions,
+    vigenere_encrypt,
+};
+pub use config::{PuzzleConfig, PuzzleMetadata, SchemaVersion};
+pub use confusable::{CONFUSABLE_PAIRS, Substitution, confusable_substitutions};
+pub use constraint::{Constraint, NumberTarget, Pattern, SequenceOrder};
+pub use cryptogram::{Cryptogram, CryptogramSolution, MAX_CRYPTOGRAM_STEPS};
+pub use cursor::Cursor;
+pub use darts::{DARTBOARD_SEGMENTS, DartOp, DartThrow, score_throws, throws_for};
+#[cfg(feature = "definitions")]
+pub use definitions::Glossary;
+pub use dictionary::{
+    Dictionary, DictionaryPolicy, LoadOptions, MatchOptions, Membership, PartOfSpeech, ProperNouns,
+    WordFlags, WordInfo,
+};
+pub use diff::DictionaryDiff;
+pub use gallry_core::{Slot, combination};
+pub use grid::LetterGrid;
+pub use inflection::InflectionRules;
+pub use intern::Interner;
+pub use morse::{MORSE_LETTERS, MorseCode, MorseSpacing, morse_encode};
+pub use normalize::Normalization;
+pub use notation::{
+    KEYPAD_LETTERS, parse_digit_slots, parse_keypad_slots, parse_slots, split_sets,
+};
+pub use numerals::{roman_numeral, roman_numeral_value, spelled_number_value};
+pub use parallel::ParMatchIter;
+pub use parlor::{ParlorPuzzle, ParlorSolution, PrizeBox, Statement};
+pub use profile::{SolverProfile, StrategyTiming};
+#[cfg(feature = "pronunciation")]
+pub use pronunciation::Pronunciations;
+pub use rhyme::{rhyme_key, rhymes};
+pub use stats::SolveStats;
+#[cfg(feature = "stemming")]
+pub use stem::stem_candidates;
+pub use strategy::Strategy;
+#[cfg(feature = "async")]
+pub use stream::{DEFAULT_YIELD_EVERY, MatchStream};
+pub use syllables::syllable_count;
+pub use symbols::SymbolTable;
+pub use verify::Rejection;
+pub use wheel::{Rotation, WheelMatch, WheelOptions, wheel_words};
+pub use wordle::{Feedback, Mark, best_guess, expected_information};
+pub use wordsearch::{Direction, WordSearchMatch, WordSearchOptions, word_search};
 
-    /// Iterates through all possible characters in this slot.
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.current < self.options.len() {
-            let result = Some(self.options[self.current]);
-            self.current += 1;
-            result
-        } else {
-            None
-        }
-    }
-}
+use checks::Check;
+use slot_index::SlotIndex;
+use stats::StatsCounter;
+use strategy::Plan;
 
 /// A generator for creating and filtering possible words based on character options.
 ///
@@ -188,41 +301,135 @@ impl Iterator for Slot {
 pub struct WordGenerator {
     /// The slots defining character options for each position
     slots: Vec<Slot>,
-    /// Optional word list for filtering
-    word_list: Option<HashSet<String>>,
+    /// Dictionaries used for filtering; empty dictionaries apply no filtering
+    dictionaries: Vec<Dictionary>,
+    /// Which registered dictionaries a word must appear in
+    dictionary_policy: DictionaryPolicy,
+    /// Options deciding which dictionary entries count as matches
+    match_options: MatchOptions,
+    /// Words that are never yielded, even without dictionary filtering
+    blocklist: Blocklist,
+    /// Conditions every yielded word must satisfy
+    constraints: Vec<Constraint>,
+    /// How `constraints` are checked, cheapest first
+    checks: Vec<Check>,
+    /// The algorithm forced with `set_strategy`, or `None` to choose per solve
+    strategy: Option<Strategy>,
+    /// Option lookups for the resolved slots, built on first use
+    slot_index: OnceLock<Vec<SlotIndex>>,
+    /// Work done by every solve so far
+    stats: StatsCounter,
+}
+
+/// A generated word that passed filtering, along with how it matched.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WordMatch {
+    /// The generated word
+    pub word: String,
+    /// The dictionary entry the word matched through, when it is not the word itself
+    ///
+    /// This is set when stem-aware matching found the base word rather than the
+    /// generated word, e.g. `"run"` for `"running"`.
+    pub stem: Option<String>,
+    /// The dictionary entry as spelled in the list, when it differs from the word
+    ///
+    /// This is set when case-insensitive matching found an entry such as `"Paris"`
+    /// for the generated word `"paris"`.
+    pub entry: Option<String>,
+    /// Names of the registered dictionaries containing the word, in registration order
+    ///
+    /// Unnamed dictionaries are reported by their one-based position, e.g. `"#2"`.
+    /// This is empty when no dictionary filtering is applied.
+    pub dictionaries: Vec<String>,
+    /// Frequency rank of the matched entry, when the word list records one
+    pub rank: Option<u32>,
+}
+
+impl From<String> for WordMatch {
+    /// Wraps a word that was not looked up in any dictionary.
+    fn from(word: String) -> Self {
+        Self::exact(word)
+    }
+}
+
+impl WordMatch {
+    /// Creates a match for a word found in the dictionary as-is.
+    fn exact(word: String) -> Self {
+        Self {
+            word,
+            stem: None,
+            entry: None,
+            dictionaries: Vec::new(),
+            rank: None,
+        }
+    }
+
+    /// Returns the spelling to display: the dictionary entry if known, else the word.
+    pub fn display_word(&self) -> &str {
+        self.entry.as_deref().unwrap_or(&self.word)
+    }
 }
 
 /// An iterator that generates and filters words based on slot options
 pub struct WordIter<'a> {
     generator: &'a WordGenerator,
+    slots: Cow<'a, [Slot]>,
     current_indices: Vec<usize>,
     slot_sizes: Vec<usize>,
+    /// The combination being examined, reused so only matches allocate
+    word: String,
+    /// Dictionary words still to judge, when solving by scanning the dictionaries
+    scanned: Option<std::vec::IntoIter<&'a str>>,
     done: bool,
+    /// Work done by this iterator, added to the generator's totals when dropped
+    stats: SolveStats,
 }
 
 impl<'a> WordIter<'a> {
     fn new(generator: &'a WordGenerator) -> Self {
-        let slot_sizes: Vec<_> = generator.slots
-            .iter()
-            .map(|slot| slot.options.len())
-            .collect();
-            
+        Self::with_strategy(generator, generator.strategy)
+    }
+
+    /// Creates an iterator solving with the requested strategy, or the automatic
+    /// choice for `None`.
+    fn with_strategy(generator: &'a WordGenerator, requested: Option<Strategy>) -> Self {
+        let Plan {
+            strategy,
+            slots,
+            combinations,
+        } = generator.plan(requested);
+        let slot_sizes: Vec<_> = slots.iter().map(|slot| slot.options().len()).collect();
+
         let has_options = slot_sizes.iter().all(|&size| size > 0);
+        let scanned =
+            (has_options && strategy == Strategy::DictScan).then(|| generator.scan_candidates());
+        // Combinations a scan never spells, or a positional index narrowed away
+        let searched = match &scanned {
+            Some(candidates) => candidates.len() as u128,
+            None => combination::count(&slots),
+        };
+        let stats = SolveStats {
+            pruned: u64::try_from(combinations.saturating_sub(searched)).unwrap_or(u64::MAX),
+            ..SolveStats::default()
+        };
         
         Self {
             generator,
-            current_indices: vec![0; generator.slots.len()],
+            current_indices: vec![0; slots.len()],
+            word: String::with_capacity(slots.len()),
+            scanned: scanned.map(Vec::into_iter),
+            slots,
             slot_sizes,
             done: !has_options,
+            stats,
         }
     }
     
-    fn build_word(&self) -> String {
-        let mut word = String::with_capacity(self.current_indices.len());
+    fn build_word(&mut self) {
+        self.word.clear();
         for (slot_idx, &char_idx) in self.current_indices.iter().enumerate() {
-            word.push(self.generator.slots[slot_idx].options[char_idx]);
+            self.word.push(self.slots[slot_idx].options()[char_idx]);
         }
-        word
     }
     
     fn increment(&mut self) -> bool {
@@ -238,60 +445,98 @@ impl<'a> WordIter<'a> {
         self.done = true;
         false
     }
-}
-
-impl<'a> Iterator for WordIter<'a> {
-    type Item = String;
     
-    fn next(&mut self) -> Option<Self::Item> {
+    fn next_match(&mut self) -> Option<WordMatch> {
+        self.next_judged(WordGenerator::evaluate)
+    }
+
+    /// Advances to the next word that `judge` accepts, returning what it made of it.
+    fn next_judged<T>(
+        &mut self,
+        judge: impl Fn(&WordGenerator, &str, &mut SolveStats) -> Option<T>,
+    ) -> Option<T> {
         if self.done {
             return None;
         }
+        if let Some(candidates) = &mut self.scanned {
+            let found = candidates.find_map(|word| judge(self.generator, word, &mut self.stats));
+            self.done = found.is_none();
+            return found;
+        }
         
         loop {
-            let word = self.build_word();
+            self.build_word();
             
             // Prepare for next iteration
             let has_next = self.increment();
             
-            // Check if the word is in the dictionary
-            if let Some(word_list) = &self.generator.word_list {
-                if word_list.is_empty() || word_list.contains(&word) {
-                    return Some(word);
-                }
-                
-                // Not in the dictionary, continue if we have more words
-                if !has_next {
-                    return None;
-                }
-            } else {
-                // No filtering, return all words
-                return Some(word);
+            if let Some(judged) = judge(self.generator, &self.word, &mut self.stats) {
+                return Some(judged);
+            }
+            #[cfg(feature = "instrument")]
+            if self.stats.examined.is_multiple_of(TRACE_MILESTONE) {
+                tracing::trace!(examined = self.stats.examined, "solve progress");
+            }
+
+            // Rejected, continue if we have more words
+            if !has_next {
+                return None;
             }
         }
     }
 }
 
+impl Drop for WordIter<'_> {
+    fn drop(&mut self) {
+        self.generator.stats.record(&self.stats);
+        trace_event!(
+            debug,
+            examined = self.stats.examined,
+            pruned = self.stats.pruned,
+            dictionary_hits = self.stats.dictionary_hits,
+            "solve ended"
+        );
+    }
+}
+
+impl<'a> Iterator for WordIter<'a> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_match().map(|word_match| word_match.word)
+    }
+}
+
+/// An iterator that generates and filters words, yielding details about each match
+pub struct MatchIter<'a> {
+    words: WordIter<'a>,
+}
+
+impl<'a> Iterator for MatchIter<'a> {
+    type Item = WordMatch;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.words.next_match()
+    }
+}
+
 /// An iterator that yields all possible combinations without filtering
 pub struct AllCombinationsIter<'a> {
-    slots: &'a [Slot],
+    slots: Cow<'a, [Slot]>,
     current_indices: Vec<usize>,
     slot_sizes: Vec<usize>,
     done: bool,
 }
 
 impl<'a> AllCombinationsIter<'a> {
-    fn new(slots: &'a [Slot]) -> Self {
-        let slot_sizes: Vec<_> = slots
-            .iter()
-            .map(|slot| slot.options.len())
-            .collect();
-            
+    fn new(slots: Cow<'a, [Slot]>) -> Self {
+        let slot_sizes: Vec<_> = slots.iter().map(|slot| slot.options().len()).collect();
+
         let has_options = slot_sizes.iter().all(|&size| size > 0);
-        
+
         Self {
-            slots,
             current_indices: vec![0; slots.len()],
+            slots,
             slot_sizes,
             done: !has_options,
         }
@@ -300,7 +545,7 @@ impl<'a> AllCombinationsIter<'a> {
     fn build_word(&self) -> String {
         let mut word = String::with_capacity(self.current_indices.len());
         for (slot_idx, &char_idx) in self.current_indices.iter().enumerate() {
-            word.push(self.slots[slot_idx].options[char_idx]);
+            word.push(self.slots[slot_idx].options()[char_idx]);
         }
         word
     }
@@ -365,21 +610,46 @@ impl WordGenerator {
     /// ```
     pub fn new(slots: Vec<Slot>, word_list: Option<HashSet<String>>) -> Self {
         let word_list = match word_list {
-            Some(list) => Some(list),
-            None => {
-                // Use the embedded wordlist
-                let word_set: HashSet<String> = EMBEDDED_WORDLIST
-                    .lines()
-                    .map(|line| line.to_string())
-                    .collect();
-
-                Some(word_set)
-            }
+            Some(list) => Dictionary::from(list),
+            // Use the embedded wordlist
+            None => Dictionary::embedded(),
         };
 
+        Self::with_dictionary(slots, word_list)
+    }
+
+    /// Creates a `WordGenerator` that filters against an already loaded [`Dictionary`].
+    ///
+    /// # Parameters
+    ///
+    /// * `slots` - A vector of `Slot`s defining character options for each position
+    /// * `dictionary` - The dictionary used for filtering generated words
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gallry_puzzle_soulver::{Dictionary, LoadOptions, Slot, WordGenerator};
+    ///
+    /// let dictionary = Dictionary::from_text("cat\ndog\n", &LoadOptions::default());
+    /// let generator = WordGenerator::with_dictionary(
+    ///     vec![Slot::new(vec!['c', 'd']), Slot::new(vec!['a', 'o']), Slot::new(vec!['t', 'g'])],
+    ///     dictionary,
+    /// );
+    ///
+    /// assert_eq!(generator.iter().count(), 2);
+    /// ```
+    pub fn with_dictionary(slots: Vec<Slot>, dictionary: Dictionary) -> Self {
         Self {
             slots,
-            word_list,
+            dictionaries: vec![dictionary],
+            dictionary_policy: DictionaryPolicy::default(),
+            match_options: MatchOptions::default(),
+            blocklist: Blocklist::new(),
+            constraints: Vec::new(),
+            checks: Vec::new(),
+            strategy: None,
+            slot_index: OnceLock::new(),
+            stats: StatsCounter::default(),
         }
     }
 
@@ -427,10 +697,7 @@ impl WordGenerator {
     /// ]);
     /// ```
     pub fn with_no_filtering(slots: Vec<Slot>) -> Self {
-        Self {
-            slots,
-            word_list: Some(HashSet::new()),
-        }
+        Self::with_dictionary(slots, Dictionary::new())
     }
 
     /// Loads a custom word list from a file at runtime.
@@ -464,12 +731,7 @@ impl WordGenerator {
     /// }
     /// ```
     pub fn load_word_list_from_file(&mut self, path: &str) -> Result<()> {
-        let content = std::fs::read_to_string(path)
-            .context(format!("Failed to read word list from {}", path))?;
-
-        let word_set: HashSet<String> = content.lines().map(|line| line.to_string()).collect();
-
-        self.word_list = Some(word_set);
+        self.set_dictionary(Dictionary::from_path(path, &LoadOptions::default())?);
         Ok(())
     }
 
@@ -500,6 +762,187 @@ impl WordGenerator {
         WordIter::new(self)
     }
 
+    /// Returns an iterator over the valid words, with details about how each matched.
+    ///
+    /// This yields the same words as [`iter`](Self::iter), wrapped in [`WordMatch`]
+    /// records.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gallry_puzzle_soulver::{Slot, WordGenerator};
+    /// use std::collections::HashSet;
+    ///
+    /// let word_list: HashSet<String> = vec!["cat".to_string()].into_iter().collect();
+    /// let generator = WordGenerator::new(
+    ///     vec![Slot::new(vec!['c', 'b']), Slot::new(vec!['a']), Slot::new(vec!['t'])],
+    ///     Some(word_list),
+    /// );
+    ///
+    /// let matches: Vec<_> = generator.iter_matches().collect();
+    /// assert_eq!(matches[0].word, "cat");
+    /// assert_eq!(matches[0].stem, None);
+    /// ```
+    pub fn iter_matches(&self) -> MatchIter<'_> {
+        MatchIter {
+            words: WordIter::new(self),
+        }
+    }
+
+    /// Returns the number of matches, as `iter_matches().count()` would, without
+    /// allocating anything for them.
+    ///
+    /// Matches are judged exactly as when iterating, but no word or match details are
+    /// copied, so this is the cheapest way to see how many candidates remain while
+    /// narrowing a puzzle down. Like iteration, it adds its work to [`stats`](Self::stats).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gallry_puzzle_soulver::{Constraint, Slot, WordGenerator};
+    /// use std::collections::HashSet;
+    ///
+    /// let word_list: HashSet<String> = ["cat", "bat", "cot"].map(String::from).into();
+    /// let mut generator = WordGenerator::new(
+    ///     vec![Slot::new(vec!['c', 'b']), Slot::new(vec!['a', 'o']), Slot::new(vec!['t'])],
+    ///     Some(word_list),
+    /// );
+    /// assert_eq!(generator.count_matches(), 3);
+    ///
+    /// generator.add_constraint(Constraint::StartsWith("c".to_string()));
+    /// assert_eq!(generator.count_matches(), 2);
+    /// ```
+    pub fn count_matches(&self) -> usize {
+        let mut words = WordIter::new(self);
+        let mut count = 0;
+        while words
+            .next_judged(|generator, word, stats| generator.is_match(word, stats).then_some(()))
+            .is_some()
+        {
+            count += 1;
+        }
+        count
+    }
+
+    /// Returns `true` if the slots could produce `word`, ignoring any filtering.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gallry_puzzle_soulver::{Slot, WordGenerator};
+    ///
+    /// let generator = WordGenerator::with_no_filtering(vec![
+    ///     Slot::new(vec!['c', 'b']),
+    ///     Slot::new(vec!['a', 'o']),
+    /// ]);
+    /// assert!(generator.can_generate("bo"));
+    /// assert!(!generator.can_generate("ca_"));
+    /// assert!(!generator.can_generate("da"));
+    /// ```
+    pub fn can_generate(&self, word: &str) -> bool {
+        let slots = self.slot_index();
+        word.chars().count() == slots.len()
+            && word.chars().zip(slots).all(|(c, slot)| slot.contains(c))
+    }
+
+    /// Returns the generator's slots, with wildcard options filled in from the alphabet.
+    pub fn slots(&self) -> Cow<'_, [Slot]> {
+        self.resolved_slots()
+    }
+
+    /// Returns the index of the option chosen in each slot to spell `word`.
+    ///
+    /// Returns `None` if the slots cannot produce the word.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gallry_puzzle_soulver::{Slot, WordGenerator};
+    ///
+    /// let generator = WordGenerator::with_no_filtering(vec![
+    ///     Slot::new(vec!['c', 'b']),
+    ///     Slot::new(vec!['a', 'o']),
+    /// ]);
+    /// assert_eq!(generator.choices("bo"), Some(vec![1, 1]));
+    /// assert_eq!(generator.choices("da"), None);
+    /// ```
+    pub fn choices(&self, word: &str) -> Option<Vec<usize>> {
+        let slots = self.slot_index();
+        if word.chars().count() != slots.len() {
+            return None;
+        }
+
+        word.chars()
+            .zip(slots)
+            .map(|(c, slot)| slot.position(c))
+            .collect()
+    }
+
+    /// Returns the number of combinations the slots produce before any filtering.
+    ///
+    /// The count saturates at `u128::MAX` for absurdly large puzzles.
+    pub fn combination_count(&self) -> u128 {
+        combination::count(&self.resolved_slots())
+    }
+
+    /// Returns the work done by every solve of this generator so far.
+    ///
+    /// Iterators add their counts when dropped, so a solve in progress is not
+    /// included until its iterator is gone. See [`SolveStats`] for an example.
+    pub fn stats(&self) -> SolveStats {
+        self.stats.snapshot()
+    }
+
+    /// Sets the counts returned by [`stats`](Self::stats) back to zero.
+    pub fn reset_stats(&self) {
+        self.stats.reset();
+    }
+
+    /// Returns the characters wildcard slots may take.
+    ///
+    /// This is the union of the alphabets of all registered dictionaries, or `a`–`z`
+    /// when no dictionary filtering is applied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gallry_puzzle_soulver::{Slot, WordGenerator};
+    ///
+    /// let generator = WordGenerator::with_no_filtering(vec![Slot::any()]);
+    /// assert_eq!(generator.alphabet().len(), 26);
+    /// ```
+    pub fn alphabet(&self) -> BTreeSet<char> {
+        let alphabet: BTreeSet<char> = self
+            .dictionaries
+            .iter()
+            .flat_map(|dictionary| dictionary.alphabet().iter().copied())
+            .collect();
+
+        if alphabet.is_empty() {
+            ('a'..='z').collect()
+        } else {
+            alphabet
+        }
+    }
+
+    /// Returns the slots with wildcard options filled in from the alphabet.
+    ///
+    /// The slots are only copied when at least one of them is a wildcard.
+    fn resolved_slots(&self) -> Cow<'_, [Slot]> {
+        if !self.slots.iter().any(Slot::is_wildcard) {
+            return Cow::Borrowed(&self.slots);
+        }
+
+        let alphabet = self.alphabet();
+        trace_event!(trace, letters = alphabet.len(), "resolved wildcard slots");
+        Cow::Owned(
+            self.slots
+                .iter()
+                .map(|slot| slot.resolved(&alphabet))
+                .collect(),
+        )
+    }
+
     /// Returns an iterator over all possible combinations without filtering.
     ///
     /// This method is useful when you need access to all possible combinations,
@@ -520,7 +963,7 @@ impl WordGenerator {
     /// println!("All possible combinations: {:?}", all_combinations);
     /// ```
     pub fn all_combinations(&self) -> AllCombinationsIter<'_> {
-        AllCombinationsIter::new(&self.slots)
+        AllCombinationsIter::new(self.resolved_slots())
     }
 
     /// Updates the word list used for filtering.
@@ -549,6 +992,346 @@ impl WordGenerator {
     /// let filtered_words: Vec<_> = generator.iter().collect();
     /// ```
     pub fn set_word_list(&mut self, word_list: HashSet<String>) {
-        self.word_list = Some(word_list);
+        self.set_dictionary(Dictionary::from(word_list));
+    }
+
+    /// Returns the first registered dictionary, if any.
+    pub fn dictionary(&self) -> Option<&Dictionary> {
+        self.dictionaries.first()
+    }
+
+    /// Returns a mutable reference to the first registered dictionary, if any.
+    pub fn dictionary_mut(&mut self) -> Option<&mut Dictionary> {
+        self.slots_changed();
+        self.dictionaries.first_mut()
+    }
+
+    /// Returns every registered dictionary, in registration order.
+    pub fn dictionaries(&self) -> &[Dictionary] {
+        &self.dictionaries
+    }
+
+    /// Replaces all registered dictionaries with a single one.
+    ///
+    /// # Parameters
+    ///
+    /// * `dictionary` - The new dictionary to use for filtering
+    pub fn set_dictionary(&mut self, dictionary: Dictionary) {
+        self.dictionaries = vec![dictionary];
+        self.slots_changed();
+    }
+
+    /// Registers an additional dictionary for filtering and match attribution.
+    ///
+    /// Each match reports which registered dictionaries contain it in
+    /// [`WordMatch::dictionaries`], and the [`DictionaryPolicy`] decides whether a word
+    /// must appear in any or all of them.
+    ///
+    /// # Parameters
+    ///
+    /// * `dictionary` - The dictionary to add
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gallry_puzzle_soulver::{Dictionary, DictionaryPolicy, LoadOptions, Slot, WordGenerator};
+    ///
+    /// let common = Dictionary::from_text("cat\n", &LoadOptions::default()).with_name("common");
+    /// let full = Dictionary::from_text("cat\nbat\n", &LoadOptions::default()).with_name("full");
+    ///
+    /// let mut generator = WordGenerator::with_dictionary(
+    ///     vec![Slot::new(vec!['c', 'b']), Slot::new(vec!['a']), Slot::new(vec!['t'])],
+    ///     common,
+    /// );
+    /// generator.add_dictionary(full);
+    ///
+    /// let matches: Vec<_> = generator.iter_matches().collect();
+    /// assert_eq!(matches[0].dictionaries, vec!["common", "full"]);
+    /// assert_eq!(matches[1].dictionaries, vec!["full"]);
+    ///
+    /// generator.set_dictionary_policy(DictionaryPolicy::All);
+    /// assert_eq!(generator.iter().collect::<Vec<_>>(), vec!["cat"]);
+    /// ```
+    pub fn add_dictionary(&mut self, dictionary: Dictionary) {
+        self.dictionaries.push(dictionary);
+        self.slots_changed();
+    }
+
+    /// Removes every entry of `words` from all registered dictionaries.
+    ///
+    /// See [`Dictionary::subtract`]. Unlike a [`Blocklist`], this leaves generators
+    /// without dictionary filtering untouched.
+    pub fn subtract_words(&mut self, words: &Dictionary) {
+        for dictionary in &mut self.dictionaries {
+            dictionary.subtract(words);
+        }
+        self.slots_changed();
+    }
+
+    /// Sets whether words must appear in any or all registered dictionaries.
+    ///
+    /// # Parameters
+    ///
+    /// * `policy` - The new policy
+    pub fn set_dictionary_policy(&mut self, policy: DictionaryPolicy) {
+        self.dictionary_policy = policy;
+    }
+
+    /// Returns whether words must appear in any or all registered dictionaries.
+    pub fn dictionary_policy(&self) -> DictionaryPolicy {
+        self.dictionary_policy
+    }
+
+    /// Updates the options deciding which dictionary entries count as matches.
+    ///
+    /// # Parameters
+    ///
+    /// * `options` - The new match options
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gallry_puzzle_soulver::{MatchOptions, ProperNouns, Slot, WordGenerator};
+    /// use std::collections::HashSet;
+    ///
+    /// let word_list: HashSet<String> = vec!["Ned".to_string(), "red".to_string()]
+    ///     .into_iter()
+    ///     .collect();
+    /// let mut generator = WordGenerator::new(
+    ///     vec![Slot::new(vec!['N', 'r']), Slot::new(vec!['e']), Slot::new(vec!['d'])],
+    ///     Some(word_list),
+    /// );
+    ///
+    /// generator.set_match_options(MatchOptions {
+    ///     proper_nouns: ProperNouns::Exclude,
+    ///     ..Default::default()
+    /// });
+    /// assert_eq!(generator.iter().collect::<Vec<_>>(), vec!["red"]);
+    /// ```
+    pub fn set_match_options(&mut self, options: MatchOptions) {
+        self.match_options = options;
+    }
+
+    /// Sets the blocklist of words that must never be yielded.
+    ///
+    /// The blocklist applies to [`iter`](Self::iter) whether or not dictionary
+    /// filtering is enabled.
+    ///
+    /// # Parameters
+    ///
+    /// * `blocklist` - The words to suppress
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gallry_puzzle_soulver::{Blocklist, Slot, WordGenerator};
+    ///
+    /// let mut generator = WordGenerator::with_no_filtering(vec![
+    ///     Slot::new(vec!['c', 'b']),
+    ///     Slot::new(vec!['a']),
+    ///     Slot::new(vec!['t']),
+    /// ]);
+    /// generator.set_blocklist(Blocklist::from_text("bat"));
+    ///
+    /// assert_eq!(generator.iter().collect::<Vec<_>>(), vec!["cat"]);
+    /// ```
+    pub fn set_blocklist(&mut self, blocklist: Blocklist) {
+        self.blocklist = blocklist;
+    }
+
+    /// Adds a condition every yielded word must satisfy.
+    ///
+    /// Like the blocklist, constraints apply whether or not dictionary filtering is
+    /// used, and are checked before any dictionary lookup. They are checked in order of
+    /// [`Constraint::cost`], stopping at the first that fails. Two or more
+    /// [`Contains`](Constraint::Contains) and [`ExcludesLetters`](Constraint::ExcludesLetters)
+    /// constraints are checked together in a single scan of the word.
+    ///
+    /// # Parameters
+    ///
+    /// * `constraint` - The condition to add
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gallry_puzzle_soulver::{Constraint, Slot, WordGenerator};
+    ///
+    /// let mut generator = WordGenerator::with_no_filtering(vec![
+    ///     Slot::new(vec!['c', 'b']),
+    ///     Slot::new(vec!['a', 'o']),
+    /// ]);
+    /// generator.add_constraint(Constraint::EndsWith("o".to_string()));
+    ///
+    /// let words: Vec<String> = generator.iter().collect();
+    /// assert_eq!(words, vec!["co", "bo"]);
+    /// ```
+    pub fn add_constraint(&mut self, constraint: Constraint) {
+        self.constraints.push(constraint);
+        self.checks = checks::plan(&self.constraints);
+    }
+
+    /// Returns the registered constraints, in the order they were added.
+    pub fn constraints(&self) -> &[Constraint] {
+        &self.constraints
+    }
+
+    /// Removes every registered constraint.
+    pub fn clear_constraints(&mut self) {
+        self.constraints.clear();
+        self.checks.clear();
+    }
+
+    /// Replaces the slots, keeping the loaded dictionaries and every other setting.
+    ///
+    /// This lets one generator solve a series of puzzles without reloading its
+    /// word lists or rebuilding their indexes.
+    ///
+    /// # Parameters
+    ///
+    /// * `slots` - The new character options for each position
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gallry_puzzle_soulver::{Slot, WordGenerator};
+    ///
+    /// let mut generator = WordGenerator::with_no_filtering(vec![Slot::new(vec!['a'])]);
+    /// generator.set_slots(vec![Slot::new(vec!['o']), Slot::new(vec!['x', 'n'])]);
+    ///
+    /// assert_eq!(generator.iter().collect::<Vec<_>>(), vec!["ox", "on"]);
+    /// ```
+    pub fn set_slots(&mut self, slots: Vec<Slot>) {
+        self.slots = slots;
+        self.slots_changed();
+    }
+
+    /// Discards what was derived from the resolved slots, after the slots or the
+    /// alphabet their wildcards draw on may have changed.
+    fn slots_changed(&mut self) {
+        self.slot_index = OnceLock::new();
+    }
+
+    /// Decides whether a generated word should be yielded, and how it matched.
+    ///
+    /// The work done is counted into `stats`. The word is only copied once it has
+    /// passed every check, so rejected combinations cost no allocation.
+    fn evaluate(&self, word: &str, stats: &mut SolveStats) -> Option<WordMatch> {
+        // The word is filled in on success; an empty String does not allocate
+        let mut word_match = WordMatch::exact(String::new());
+        if !self.judge(word, stats, Some(&mut word_match)) {
+            return None;
+        }
+        word_match.word = word.to_string();
+        Some(word_match)
+    }
+
+    /// Decides whether a generated word should be yielded, without recording how it
+    /// matched, so nothing is allocated for matches either.
+    fn is_match(&self, word: &str, stats: &mut SolveStats) -> bool {
+        self.judge(word, stats, None)
+    }
+
+    /// Checks a word against the blocklist, constraints and dictionaries, recording
+    /// how it matched into `record` when given.
+    fn judge(
+        &self,
+        word: &str,
+        stats: &mut SolveStats,
+        mut record: Option<&mut WordMatch>,
+    ) -> bool {
+        stats.examined += 1;
+        if self.blocklist.blocks(word)
+            || !self.checks.iter().all(|check| match check {
+                Check::Constraint(index) => self.accepts(&self.constraints[*index], word),
+                Check::Substrings(substrings) => substrings.accepts(word),
+            })
+        {
+            return false;
+        }
+
+        let mut filtered = false;
+        let mut found = false;
+        for (index, dictionary) in self.dictionaries.iter().enumerate() {
+            // Empty dictionaries apply no filtering
+            if dictionary.is_empty() {
+                continue;
+            }
+            filtered = true;
+
+            if self.match_in(dictionary, word, record.as_deref_mut()) {
+                stats.dictionary_hits += 1;
+                found = true;
+                match record.as_deref_mut() {
+                    Some(word_match) => {
+                        let name = match dictionary.name() {
+                            "" => format!("#{}", index + 1),
+                            name => name.to_string(),
+                        };
+                        word_match.dictionaries.push(name);
+                    }
+                    // Without attribution to record, one dictionary is enough
+                    None if self.dictionary_policy == DictionaryPolicy::Any => return true,
+                    None => {}
+                }
+            } else if self.dictionary_policy == DictionaryPolicy::All {
+                return false;
+            }
+        }
+
+        !filtered || found
+    }
+
+    /// Checks a constraint, judging rhymes and syllables by the first dictionary's
+    /// pronunciation table when the words are in it.
+    fn accepts(&self, constraint: &Constraint, word: &str) -> bool {
+        #[cfg(feature = "pronunciation")]
+        if let Some(dictionary) = self.dictionary() {
+            let pronunciations = dictionary.pronunciations();
+            let judged = match constraint {
+                Constraint::RhymesWith(target) => pronunciations.rhymes(word, target),
+                Constraint::Syllables(count) => pronunciations
+                    .syllables(word)
+                    .map(|syllables| syllables == *count),
+                _ => None,
+            };
+            if let Some(accepted) = judged {
+                return accepted;
+            }
+        }
+
+        constraint.accepts(word)
+    }
+
+    /// Looks a match up in one dictionary, recording how it matched when asked to.
+    fn match_in(
+        &self,
+        dictionary: &Dictionary,
+        word: &str,
+        record: Option<&mut WordMatch>,
+    ) -> bool {
+        if let Some((entry, info)) = dictionary.find(word, &self.match_options) {
+            if let Some(word_match) = record {
+                if word_match.entry.is_none() && entry != word {
+                    word_match.entry = Some(entry.to_string());
+                }
+                word_match.rank = word_match.rank.or(info.rank);
+            }
+            return true;
+        }
+
+        #[cfg(feature = "stemming")]
+        if self.match_options.stemming
+            && let Some((stem, info)) = dictionary.lookup_stem(word, &self.match_options)
+        {
+            if let Some(word_match) = record {
+                if word_match.stem.is_none() {
+                    word_match.stem = Some(stem.to_string());
+                }
+                word_match.rank = word_match.rank.or(info.rank);
+            }
+            return true;
+        }
+
+        false
     }
 }
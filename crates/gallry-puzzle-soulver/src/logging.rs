@@ -0,0 +1,98 @@
+//! Diagnostic logging to standard error, selected with `-v`, `-vv` and `--quiet`.
+//!
+//! The flags are accepted anywhere on the command line, before or after the
+//! subcommand, so they are taken out of the arguments before argh parses them.
+
+use argh::{CommandInfoWithArgs, FlagInfoKind};
+use tracing::level_filters::LevelFilter;
+
+/// Removes the verbosity flags from the arguments and returns the level they select.
+///
+/// Warnings are shown by default; each `v` of `-v`, `-vv` or `--verbose` shows one more
+/// level of detail, and `-q` or `--quiet` shows only errors. The program name, the
+/// values of options and any arguments after `--` are kept as they are, so searching
+/// with `--regex -v` still works.
+///
+/// # Parameters
+///
+/// * `args` - The command line, starting with the program name
+/// * `cli` - The options and subcommands of the program, as argh describes them
+/// * `default` - The subcommand whose options apply when the first argument names none
+///
+/// # Returns
+///
+/// The most detailed level to log
+pub fn take_verbosity(
+    args: &mut Vec<String>,
+    cli: &CommandInfoWithArgs,
+    default: &CommandInfoWithArgs,
+) -> LevelFilter {
+    let end = args
+        .iter()
+        .position(|arg| arg == "--")
+        .unwrap_or(args.len());
+    let mut verbose = 0;
+    let mut quiet = false;
+    let mut command = cli;
+    // Whether the previous argument was an option that takes this one as its value
+    let mut value = false;
+    let mut index = 0;
+    args.retain(|arg| {
+        index += 1;
+        if index == 1 || index > end {
+            return true;
+        }
+        if std::mem::take(&mut value) {
+            return true;
+        }
+
+        if arg == "-q" || arg == "--quiet" {
+            quiet = true;
+        } else if arg == "--verbose" {
+            verbose += 1;
+        } else if let Some(vs) = arg
+            .strip_prefix('-')
+            .filter(|flag| !flag.is_empty() && flag.chars().all(|c| c == 'v'))
+        {
+            verbose += vs.len();
+        } else {
+            if arg.starts_with('-') {
+                value = takes_value(command, arg);
+            } else if let Some(subcommand) = command.commands.iter().find(|sub| sub.name == arg) {
+                command = &subcommand.command;
+            } else if std::ptr::eq(command, cli) {
+                command = default;
+            }
+            return true;
+        }
+        false
+    });
+
+    match verbose {
+        _ if quiet => LevelFilter::ERROR,
+        0 => LevelFilter::WARN,
+        1 => LevelFilter::INFO,
+        2 => LevelFilter::DEBUG,
+        _ => LevelFilter::TRACE,
+    }
+}
+
+/// Returns `true` if `flag` is an option of `command` that is followed by a value.
+fn takes_value(command: &CommandInfoWithArgs, flag: &str) -> bool {
+    command.flags.iter().any(|info| {
+        let named = info.long == flag
+            || info.short.is_some_and(|short| {
+                flag.strip_prefix('-') == Some(short.encode_utf8(&mut [0; 4]))
+            });
+        named && matches!(info.kind, FlagInfoKind::Option { .. })
+    })
+}
+
+/// Sends log events at `level` and above to standard error.
+pub fn init(level: LevelFilter) {
+    tracing_subscriber::fmt()
+        .with_max_level(level)
+        .with_writer(std::io::stderr)
+        .with_target(false)
+        .init();
+}
@@ -1,60 +1,265 @@
-use anyhow::{Context, Result};
-use argh::FromArgs;
-use gallry_puzzle_soulver::{Slot, WordGenerator};
+use argh::{ArgsInfo, FromArgs};
+use commands::{
+    a1z26, acrostic, analyze, bee, caesar, chess, code, combos, config, cryptogram, daemon, darts,
+    dates, dict, glob, json_schema, man, morse, narrow, parlor, repl, rpc, serve, solve, symbols,
+    t9, tui, verify, vigenere, wheel, wordle, wordsearch,
+};
+use std::process::ExitCode;
+
+mod color;
+mod commands;
+mod defaults;
+mod filter;
+mod logging;
+mod output;
+mod report;
+mod template;
+
+/// Exit status when a solve found no matches, or no candidate was verified
+const NO_MATCHES: u8 = 1;
+/// Exit status for invalid arguments, slots, puzzle files or settings
+const USAGE_ERROR: u8 = 2;
+/// Exit status when reading or writing a file, or running a command, failed
+const IO_ERROR: u8 = 3;
 
 /// Finds possible words based on sets of allowed characters
-#[derive(FromArgs)]
-struct Args {
-    /// character sets for each position (e.g., ABC DEF GHI)
-    #[argh(positional)]
-    char_sets: Vec<String>,
-
-    /// optional path to a custom word list file
-    #[argh(option, short = 'w')]
-    word_list: Option<String>,
-
-    /// show all combinations, even those not in the word list
-    #[argh(switch, short = 'a')]
-    all_combinations: bool,
+#[derive(ArgsInfo, FromArgs)]
+#[argh(
+    note = "Without a subcommand the arguments are character sets to solve, as in \
+            gallery-puzzle-soulver cbr aio tse. A first set spelling a subcommand name, such as \
+            man or bee, runs that subcommand instead; put -- or solve in front to solve it, as \
+            in gallery-puzzle-soulver -- man bee tse.
+Add -v or -vv anywhere for progress logs on stderr, or --quiet for errors only.",
+    error_code(
+        1,
+        "a solving subcommand such as solve or code found no matches, or verify no valid candidates"
+    ),
+    error_code(2, "invalid arguments, slots, puzzle files or settings"),
+    error_code(3, "reading or writing a file, or running a command, failed")
+)]
+struct Cli {
+    #[argh(subcommand)]
+    command: Command,
 }
 
-fn main() -> Result<()> {
-    let args: Args = argh::from_env();
+#[derive(ArgsInfo, FromArgs)]
+#[argh(subcommand)]
+enum Command {
+    Solve(SolveCommand),
+    Combos(combos::CombosArgs),
+    Verify(verify::VerifyArgs),
+    Wordle(wordle::WordleArgs),
+    Bee(bee::BeeArgs),
+    Parlor(parlor::ParlorArgs),
+    Darts(darts::DartsArgs),
+    Code(code::CodeArgs),
+    A1z26(a1z26::A1z26Args),
+    Caesar(caesar::CaesarArgs),
+    Vigenere(vigenere::VigenereArgs),
+    Cryptogram(cryptogram::CryptogramArgs),
+    Acrostic(acrostic::AcrosticArgs),
+    T9(t9::T9Args),
+    Morse(morse::MorseArgs),
+    Symbols(symbols::SymbolsArgs),
+    Chess(chess::ChessArgs),
+    WordSearch(wordsearch::WordSearchArgs),
+    Match(glob::MatchArgs),
+    Wheel(wheel::WheelArgs),
+    Dates(dates::DatesArgs),
+    Dict(dict::DictArgs),
+    Analyze(analyze::AnalyzeArgs),
+    Config(config::ConfigArgs),
+    JsonSchema(json_schema::JsonSchemaArgs),
+    Man(man::ManArgs),
+    Narrow(narrow::NarrowArgs),
+    Repl(repl::ReplArgs),
+    Tui(tui::TuiArgs),
+    Serve(serve::ServeArgs),
+    Rpc(rpc::RpcArgs),
+    Daemon(daemon::DaemonArgs),
+}
 
-    if args.char_sets.is_empty() {
-        eprintln!("Error: You must provide at least one character set");
-        std::process::exit(1);
+/// The `solve` arguments, boxed as they are far larger than any other subcommand's.
+///
+/// The alias lets `ArgsInfo` be derived for [`Command`], which cannot name a generic type.
+type SolveCommand = Box<solve::SolveArgs>;
+
+impl ArgsInfo for SolveCommand {
+    fn get_args_info() -> argh::CommandInfoWithArgs {
+        solve::SolveArgs::get_args_info()
     }
+}
 
-    // Convert each character set to a Slot
-    let slots: Vec<Slot> = args.char_sets
-        .iter()
-        .map(|s| Slot::new(s.chars().collect()))
-        .collect();
+/// First arguments that select a subcommand; anything else is parsed as `solve`
+const SUBCOMMAND_NAMES: &[&str] = &[
+    "solve",
+    "combos",
+    "verify",
+    "wordle",
+    "bee",
+    "parlor",
+    "darts",
+    "code",
+    "a1z26",
+    "caesar",
+    "vigenere",
+    "cryptogram",
+    "acrostic",
+    "t9",
+    "morse",
+    "symbols",
+    "chess",
+    "wordsearch",
+    "match",
+    "wheel",
+    "dates",
+    "dict",
+    "analyze",
+    "config",
+    "json-schema",
+    "man",
+    "narrow",
+    "repl",
+    "tui",
+    "serve",
+    "rpc",
+    "daemon",
+    "help",
+];
 
-    // Create the appropriate generator based on arguments
-    let mut generator = if args.all_combinations {
-        WordGenerator::with_no_filtering(slots)
-    } else {
-        WordGenerator::with_slots(slots)
+fn main() -> ExitCode {
+    let mut args: Vec<String> = std::env::args().collect();
+    let level = logging::take_verbosity(
+        &mut args,
+        &Cli::get_args_info(),
+        &solve::SolveArgs::get_args_info(),
+    );
+    logging::init(level);
+
+    // Solving and verifying commands report how many matches they found
+    let result = match parse_command(&args) {
+        Command::Solve(args) => solve::run(*args).map(Some),
+        Command::Combos(args) => combos::run(args).map(Some),
+        Command::Verify(args) => verify::run(args).map(Some),
+        Command::Wordle(args) => wordle::run(args).map(|()| None),
+        Command::Bee(args) => bee::run(args).map(|()| None),
+        Command::Parlor(args) => parlor::run(args).map(|()| None),
+        Command::Darts(args) => darts::run(args).map(|()| None),
+        Command::Code(args) => code::run(args).map(Some),
+        Command::A1z26(args) => a1z26::run(args).map(Some),
+        Command::Caesar(args) => caesar::run(args).map(|()| None),
+        Command::Vigenere(args) => vigenere::run(args).map(|()| None),
+        Command::Cryptogram(args) => cryptogram::run(args).map(Some),
+        Command::Acrostic(args) => acrostic::run(args).map(Some),
+        Command::T9(args) => t9::run(args).map(Some),
+        Command::Morse(args) => morse::run(args).map(Some),
+        Command::Symbols(args) => symbols::run(args).map(Some),
+        Command::Chess(args) => chess::run(args).map(Some),
+        Command::WordSearch(args) => wordsearch::run(args).map(Some),
+        Command::Match(args) => glob::run(args).map(Some),
+        Command::Wheel(args) => wheel::run(args).map(Some),
+        Command::Dates(args) => dates::run(args).map(Some),
+        Command::Dict(args) => dict::run(args).map(|()| None),
+        Command::Analyze(args) => analyze::run(args).map(|()| None),
+        Command::Config(args) => config::run(args).map(|()| None),
+        Command::JsonSchema(args) => json_schema::run(args).map(|()| None),
+        Command::Man(args) => man::run(args).map(|()| None),
+        Command::Narrow(args) => narrow::run(args).map(|()| None),
+        Command::Repl(args) => repl::run(args).map(|()| None),
+        Command::Tui(args) => tui::run(args).map(|()| None),
+        Command::Serve(args) => serve::run(args).map(|()| None),
+        Command::Rpc(args) => rpc::run(args).map(|()| None),
+        Command::Daemon(args) => daemon::run(args).map(|()| None),
     };
 
-    // Load custom word list if provided
-    if let Some(path) = args.word_list {
-        generator.load_word_list_from_file(&path)
-            .with_context(|| format!("Failed to load word list from '{}'", path))?;
+    match result {
+        Ok(Some(0)) => ExitCode::from(NO_MATCHES),
+        Ok(_) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("Error: {:?}", error);
+            ExitCode::from(error_status(&error))
+        }
+    }
+}
+
+/// Returns the exit status for an error: I/O failures anywhere in its chain of
+/// causes are reported as such, and anything else was a problem with the input.
+fn error_status(error: &anyhow::Error) -> u8 {
+    if error
+        .chain()
+        .any(|cause| cause.downcast_ref::<std::io::Error>().is_some())
+    {
+        IO_ERROR
+    } else {
+        USAGE_ERROR
     }
+}
 
-    // Generate and display the words
-    if args.all_combinations {
-        for word in generator.all_combinations() {
-            println!("{}", word);
-        }
+/// Parses the command line, treating arguments without a subcommand as `solve`.
+///
+/// This keeps the original bare form, e.g. `gallery-puzzle-soulver cbr aio tse`, working.
+/// A first argument naming a subcommand always selects it, so a puzzle whose first set is
+/// such a name needs `--` or `solve` in front; when it looks like that was forgotten, a
+/// warning or the usage error says so.
+fn parse_command(args: &[String]) -> Command {
+    let first = args.get(1).map(String::as_str);
+    let program = args
+        .first()
+        .and_then(|program| std::path::Path::new(program).file_name())
+        .and_then(|name| name.to_str())
+        .unwrap_or("gallery-puzzle-soulver");
+    let rest: Vec<&str> = args.iter().skip(1).map(String::as_str).collect();
+
+    let parsed = if first
+        .is_some_and(|first| SUBCOMMAND_NAMES.contains(&first) || first == "--help")
+    {
+        Cli::from_args(&[program], &rest).map(|cli| cli.command)
     } else {
-        for word in generator.iter() {
-            println!("{}", word);
+        solve::SolveArgs::from_args(&[program], &rest).map(|args| Command::Solve(Box::new(args)))
+    };
+    // Subcommand names such as man or bee are also plausible character sets
+    let ambiguous = first.filter(|&first| {
+        !matches!(first, "solve" | "help")
+            && SUBCOMMAND_NAMES.contains(&first)
+            && rest.iter().all(|arg| is_char_set(arg))
+    });
+    match parsed {
+        Ok(command) => {
+            if let Some(name) = ambiguous.filter(|_| rest.len() > 2) {
+                tracing::warn!(
+                    "Ran the {} subcommand; to solve '{}' as the first character set, run {} -- {}",
+                    name,
+                    name,
+                    program,
+                    rest.join(" ")
+                );
+            }
+            command
         }
+        Err(early_exit) => match early_exit.status {
+            Ok(()) => {
+                println!("{}", early_exit.output);
+                std::process::exit(0);
+            }
+            Err(()) => {
+                eprintln!("{}", early_exit.output);
+                if let Some(name) = ambiguous {
+                    eprintln!(
+                        "'{}' is a subcommand; to solve it as a character set, run {} -- {}",
+                        name,
+                        program,
+                        rest.join(" ")
+                    );
+                }
+                eprintln!("Run {} --help for more information.", program);
+                std::process::exit(USAGE_ERROR.into());
+            }
+        },
     }
+}
 
-    Ok(())
-} 
+/// Returns whether an argument reads as a character set of the bare form rather than a
+/// flag or a subcommand's value, such as `cbr`.
+fn is_char_set(arg: &str) -> bool {
+    !arg.is_empty() && !arg.starts_with('-') && arg.chars().all(char::is_alphabetic)
+}
@@ -0,0 +1,329 @@
+//! Morse code read as slots.
+//!
+//! Tapped or flashed messages often lose the pauses between letters, so a run such as
+//! `...---...` could be "sos" or any of many other letter sequences. A [`MorseCode`]
+//! tries every split point and, for each word length the code allows, gives the
+//! letters each position could hold as slots, solved against the dictionary like any
+//! other puzzle. Since the options of neighbouring positions depend on each other, a
+//! [`Morse`](crate::Constraint::Morse) constraint then keeps only the words that
+//! spell the code exactly.
+
+use crate::{Constraint, Slot, WordGenerator};
+use anyhow::{Result, bail};
+
+/// The Morse code of each letter, `a` first
+pub const MORSE_LETTERS: [&str; 26] = [
+    ".-", "-...", "-.-.", "-..", ".", "..-.", "--.", "....", "..", ".---", "-.-", ".-..", "--",
+    "-.", "---", ".--.", "--.-", ".-.", "...", "-", "..-", "...-", ".--", "-..-", "-.--", "--..",
+];
+
+/// Returns the Morse code of a letter, ignoring case.
+fn letter_code(letter: char) -> Option<&'static str> {
+    letter
+        .is_ascii_alphabetic()
+        .then(|| MORSE_LETTERS[usize::from(letter.to_ascii_lowercase() as u8 - b'a')])
+}
+
+/// Writes text in Morse code, with a space between letters and ` / ` between words.
+///
+/// Characters other than letters and spaces are left out.
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::morse_encode;
+///
+/// assert_eq!(morse_encode("SOS"), "... --- ...");
+/// assert_eq!(morse_encode("no way"), "-. --- / .-- .- -.--");
+/// ```
+pub fn morse_encode(text: &str) -> String {
+    text.split_whitespace()
+        .map(|word| {
+            word.chars()
+                .filter_map(letter_code)
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" / ")
+}
+
+/// Returns `true` if a word spells Morse code whose groups are separated by spaces.
+///
+/// Each group holds one or more whole letters, so the letters of the word must fill
+/// the groups exactly, without a letter running across a space. A group of `?` is any
+/// one letter.
+pub(crate) fn spells_morse(word: &str, code: &str) -> bool {
+    let mut groups = code.split_whitespace();
+    let mut rest = "";
+    for letter in word.chars() {
+        if rest.is_empty() {
+            match groups.next() {
+                Some("?") if letter.is_ascii_alphabetic() => continue,
+                Some(group) => rest = group,
+                None => return false,
+            }
+        }
+        match letter_code(letter).and_then(|letter| rest.strip_prefix(letter)) {
+            Some(after) => rest = after,
+            None => return false,
+        }
+    }
+    rest.is_empty() && groups.next().is_none()
+}
+
+/// What the gaps in a [`MorseCode`] mean.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum MorseSpacing {
+    /// Every gap separates two letters, so each group is one letter
+    #[default]
+    Letters,
+    /// Gaps separate letters, but a group may hold several letters whose split points
+    /// are unknown
+    Runs,
+}
+
+/// One word of Morse code, to be read as letters.
+///
+/// Dots are written `.` and dashes `-`, and groups are separated by spaces. A group
+/// of `?` stands for one unknown letter.
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::{Dictionary, LoadOptions, MorseCode, MorseSpacing, WordGenerator};
+///
+/// let code = MorseCode::parse("...---...", MorseSpacing::Runs).unwrap();
+/// assert_eq!(code.lengths(), (3..=9).collect::<Vec<_>>());
+///
+/// let dictionary = Dictionary::from_text("sos\nsow\ntoo\n", &LoadOptions::default());
+/// let mut generator = WordGenerator::with_dictionary(Vec::new(), dictionary);
+/// code.apply(&mut generator, 3);
+/// assert_eq!(generator.iter().collect::<Vec<_>>(), ["sos"]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MorseCode {
+    /// The groups of dots, dashes and `?` between the gaps
+    groups: Vec<String>,
+    /// What the gaps mean
+    spacing: MorseSpacing,
+}
+
+impl MorseCode {
+    /// Parses one word of Morse code.
+    ///
+    /// Besides `.` and `-`, the dots `·` and `•` and the dashes `_`, `–` and `−` are
+    /// accepted.
+    ///
+    /// # Parameters
+    ///
+    /// * `code` - The dots and dashes, such as `... --- ...` or `...---...`
+    /// * `spacing` - Whether each group is a single letter
+    ///
+    /// # Returns
+    ///
+    /// The code, or an error naming a character that is not Morse, a group that is not
+    /// a letter when each group is one, or a `?` mixed with dots and dashes
+    pub fn parse(code: &str, spacing: MorseSpacing) -> Result<Self> {
+        let mut groups = Vec::new();
+        for (index, group) in code.split_whitespace().enumerate() {
+            let group: String = group
+                .chars()
+                .map(|c| match c {
+                    '.' | '·' | '•' => Ok('.'),
+                    '-' | '_' | '–' | '−' => Ok('-'),
+                    '?' => Ok('?'),
+                    _ => bail!(
+                        "Group {} '{}' has '{}', not a dot or dash",
+                        index + 1,
+                        group,
+                        c
+                    ),
+                })
+                .collect::<Result<_>>()?;
+            if group.contains('?') && group != "?" {
+                bail!(
+                    "Group {} '{}' mixes ? with dots and dashes",
+                    index + 1,
+                    group
+                );
+            }
+            if spacing == MorseSpacing::Letters
+                && group != "?"
+                && !MORSE_LETTERS.contains(&group.as_str())
+            {
+                bail!(
+                    "Group {} '{}' is not the code of a letter",
+                    index + 1,
+                    group
+                );
+            }
+            groups.push(group);
+        }
+        if groups.is_empty() {
+            bail!("No dots or dashes to read");
+        }
+        Ok(MorseCode { groups, spacing })
+    }
+
+    /// Returns the groups of the code, with `.` for dots and `-` for dashes.
+    pub fn groups(&self) -> &[String] {
+        &self.groups
+    }
+
+    /// Returns the number of letters the code could spell, shortest first.
+    pub fn lengths(&self) -> Vec<usize> {
+        match self.spacing {
+            MorseSpacing::Letters => vec![self.groups.len()],
+            MorseSpacing::Runs => {
+                let end = self.symbols().len();
+                self.reachable()
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, offsets)| offsets[end])
+                    .map(|(length, _)| length)
+                    .collect()
+            }
+        }
+    }
+
+    /// Returns slots for a word of `length` letters, each holding the letters its
+    /// position could be under some split of the code.
+    ///
+    /// A letter's options depend on where the letters before it ended, so words from
+    /// these slots must still be checked with [`constraint`](Self::constraint).
+    ///
+    /// # Returns
+    ///
+    /// The slots, or `None` if the code cannot spell a word of that length
+    pub fn slots(&self, length: usize) -> Option<Vec<Slot>> {
+        if self.spacing == MorseSpacing::Letters {
+            return (length == self.groups.len()).then(|| {
+                self.groups
+                    .iter()
+                    .map(|group| match letter_of(group) {
+                        Some(letter) => Slot::new(vec![letter]),
+                        None => Slot::any(),
+                    })
+                    .collect()
+            });
+        }
+
+        let symbols = self.symbols();
+        let end = symbols.len();
+        let forward = self.reachable();
+        if length == 0 || !forward.get(length)?[end] {
+            return None;
+        }
+        // backward[left][offset]: the end can be reached from offset with `left` letters
+        let mut backward = vec![vec![false; end + 1]; length + 1];
+        backward[0][end] = true;
+        for left in 1..=length {
+            for offset in 0..end {
+                backward[left][offset] =
+                    letters_at(&symbols, offset).any(|(_, next)| backward[left - 1][next]);
+            }
+        }
+
+        let slots = (0..length)
+            .map(|position| {
+                let mut letters: Vec<char> = (0..end)
+                    .filter(|&offset| forward[position][offset])
+                    .flat_map(|offset| letters_at(&symbols, offset))
+                    .filter(|&(_, next)| backward[length - position - 1][next])
+                    .map(|(letter, _)| letter)
+                    .collect();
+                letters.sort_unstable();
+                letters.dedup();
+                Slot::new(letters)
+            })
+            .collect();
+        Some(slots)
+    }
+
+    /// Returns the constraint keeping only words that spell the code, when the slots
+    /// alone do not ensure it.
+    pub fn constraint(&self) -> Option<Constraint> {
+        (self.spacing == MorseSpacing::Runs).then(|| Constraint::Morse(self.groups.join(" ")))
+    }
+
+    /// Sets a generator's slots to solve for words of `length` letters, adding the
+    /// constraint they need to its own.
+    ///
+    /// # Returns
+    ///
+    /// `false`, leaving the generator as it was, if the code cannot spell a word of
+    /// that length
+    pub fn apply(&self, generator: &mut WordGenerator, length: usize) -> bool {
+        let Some(slots) = self.slots(length) else {
+            return false;
+        };
+        generator.set_slots(slots);
+        if let Some(constraint) = self.constraint() {
+            generator.add_constraint(constraint);
+        }
+        true
+    }
+
+    /// Returns the dots, dashes and `?` of the code run together, each with whether a
+    /// gap follows it.
+    fn symbols(&self) -> Vec<(char, bool)> {
+        self.groups
+            .iter()
+            .flat_map(|group| {
+                let last = group.len() - 1;
+                group
+                    .chars()
+                    .enumerate()
+                    .map(move |(index, symbol)| (symbol, index == last))
+            })
+            .collect()
+    }
+
+    /// Returns, for each number of letters, the offsets into the symbols those letters
+    /// can end at.
+    fn reachable(&self) -> Vec<Vec<bool>> {
+        let symbols = self.symbols();
+        let end = symbols.len();
+        // Every letter takes at least one symbol, so no more letters than symbols
+        let mut reachable = vec![vec![false; end + 1]; end + 1];
+        reachable[0][0] = true;
+        for length in 1..=end {
+            for offset in 0..end {
+                if reachable[length - 1][offset] {
+                    for (_, next) in letters_at(&symbols, offset) {
+                        reachable[length][next] = true;
+                    }
+                }
+            }
+        }
+        reachable
+    }
+}
+
+/// Returns the letter a group of dots and dashes is the code of.
+fn letter_of(group: &str) -> Option<char> {
+    MORSE_LETTERS
+        .iter()
+        .position(|code| *code == group)
+        .map(|index| char::from(b'a' + index as u8))
+}
+
+/// Returns the letters that can start at an offset into the symbols, each with the
+/// offset after it.
+///
+/// A letter may not run across a gap, and a `?` is any single letter.
+fn letters_at(symbols: &[(char, bool)], offset: usize) -> impl Iterator<Item = (char, usize)> + '_ {
+    let unknown = symbols[offset].0 == '?';
+    ('a'..='z').filter_map(move |letter| {
+        if unknown {
+            return Some((letter, offset + 1));
+        }
+        let code = letter_code(letter)?;
+        let taken = symbols.get(offset..offset + code.len())?;
+        let fits = taken.iter().map(|(symbol, _)| *symbol).eq(code.chars())
+            && taken[..taken.len() - 1].iter().all(|(_, gap)| !gap);
+        fits.then_some((letter, offset + code.len()))
+    })
+}
@@ -0,0 +1,87 @@
+//! Unicode normalization applied to dictionary entries and candidate words.
+//!
+//! The same accented word can be encoded either precomposed (`é` as one code point)
+//! or decomposed (`e` followed by a combining acute accent). Normalizing both sides
+//! of every lookup with the same [`Normalization`] makes them compare equal.
+
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::{UnicodeNormalization, is_nfc_quick};
+
+/// How words are normalized before they are stored or looked up.
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::Normalization;
+///
+/// let decomposed = "cafe\u{301}";
+/// assert_eq!(Normalization::Nfc.apply(decomposed), "caf\u{e9}");
+/// assert_eq!(Normalization::StripDiacritics.apply(decomposed), "cafe");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Normalization {
+    /// Words are compared exactly as encoded
+    None,
+    /// Words are converted to Unicode Normalization Form C
+    #[default]
+    Nfc,
+    /// Words are decomposed and stripped of combining marks, so `"café"` becomes `"cafe"`
+    StripDiacritics,
+}
+
+impl Normalization {
+    /// Normalizes a word, borrowing it unchanged when no conversion is needed.
+    pub fn apply(self, word: &str) -> Cow<'_, str> {
+        // Plain ASCII is already in every normal form and has no diacritics
+        if word.is_ascii() {
+            return Cow::Borrowed(word);
+        }
+
+        match self {
+            Normalization::None => Cow::Borrowed(word),
+            Normalization::Nfc => {
+                if is_nfc_quick(word.chars()) == unicode_normalization::IsNormalized::Yes {
+                    Cow::Borrowed(word)
+                } else {
+                    Cow::Owned(word.nfc().collect())
+                }
+            }
+            Normalization::StripDiacritics => Cow::Owned(
+                word.nfd()
+                    .filter(|c| !is_combining_mark(*c))
+                    .nfc()
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl FromStr for Normalization {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(Normalization::None),
+            "nfc" => Ok(Normalization::Nfc),
+            "strip-diacritics" => Ok(Normalization::StripDiacritics),
+            _ => Err(format!(
+                "unknown normalization '{}', expected none, nfc or strip-diacritics",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Normalization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Normalization::None => "none",
+            Normalization::Nfc => "nfc",
+            Normalization::StripDiacritics => "strip-diacritics",
+        };
+        f.write_str(name)
+    }
+}
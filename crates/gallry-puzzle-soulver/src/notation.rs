@@ -0,0 +1,181 @@
+//! Text notation for slots, as typed on the command line and written in puzzle files.
+//!
+//! Each slot is written as a set of characters with a few special forms:
+//!
+//! - `?` alone accepts any letter of the dictionary's alphabet
+//! - `[a-f]` adds every character in the range; brackets may hold several ranges
+//!   and single characters, as in `[a-cx]`
+//! - A leading `!` negates the set, so `!qz` accepts any letter except `q` and `z`
+//! - `\` makes the next character literal, as in `\?` or `\[`
+//!
+//! A whole puzzle can also be written as one string with the sets joined by a
+//! separator, as in `cbr/aio/tse`; see [`split_sets`]. Words typed on a phone keypad,
+//! such as `4663`, have a notation of their own; see [`parse_keypad_slots`].
+
+use crate::Slot;
+use anyhow::Result;
+use std::collections::BTreeSet;
+
+/// Splits a whole puzzle written as one string, such as `cbr/aio/tse`, into its sets.
+///
+/// Escaped separators do not split, and escapes are kept so that
+/// [`Slot::from_str`](crate::Slot#impl-FromStr-for-Slot) can read them. An empty string
+/// has no sets.
+///
+/// # Parameters
+///
+/// * `text` - The sets joined by `separator`
+/// * `separator` - The character between sets
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::split_sets;
+///
+/// assert_eq!(split_sets("cbr/aio/tse", '/'), vec!["cbr", "aio", "tse"]);
+/// assert_eq!(split_sets("a\\/b,c", ','), vec!["a\\/b", "c"]);
+/// assert_eq!(split_sets("a\\/b/c", '/'), vec!["a\\/b", "c"]);
+/// ```
+pub fn split_sets(text: &str, separator: char) -> Vec<String> {
+    if text.is_empty() {
+        return Vec::new();
+    }
+
+    let mut sets = vec![String::new()];
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        let set = sets.last_mut().expect("sets start with one entry");
+        if c == '\\' {
+            set.push(c);
+            set.extend(chars.next());
+        } else if c == separator {
+            sets.push(String::new());
+        } else {
+            set.push(c);
+        }
+    }
+    sets
+}
+
+/// Parses one slot per set, reporting which set is invalid.
+///
+/// # Parameters
+///
+/// * `sets` - The sets for each position, in the notation accepted by
+///   [`Slot::from_str`](crate::Slot#impl-FromStr-for-Slot)
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::parse_slots;
+///
+/// let slots = parse_slots(&["cb", "[a-c]", "?"]).unwrap();
+/// assert_eq!(slots[1].options(), ['a', 'b', 'c']);
+///
+/// let error = parse_slots(&["cb", "a]"]).unwrap_err();
+/// assert_eq!(
+///     error.to_string(),
+///     "Invalid slot 2 'a]': unmatched ']' at character 2; write \\] for a literal ']'"
+/// );
+/// ```
+pub fn parse_slots<S: AsRef<str>>(sets: &[S]) -> Result<Vec<Slot>> {
+    sets.iter()
+        .enumerate()
+        .map(|(index, set)| {
+            let set = set.as_ref();
+            set.parse()
+                .map_err(|error| anyhow::anyhow!("Invalid slot {} '{}': {}", index + 1, set, error))
+        })
+        .collect()
+}
+
+/// Parses the slots of a numeric code, such as a keypad or safe combination.
+///
+/// Sets are written as for [`parse_slots`], but wildcards such as `?` and `!13` take
+/// their options from the digits `0`–`9` rather than a dictionary's alphabet.
+///
+/// # Parameters
+///
+/// * `sets` - The candidate digits for each position
+///
+/// # Returns
+///
+/// The slots, or an error naming a set that is invalid or allows a non-digit
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::parse_digit_slots;
+///
+/// let slots = parse_digit_slots(&["13", "!0", "[2-4]"]).unwrap();
+/// assert_eq!(slots[1].options(), ['1', '2', '3', '4', '5', '6', '7', '8', '9']);
+/// assert!(!slots[1].is_wildcard());
+/// assert!(parse_digit_slots(&["1a"]).is_err());
+/// ```
+pub fn parse_digit_slots<S: AsRef<str>>(sets: &[S]) -> Result<Vec<Slot>> {
+    let digits: BTreeSet<char> = ('0'..='9').collect();
+    let slots: Vec<Slot> = parse_slots(sets)?
+        .iter()
+        // Fixed to the digits, so a generator does not resolve them again
+        .map(|slot| Slot::new(slot.resolved(&digits).options().to_vec()))
+        .collect();
+    for (index, slot) in slots.iter().enumerate() {
+        if let Some(c) = slot.options().iter().find(|c| !c.is_ascii_digit()) {
+            let set = sets[index].as_ref();
+            anyhow::bail!(
+                "Invalid slot {} '{}': '{}' is not a digit",
+                index + 1,
+                set,
+                c
+            );
+        }
+    }
+    Ok(slots)
+}
+
+/// Letters on the keys `0` to `9` of a phone keypad; `0` and `1` have none
+pub const KEYPAD_LETTERS: [&str; 10] = [
+    "", "", "abc", "def", "ghi", "jkl", "mno", "pqrs", "tuv", "wxyz",
+];
+
+/// Parses the key presses of one word typed on a phone keypad, T9 style.
+///
+/// Each digit from `2` to `9` becomes a slot holding the letters on its key, as
+/// listed in [`KEYPAD_LETTERS`], and `?` an unknown key that accepts any letter.
+///
+/// # Parameters
+///
+/// * `keys` - The keys pressed, one per letter, such as `4663`
+///
+/// # Returns
+///
+/// The slots, or an error naming a key without letters or a character that is not a
+/// key
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::{WordGenerator, parse_keypad_slots};
+///
+/// let slots = parse_keypad_slots("4663").unwrap();
+/// assert_eq!(slots[0].options(), ['g', 'h', 'i']);
+///
+/// let words: Vec<String> = WordGenerator::with_slots(slots).iter().collect();
+/// assert!(words.contains(&"good".to_string()) && words.contains(&"home".to_string()));
+/// assert!(parse_keypad_slots("401").is_err());
+/// ```
+pub fn parse_keypad_slots(keys: &str) -> Result<Vec<Slot>> {
+    keys.chars()
+        .enumerate()
+        .map(|(index, key)| {
+            if key == '?' {
+                return Ok(Slot::any());
+            }
+            match key.to_digit(10).map(|digit| KEYPAD_LETTERS[digit as usize]) {
+                Some("") => anyhow::bail!("Key {} '{}' has no letters", index + 1, key),
+                Some(letters) => Ok(Slot::new(letters.chars().collect())),
+                None => anyhow::bail!("Key {} '{}' is not a keypad digit", index + 1, key),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,272 @@
+//! Reading numbers out of words, spelled out or as Roman numerals.
+//!
+//! Some answers are numbers in disguise: a word like "seven" or "mix" (1009) that
+//! opens a numeric lock once read as a value. [`spelled_number_value`] and
+//! [`roman_numeral_value`] convert candidates to values, and the
+//! [`SpelledNumber`](crate::Constraint::SpelledNumber) and
+//! [`RomanNumeral`](crate::Constraint::RomanNumeral) constraints keep only the
+//! words that are numbers, optionally of a given value.
+
+/// Names of the numbers from zero to nineteen
+const UNITS: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+
+/// Names of the multiples of ten from twenty to ninety, with their values
+const TENS: [(&str, u64); 8] = [
+    ("twenty", 20),
+    ("thirty", 30),
+    ("forty", 40),
+    ("fifty", 50),
+    ("sixty", 60),
+    ("seventy", 70),
+    ("eighty", 80),
+    ("ninety", 90),
+];
+
+/// Names of the scales above a hundred, largest first
+const SCALES: [(&str, u64); 3] = [
+    ("billion", 1_000_000_000),
+    ("million", 1_000_000),
+    ("thousand", 1_000),
+];
+
+/// One word of a spelled-out number.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Token {
+    /// Zero to nineteen
+    Unit(u64),
+    /// Twenty to ninety
+    Tens(u64),
+    /// The word "hundred"
+    Hundred,
+    /// Thousand, million or billion
+    Scale(u64),
+    /// The word "and", as in "one hundred and five"
+    And,
+}
+
+/// Returns every token with its spelling.
+fn tokens() -> impl Iterator<Item = (&'static str, Token)> {
+    UNITS
+        .iter()
+        .enumerate()
+        .map(|(value, name)| (*name, Token::Unit(value as u64)))
+        .chain(TENS.iter().map(|&(name, value)| (name, Token::Tens(value))))
+        .chain([("hundred", Token::Hundred), ("and", Token::And)])
+        .chain(
+            SCALES
+                .iter()
+                .map(|&(name, value)| (name, Token::Scale(value))),
+        )
+}
+
+/// Returns the value of a spelled-out number, such as `seven`, `twenty-one` or
+/// `onehundredandfive`.
+///
+/// Case is ignored, as are spaces and hyphens between the words, so a number reads
+/// the same as a phrase or run together as the letters of one answer. Only numbers
+/// written the usual way are recognized: "nineteen hundred" and "one two" are not.
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::spelled_number_value;
+///
+/// assert_eq!(spelled_number_value("Seven"), Some(7));
+/// assert_eq!(spelled_number_value("twenty-one"), Some(21));
+/// assert_eq!(spelled_number_value("onehundredandfive"), Some(105));
+/// assert_eq!(spelled_number_value("two thousand twenty"), Some(2020));
+/// assert_eq!(spelled_number_value("seventeen"), Some(17));
+/// assert_eq!(spelled_number_value("seventy ten"), None);
+/// assert_eq!(spelled_number_value("heaven"), None);
+/// ```
+pub fn spelled_number_value(word: &str) -> Option<u64> {
+    let letters: String = word
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .flat_map(char::to_lowercase)
+        .collect();
+    if letters.is_empty() {
+        return None;
+    }
+    read_tokens(&letters, &mut Vec::new())
+}
+
+/// Splits the rest of a number into tokens in every way, returning the value of the
+/// first split that reads as a number.
+///
+/// Names such as "six", "sixteen" and "sixty" share a beginning, so a split that
+/// fails further on is undone and the next name tried.
+fn read_tokens(rest: &str, read: &mut Vec<Token>) -> Option<u64> {
+    if rest.is_empty() {
+        return evaluate(read);
+    }
+    for (name, token) in tokens() {
+        if let Some(after) = rest.strip_prefix(name) {
+            read.push(token);
+            let value = read_tokens(after, read);
+            read.pop();
+            if value.is_some() {
+                return value;
+            }
+        }
+    }
+    None
+}
+
+/// Returns the value of a sequence of tokens, if it is a number written the usual way.
+fn evaluate(tokens: &[Token]) -> Option<u64> {
+    if tokens == [Token::Unit(0)] {
+        return Some(0);
+    }
+
+    let mut total = 0;
+    let mut index = 0;
+    let mut last_scale = u64::MAX;
+    while index < tokens.len() {
+        // "and" may only lead the last part, as in "one thousand and five"
+        let joined = index > 0 && tokens[index] == Token::And;
+        if joined {
+            index += 1;
+        }
+        let (value, next) = group(tokens, index)?;
+        index = next;
+        match tokens.get(index) {
+            Some(&Token::Scale(scale)) if !joined && scale < last_scale => {
+                total += value * scale;
+                last_scale = scale;
+                index += 1;
+            }
+            None if !joined || value < 100 => total += value,
+            _ => return None,
+        }
+    }
+    Some(total)
+}
+
+/// Reads a number from one to 999 starting at `index`, returning it with the index
+/// of the token after it.
+fn group(tokens: &[Token], mut index: usize) -> Option<(u64, usize)> {
+    let mut value = 0;
+    if let [Token::Unit(hundreds @ 1..=9), Token::Hundred, ..] = tokens[index..] {
+        value = hundreds * 100;
+        index += 2;
+        match tokens.get(index) {
+            None | Some(Token::Scale(_)) => return Some((value, index)),
+            Some(Token::And) => index += 1,
+            Some(_) => {}
+        }
+    }
+    match tokens.get(index)? {
+        Token::Unit(units @ 1..=19) => Some((value + units, index + 1)),
+        Token::Tens(tens) => match tokens.get(index + 1) {
+            Some(Token::Unit(units @ 1..=9)) => Some((value + tens + units, index + 2)),
+            _ => Some((value + tens, index + 1)),
+        },
+        _ => None,
+    }
+}
+
+/// Roman numeral symbols with their values, including the subtractive pairs, largest
+/// first
+const ROMAN_SYMBOLS: [(&str, u32); 13] = [
+    ("M", 1000),
+    ("CM", 900),
+    ("D", 500),
+    ("CD", 400),
+    ("C", 100),
+    ("XC", 90),
+    ("L", 50),
+    ("XL", 40),
+    ("X", 10),
+    ("IX", 9),
+    ("V", 5),
+    ("IV", 4),
+    ("I", 1),
+];
+
+/// Returns a number from 1 to 3999 written as a Roman numeral, in uppercase.
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::roman_numeral;
+///
+/// assert_eq!(roman_numeral(1923).as_deref(), Some("MCMXXIII"));
+/// assert_eq!(roman_numeral(0), None);
+/// ```
+pub fn roman_numeral(value: u32) -> Option<String> {
+    if !(1..=3999).contains(&value) {
+        return None;
+    }
+    let mut numeral = String::new();
+    let mut rest = value;
+    for (symbol, worth) in ROMAN_SYMBOLS {
+        while rest >= worth {
+            numeral.push_str(symbol);
+            rest -= worth;
+        }
+    }
+    Some(numeral)
+}
+
+/// Returns the value of a Roman numeral, ignoring case.
+///
+/// Only numerals in the standard form are accepted, the one [`roman_numeral`]
+/// writes, so `IIII` and `IC` are not numerals, and neither are most words made of
+/// the letters I, V, X, L, C, D and M.
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::roman_numeral_value;
+///
+/// assert_eq!(roman_numeral_value("XIV"), Some(14));
+/// assert_eq!(roman_numeral_value("mix"), Some(1009));
+/// assert_eq!(roman_numeral_value("civil"), None);
+/// ```
+pub fn roman_numeral_value(word: &str) -> Option<u32> {
+    let numeral = word.to_ascii_uppercase();
+    let worth = |c: char| match c {
+        'I' => Some(1),
+        'V' => Some(5),
+        'X' => Some(10),
+        'L' => Some(50),
+        'C' => Some(100),
+        'D' => Some(500),
+        'M' => Some(1000),
+        _ => None,
+    };
+    let values: Vec<i64> = numeral.chars().map(worth).collect::<Option<_>>()?;
+
+    let mut total = 0;
+    for (index, &value) in values.iter().enumerate() {
+        match values.get(index + 1) {
+            Some(&next) if next > value => total -= value,
+            _ => total += value,
+        }
+    }
+    // Checking against the standard form rejects repeats and odd subtractions alike
+    let total = u32::try_from(total).ok()?;
+    (roman_numeral(total)? == numeral).then_some(total)
+}
@@ -0,0 +1,816 @@
+//! Rendering of solver results in the formats selectable with `--output`.
+
+use crate::color;
+use crate::filter::FilterCommand;
+use anyhow::Result;
+use gallry_puzzle_soulver::{PositionalFrequencies, WordGenerator, WordMatch};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::Write;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+use tracing::{debug, info};
+
+/// How results are written to standard output.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// One human-readable line per match
+    #[default]
+    Text,
+    /// A single JSON document with the puzzle, the matches and statistics
+    Json,
+    /// One JSON object per match, written as soon as the match is found
+    Ndjson,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "ndjson" => Ok(OutputFormat::Ndjson),
+            _ => Err(format!(
+                "unknown output format '{}', expected text, json or ndjson",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            OutputFormat::Text => "text",
+            OutputFormat::Json => "json",
+            OutputFormat::Ndjson => "ndjson",
+        };
+        f.write_str(name)
+    }
+}
+
+/// How `--group-by` splits text results under headers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GroupBy {
+    /// One group per first letter, in alphabetical order
+    FirstLetter,
+    /// One group per word length, shortest first
+    Length,
+    /// High, medium and low thirds of the best positional letter frequency score
+    ScoreBand,
+}
+
+impl FromStr for GroupBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "first-letter" => Ok(GroupBy::FirstLetter),
+            "length" => Ok(GroupBy::Length),
+            "score-band" => Ok(GroupBy::ScoreBand),
+            _ => Err(format!(
+                "unknown grouping '{}', expected first-letter, length or score-band",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for GroupBy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            GroupBy::FirstLetter => "first-letter",
+            GroupBy::Length => "length",
+            GroupBy::ScoreBand => "score-band",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Whether result lines end with NUL; see [`set_nul_terminated`]
+static NUL_TERMINATED: AtomicBool = AtomicBool::new(false);
+
+/// A match along with the annotations the CLI prints next to it.
+pub struct Solution {
+    /// The match as reported by the generator
+    pub word_match: WordMatch,
+    /// Definition of the word or its stem, if a glossary was loaded
+    pub definition: Option<String>,
+    /// Positional letter frequency score, when matches were ranked with `--top`
+    pub score: Option<f64>,
+}
+
+/// Options applied to the generator's matches before they are written.
+#[derive(Default)]
+pub struct ResultOptions {
+    /// External program deciding which matches to keep
+    pub filter: Option<FilterCommand>,
+    /// Maximum number of matches to write
+    pub limit: Option<usize>,
+    /// Threads to solve on
+    pub threads: usize,
+    /// Keep only this many of the best scoring matches, best first
+    pub top: Option<usize>,
+    /// Score every match even when not ranking, for output that prints scores
+    pub score: bool,
+    /// Keep only this many matches chosen at random, in solve order
+    pub sample: Option<usize>,
+    /// Seed for `sample`, making the choice the same on every run
+    pub seed: Option<u64>,
+}
+
+impl ResultOptions {
+    /// Reports an error that ended the results early, once they have been written.
+    pub fn finish(&self) -> Result<()> {
+        self.filter.as_ref().map_or(Ok(()), FilterCommand::finish)
+    }
+}
+
+/// The document written by `--output json`.
+#[derive(Serialize)]
+struct JsonReport<'a> {
+    /// Name of the puzzle when solving a batch
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<&'a str>,
+    puzzle: JsonPuzzle,
+    matches: Vec<JsonMatch<'a>>,
+    stats: JsonStats,
+}
+
+/// Echo of the puzzle that was solved.
+#[derive(Serialize)]
+struct JsonPuzzle {
+    /// Options of every slot, in position order
+    slots: Vec<String>,
+    /// Names of the dictionaries used for filtering
+    dictionaries: Vec<String>,
+    /// Whether words must appear in any or all dictionaries
+    policy: String,
+}
+
+/// A single match and how it was formed.
+#[derive(Serialize)]
+struct JsonMatch<'a> {
+    /// Name of the puzzle when solving a batch
+    #[serde(skip_serializing_if = "Option::is_none")]
+    puzzle: Option<&'a str>,
+    word: &'a str,
+    entry: Option<&'a str>,
+    stem: Option<&'a str>,
+    dictionaries: &'a [String],
+    rank: Option<u32>,
+    /// Positional letter frequency score, when matches were ranked
+    #[serde(skip_serializing_if = "Option::is_none")]
+    score: Option<f64>,
+    /// Index of the option chosen in each slot
+    choices: Vec<usize>,
+    definition: Option<&'a str>,
+}
+
+/// Statistics about the solve.
+#[derive(Serialize)]
+struct JsonStats {
+    /// Combinations the slots produce before filtering
+    combinations: u128,
+    /// Number of matches reported
+    matches: usize,
+    /// Wall-clock time spent solving, in milliseconds
+    elapsed_ms: u128,
+}
+
+/// The object written by `--count` with JSON output.
+#[derive(Serialize)]
+struct JsonCount {
+    matches: usize,
+    /// Combinations the slots produce before filtering
+    combinations: u128,
+    /// Share of the combinations rejected, from 0 to 100
+    pruned_percent: f64,
+}
+
+/// Writes a summary of the work the generator did to standard error.
+///
+/// Standard output is left to the results, so the summary can be read while piping
+/// them elsewhere.
+pub fn write_stats(generator: &WordGenerator, started: Instant) {
+    let stats = generator.stats();
+    eprintln!("combinations:     {}", generator.combination_count());
+    eprintln!("examined:         {}", stats.examined);
+    eprintln!("pruned:           {}", stats.pruned);
+    eprintln!("dictionary hits:  {}", stats.dictionary_hits);
+    eprintln!("elapsed:          {:.1?}", started.elapsed());
+    match peak_memory() {
+        Some(bytes) => eprintln!("peak memory:      {:.1} MiB", bytes as f64 / 1048576.0),
+        None => eprintln!("peak memory:      unknown"),
+    }
+}
+
+/// Returns the process's peak resident memory in bytes, where the platform reports it.
+#[cfg(target_os = "linux")]
+fn peak_memory() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmHWM:"))?;
+    let kilobytes: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kilobytes * 1024)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn peak_memory() -> Option<u64> {
+    None
+}
+
+/// Ends result lines with NUL instead of a newline, as asked with `--print0`.
+///
+/// Only lines holding results are affected; whole JSON documents and summaries keep
+/// their newlines.
+pub fn set_nul_terminated(enabled: bool) {
+    NUL_TERMINATED.store(enabled, Ordering::Relaxed);
+}
+
+/// Writes one result to standard output, ended with a newline or NUL.
+pub fn write_line(line: &str) {
+    if NUL_TERMINATED.load(Ordering::Relaxed) {
+        print!("{}\0", line);
+        // Standard output only flushes itself at newlines
+        let _ = std::io::stdout().flush();
+    } else {
+        println!("{}", line);
+    }
+}
+
+/// Writes text results in columns across the terminal, filling each column in turn
+/// like `ls`.
+///
+/// The width is that of the terminal on standard output, else `$COLUMNS`, else 80.
+pub fn write_columns(generator: &WordGenerator, solutions: impl Iterator<Item = Solution>) {
+    let lines: Vec<String> = solutions
+        .map(|solution| text_line(generator, &solution))
+        .collect();
+    for row in column_rows(&lines, terminal_width()) {
+        write_line(&row);
+    }
+}
+
+/// Lays `lines` out in as few rows as fit within `width`, two spaces between columns.
+fn column_rows(lines: &[String], width: usize) -> Vec<String> {
+    const GAP: usize = 2;
+    let widths: Vec<usize> = lines
+        .iter()
+        .map(|line| color::visible_width(line))
+        .collect();
+
+    // One column always fits, however long its lines
+    let mut layout = (
+        lines.len().max(1),
+        vec![widths.iter().copied().max().unwrap_or(0)],
+    );
+    for rows in 1..lines.len() {
+        let columns: Vec<usize> = widths
+            .chunks(rows)
+            .map(|column| column.iter().copied().max().unwrap_or(0))
+            .collect();
+        if columns.iter().sum::<usize>() + GAP * (columns.len() - 1) <= width {
+            layout = (rows, columns);
+            break;
+        }
+    }
+
+    let (rows, columns) = layout;
+    (0..rows.min(lines.len()))
+        .map(|row| {
+            let mut text = String::new();
+            for (column, column_width) in columns.iter().enumerate() {
+                let Some(line) = lines.get(column * rows + row) else {
+                    break;
+                };
+                text.push_str(line);
+                // Pad up to the next column, if there is one on this row
+                if lines.get((column + 1) * rows + row).is_some() {
+                    let padding = column_width + GAP - widths[column * rows + row];
+                    text.push_str(&" ".repeat(padding));
+                }
+            }
+            text
+        })
+        .collect()
+}
+
+/// Returns the width to lay columns out in.
+fn terminal_width() -> usize {
+    terminal_size::terminal_size()
+        .map(|(terminal_size::Width(width), _)| usize::from(width))
+        .or_else(|| std::env::var("COLUMNS").ok()?.parse().ok())
+        .unwrap_or(80)
+}
+
+/// Writes the options of each slot used by at least one solution, and those no
+/// solution uses, as in `slot 2: a,o — b,i eliminated`.
+pub fn write_pruned(generator: &WordGenerator, solutions: impl Iterator<Item = Solution>) {
+    let slots = generator.slots();
+    let mut used: Vec<Vec<bool>> = slots
+        .iter()
+        .map(|slot| vec![false; slot.options().len()])
+        .collect();
+    for solution in solutions {
+        let Some(choices) = generator.choices(&solution.word_match.word) else {
+            continue;
+        };
+        for (position, index) in choices.into_iter().enumerate() {
+            used[position][index] = true;
+        }
+    }
+
+    for (position, slot) in slots.iter().enumerate() {
+        let mut kept = Vec::new();
+        let mut eliminated = Vec::new();
+        for (&option, &used) in slot.options().iter().zip(&used[position]) {
+            if used {
+                kept.push(option.to_string());
+            } else {
+                eliminated.push(option.to_string());
+            }
+        }
+        let kept = kept.join(",");
+        match eliminated.as_slice() {
+            [] => write_line(&format!("slot {}: {}", position + 1, kept)),
+            _ => write_line(&format!(
+                "slot {}: {} — {} eliminated",
+                position + 1,
+                kept,
+                eliminated.join(",")
+            )),
+        }
+    }
+}
+
+/// Writes text results under a header per group, each naming how many matches it holds.
+///
+/// Matches keep their solve (or ranking) order within a group. Score bands compare
+/// each match with the best score among them, so they are meaningful for any word list.
+pub fn write_groups(
+    generator: &WordGenerator,
+    solutions: impl Iterator<Item = Solution>,
+    group_by: GroupBy,
+) {
+    let stats = (group_by == GroupBy::ScoreBand).then(|| letter_stats(generator));
+    let solutions: Vec<Solution> = solutions
+        .map(|mut solution| {
+            if let Some(stats) = &stats {
+                solution.score = Some(score(stats.as_ref(), &solution));
+            }
+            solution
+        })
+        .collect();
+    let best = solutions
+        .iter()
+        .filter_map(|solution| solution.score)
+        .fold(0.0, f64::max);
+
+    // Groups sort by their position, then by label
+    let mut groups: BTreeMap<(usize, String), Vec<&Solution>> = BTreeMap::new();
+    for solution in &solutions {
+        let word = &solution.word_match.word;
+        let key = match group_by {
+            GroupBy::FirstLetter => {
+                let first = word.chars().next().map(|c| c.to_lowercase().collect());
+                (0, first.unwrap_or_default())
+            }
+            GroupBy::Length => {
+                let length = word.chars().count();
+                (length, format!("{} letters", length))
+            }
+            GroupBy::ScoreBand => {
+                let ratio = if best > 0.0 {
+                    solution.score.unwrap_or(0.0) / best
+                } else {
+                    0.0
+                };
+                match ratio {
+                    _ if ratio >= 2.0 / 3.0 => (0, "high score".to_string()),
+                    _ if ratio >= 1.0 / 3.0 => (1, "medium score".to_string()),
+                    _ => (2, "low score".to_string()),
+                }
+            }
+        };
+        groups.entry(key).or_default().push(solution);
+    }
+
+    for (index, ((_, label), members)) in groups.iter().enumerate() {
+        if index > 0 && !NUL_TERMINATED.load(Ordering::Relaxed) {
+            println!();
+        }
+        let noun = if members.len() == 1 {
+            "match"
+        } else {
+            "matches"
+        };
+        write_line(&format!("{} ({} {})", label, members.len(), noun));
+        for solution in members {
+            write_line(&format!("  {}", text_line(generator, solution)));
+        }
+    }
+}
+
+/// Writes results in the requested format.
+///
+/// # Parameters
+///
+/// * `format` - The output format
+/// * `generator` - The generator the solutions came from
+/// * `solutions` - The solutions to write
+/// * `started` - When solving started, used for timing statistics
+pub fn write_solutions(
+    format: OutputFormat,
+    generator: &WordGenerator,
+    solutions: impl Iterator<Item = Solution>,
+    started: Instant,
+) -> Result<()> {
+    match format {
+        OutputFormat::Text => {
+            for solution in solutions {
+                write_line(&text_line(generator, &solution));
+            }
+        }
+        OutputFormat::Json => {
+            let solutions: Vec<Solution> = solutions.collect();
+            let report = json_report(None, generator, &solutions, started);
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        }
+        OutputFormat::Ndjson => {
+            // Each match is flushed as it is written, so it reaches consumers at once
+            for solution in solutions {
+                write_line(&ndjson_line(generator, &solution)?);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Writes only how many matches were found, out of how many combinations.
+///
+/// # Parameters
+///
+/// * `format` - The output format; JSON and NDJSON both write a single object
+/// * `generator` - The generator the solutions came from
+/// * `solutions` - The solutions to count
+pub fn write_count(
+    format: OutputFormat,
+    generator: &WordGenerator,
+    solutions: impl Iterator<Item = Solution>,
+) -> Result<()> {
+    let matches = solutions.count();
+    let combinations = generator.combination_count();
+    let pruned = pruned_percent(combinations, matches);
+    match format {
+        OutputFormat::Text => println!(
+            "{} matches of {} combinations ({:.1}% pruned)",
+            matches, combinations, pruned
+        ),
+        OutputFormat::Json | OutputFormat::Ndjson => {
+            let count = JsonCount {
+                matches,
+                combinations,
+                pruned_percent: pruned,
+            };
+            println!("{}", serde_json::to_string(&count)?);
+        }
+    }
+    Ok(())
+}
+
+/// Returns the percentage of combinations that did not make it into the matches.
+pub fn pruned_percent(combinations: u128, matches: usize) -> f64 {
+    if combinations == 0 {
+        0.0
+    } else {
+        100.0 * (1.0 - matches as f64 / combinations as f64)
+    }
+}
+
+/// Solves and writes several named puzzles one after another.
+///
+/// Text output gets a header per puzzle, JSON output is combined into a single
+/// document with a `puzzles` array, and NDJSON objects carry a `puzzle` field.
+/// Puzzles are solved lazily, so only one generator is held in memory at a time.
+///
+/// # Parameters
+///
+/// * `format` - The output format
+/// * `puzzles` - The named generators to solve, in order
+/// * `options` - Options applied to the matches of every puzzle
+///
+/// # Returns
+///
+/// The number of matches written across all puzzles
+pub fn write_batch(
+    format: OutputFormat,
+    puzzles: impl Iterator<Item = Result<(String, WordGenerator)>>,
+    options: &ResultOptions,
+) -> Result<usize> {
+    let mut reports = Vec::new();
+    let mut written = 0;
+    for (index, puzzle) in puzzles.enumerate() {
+        let (name, generator) = puzzle?;
+        let started = Instant::now();
+        let solutions = select(&generator, options)?.inspect(|_| written += 1);
+        match format {
+            OutputFormat::Text => {
+                if index > 0 && !NUL_TERMINATED.load(Ordering::Relaxed) {
+                    println!();
+                }
+                write_line(&format!("== {} ==", name));
+                write_solutions(format, &generator, solutions, started)?;
+            }
+            OutputFormat::Json => {
+                let solutions: Vec<Solution> = solutions.collect();
+                let report = json_report(Some(&name), &generator, &solutions, started);
+                reports.push(serde_json::to_value(report)?);
+            }
+            OutputFormat::Ndjson => {
+                for solution in solutions {
+                    let json = json_match(Some(&name), &generator, &solution);
+                    write_line(&serde_json::to_string(&json)?);
+                }
+            }
+        }
+        options.finish()?;
+    }
+
+    if format == OutputFormat::Json {
+        let document = serde_json::json!({ "puzzles": reports });
+        println!("{}", serde_json::to_string_pretty(&document)?);
+    }
+    Ok(written)
+}
+
+/// Returns the generator's matches after applying the result options.
+pub fn select<'a>(
+    generator: &'a WordGenerator,
+    options: &'a ResultOptions,
+) -> Result<Box<dyn Iterator<Item = Solution> + 'a>> {
+    let combinations = generator.combination_count();
+    // Both paths are lazy, so streamed output starts with the first matches and a
+    // limit stops the search early
+    let solutions: Box<dyn Iterator<Item = Solution> + 'a> = if options.threads > 1 {
+        info!(
+            "solving {} combinations on {} threads",
+            combinations, options.threads
+        );
+        Box::new(
+            generator
+                .par_iter_matches(options.threads)
+                .map(|word_match| annotate(generator, word_match)),
+        )
+    } else {
+        info!("solving {} combinations lazily on one thread", combinations);
+        Box::new(solve(generator))
+    };
+    select_from(generator, solutions, options)
+}
+
+/// Filters, samples, scores and limits solutions found elsewhere, as [`select`] does.
+///
+/// # Parameters
+///
+/// * `generator` - The generator the solutions belong to, for scoring them
+/// * `solutions` - Every match of the puzzle, in order
+/// * `options` - Which of the solutions to keep
+pub fn select_from<'a>(
+    generator: &'a WordGenerator,
+    mut solutions: Box<dyn Iterator<Item = Solution> + 'a>,
+    options: &'a ResultOptions,
+) -> Result<Box<dyn Iterator<Item = Solution> + 'a>> {
+    if let Some(filter) = &options.filter {
+        info!("filtering matches with '{}'", filter.command);
+        solutions = filter.apply(solutions)?;
+    }
+    if let Some(amount) = options.sample {
+        let seed = options.seed.unwrap_or_else(rand::random);
+        info!("sampling {} matches with seed {}", amount, seed);
+        solutions = Box::new(sample(solutions, amount, seed).into_iter());
+    }
+    if let Some(top) = options.top {
+        debug!("scoring every match to keep the best {}", top);
+        solutions = Box::new(rank(generator, solutions).into_iter().take(top));
+    } else if options.score {
+        let stats = letter_stats(generator);
+        solutions = Box::new(solutions.map(move |mut solution| {
+            solution.score = Some(score(stats.as_ref(), &solution));
+            solution
+        }));
+    }
+    Ok(Box::new(
+        solutions.take(options.limit.unwrap_or(usize::MAX)),
+    ))
+}
+
+/// Chooses `amount` of the solutions at random, keeping their order.
+fn sample(solutions: impl Iterator<Item = Solution>, amount: usize, seed: u64) -> Vec<Solution> {
+    let solutions: Vec<Solution> = solutions.collect();
+    let mut rng = StdRng::seed_from_u64(seed);
+    let amount = amount.min(solutions.len());
+    let mut chosen = rand::seq::index::sample(&mut rng, solutions.len(), amount).into_vec();
+    chosen.sort_unstable();
+
+    let mut chosen = chosen.into_iter().peekable();
+    solutions
+        .into_iter()
+        .enumerate()
+        .filter(|(index, _)| chosen.next_if_eq(index).is_some())
+        .map(|(_, solution)| solution)
+        .collect()
+}
+
+/// Scores every solution and sorts them best first.
+///
+/// Solutions are ordered by [`PositionalFrequencies::score`], then by frequency rank
+/// when the word list records one, keeping solve order among equals.
+fn rank(generator: &WordGenerator, solutions: impl Iterator<Item = Solution>) -> Vec<Solution> {
+    let stats = letter_stats(generator);
+    let mut ranked: Vec<Solution> = solutions
+        .map(|mut solution| {
+            solution.score = Some(score(stats.as_ref(), &solution));
+            solution
+        })
+        .collect();
+    ranked.sort_by(|a, b| {
+        let score = |solution: &Solution| solution.score.unwrap_or(0.0);
+        let rank = |solution: &Solution| solution.word_match.rank.unwrap_or(u32::MAX);
+        score(b)
+            .total_cmp(&score(a))
+            .then_with(|| rank(a).cmp(&rank(b)))
+    });
+    ranked
+}
+
+/// Scores a solution, or returns 0.0 without statistics to score against.
+fn score(stats: Option<&PositionalFrequencies>, solution: &Solution) -> f64 {
+    stats.map_or(0.0, |stats| stats.score(&solution.word_match.word))
+}
+
+/// Returns the letter statistics matches are scored with.
+///
+/// The statistics come from the first non-empty dictionary, for words as long as the
+/// puzzle; without a dictionary there is nothing to score against.
+pub fn letter_stats(generator: &WordGenerator) -> Option<PositionalFrequencies> {
+    let word_len = generator.slots().len();
+    generator
+        .dictionaries()
+        .iter()
+        .find(|dictionary| !dictionary.is_empty())
+        .map(|dictionary| dictionary.positional_frequencies(word_len))
+}
+
+/// Returns the generator's matches along with their annotations.
+pub fn solve(generator: &WordGenerator) -> impl Iterator<Item = Solution> + '_ {
+    generator
+        .iter_matches()
+        .map(|word_match| annotate(generator, word_match))
+}
+
+/// Attaches the annotations printed next to a match.
+pub fn annotate(generator: &WordGenerator, word_match: WordMatch) -> Solution {
+    Solution {
+        definition: definition_of(generator, &word_match),
+        word_match,
+        score: None,
+    }
+}
+
+/// Returns a solution as the object written for it by `--output ndjson`.
+pub fn json_value(generator: &WordGenerator, solution: &Solution) -> Result<serde_json::Value> {
+    Ok(serde_json::to_value(json_match(None, generator, solution))?)
+}
+
+/// Formats a solution as one line of `--output ndjson`, without the line ending.
+pub fn ndjson_line(generator: &WordGenerator, solution: &Solution) -> Result<String> {
+    Ok(serde_json::to_string(&json_match(
+        None, generator, solution,
+    ))?)
+}
+
+/// Returns the definition to print next to a match, falling back to its stem's.
+#[cfg(feature = "definitions")]
+fn definition_of(generator: &WordGenerator, word_match: &WordMatch) -> Option<String> {
+    let dictionary = generator.dictionary()?;
+    dictionary
+        .definition(&word_match.word)
+        .or_else(|| dictionary.definition(word_match.stem.as_deref()?))
+        .map(str::to_string)
+}
+
+#[cfg(not(feature = "definitions"))]
+fn definition_of(_generator: &WordGenerator, _word_match: &WordMatch) -> Option<String> {
+    None
+}
+
+/// Formats a solution as a line of text, colored when [`color::enabled`].
+///
+/// Letters from slots with several options are highlighted, so the letters that were
+/// actually decided by the solve stand out from the fixed ones.
+pub fn text_line(generator: &WordGenerator, solution: &Solution) -> String {
+    let word_match = &solution.word_match;
+    let mut line = if color::enabled() {
+        let ambiguous: Vec<bool> = generator
+            .slots()
+            .iter()
+            .map(|slot| slot.options().len() > 1)
+            .collect();
+        color::highlight(word_match.display_word(), &ambiguous)
+    } else {
+        word_match.display_word().to_string()
+    };
+    if let Some(stem) = &word_match.stem {
+        line.push_str(&color::muted(&format!(" ({})", stem)));
+    }
+    if generator.dictionaries().len() > 1 && !word_match.dictionaries.is_empty() {
+        line.push_str(&color::muted(&format!(
+            " [{}]",
+            word_match.dictionaries.join(", ")
+        )));
+    }
+    if let Some(score) = solution.score {
+        line.push_str(&color::muted(&format!(" {:.3}", score)));
+    }
+    if let Some(definition) = &solution.definition {
+        line.push_str(&format!(" - {}", definition));
+    }
+    line
+}
+
+/// Describes which option each slot contributed to a word, as in `c[1] a[0] t[2]`.
+///
+/// Indices count from 0 within each slot's options, like `choices` in JSON output.
+/// Words the slots cannot spell have no explanation.
+pub fn explanation(generator: &WordGenerator, word: &str) -> Option<String> {
+    let choices = generator.choices(word)?;
+    let slots = generator.slots();
+    let parts: Vec<String> = choices
+        .iter()
+        .zip(slots.iter())
+        .map(|(&index, slot)| format!("{}[{}]", slot.options()[index], index))
+        .collect();
+    Some(parts.join(" "))
+}
+
+fn json_puzzle(generator: &WordGenerator) -> JsonPuzzle {
+    JsonPuzzle {
+        slots: generator
+            .slots()
+            .iter()
+            .map(|slot| slot.options().iter().collect())
+            .collect(),
+        dictionaries: generator
+            .dictionaries()
+            .iter()
+            .filter(|dictionary| !dictionary.is_empty())
+            .map(|dictionary| dictionary.name().to_string())
+            .collect(),
+        policy: generator.dictionary_policy().to_string(),
+    }
+}
+
+fn json_report<'a>(
+    name: Option<&'a str>,
+    generator: &WordGenerator,
+    solutions: &'a [Solution],
+    started: Instant,
+) -> JsonReport<'a> {
+    JsonReport {
+        name,
+        puzzle: json_puzzle(generator),
+        matches: solutions
+            .iter()
+            .map(|solution| json_match(None, generator, solution))
+            .collect(),
+        stats: JsonStats {
+            combinations: generator.combination_count(),
+            matches: solutions.len(),
+            elapsed_ms: started.elapsed().as_millis(),
+        },
+    }
+}
+
+fn json_match<'a>(
+    puzzle: Option<&'a str>,
+    generator: &WordGenerator,
+    solution: &'a Solution,
+) -> JsonMatch<'a> {
+    let word_match = &solution.word_match;
+    JsonMatch {
+        puzzle,
+        word: &word_match.word,
+        entry: word_match.entry.as_deref(),
+        stem: word_match.stem.as_deref(),
+        dictionaries: &word_match.dictionaries,
+        rank: word_match.rank,
+        score: solution.score,
+        choices: generator.choices(&word_match.word).unwrap_or_default(),
+        definition: solution.definition.as_deref(),
+    }
+}
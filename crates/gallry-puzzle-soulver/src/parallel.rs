@@ -0,0 +1,243 @@
+//! Multi-threaded solving for large search spaces.
+//!
+//! Combinations are numbered in the order [`WordGenerator::iter`] produces them, and
+//! the numbers are split into chunks that worker threads claim one at a time. The
+//! chunks are solved a window at a time, and each window's matches are handed on in
+//! chunk order before the next window starts, so the matches stream out in the same
+//! order as sequential solving whatever the scheduling, and a consumer that stops early
+//! stops the search. A positional index narrows the slots before they are numbered; a
+//! dictionary scan runs on one thread.
+
+use crate::combination::{count, decode, increment, spell_into};
+use crate::strategy::Plan;
+use crate::{MatchIter, Slot, SolveStats, Strategy, WordGenerator, WordMatch};
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Chunks handed out per thread, so threads that finish early can take more work
+const CHUNKS_PER_THREAD: u128 = 16;
+/// Chunks per thread in a window, solved together before their matches are handed on
+const WINDOW_CHUNKS_PER_THREAD: u128 = 2;
+/// Most combinations in a chunk, so the first matches of a huge search arrive quickly
+const MAX_CHUNK_LEN: u128 = 1 << 16;
+
+impl WordGenerator {
+    /// Returns every match, solving on up to `threads` threads.
+    ///
+    /// The matches are the same, and in the same order, as those of
+    /// [`iter_matches`](Self::iter_matches). Every match is collected before returning;
+    /// [`par_iter_matches`](Self::par_iter_matches) hands them on as they are found.
+    ///
+    /// # Parameters
+    ///
+    /// * `threads` - Maximum number of threads to use; `0` or `1` solves on the
+    ///   calling thread
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gallry_puzzle_soulver::{Slot, WordGenerator};
+    ///
+    /// let generator = WordGenerator::with_slots(vec![
+    ///     Slot::new(vec!['c', 'b', 'r']),
+    ///     Slot::new(vec!['a', 'i', 'o']),
+    ///     Slot::new(vec!['t', 's', 'e']),
+    /// ]);
+    ///
+    /// let sequential: Vec<_> = generator.iter_matches().collect();
+    /// assert_eq!(generator.par_matches(4), sequential);
+    /// ```
+    pub fn par_matches(&self, threads: usize) -> Vec<WordMatch> {
+        self.par_iter_matches(threads).collect()
+    }
+
+    /// Returns an iterator over the matches, solving on up to `threads` threads.
+    ///
+    /// The matches are the same, and in the same order, as those of
+    /// [`iter_matches`](Self::iter_matches). The search runs a window of chunks at a
+    /// time as the iterator is advanced, so the first matches arrive long before a
+    /// large search finishes, and dropping the iterator stops it.
+    ///
+    /// # Parameters
+    ///
+    /// * `threads` - Maximum number of threads to use; `0` or `1` solves on the
+    ///   calling thread
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gallry_puzzle_soulver::{Slot, WordGenerator};
+    ///
+    /// let generator = WordGenerator::with_slots(vec![
+    ///     Slot::new(vec!['c', 'b', 'r']),
+    ///     Slot::new(vec!['a', 'i', 'o']),
+    ///     Slot::new(vec!['t', 's', 'e']),
+    /// ]);
+    ///
+    /// let first: Vec<_> = generator.par_iter_matches(4).take(2).collect();
+    /// let sequential: Vec<_> = generator.iter_matches().take(2).collect();
+    /// assert_eq!(first, sequential);
+    /// ```
+    #[cfg_attr(
+        feature = "instrument",
+        tracing::instrument(level = "debug", skip(self))
+    )]
+    pub fn par_iter_matches(&self, threads: usize) -> ParMatchIter<'_> {
+        let sequential = || ParMatchIter {
+            search: Search::Sequential(self.iter_matches()),
+        };
+        if threads <= 1 {
+            trace_event!(debug, "solving on one thread");
+            return sequential();
+        }
+        // Scanning has no combination numbers to split, and is fast enough on one thread
+        let Plan {
+            strategy,
+            slots,
+            combinations,
+        } = self.plan(self.strategy);
+        let total = count(&slots);
+        if strategy == Strategy::DictScan || total < 2 {
+            trace_event!(debug, combinations = total, "solving on one thread");
+            return sequential();
+        }
+        self.stats.record(&SolveStats {
+            pruned: u64::try_from(combinations - total).unwrap_or(u64::MAX),
+            ..SolveStats::default()
+        });
+
+        let chunk_len = (total / (threads as u128 * CHUNKS_PER_THREAD)).clamp(1, MAX_CHUNK_LEN);
+        trace_event!(
+            debug,
+            combinations = total,
+            chunks = total.div_ceil(chunk_len) as u64,
+            "solving in chunks"
+        );
+        ParMatchIter {
+            search: Search::Chunked(ChunkedSearch {
+                generator: self,
+                slots,
+                threads,
+                total,
+                chunk_len,
+                next_start: 0,
+                ready: Vec::new().into_iter(),
+            }),
+        }
+    }
+
+    /// Evaluates `len` consecutive combinations starting at combination number `start`.
+    fn solve_range(&self, slots: &[Slot], start: u128, len: u128) -> Vec<WordMatch> {
+        let mut indices = decode(slots, start);
+        let mut matches = Vec::new();
+        let mut stats = SolveStats::default();
+        let mut word = String::new();
+        for _ in 0..len {
+            spell_into(slots, &indices, &mut word);
+            matches.extend(self.evaluate(&word, &mut stats));
+            increment(slots, &mut indices);
+        }
+        self.stats.record(&stats);
+        matches
+    }
+}
+
+/// An iterator over the matches of a generator, solved on several threads by
+/// [`WordGenerator::par_iter_matches`]
+pub struct ParMatchIter<'a> {
+    search: Search<'a>,
+}
+
+/// How a [`ParMatchIter`] finds its matches.
+enum Search<'a> {
+    /// On the calling thread, when the search is not worth splitting
+    Sequential(MatchIter<'a>),
+    /// In numbered chunks, a window of them at a time
+    Chunked(ChunkedSearch<'a>),
+}
+
+impl Iterator for ParMatchIter<'_> {
+    type Item = WordMatch;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.search {
+            Search::Sequential(matches) => matches.next(),
+            Search::Chunked(search) => search.next(),
+        }
+    }
+}
+
+/// The state of a search split into chunks of numbered combinations.
+struct ChunkedSearch<'a> {
+    generator: &'a WordGenerator,
+    /// The slots the combinations are numbered over
+    slots: Cow<'a, [Slot]>,
+    /// Most threads to solve a window on
+    threads: usize,
+    /// Number of combinations
+    total: u128,
+    /// Combinations in a chunk
+    chunk_len: u128,
+    /// Number of the first combination not yet solved
+    next_start: u128,
+    /// Matches of the last window not yet handed on
+    ready: std::vec::IntoIter<WordMatch>,
+}
+
+impl ChunkedSearch<'_> {
+    fn next(&mut self) -> Option<WordMatch> {
+        loop {
+            if let Some(word_match) = self.ready.next() {
+                return Some(word_match);
+            }
+            if self.next_start >= self.total {
+                return None;
+            }
+            self.solve_window();
+        }
+    }
+
+    /// Solves the next window of chunks on the worker threads, keeping its matches in
+    /// chunk order.
+    fn solve_window(&mut self) {
+        let window_start = self.next_start;
+        let window_len = (self.chunk_len * self.threads as u128 * WINDOW_CHUNKS_PER_THREAD)
+            .min(self.total - window_start);
+        let chunk_count = window_len.div_ceil(self.chunk_len) as usize;
+        let next_chunk = AtomicUsize::new(0);
+        let (generator, slots, chunk_len) = (self.generator, &self.slots, self.chunk_len);
+
+        let mut chunks: Vec<(usize, Vec<WordMatch>)> = std::thread::scope(|scope| {
+            let workers: Vec<_> = (0..self.threads.min(chunk_count))
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut done = Vec::new();
+                        loop {
+                            let chunk = next_chunk.fetch_add(1, Ordering::Relaxed);
+                            if chunk >= chunk_count {
+                                return done;
+                            }
+                            let offset = chunk as u128 * chunk_len;
+                            let len = chunk_len.min(window_len - offset);
+                            let start = window_start + offset;
+                            done.push((chunk, generator.solve_range(slots, start, len)));
+                        }
+                    })
+                })
+                .collect();
+
+            workers
+                .into_iter()
+                .flat_map(|worker| worker.join().expect("solver thread panicked"))
+                .collect()
+        });
+
+        chunks.sort_unstable_by_key(|(chunk, _)| *chunk);
+        self.ready = chunks
+            .into_iter()
+            .flat_map(|(_, matches)| matches)
+            .collect::<Vec<_>>()
+            .into_iter();
+        self.next_start = window_start + window_len;
+    }
+}
@@ -0,0 +1,386 @@
+//! Logic puzzles of boxes that bear statements, one of which holds a prize.
+//!
+//! Each box displays statements about where the prize is or about which boxes tell
+//! the truth. A box is true when all of its statements are true and false when all of
+//! them are false; the rules of the game promise at least one of each. A solution puts
+//! the prize in one box and gives every statement a truth value such that each
+//! statement is true exactly when what it says holds. The box to open is the one that
+//! holds the prize in every solution.
+//!
+//! ## Statement notation
+//!
+//! Statements are written with a box name, or `this` for the box bearing them:
+//!
+//! - `in BOX` - the prize is in the box
+//! - `empty BOX` - the prize is not in the box
+//! - `true BOX` - every statement on the box is true
+//! - `false BOX` - every statement on the box is false
+//!
+//! They combine with `not`, `and` and `or`, binding in that order, and parentheses.
+
+use anyhow::Result;
+
+/// Most statements a puzzle may have, as every combination of truth values is tried
+const MAX_STATEMENTS: usize = 20;
+
+/// What a statement on a box claims.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Statement {
+    /// The prize is in the box at this index
+    PrizeIn(usize),
+    /// Every statement on the box at this index is true
+    BoxTrue(usize),
+    /// Every statement on the box at this index is false
+    BoxFalse(usize),
+    /// The inner statement does not hold
+    Not(Box<Statement>),
+    /// Every inner statement holds
+    All(Vec<Statement>),
+    /// At least one inner statement holds
+    Any(Vec<Statement>),
+}
+
+/// A box of a [`ParlorPuzzle`] and the statements it displays.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PrizeBox {
+    /// Name the statements refer to the box by, such as its color
+    pub name: String,
+    /// Statements displayed on the box, in order
+    pub statements: Vec<Statement>,
+}
+
+/// A consistent reading of a [`ParlorPuzzle`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParlorSolution {
+    /// Index of the box holding the prize
+    pub prize: usize,
+    /// Truth of each statement, grouped by box like the puzzle's statements
+    pub truths: Vec<Vec<bool>>,
+}
+
+impl ParlorSolution {
+    /// Returns `Some(true)` if every statement on the box is true, `Some(false)` if
+    /// every one is false, and `None` if they are mixed.
+    pub fn box_truth(&self, index: usize) -> Option<bool> {
+        let truths = &self.truths[index];
+        if truths.iter().all(|&truth| truth) {
+            Some(true)
+        } else if truths.iter().all(|&truth| !truth) {
+            Some(false)
+        } else {
+            None
+        }
+    }
+}
+
+/// Boxes bearing statements, exactly one of which holds the prize.
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::ParlorPuzzle;
+///
+/// let puzzle = ParlorPuzzle::parse(&[
+///     "blue: in this",
+///     "white: empty black",
+///     "black: in white or in blue",
+/// ])
+/// .unwrap();
+///
+/// let answer = puzzle.answer().expect("one box holds the prize in every solution");
+/// assert_eq!(puzzle.boxes()[answer].name, "white");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParlorPuzzle {
+    boxes: Vec<PrizeBox>,
+    /// Fewest boxes whose statements are all true
+    min_true_boxes: usize,
+    /// Fewest boxes whose statements are all false
+    min_false_boxes: usize,
+}
+
+impl ParlorPuzzle {
+    /// Creates a puzzle under the game's rules: at least one true and one false box.
+    pub fn new(boxes: Vec<PrizeBox>) -> Self {
+        Self {
+            boxes,
+            min_true_boxes: 1,
+            min_false_boxes: 1,
+        }
+    }
+
+    /// Parses a puzzle from one description per box, such as `blue: in this; true white`.
+    ///
+    /// A description is the box's name, a colon, and its statements in the
+    /// [notation](self) separated by semicolons. Names are matched case-insensitively.
+    ///
+    /// # Parameters
+    ///
+    /// * `boxes` - The description of each box
+    ///
+    /// # Returns
+    ///
+    /// The puzzle, or an error naming the box whose description is invalid
+    pub fn parse<S: AsRef<str>>(boxes: &[S]) -> Result<Self> {
+        let mut descriptions = Vec::with_capacity(boxes.len());
+        for description in boxes {
+            let description = description.as_ref();
+            let Some((name, statements)) = description.split_once(':') else {
+                anyhow::bail!("Invalid box '{}': expected NAME: STATEMENTS", description);
+            };
+            descriptions.push((name.trim().to_lowercase(), statements));
+        }
+        let names: Vec<&str> = descriptions.iter().map(|(name, _)| name.as_str()).collect();
+        if let Some((index, name)) = names
+            .iter()
+            .enumerate()
+            .find(|&(index, name)| name.is_empty() || names[..index].contains(name))
+        {
+            anyhow::bail!(
+                "Invalid box {}: '{}' is empty or named twice",
+                index + 1,
+                name
+            );
+        }
+
+        let mut parsed = Vec::with_capacity(descriptions.len());
+        for (index, (name, statements)) in descriptions.iter().enumerate() {
+            let statements = statements
+                .split(';')
+                .filter(|statement| !statement.trim().is_empty())
+                .map(|statement| parse_statement(statement, &names, index))
+                .collect::<std::result::Result<Vec<_>, String>>()
+                .map_err(|error| anyhow::anyhow!("Invalid box '{}': {}", name, error))?;
+            if statements.is_empty() {
+                anyhow::bail!("Invalid box '{}': it has no statements", name);
+            }
+            parsed.push(PrizeBox {
+                name: name.clone(),
+                statements,
+            });
+        }
+        Ok(Self::new(parsed))
+    }
+
+    /// Returns the puzzle with the fewest true and false boxes its rules promise.
+    ///
+    /// # Parameters
+    ///
+    /// * `min_true_boxes` - Fewest boxes whose statements are all true
+    /// * `min_false_boxes` - Fewest boxes whose statements are all false
+    pub fn with_rules(mut self, min_true_boxes: usize, min_false_boxes: usize) -> Self {
+        self.min_true_boxes = min_true_boxes;
+        self.min_false_boxes = min_false_boxes;
+        self
+    }
+
+    /// Returns the boxes in order.
+    pub fn boxes(&self) -> &[PrizeBox] {
+        &self.boxes
+    }
+
+    /// Returns every consistent reading of the puzzle, by prize box and then by the
+    /// truth values of the statements.
+    ///
+    /// # Returns
+    ///
+    /// The solutions, or an error if the puzzle has too many statements to try every
+    /// combination of truth values
+    pub fn solutions(&self) -> Result<Vec<ParlorSolution>> {
+        let total: usize = self.boxes.iter().map(|b| b.statements.len()).sum();
+        if total > MAX_STATEMENTS {
+            anyhow::bail!(
+                "the puzzle has {} statements; at most {} are supported",
+                total,
+                MAX_STATEMENTS
+            );
+        }
+
+        let mut solutions = Vec::new();
+        for prize in 0..self.boxes.len() {
+            for mask in 0..1u32 << total {
+                let truths = self.split_truths(mask);
+                if self.is_consistent(prize, &truths) {
+                    solutions.push(ParlorSolution { prize, truths });
+                }
+            }
+        }
+        Ok(solutions)
+    }
+
+    /// Returns the box that holds the prize in every solution, or `None` if the
+    /// puzzle has no solution or several boxes could hold it.
+    pub fn answer(&self) -> Option<usize> {
+        let solutions = self.solutions().ok()?;
+        let prize = solutions.first()?.prize;
+        solutions
+            .iter()
+            .all(|solution| solution.prize == prize)
+            .then_some(prize)
+    }
+
+    /// Groups the bits of `mask`, one per statement, by box.
+    fn split_truths(&self, mask: u32) -> Vec<Vec<bool>> {
+        let mut bit = 0;
+        self.boxes
+            .iter()
+            .map(|prize_box| {
+                let truths = (bit..bit + prize_box.statements.len())
+                    .map(|bit| mask & (1 << bit) != 0)
+                    .collect();
+                bit += prize_box.statements.len();
+                truths
+            })
+            .collect()
+    }
+
+    /// Returns `true` if every statement is true exactly when it holds, and the
+    /// rules' counts of true and false boxes are met.
+    fn is_consistent(&self, prize: usize, truths: &[Vec<bool>]) -> bool {
+        let statements = self.boxes.iter().map(|prize_box| &prize_box.statements);
+        let holds = statements.zip(truths).all(|(statements, box_truths)| {
+            statements
+                .iter()
+                .zip(box_truths)
+                .all(|(statement, &truth)| evaluate(statement, prize, truths) == truth)
+        });
+        if !holds {
+            return false;
+        }
+
+        let boxes_all = |value: bool| {
+            truths
+                .iter()
+                .filter(|truths| truths.iter().all(|&truth| truth == value))
+                .count()
+        };
+        boxes_all(true) >= self.min_true_boxes && boxes_all(false) >= self.min_false_boxes
+    }
+}
+
+/// Returns whether a statement holds with the prize in box `prize` and the given
+/// truth values of every statement.
+fn evaluate(statement: &Statement, prize: usize, truths: &[Vec<bool>]) -> bool {
+    match statement {
+        Statement::PrizeIn(index) => prize == *index,
+        Statement::BoxTrue(index) => truths[*index].iter().all(|&truth| truth),
+        Statement::BoxFalse(index) => truths[*index].iter().all(|&truth| !truth),
+        Statement::Not(inner) => !evaluate(inner, prize, truths),
+        Statement::All(inner) => inner.iter().all(|inner| evaluate(inner, prize, truths)),
+        Statement::Any(inner) => inner.iter().any(|inner| evaluate(inner, prize, truths)),
+    }
+}
+
+/// Parses one statement of the box at index `this`, resolving box names in `names`.
+fn parse_statement(
+    text: &str,
+    names: &[&str],
+    this: usize,
+) -> std::result::Result<Statement, String> {
+    let spaced = text.replace('(', " ( ").replace(')', " ) ");
+    let tokens: Vec<String> = spaced.split_whitespace().map(str::to_lowercase).collect();
+    let mut parser = StatementParser {
+        tokens: &tokens,
+        next: 0,
+        names,
+        this,
+    };
+    let statement = parser.any()?;
+    match parser.tokens.get(parser.next) {
+        None => Ok(statement),
+        Some(token) => Err(format!("unexpected '{}' in '{}'", token, text.trim())),
+    }
+}
+
+/// A recursive descent parser over the words of a statement.
+struct StatementParser<'a> {
+    tokens: &'a [String],
+    /// Index of the next token to read
+    next: usize,
+    names: &'a [&'a str],
+    /// Index of the box bearing the statement
+    this: usize,
+}
+
+impl<'a> StatementParser<'a> {
+    /// Reads the next token, if any.
+    fn take(&mut self) -> Option<&'a str> {
+        let token = self.tokens.get(self.next)?;
+        self.next += 1;
+        Some(token)
+    }
+
+    /// Reads the next token if it is `expected`.
+    fn accept(&mut self, expected: &str) -> bool {
+        let found = self
+            .tokens
+            .get(self.next)
+            .is_some_and(|token| token == expected);
+        self.next += usize::from(found);
+        found
+    }
+
+    /// Parses statements joined by `or`.
+    fn any(&mut self) -> std::result::Result<Statement, String> {
+        let mut inner = vec![self.all()?];
+        while self.accept("or") {
+            inner.push(self.all()?);
+        }
+        Ok(if inner.len() == 1 {
+            inner.remove(0)
+        } else {
+            Statement::Any(inner)
+        })
+    }
+
+    /// Parses statements joined by `and`.
+    fn all(&mut self) -> std::result::Result<Statement, String> {
+        let mut inner = vec![self.unary()?];
+        while self.accept("and") {
+            inner.push(self.unary()?);
+        }
+        Ok(if inner.len() == 1 {
+            inner.remove(0)
+        } else {
+            Statement::All(inner)
+        })
+    }
+
+    /// Parses a negated or parenthesized statement, or a claim about one box.
+    fn unary(&mut self) -> std::result::Result<Statement, String> {
+        if self.accept("not") {
+            return Ok(Statement::Not(Box::new(self.unary()?)));
+        }
+        if self.accept("(") {
+            let statement = self.any()?;
+            if !self.accept(")") {
+                return Err("unmatched '('".to_string());
+            }
+            return Ok(statement);
+        }
+
+        let claim: fn(usize) -> Statement = match self.take() {
+            Some("in") => Statement::PrizeIn,
+            Some("empty") => |index| Statement::Not(Box::new(Statement::PrizeIn(index))),
+            Some("true") => Statement::BoxTrue,
+            Some("false") => Statement::BoxFalse,
+            Some(word) => {
+                return Err(format!(
+                    "unknown claim '{}', expected in, empty, true, false or not",
+                    word
+                ));
+            }
+            None => return Err("a statement ends early".to_string()),
+        };
+        let index = match self.take() {
+            Some("this") => self.this,
+            Some(name) => self
+                .names
+                .iter()
+                .position(|known| *known == name)
+                .ok_or_else(|| format!("no box is named '{}'", name))?,
+            None => return Err("a claim is missing its box".to_string()),
+        };
+        Ok(claim(index))
+    }
+}
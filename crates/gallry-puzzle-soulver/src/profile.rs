@@ -0,0 +1,147 @@
+//! Timing every solving strategy on one puzzle.
+//!
+//! The automatic choice between strategies is a rule of thumb. A profile runs each
+//! strategy that applies to the puzzle under a time budget and reports how long it
+//! took and how much work it skipped, so a user can pick one with
+//! [`WordGenerator::set_strategy`] and the rule can be tuned against real puzzles.
+
+use crate::{SolveStats, Strategy, WordGenerator, WordIter};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Combinations examined between checks of the time budget
+const CHECK_EVERY: u64 = 1 << 10;
+
+/// How one strategy fared in a [`SolverProfile`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StrategyTiming {
+    /// The strategy that was run
+    pub strategy: Strategy,
+    /// Time spent, including building any candidate list or narrowed slots
+    pub elapsed: Duration,
+    /// Matches found before finishing or running out of time
+    pub matches: usize,
+    /// Combinations examined and pruned, and dictionary hits
+    pub stats: SolveStats,
+    /// Whether the solve finished within the budget
+    pub completed: bool,
+}
+
+/// Timings of every strategy that applies to a puzzle, from
+/// [`WordGenerator::profile`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SolverProfile {
+    /// Combinations the slots produce before filtering
+    pub combinations: u128,
+    /// The strategy a solve would choose automatically
+    pub automatic: Strategy,
+    /// One timing per applicable strategy, in the order of [`Strategy::ALL`]
+    pub timings: Vec<StrategyTiming>,
+}
+
+impl SolverProfile {
+    /// Returns the quickest strategy that finished within the budget, if any did.
+    pub fn fastest(&self) -> Option<&StrategyTiming> {
+        self.timings
+            .iter()
+            .filter(|timing| timing.completed)
+            .min_by_key(|timing| timing.elapsed)
+    }
+}
+
+impl fmt::Display for SolverProfile {
+    /// Writes one line per strategy under a summary of the puzzle.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} combinations; automatic strategy: {}",
+            self.combinations, self.automatic
+        )?;
+        for timing in &self.timings {
+            let elapsed = format!("{:.1?}", timing.elapsed);
+            writeln!(
+                f,
+                "{:<17} {:>10}{} {:>8} matches {:>12} examined {:>12} pruned",
+                timing.strategy.to_string(),
+                elapsed,
+                if timing.completed { " " } else { "+" },
+                timing.matches,
+                timing.stats.examined,
+                timing.stats.pruned,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl WordGenerator {
+    /// Runs every strategy that applies to the puzzle and reports how each fared.
+    ///
+    /// Each strategy counts matches as [`count_matches`](Self::count_matches) does,
+    /// stopping early once it has run for `budget`; such timings are marked
+    /// incomplete. Strategies that could miss matches under the generator's matching
+    /// options are left out, as they would enumerate anyway. The work is not added to
+    /// [`stats`](Self::stats).
+    ///
+    /// # Parameters
+    ///
+    /// * `budget` - Longest time to spend on each strategy
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gallry_puzzle_soulver::{Slot, WordGenerator};
+    /// use std::time::Duration;
+    ///
+    /// let generator = WordGenerator::with_slots(vec![Slot::any(); 3]);
+    /// let profile = generator.profile(Duration::from_secs(1));
+    ///
+    /// let fastest = profile.fastest().expect("a strategy finished");
+    /// println!("{}", profile);
+    /// println!("fastest: {}", fastest.strategy);
+    /// ```
+    pub fn profile(&self, budget: Duration) -> SolverProfile {
+        let slots = self.resolved_slots();
+        let timings = Strategy::ALL
+            .into_iter()
+            .filter(|&strategy| self.choose_strategy(&slots, Some(strategy)) == strategy)
+            .map(|strategy| self.time_strategy(strategy, budget))
+            .collect();
+
+        SolverProfile {
+            combinations: crate::combination::count(&slots),
+            automatic: self.choose_strategy(&slots, None),
+            timings,
+        }
+    }
+
+    /// Counts matches with one strategy until done or out of time.
+    fn time_strategy(&self, strategy: Strategy, budget: Duration) -> StrategyTiming {
+        let started = Instant::now();
+        let mut words = WordIter::with_strategy(self, Some(strategy));
+        let mut matches = 0;
+        let mut completed = true;
+        // Stops at every match, and now and then without one to look at the clock
+        let judge = |generator: &WordGenerator, word: &str, stats: &mut SolveStats| {
+            let found = generator.is_match(word, stats);
+            (found || stats.examined.is_multiple_of(CHECK_EVERY)).then_some(found)
+        };
+        while let Some(found) = words.next_judged(judge) {
+            matches += usize::from(found);
+            if !words.done && started.elapsed() >= budget {
+                completed = false;
+                break;
+            }
+        }
+
+        trace_event!(debug, %strategy, matches, completed, "profiled a strategy");
+        StrategyTiming {
+            strategy,
+            elapsed: started.elapsed(),
+            matches,
+            // Taken, so dropping the iterator leaves the generator's totals alone
+            stats: std::mem::take(&mut words.stats),
+            completed,
+        }
+    }
+}
@@ -0,0 +1,140 @@
+//! Pronunciation tables for accurate rhyme detection and syllable counts.
+//!
+//! A [`Pronunciations`] table is loaded from text in the CMU Pronouncing Dictionary
+//! format: one `WORD  PHONE PHONE ...` entry per line, with stress digits on vowel
+//! phones, alternative pronunciations written as `WORD(1)`, and `;;;` comments.
+
+use crate::short::ShortString;
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+
+/// A mapping from words to their phone sequences.
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::Pronunciations;
+///
+/// let table = Pronunciations::from_text("EIGHT  EY1 T\nLATE  L EY1 T\nLAT  L AA1 T\n");
+/// assert_eq!(table.rhymes("eight", "late"), Some(true));
+/// assert_eq!(table.rhymes("late", "lat"), Some(false));
+/// assert_eq!(table.rhymes("late", "unknown"), None);
+/// assert_eq!(table.syllables("eight"), Some(1));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Pronunciations {
+    /// Phone sequences keyed by lowercased word, in the order they were loaded
+    phones: HashMap<String, Vec<Vec<String>>>,
+}
+
+impl Pronunciations {
+    /// Creates an empty table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses a table in the CMU Pronouncing Dictionary format.
+    ///
+    /// Blank lines, lines starting with `;;;` and lines without phones are ignored.
+    pub fn from_text(text: &str) -> Self {
+        let mut table = Self::new();
+        for line in text.lines() {
+            if line.starts_with(";;;") {
+                continue;
+            }
+
+            let mut fields = line.split_whitespace();
+            let Some(word) = fields.next() else {
+                continue;
+            };
+            let phones: Vec<String> = fields.map(str::to_string).collect();
+            if phones.is_empty() {
+                continue;
+            }
+
+            // Alternative pronunciations are numbered, as in "READ(1)"
+            let word = word.split_once('(').map_or(word, |(word, _)| word);
+            table
+                .phones
+                .entry(word.to_lowercase())
+                .or_default()
+                .push(phones);
+        }
+        table
+    }
+
+    /// Reads and parses a pronunciation table file.
+    ///
+    /// # Parameters
+    ///
+    /// * `path` - Path to the table, such as a copy of `cmudict.dict`
+    ///
+    /// # Returns
+    ///
+    /// The parsed table, or an error if the file could not be read
+    pub fn from_path(path: &str) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read pronunciations from {}", path))?;
+        Ok(Self::from_text(&content))
+    }
+
+    /// Returns every known pronunciation of a word, ignoring case.
+    pub fn pronunciations(&self, word: &str) -> &[Vec<String>] {
+        self.phones
+            .get(&*ShortString::lowercase(word))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Returns whether two words rhyme, or `None` if either is not in the table.
+    ///
+    /// Words rhyme when some pronunciations of each share every phone from the last
+    /// stressed vowel onwards.
+    pub fn rhymes(&self, a: &str, b: &str) -> Option<bool> {
+        let a = self.pronunciations(a);
+        let b = self.pronunciations(b);
+        if a.is_empty() || b.is_empty() {
+            return None;
+        }
+
+        Some(
+            a.iter()
+                .any(|a| b.iter().any(|b| rhyming_part(a) == rhyming_part(b))),
+        )
+    }
+
+    /// Returns the number of syllables in a word's first pronunciation, or `None` if it
+    /// is not in the table.
+    ///
+    /// Every vowel phone carries a stress digit, so syllables are counted from those.
+    pub fn syllables(&self, word: &str) -> Option<usize> {
+        let phones = self.pronunciations(word).first()?;
+        Some(
+            phones
+                .iter()
+                .filter(|phone| phone.ends_with(|c: char| c.is_ascii_digit()))
+                .count(),
+        )
+    }
+
+    /// Returns the number of words in the table.
+    pub fn len(&self) -> usize {
+        self.phones.len()
+    }
+
+    /// Returns `true` if the table has no words.
+    pub fn is_empty(&self) -> bool {
+        self.phones.is_empty()
+    }
+}
+
+/// Returns the phones from the last stressed vowel to the end.
+///
+/// Primary stress is preferred; words without any stress marks rhyme on all phones.
+fn rhyming_part(phones: &[String]) -> &[String] {
+    let last_with = |stresses: &[char]| phones.iter().rposition(|phone| phone.ends_with(stresses));
+    let start = last_with(&['1'])
+        .or_else(|| last_with(&['2', '0']))
+        .unwrap_or(0);
+    &phones[start..]
+}
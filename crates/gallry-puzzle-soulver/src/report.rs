@@ -0,0 +1,267 @@
+//! Formatted solve write-ups selectable with `--report`.
+//!
+//! A report is assembled once as a list of sections, each holding a table and/or
+//! a bullet list, and then rendered as either Markdown or a standalone HTML page.
+
+use crate::output::{self, Solution};
+use gallry_puzzle_soulver::WordGenerator;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Instant;
+
+/// Number of candidates listed in the report, best scoring first
+const TOP_CANDIDATES: usize = 20;
+
+/// The markup a report is rendered in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReportFormat {
+    /// GitHub-flavored Markdown
+    Markdown,
+    /// A standalone HTML page
+    Html,
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "md" | "markdown" => Ok(ReportFormat::Markdown),
+            "html" => Ok(ReportFormat::Html),
+            _ => Err(format!(
+                "unknown report format '{}', expected md or html",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ReportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ReportFormat::Markdown => "md",
+            ReportFormat::Html => "html",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A titled part of the report.
+struct Section {
+    title: &'static str,
+    /// Column headers followed by rows of cells
+    table: Option<(Vec<&'static str>, Vec<Vec<String>>)>,
+    /// Bullet points shown after the table
+    items: Vec<String>,
+}
+
+/// Builds a report of the solutions and renders it in the requested format.
+///
+/// # Parameters
+///
+/// * `format` - The markup to render
+/// * `generator` - The generator the solutions came from
+/// * `solutions` - The solutions to report on
+/// * `started` - When solving started, used for timing statistics
+pub fn render(
+    format: ReportFormat,
+    generator: &WordGenerator,
+    solutions: impl Iterator<Item = Solution>,
+    started: Instant,
+) -> String {
+    let solutions: Vec<Solution> = solutions.collect();
+    let elapsed = started.elapsed();
+    let sections = vec![
+        puzzle_section(generator),
+        slots_section(generator, &solutions),
+        candidates_section(generator, &solutions),
+        statistics_section(generator, &solutions, elapsed.as_millis()),
+    ];
+
+    match format {
+        ReportFormat::Markdown => render_markdown(&sections),
+        ReportFormat::Html => render_html(&sections),
+    }
+}
+
+fn puzzle_section(generator: &WordGenerator) -> Section {
+    let rows = generator
+        .slots()
+        .iter()
+        .enumerate()
+        .map(|(position, slot)| vec![(position + 1).to_string(), slot.options().iter().collect()])
+        .collect();
+
+    let dictionaries: Vec<&str> = generator
+        .dictionaries()
+        .iter()
+        .filter(|dictionary| !dictionary.is_empty())
+        .map(|dictionary| dictionary.name())
+        .collect();
+    let dictionaries = if dictionaries.is_empty() {
+        "none (all combinations)".to_string()
+    } else {
+        format!(
+            "{} (policy: {})",
+            dictionaries.join(", "),
+            generator.dictionary_policy()
+        )
+    };
+
+    Section {
+        title: "Puzzle",
+        table: Some((vec!["Position", "Options"], rows)),
+        items: vec![format!("Dictionaries: {}", dictionaries)],
+    }
+}
+
+/// Splits every slot's options into those used by some match and those pruned.
+fn slots_section(generator: &WordGenerator, solutions: &[Solution]) -> Section {
+    let rows = generator
+        .slots()
+        .iter()
+        .enumerate()
+        .map(|(position, slot)| {
+            let (possible, pruned): (String, String) = slot.options().iter().partition(|c| {
+                solutions
+                    .iter()
+                    .any(|solution| solution.word_match.word.chars().nth(position) == Some(**c))
+            });
+            vec![(position + 1).to_string(), possible, pruned]
+        })
+        .collect();
+
+    Section {
+        title: "Slots",
+        table: Some((vec!["Position", "Possible", "Pruned"], rows)),
+        items: Vec::new(),
+    }
+}
+
+/// Lists the best candidates by positional letter frequency score.
+fn candidates_section(generator: &WordGenerator, solutions: &[Solution]) -> Section {
+    let stats = output::letter_stats(generator);
+
+    let mut scored: Vec<(&Solution, Option<f64>)> = solutions
+        .iter()
+        .map(|solution| {
+            let score = stats
+                .as_ref()
+                .map(|stats| stats.score(&solution.word_match.word));
+            (solution, score)
+        })
+        .collect();
+    scored.sort_by(|a, b| b.1.unwrap_or(0.0).total_cmp(&a.1.unwrap_or(0.0)));
+
+    let rows = scored
+        .into_iter()
+        .take(TOP_CANDIDATES)
+        .enumerate()
+        .map(|(index, (solution, score))| {
+            let word_match = &solution.word_match;
+            vec![
+                (index + 1).to_string(),
+                word_match.display_word().to_string(),
+                score.map_or_else(|| "-".to_string(), |score| format!("{:.3}", score)),
+                word_match
+                    .rank
+                    .map_or_else(|| "-".to_string(), |rank| rank.to_string()),
+                solution
+                    .definition
+                    .clone()
+                    .unwrap_or_else(|| "-".to_string()),
+            ]
+        })
+        .collect();
+
+    Section {
+        title: "Top candidates",
+        table: Some((vec!["#", "Word", "Score", "Rank", "Definition"], rows)),
+        items: Vec::new(),
+    }
+}
+
+fn statistics_section(
+    generator: &WordGenerator,
+    solutions: &[Solution],
+    elapsed_ms: u128,
+) -> Section {
+    let combinations = generator.combination_count();
+    let pruned = output::pruned_percent(combinations, solutions.len());
+
+    Section {
+        title: "Statistics",
+        table: None,
+        items: vec![
+            format!("Combinations: {}", combinations),
+            format!("Matches: {}", solutions.len()),
+            format!("Pruned: {:.1}% of combinations", pruned),
+            format!("Elapsed: {} ms", elapsed_ms),
+        ],
+    }
+}
+
+fn render_markdown(sections: &[Section]) -> String {
+    let escape = |cell: &str| cell.replace('|', "\\|");
+
+    let mut out = String::from("# Puzzle report\n");
+    for section in sections {
+        out.push_str(&format!("\n## {}\n\n", section.title));
+        if let Some((headers, rows)) = &section.table {
+            out.push_str(&format!("| {} |\n", headers.join(" | ")));
+            out.push_str(&format!("|{}\n", "---|".repeat(headers.len())));
+            for row in rows {
+                let cells: Vec<String> = row.iter().map(|cell| escape(cell)).collect();
+                out.push_str(&format!("| {} |\n", cells.join(" | ")));
+            }
+            if !section.items.is_empty() {
+                out.push('\n');
+            }
+        }
+        for item in &section.items {
+            out.push_str(&format!("- {}\n", escape(item)));
+        }
+    }
+    out
+}
+
+fn render_html(sections: &[Section]) -> String {
+    let mut out = String::from(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>Puzzle report</title>\n</head>\n<body>\n<h1>Puzzle report</h1>\n",
+    );
+    for section in sections {
+        out.push_str(&format!("<h2>{}</h2>\n", section.title));
+        if let Some((headers, rows)) = &section.table {
+            out.push_str("<table>\n<tr>");
+            for header in headers {
+                out.push_str(&format!("<th>{}</th>", escape_html(header)));
+            }
+            out.push_str("</tr>\n");
+            for row in rows {
+                out.push_str("<tr>");
+                for cell in row {
+                    out.push_str(&format!("<td>{}</td>", escape_html(cell)));
+                }
+                out.push_str("</tr>\n");
+            }
+            out.push_str("</table>\n");
+        }
+        if !section.items.is_empty() {
+            out.push_str("<ul>\n");
+            for item in &section.items {
+                out.push_str(&format!("<li>{}</li>\n", escape_html(item)));
+            }
+            out.push_str("</ul>\n");
+        }
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,68 @@
+//! Spelling-based rhyme detection.
+//!
+//! Without a pronunciation table, two words are taken to rhyme when they share the
+//! spelling from their last vowel group to the end, ignoring a silent final `e` when
+//! locating that group. This is right for most short riddle answers ("light" and
+//! "night", "bake" and "cake") but misses rhymes spelled differently ("eight" and
+//! "late"); the `pronunciation` feature handles those.
+
+use crate::short::{Chars, lowercase_chars};
+
+/// Returns the part of a word that has to match for another word to rhyme with it.
+///
+/// This is the last vowel group and everything after it, where `y` counts as a vowel
+/// except at the start of the word.
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::rhyme_key;
+///
+/// assert_eq!(rhyme_key("Light"), "ight");
+/// assert_eq!(rhyme_key("bake"), "ake");
+/// assert_eq!(rhyme_key("sky"), "y");
+/// ```
+pub fn rhyme_key(word: &str) -> String {
+    key_chars(word).into_iter().collect()
+}
+
+/// Returns the rhyme key as characters, on the stack for short words.
+fn key_chars(word: &str) -> Chars {
+    let mut chars = lowercase_chars(word);
+    let is_vowel = |index: usize| match chars[index] {
+        'a' | 'e' | 'i' | 'o' | 'u' => true,
+        'y' => index > 0,
+        _ => false,
+    };
+
+    // A final e after a consonant is silent, so the vowel before it carries the rhyme
+    let mut end = chars.len();
+    if end > 2 && chars[end - 1] == 'e' && !is_vowel(end - 2) {
+        end -= 1;
+    }
+
+    let Some(last) = (0..end).rev().find(|&index| is_vowel(index)) else {
+        return chars;
+    };
+    let start = (0..=last)
+        .rev()
+        .take_while(|&index| is_vowel(index))
+        .last()
+        .unwrap_or(last);
+    chars.drain(..start);
+    chars
+}
+
+/// Returns `true` if two words rhyme by spelling, ignoring case.
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::rhymes;
+///
+/// assert!(rhymes("table", "cable"));
+/// assert!(!rhymes("bake", "back"));
+/// ```
+pub fn rhymes(a: &str, b: &str) -> bool {
+    key_chars(a) == key_chars(b)
+}
@@ -0,0 +1,93 @@
+//! Solving by scanning the dictionary instead of enumerating combinations.
+//!
+//! A puzzle with many slots of many options has far more combinations than the
+//! dictionary has words of the right length, so checking each of those words against
+//! the slots does less work than spelling every combination. Each slot is reduced to a
+//! bitmask of its ASCII options, so most words are rejected by a few mask tests before
+//! any option is looked up.
+//!
+//! Scanning only finds words spelled exactly as a dictionary entry, so it is used only
+//! when no matching option could find an entry spelled otherwise.
+
+use crate::slot_index::SlotIndex;
+use crate::{Normalization, Slot, WordGenerator};
+
+/// How many times more combinations than dictionary words make scanning worthwhile
+const SCAN_FACTOR: u128 = 4;
+
+impl WordGenerator {
+    /// Returns `true` if scanning the dictionaries finds exactly the matches that
+    /// enumerating the slots would.
+    pub(crate) fn can_scan(&self, slots: &[Slot]) -> bool {
+        let ascii = slots
+            .iter()
+            .all(|slot| slot.options().iter().all(char::is_ascii));
+        // A repeated option spells its words twice, but a scan finds each entry once
+        let distinct = slots.iter().all(|slot| {
+            let options = slot.options();
+            options
+                .iter()
+                .enumerate()
+                .all(|(i, c)| !options[..i].contains(c))
+        });
+        // Case folding, stems and normalization can match a combination to an entry
+        // spelled differently, which a scan of the entries cannot find
+        distinct
+            && !self.match_options.case_insensitive
+            && !self.match_options.stemming
+            && self
+                .dictionaries
+                .iter()
+                .any(|dictionary| !dictionary.is_empty())
+            && self
+                .dictionaries
+                .iter()
+                .all(|dictionary| ascii || dictionary.normalization() == Normalization::None)
+    }
+
+    /// Returns `true` if the slots are better solved by scanning the dictionaries.
+    pub(crate) fn prefers_scan(&self, slots: &[Slot]) -> bool {
+        if !self.can_scan(slots) {
+            return false;
+        }
+        // Compare against the whole dictionaries first, so small puzzles never pay
+        // for bucketing the words by length
+        let combinations = crate::combination::count(slots);
+        let words: usize = self.dictionaries.iter().map(|d| d.len()).sum();
+        if combinations <= SCAN_FACTOR * words as u128 {
+            return false;
+        }
+        let candidates: usize = self
+            .dictionaries
+            .iter()
+            .map(|dictionary| dictionary.words_of_len(slots.len()).count())
+            .sum();
+        combinations > SCAN_FACTOR * candidates as u128
+    }
+
+    /// Returns the dictionary words the resolved slots can spell, in enumeration order
+    /// and without duplicates, for [`evaluate`](Self::evaluate) to judge.
+    pub(crate) fn scan_candidates(&self) -> Vec<&str> {
+        let slots = self.slot_index();
+
+        // Option indices sort in enumeration order, even for puzzles too long to number
+        let mut candidates: Vec<(Vec<usize>, &str)> = self
+            .dictionaries
+            .iter()
+            .flat_map(|dictionary| dictionary.words_of_len(slots.len()))
+            .filter(|word| word.chars().zip(slots).all(|(c, slot)| slot.contains(c)))
+            .map(|word| (option_indices(slots, word), word))
+            .collect();
+        candidates.sort_unstable();
+        candidates.dedup_by(|a, b| a.0 == b.0);
+        candidates.into_iter().map(|(_, word)| word).collect()
+    }
+}
+
+/// Returns the option chosen in each slot to spell `word`, which the slots must allow.
+fn option_indices(slots: &[SlotIndex], word: &str) -> Vec<usize> {
+    word.chars()
+        .zip(slots)
+        .map(|(c, slot)| slot.position(c).unwrap_or_default())
+        .collect()
+}
@@ -0,0 +1,48 @@
+//! Stack storage for the short words examined while solving.
+//!
+//! Filtering folds case and splits words into characters for every candidate, and
+//! puzzle answers are short, so these copies live on the stack up to
+//! [`INLINE_CHARS`] characters and only longer words fall back to the heap.
+
+use smallvec::SmallVec;
+use std::ops::Deref;
+
+/// Characters kept on the stack before a copy spills to the heap
+pub(crate) const INLINE_CHARS: usize = 24;
+
+/// Bytes kept on the stack before a copy spills to the heap, enough for
+/// [`INLINE_CHARS`] characters of any script
+const INLINE_BYTES: usize = INLINE_CHARS * 4;
+
+/// The characters of a word, for analyses that index into it.
+pub(crate) type Chars = SmallVec<[char; INLINE_CHARS]>;
+
+/// A copy of a word, on the stack when it is short.
+pub(crate) struct ShortString(SmallVec<[u8; INLINE_BYTES]>);
+
+impl ShortString {
+    /// Returns the word in lowercase, as [`str::to_lowercase`] would.
+    pub(crate) fn lowercase(word: &str) -> Self {
+        let mut bytes = SmallVec::new();
+        if word.is_ascii() {
+            bytes.extend(word.bytes().map(|byte| byte.to_ascii_lowercase()));
+        } else {
+            // Final sigma and other context-dependent mappings need the full algorithm
+            bytes.extend_from_slice(word.to_lowercase().as_bytes());
+        }
+        Self(bytes)
+    }
+}
+
+impl Deref for ShortString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        std::str::from_utf8(&self.0).expect("built from whole strings")
+    }
+}
+
+/// Returns the characters of a word in lowercase.
+pub(crate) fn lowercase_chars(word: &str) -> Chars {
+    ShortString::lowercase(word).chars().collect()
+}
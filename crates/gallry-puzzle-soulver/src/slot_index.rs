@@ -0,0 +1,78 @@
+//! Constant-time lookups of a slot's options.
+//!
+//! Slots keep their options in a list, so asking whether a slot allows a character,
+//! or which option it is, means searching that list. Wildcard slots hold the whole
+//! alphabet, and scanning the dictionary asks for every character of every word, so
+//! a generator builds a table per slot once and answers from it. Each table keeps a
+//! bitmask of its ASCII options too, so most characters a slot rejects cost a single
+//! mask test.
+
+use crate::{Slot, WordGenerator};
+use std::collections::HashMap;
+
+/// Marks an ASCII character that is not an option of the slot
+const ABSENT: u32 = u32::MAX;
+
+/// The options of one slot, indexed by character.
+#[derive(Clone, Debug)]
+pub(crate) struct SlotIndex {
+    /// Bit `c` is set when the ASCII character `c` is an option
+    mask: u128,
+    /// Option index of each ASCII character, or `ABSENT`
+    ascii: [u32; 128],
+    /// Option index of each character outside ASCII
+    other: HashMap<char, usize>,
+}
+
+impl SlotIndex {
+    /// Builds the table for a slot; a repeated option maps to its first position.
+    pub(crate) fn new(slot: &Slot) -> Self {
+        let mut index = Self {
+            mask: 0,
+            ascii: [ABSENT; 128],
+            other: HashMap::new(),
+        };
+        for (position, &c) in slot.options().iter().enumerate().rev() {
+            if c.is_ascii() {
+                index.mask |= 1 << c as u32;
+            }
+            if c.is_ascii() && position < ABSENT as usize {
+                index.ascii[c as usize] = position as u32;
+            } else {
+                index.other.insert(c, position);
+            }
+        }
+        index
+    }
+
+    /// Returns the position of `c` among the slot's options.
+    pub(crate) fn position(&self, c: char) -> Option<usize> {
+        if c.is_ascii() {
+            match self.ascii[c as usize] {
+                ABSENT => self.other.get(&c).copied(),
+                position => Some(position as usize),
+            }
+        } else {
+            self.other.get(&c).copied()
+        }
+    }
+
+    /// Returns `true` if `c` is one of the slot's options.
+    pub(crate) fn contains(&self, c: char) -> bool {
+        if c.is_ascii() {
+            self.mask & (1 << c as u32) != 0
+        } else {
+            self.other.contains_key(&c)
+        }
+    }
+}
+
+impl WordGenerator {
+    /// Returns a lookup table for each resolved slot, building them on first use.
+    pub(crate) fn slot_index(&self) -> &[SlotIndex] {
+        self.slot_index.get_or_init(|| {
+            trace_event!(trace, slots = self.slots.len(), "indexed slot options");
+            self.resolved_slots().iter().map(SlotIndex::new).collect()
+        })
+    }
+}
@@ -0,0 +1,80 @@
+//! Counters describing how much work a solve did.
+//!
+//! Solving loops count into a local [`SolveStats`] and add it to the generator's
+//! totals when they finish, so the counters cost nothing per combination even when
+//! several threads share one generator.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Work done by a generator's solves.
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::{Dictionary, LoadOptions, Slot, WordGenerator};
+///
+/// let dictionary = Dictionary::from_text("cat\ncot\n", &LoadOptions::default());
+/// let generator = WordGenerator::with_dictionary(
+///     vec![Slot::new(vec!['c', 'b']), Slot::new(vec!['a', 'o']), Slot::new(vec!['t'])],
+///     dictionary,
+/// );
+///
+/// assert_eq!(generator.iter().count(), 2);
+/// let stats = generator.stats();
+/// assert_eq!(stats.examined, 4);
+/// assert_eq!(stats.dictionary_hits, 2);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SolveStats {
+    /// Combinations built and checked against the constraints and dictionaries
+    pub examined: u64,
+    /// Combinations skipped without being built because no dictionary word could be
+    /// spelled with them, by a dictionary scan or a positional index; enumerating
+    /// examines every combination it reaches
+    pub pruned: u64,
+    /// Successful dictionary lookups, counting a word once per dictionary containing it
+    pub dictionary_hits: u64,
+}
+
+impl SolveStats {
+    /// Adds another set of counts to this one.
+    pub fn add(&mut self, other: &SolveStats) {
+        self.examined += other.examined;
+        self.pruned += other.pruned;
+        self.dictionary_hits += other.dictionary_hits;
+    }
+}
+
+/// Running totals shared by every solve of a generator.
+#[derive(Debug, Default)]
+pub(crate) struct StatsCounter {
+    examined: AtomicU64,
+    pruned: AtomicU64,
+    dictionary_hits: AtomicU64,
+}
+
+impl StatsCounter {
+    /// Adds the counts of a finished (or abandoned) solve.
+    pub(crate) fn record(&self, stats: &SolveStats) {
+        self.examined.fetch_add(stats.examined, Ordering::Relaxed);
+        self.pruned.fetch_add(stats.pruned, Ordering::Relaxed);
+        self.dictionary_hits
+            .fetch_add(stats.dictionary_hits, Ordering::Relaxed);
+    }
+
+    /// Returns the totals so far.
+    pub(crate) fn snapshot(&self) -> SolveStats {
+        SolveStats {
+            examined: self.examined.load(Ordering::Relaxed),
+            pruned: self.pruned.load(Ordering::Relaxed),
+            dictionary_hits: self.dictionary_hits.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Sets every total back to zero.
+    pub(crate) fn reset(&self) {
+        self.examined.store(0, Ordering::Relaxed);
+        self.pruned.store(0, Ordering::Relaxed);
+        self.dictionary_hits.store(0, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,74 @@
+//! A small suffix-stripping stemmer used for stem-aware dictionary matching.
+//!
+//! Rather than reducing words to an abstract stem, [`stem_candidates`] proposes the
+//! plausible base words a candidate could have been inflected from. Each proposal is
+//! then looked up in the dictionary, so only real words are ever reported as stems.
+
+/// Suffix rewrites tried in order, as `(suffix, replacement)` pairs.
+const SUFFIX_RULES: &[(&str, &str)] = &[
+    ("ies", "y"),
+    ("ied", "y"),
+    ("ying", "ie"),
+    ("es", ""),
+    ("s", ""),
+    ("ed", ""),
+    ("ed", "e"),
+    ("ing", ""),
+    ("ing", "e"),
+    ("est", ""),
+    ("est", "e"),
+    ("er", ""),
+    ("er", "e"),
+    ("ly", ""),
+];
+
+/// Returns the base words a candidate may have been inflected from, most likely first.
+///
+/// The candidate itself is not included. Doubled final consonants left behind by
+/// stripping a suffix are also undone ("running" -> "run").
+///
+/// # Parameters
+///
+/// * `word` - The inflected candidate word
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::stem_candidates;
+///
+/// assert!(stem_candidates("running").contains(&"run".to_string()));
+/// assert!(stem_candidates("berries").contains(&"berry".to_string()));
+/// assert!(stem_candidates("hoped").contains(&"hope".to_string()));
+/// ```
+pub fn stem_candidates(word: &str) -> Vec<String> {
+    let mut candidates: Vec<String> = Vec::new();
+    let mut push = |candidate: String| {
+        if candidate.chars().count() >= 2 && candidate != word && !candidates.contains(&candidate) {
+            candidates.push(candidate);
+        }
+    };
+
+    for (suffix, replacement) in SUFFIX_RULES {
+        // "ss" endings such as "glass" are not plurals
+        if *suffix == "s" && word.ends_with("ss") {
+            continue;
+        }
+
+        let Some(stem) = word.strip_suffix(suffix) else {
+            continue;
+        };
+        push(format!("{}{}", stem, replacement));
+
+        if replacement.is_empty() {
+            let mut chars = stem.chars().rev();
+            if let (Some(last), Some(before)) = (chars.next(), chars.next())
+                && last == before
+                && !matches!(last, 'a' | 'e' | 'i' | 'o' | 'u' | 'l' | 's')
+            {
+                push(stem[..stem.len() - last.len_utf8()].to_string());
+            }
+        }
+    }
+
+    candidates
+}
@@ -0,0 +1,164 @@
+//! Choosing how a generator searches for matches.
+//!
+//! Every strategy finds the same matches in the same order; they differ only in how
+//! much work that takes. The generator picks one per solve unless a caller forces a
+//! choice with [`WordGenerator::set_strategy`], as benchmarks comparing them do.
+
+use crate::{Slot, WordGenerator};
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+/// An algorithm for finding the words a generator's slots spell.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Strategy {
+    /// Spell every combination of the slots and look each one up
+    Enumerate,
+    /// Check every dictionary word of the right length against the slots
+    DictScan,
+    /// Drop the options no dictionary word has at their position, then enumerate
+    PositionalIndex,
+}
+
+impl Strategy {
+    /// Every strategy, in the order [`profile`](WordGenerator::profile) runs them.
+    pub const ALL: [Strategy; 3] = [
+        Strategy::Enumerate,
+        Strategy::DictScan,
+        Strategy::PositionalIndex,
+    ];
+}
+
+impl FromStr for Strategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "enumerate" => Ok(Strategy::Enumerate),
+            "dict-scan" => Ok(Strategy::DictScan),
+            "positional-index" => Ok(Strategy::PositionalIndex),
+            _ => Err(format!(
+                "unknown strategy '{}', expected enumerate, dict-scan or positional-index",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Strategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Strategy::Enumerate => "enumerate",
+            Strategy::DictScan => "dict-scan",
+            Strategy::PositionalIndex => "positional-index",
+        })
+    }
+}
+
+/// How a solve will search, as decided by `WordGenerator::plan`.
+pub(crate) struct Plan<'a> {
+    /// The strategy to use
+    pub(crate) strategy: Strategy,
+    /// The slots to enumerate, narrowed by a positional index
+    pub(crate) slots: Cow<'a, [Slot]>,
+    /// Combinations of the slots before any narrowing
+    pub(crate) combinations: u128,
+}
+
+impl WordGenerator {
+    /// Forces the algorithm used to find matches, or restores the automatic choice.
+    ///
+    /// The automatic choice scans the dictionaries when the slots have many more
+    /// combinations than there are words to scan, and enumerates otherwise. A forced
+    /// [`DictScan`](Strategy::DictScan) or [`PositionalIndex`](Strategy::PositionalIndex)
+    /// falls back to enumeration when it could miss a match, such as with
+    /// case-insensitive or stem-aware matching, so results never depend on the strategy.
+    ///
+    /// [`advance`](Self::advance) and [`Cursor`](crate::Cursor) always enumerate, since
+    /// their positions count combinations.
+    ///
+    /// # Parameters
+    ///
+    /// * `strategy` - The algorithm to use, or `None` to let the generator choose
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gallry_puzzle_soulver::{Slot, Strategy, WordGenerator};
+    /// use std::collections::HashSet;
+    ///
+    /// let word_list: HashSet<String> = ["cat", "cot", "dog"].map(String::from).into();
+    /// let mut generator = WordGenerator::new(
+    ///     vec![Slot::new(vec!['c', 'd']), Slot::new(vec!['a', 'o']), Slot::new(vec!['t', 'g'])],
+    ///     Some(word_list),
+    /// );
+    /// let expected: Vec<String> = generator.iter().collect();
+    ///
+    /// generator.set_strategy(Some(Strategy::DictScan));
+    /// assert_eq!(generator.strategy(), Strategy::DictScan);
+    /// assert_eq!(generator.iter().collect::<Vec<_>>(), expected);
+    /// ```
+    pub fn set_strategy(&mut self, strategy: Option<Strategy>) {
+        self.strategy = strategy;
+    }
+
+    /// Returns the algorithm the next solve will use.
+    ///
+    /// This is the strategy set with [`set_strategy`](Self::set_strategy) when it applies
+    /// to the current slots and matching options, or else the automatic choice.
+    pub fn strategy(&self) -> Strategy {
+        self.choose_strategy(&self.resolved_slots(), self.strategy)
+    }
+
+    /// Returns the strategy for solving the given slots, given the one requested.
+    pub(crate) fn choose_strategy(&self, slots: &[Slot], requested: Option<Strategy>) -> Strategy {
+        match requested {
+            Some(Strategy::Enumerate) => Strategy::Enumerate,
+            Some(strategy) if self.can_scan(slots) => strategy,
+            Some(_) => Strategy::Enumerate,
+            None if self.prefers_scan(slots) => Strategy::DictScan,
+            None => Strategy::Enumerate,
+        }
+    }
+
+    /// Plans a solve with the requested strategy, or the automatic choice for `None`.
+    pub(crate) fn plan(&self, requested: Option<Strategy>) -> Plan<'_> {
+        let slots = self.resolved_slots();
+        let strategy = self.choose_strategy(&slots, requested);
+        trace_event!(debug, %strategy, "chose a solving strategy");
+        let combinations = crate::combination::count(&slots);
+        let slots = match strategy {
+            Strategy::PositionalIndex => Cow::Owned(self.positional_slots(&slots)),
+            _ => slots,
+        };
+        Plan {
+            strategy,
+            slots,
+            combinations,
+        }
+    }
+
+    /// Returns the slots without the options no dictionary word has at their position.
+    fn positional_slots(&self, slots: &[Slot]) -> Vec<Slot> {
+        let mut letters = vec![HashSet::new(); slots.len()];
+        let words = self
+            .dictionaries
+            .iter()
+            .flat_map(|dictionary| dictionary.words_of_len(slots.len()));
+        for word in words {
+            for (position, c) in word.chars().enumerate() {
+                letters[position].insert(c);
+            }
+        }
+
+        slots
+            .iter()
+            .zip(&letters)
+            .map(|(slot, letters)| {
+                let options = slot.options().iter().filter(|c| letters.contains(c));
+                Slot::new(options.copied().collect())
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,117 @@
+//! Solving as an asynchronous [`Stream`], behind the `async` feature.
+//!
+//! A solve can examine millions of combinations between two matches, so a stream
+//! that solved until its next match could hold an executor thread for seconds. A
+//! [`MatchStream`] instead examines a bounded number of combinations per poll and
+//! yields to the executor in between, so other tasks keep running.
+
+use crate::{Cursor, WordGenerator, WordMatch};
+use futures_core::Stream;
+use std::collections::VecDeque;
+use std::ops::Deref;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Combinations a stream examines per poll unless told otherwise
+pub const DEFAULT_YIELD_EVERY: u128 = 10_000;
+
+/// A stream of a generator's matches, in the order of [`WordGenerator::iter_matches`].
+///
+/// The generator is held through any pointer to it, so a stream can borrow one, as
+/// returned by [`WordGenerator::stream`], or share an `Arc<WordGenerator>` to be moved
+/// into a spawned task.
+///
+/// # Examples
+///
+/// ```
+/// use futures_core::Stream;
+/// use gallry_puzzle_soulver::{MatchStream, Slot, WordGenerator};
+/// use std::pin::pin;
+/// use std::sync::Arc;
+/// use std::task::{Context, Poll, Waker};
+///
+/// let generator = Arc::new(WordGenerator::with_slots(vec![
+///     Slot::new(vec!['c', 'b']),
+///     Slot::new(vec!['a', 'o']),
+///     Slot::new(vec!['t']),
+/// ]));
+///
+/// // Examine one combination per poll, yielding whenever it is not a match
+/// let mut stream = pin!(MatchStream::new(generator, 1));
+/// let mut context = Context::from_waker(Waker::noop());
+/// let mut words = Vec::new();
+/// loop {
+///     match stream.as_mut().poll_next(&mut context) {
+///         Poll::Ready(Some(word_match)) => words.push(word_match.word),
+///         Poll::Ready(None) => break,
+///         Poll::Pending => continue,
+///     }
+/// }
+/// assert_eq!(words, vec!["cat", "cot", "bat", "bot"]);
+/// ```
+pub struct MatchStream<G> {
+    generator: G,
+    cursor: Cursor,
+    /// Combinations examined per poll
+    yield_every: u128,
+    /// Matches found by the last poll and not yet returned
+    pending: VecDeque<WordMatch>,
+}
+
+impl<G: Deref<Target = WordGenerator>> MatchStream<G> {
+    /// Creates a stream over the matches of `generator`.
+    ///
+    /// # Parameters
+    ///
+    /// * `generator` - The generator to solve, such as a reference or an `Arc`
+    /// * `yield_every` - Combinations to examine per poll before yielding to the
+    ///   executor; at least one is examined
+    pub fn new(generator: G, yield_every: u128) -> Self {
+        Self {
+            generator,
+            cursor: Cursor::new(),
+            yield_every: yield_every.max(1),
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Returns how far the solve has got, e.g. to report progress.
+    pub fn cursor(&self) -> Cursor {
+        self.cursor
+    }
+}
+
+impl<G: Deref<Target = WordGenerator> + Unpin> Stream for MatchStream<G> {
+    type Item = WordMatch;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.pending.is_empty() && !this.cursor.is_done(&this.generator) {
+            let found = this
+                .generator
+                .advance(&mut this.cursor, usize::MAX, this.yield_every);
+            this.pending.extend(found);
+        }
+
+        match this.pending.pop_front() {
+            Some(word_match) => Poll::Ready(Some(word_match)),
+            None if this.cursor.is_done(&this.generator) => Poll::Ready(None),
+            None => {
+                // Nothing found in this batch; let other tasks run before the next
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl WordGenerator {
+    /// Returns a stream of the matches, examining [`DEFAULT_YIELD_EVERY`] combinations
+    /// per poll.
+    ///
+    /// See [`MatchStream`] for streams that own their generator or yield at another
+    /// interval.
+    pub fn stream(&self) -> MatchStream<&Self> {
+        MatchStream::new(self, DEFAULT_YIELD_EVERY)
+    }
+}
@@ -0,0 +1,75 @@
+//! A suffix array over dictionary entries for fast substring queries.
+//!
+//! All entries are concatenated into one newline-separated text, and the starting
+//! offset of every suffix is sorted. The suffixes beginning with a query are then
+//! adjacent, so a query is two binary searches followed by a short scan.
+
+/// A generalized suffix array over a set of words.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SubstringIndex {
+    /// The sorted words joined with `'\n'`, which sorts before any letter
+    text: String,
+    /// Byte offset at which each word starts in `text`
+    starts: Vec<u32>,
+    /// Byte offsets of every suffix of every word, sorted by suffix
+    suffixes: Vec<u32>,
+}
+
+impl SubstringIndex {
+    /// Builds the index over the given words.
+    pub(crate) fn new<'a>(words: impl Iterator<Item = &'a str>) -> Self {
+        let mut words: Vec<&str> = words.collect();
+        words.sort_unstable();
+
+        let mut index = Self::default();
+        for word in words {
+            index.starts.push(index.text.len() as u32);
+            for (offset, _) in word.char_indices() {
+                index.suffixes.push((index.text.len() + offset) as u32);
+            }
+            index.text.push_str(word);
+            index.text.push('\n');
+        }
+
+        let text = index.text.as_str();
+        index
+            .suffixes
+            .sort_unstable_by(|a, b| suffix_of(text, *a).cmp(suffix_of(text, *b)));
+        index
+    }
+
+    /// Returns every word containing `substring`, sorted and without duplicates.
+    pub(crate) fn containing(&self, substring: &str) -> Vec<&str> {
+        let text = self.text.as_str();
+        let first = self
+            .suffixes
+            .partition_point(|offset| suffix_of(text, *offset) < substring);
+
+        let mut words: Vec<usize> = self.suffixes[first..]
+            .iter()
+            .take_while(|offset| suffix_of(text, **offset).starts_with(substring))
+            .map(|offset| self.starts.partition_point(|start| start <= offset) - 1)
+            .collect();
+        words.sort_unstable();
+        words.dedup();
+
+        words.into_iter().map(|index| self.word(index)).collect()
+    }
+
+    /// Returns the word with the given index in sorted order.
+    fn word(&self, index: usize) -> &str {
+        let start = self.starts[index] as usize;
+        let end = self
+            .starts
+            .get(index + 1)
+            .map_or(self.text.len(), |end| *end as usize);
+        // Drop the trailing separator
+        &self.text[start..end - 1]
+    }
+}
+
+/// Returns the suffix starting at `offset`, up to the end of its word.
+fn suffix_of(text: &str, offset: u32) -> &str {
+    let rest = &text[offset as usize..];
+    rest.split('\n').next().unwrap_or_default()
+}
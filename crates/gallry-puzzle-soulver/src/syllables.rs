@@ -0,0 +1,59 @@
+//! Spelling-based syllable estimates.
+//!
+//! Without a pronunciation table, syllables are estimated by counting vowel groups and
+//! discounting common silent endings. The estimate is usually right for the short words
+//! puzzles use, but can be off by one on words like "created"; the `pronunciation`
+//! feature counts vowel phones instead.
+
+use crate::short::lowercase_chars;
+
+/// Estimates the number of syllables in a word from its spelling.
+///
+/// Counts groups of consecutive vowels, where `y` is a vowel except at the start of
+/// the word, then discounts a silent final `e` (but not `-le`, as in "table") and the
+/// silent `e` of `-ed` and `-es` endings. Words with letters count at least one
+/// syllable.
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::syllable_count;
+///
+/// assert_eq!(syllable_count("cat"), 1);
+/// assert_eq!(syllable_count("table"), 2);
+/// assert_eq!(syllable_count("baked"), 1);
+/// assert_eq!(syllable_count("wanted"), 2);
+/// assert_eq!(syllable_count("rhythm"), 1);
+/// ```
+pub fn syllable_count(word: &str) -> usize {
+    let mut chars = lowercase_chars(word);
+    chars.retain(|c| c.is_alphabetic());
+    if chars.is_empty() {
+        return 0;
+    }
+
+    let is_vowel = |index: usize| match chars[index] {
+        'a' | 'e' | 'i' | 'o' | 'u' => true,
+        'y' => index > 0,
+        _ => false,
+    };
+    let groups = (0..chars.len())
+        .filter(|&index| is_vowel(index) && (index == 0 || !is_vowel(index - 1)))
+        .count();
+
+    let len = chars.len();
+    let ends_with = |suffix: &[char]| chars.ends_with(suffix);
+    let is_consonant = |index: usize| !is_vowel(index);
+    let silent = if len > 2 && ends_with(&['e']) && is_consonant(len - 2) {
+        // "-le" after a consonant is its own syllable, as in "table"
+        !(chars[len - 2] == 'l' && len > 3 && is_consonant(len - 3))
+    } else if len > 3 && ends_with(&['e', 'd']) && is_consonant(len - 3) {
+        !matches!(chars[len - 3], 't' | 'd')
+    } else if len > 3 && ends_with(&['e', 's']) && is_consonant(len - 3) {
+        !matches!(chars[len - 3], 's' | 'x' | 'z' | 'c' | 'g' | 'h')
+    } else {
+        false
+    };
+
+    (groups - usize::from(silent && groups > 1)).max(1)
+}
@@ -0,0 +1,194 @@
+//! Pictographic ciphers, where each symbol stands for a letter.
+//!
+//! Pigpen, the sigils carved around the house and other symbol alphabets all come
+//! down to a table from symbols to letters. A [`SymbolTable`] names each symbol by an
+//! ID of the user's choosing, such as `sun` or `3`, and holds the letters it may stand
+//! for: one when the symbol is known, several when it is narrowed down, or none yet.
+//! A message written in the symbols then becomes slots, solved against the dictionary
+//! like any other puzzle.
+
+use crate::{Constraint, Slot, WordGenerator};
+use anyhow::{Context, Result, bail};
+use std::collections::BTreeMap;
+
+/// The letters each symbol of a pictographic cipher may stand for.
+///
+/// Symbols are assumed to stand for different letters, as in a substitution cipher,
+/// so a symbol missing from the table may be any letter not already known to belong
+/// to another symbol.
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::{Dictionary, LoadOptions, SymbolTable, WordGenerator};
+///
+/// let table = SymbolTable::from_text("sun = s\nmoon = ae\n").unwrap();
+/// let message = ["sun", "moon", "star"];
+/// let slots = table.slots(&message);
+/// assert_eq!(slots[1].options(), ['a', 'e']);
+///
+/// let dictionary = Dictionary::from_text("sat\nset\nsee\n", &LoadOptions::default());
+/// let mut generator = WordGenerator::with_dictionary(Vec::new(), dictionary);
+/// table.apply(&mut generator, &message);
+/// assert_eq!(generator.iter().collect::<Vec<_>>(), ["sat", "set"]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SymbolTable {
+    /// The lowercase letters each symbol may stand for, sorted; empty when unknown
+    letters: BTreeMap<String, Vec<char>>,
+}
+
+impl SymbolTable {
+    /// Creates an empty table, where every symbol may be any letter.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses a table with one `symbol = letters` entry per line.
+    ///
+    /// Several letters mean the symbol is one of them, and `?` that it is unknown.
+    /// Blank lines and lines starting with `#` are ignored.
+    ///
+    /// # Returns
+    ///
+    /// The table, or an error naming the first line that is not an entry
+    pub fn from_text(text: &str) -> Result<Self> {
+        let mut table = Self::new();
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((symbol, letters)) = line.split_once('=') else {
+                bail!(
+                    "Line {} '{}' is not a symbol = letters entry",
+                    number + 1,
+                    line
+                );
+            };
+            table
+                .insert(symbol.trim(), letters.trim())
+                .with_context(|| format!("Invalid entry on line {}", number + 1))?;
+        }
+        Ok(table)
+    }
+
+    /// Reads a table file in the format of [`from_text`](Self::from_text).
+    pub fn from_path(path: &str) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read symbol table from '{}'", path))?;
+        Self::from_text(&content).with_context(|| format!("Failed to parse '{}'", path))
+    }
+
+    /// Sets the letters a symbol may stand for, replacing any it had.
+    ///
+    /// # Parameters
+    ///
+    /// * `symbol` - The symbol's ID, compared exactly
+    /// * `letters` - The letters it may be, or `?` if unknown
+    ///
+    /// # Returns
+    ///
+    /// An error if the ID is empty or a letter is not from `a` to `z`
+    pub fn insert(&mut self, symbol: &str, letters: &str) -> Result<()> {
+        if symbol.is_empty() || symbol.contains(char::is_whitespace) {
+            bail!("Symbol '{}' must be a name without spaces", symbol);
+        }
+        let mut options = Vec::new();
+        if letters != "?" {
+            for letter in letters.chars() {
+                if !letter.is_ascii_alphabetic() {
+                    bail!(
+                        "Symbol '{}' has '{}', which is not a letter",
+                        symbol,
+                        letter
+                    );
+                }
+                options.push(letter.to_ascii_lowercase());
+            }
+            if options.is_empty() {
+                bail!("Symbol '{}' has no letters; use ? if it is unknown", symbol);
+            }
+        }
+        options.sort_unstable();
+        options.dedup();
+        self.letters.insert(symbol.to_string(), options);
+        Ok(())
+    }
+
+    /// Returns the letters a symbol may stand for, or `None` if it is not in the table
+    /// or unknown.
+    pub fn letters(&self, symbol: &str) -> Option<&[char]> {
+        self.letters
+            .get(symbol)
+            .map(Vec::as_slice)
+            .filter(|letters| !letters.is_empty())
+    }
+
+    /// Returns the letter a symbol is known to stand for, if only one is possible.
+    fn known(&self, symbol: &str) -> Option<char> {
+        match self.letters(symbol)? {
+            [letter] => Some(*letter),
+            _ => None,
+        }
+    }
+
+    /// Returns one slot per symbol of a message, holding the letters it may stand for.
+    ///
+    /// Symbols missing from the table or unknown get every letter no other symbol is
+    /// known to stand for.
+    pub fn slots<S: AsRef<str>>(&self, symbols: &[S]) -> Vec<Slot> {
+        let claimed: Vec<char> = self
+            .letters
+            .keys()
+            .filter_map(|symbol| self.known(symbol))
+            .collect();
+        let unclaimed: Vec<char> = ('a'..='z')
+            .filter(|letter| !claimed.contains(letter))
+            .collect();
+        symbols
+            .iter()
+            .map(|symbol| {
+                Slot::new(
+                    self.letters(symbol.as_ref())
+                        .map_or_else(|| unclaimed.clone(), <[char]>::to_vec),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the constraint that a repeated symbol is the same letter each time and
+    /// different symbols are different letters, when the slots leave that open.
+    ///
+    /// Symbols known to stand for the same letter count as one.
+    pub fn constraint<S: AsRef<str>>(&self, symbols: &[S]) -> Option<Constraint> {
+        let mut classes: Vec<(Option<char>, &str)> = Vec::new();
+        let mut pattern = String::new();
+        for symbol in symbols.iter().map(AsRef::as_ref) {
+            let known = self.known(symbol);
+            let class = classes
+                .iter()
+                .position(|&(letter, other)| {
+                    other == symbol || (known.is_some() && letter == known)
+                })
+                .unwrap_or_else(|| {
+                    classes.push((known, symbol));
+                    classes.len() - 1
+                });
+            pattern.push(char::from_u32('a' as u32 + class as u32)?);
+        }
+        let all_known = symbols
+            .iter()
+            .all(|symbol| self.known(symbol.as_ref()).is_some());
+        (!all_known).then_some(Constraint::RepeatPattern(pattern))
+    }
+
+    /// Sets a generator's slots to read a message, adding the constraint they need to
+    /// its own.
+    pub fn apply<S: AsRef<str>>(&self, generator: &mut WordGenerator, symbols: &[S]) {
+        generator.set_slots(self.slots(symbols));
+        if let Some(constraint) = self.constraint(symbols) {
+            generator.add_constraint(constraint);
+        }
+    }
+}
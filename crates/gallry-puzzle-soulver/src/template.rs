@@ -0,0 +1,164 @@
+//! Output templates for `--format`, such as `{word}\t{score}\t{indices}`.
+//!
+//! A template is parsed once, so unknown placeholders are reported before solving,
+//! and then rendered once per match. `\t`, `\n` and `\\` escapes are expanded so the
+//! template can be written in a plain shell string, and `{{` and `}}` are literal braces.
+
+use crate::output::Solution;
+use gallry_puzzle_soulver::WordGenerator;
+use std::str::FromStr;
+
+/// A value of a match that a template can print.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Field {
+    /// The matched word
+    Word,
+    /// The dictionary entry, when it is spelled differently from the word
+    Entry,
+    /// The stem the word was matched through
+    Stem,
+    /// Dictionaries containing the word, comma-separated
+    Dictionaries,
+    /// Frequency rank from the word list
+    Rank,
+    /// Positional letter frequency score
+    Score,
+    /// Index of the option chosen in each slot, comma-separated
+    Indices,
+    /// Definition of the word or its stem
+    Definition,
+}
+
+impl Field {
+    const ALL: [(&'static str, Field); 8] = [
+        ("word", Field::Word),
+        ("entry", Field::Entry),
+        ("stem", Field::Stem),
+        ("dictionaries", Field::Dictionaries),
+        ("rank", Field::Rank),
+        ("score", Field::Score),
+        ("indices", Field::Indices),
+        ("definition", Field::Definition),
+    ];
+}
+
+/// A piece of a template.
+#[derive(Clone, Debug, PartialEq)]
+enum Segment {
+    Text(String),
+    Field(Field),
+}
+
+/// A parsed `--format` template.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Template {
+    segments: Vec<Segment>,
+}
+
+impl Template {
+    /// Returns `true` if the template prints scores, so matches need scoring.
+    pub fn uses_score(&self) -> bool {
+        self.segments.contains(&Segment::Field(Field::Score))
+    }
+
+    /// Formats one match. Values a match does not have are left empty.
+    pub fn render(&self, generator: &WordGenerator, solution: &Solution) -> String {
+        let word_match = &solution.word_match;
+        let mut line = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Text(text) => line.push_str(text),
+                Segment::Field(field) => {
+                    let value = match field {
+                        Field::Word => word_match.word.clone(),
+                        Field::Entry => word_match.entry.clone().unwrap_or_default(),
+                        Field::Stem => word_match.stem.clone().unwrap_or_default(),
+                        Field::Dictionaries => word_match.dictionaries.join(","),
+                        Field::Rank => word_match
+                            .rank
+                            .map(|rank| rank.to_string())
+                            .unwrap_or_default(),
+                        Field::Score => solution
+                            .score
+                            .map(|score| format!("{:.3}", score))
+                            .unwrap_or_default(),
+                        Field::Indices => generator
+                            .choices(&word_match.word)
+                            .unwrap_or_default()
+                            .iter()
+                            .map(|index| index.to_string())
+                            .collect::<Vec<_>>()
+                            .join(","),
+                        Field::Definition => solution.definition.clone().unwrap_or_default(),
+                    };
+                    line.push_str(&value);
+                }
+            }
+        }
+        line
+    }
+}
+
+impl FromStr for Template {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let mut segments = Vec::new();
+        let mut text = String::new();
+        let mut chars = s.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    Some('t') => text.push('\t'),
+                    Some('n') => text.push('\n'),
+                    Some('\\') => text.push('\\'),
+                    Some(other) => {
+                        text.push('\\');
+                        text.push(other);
+                    }
+                    None => text.push('\\'),
+                },
+                '{' if chars.as_str().starts_with('{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.as_str().starts_with('}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '{' => {
+                    let (name, rest) = chars
+                        .as_str()
+                        .split_once('}')
+                        .ok_or("unclosed '{' in template; write {{ for a literal '{'")?;
+                    let field = Field::ALL
+                        .iter()
+                        .find(|(known, _)| *known == name)
+                        .map(|(_, field)| *field)
+                        .ok_or_else(|| {
+                            let valid: Vec<String> = Field::ALL
+                                .iter()
+                                .map(|(known, _)| format!("{{{}}}", known))
+                                .collect();
+                            format!(
+                                "unknown placeholder '{{{}}}'; valid placeholders are {}",
+                                name,
+                                valid.join(", ")
+                            )
+                        })?;
+                    if !text.is_empty() {
+                        segments.push(Segment::Text(std::mem::take(&mut text)));
+                    }
+                    segments.push(Segment::Field(field));
+                    chars = rest.chars();
+                }
+                '}' => return Err("unmatched '}' in template; write }} for a literal '}'".into()),
+                c => text.push(c),
+            }
+        }
+        if !text.is_empty() {
+            segments.push(Segment::Text(text));
+        }
+        Ok(Template { segments })
+    }
+}
@@ -0,0 +1,120 @@
+//! Checking candidate answers against a puzzle.
+//!
+//! Where solving enumerates the combinations a puzzle allows, verifying takes a word
+//! that was suggested from elsewhere and explains why it would or would not have been
+//! found.
+
+use crate::{Constraint, SolveStats, WordGenerator, WordMatch};
+use std::fmt;
+
+/// The first reason a candidate is not a solution of a puzzle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Rejection {
+    /// The candidate does not have one letter per slot
+    Length {
+        /// Number of slots in the puzzle
+        expected: usize,
+        /// Number of letters in the candidate
+        found: usize,
+    },
+    /// A letter is not among the options of its slot
+    Slot {
+        /// Zero-based position of the slot
+        position: usize,
+        /// The letter the candidate has there
+        letter: char,
+    },
+    /// The word is on the generator's blocklist
+    Blocked,
+    /// The word does not satisfy this constraint
+    Constraint(Constraint),
+    /// The word is not in the word lists, or not in all of them when all are required
+    NotInWordLists,
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rejection::Length { expected, found } => {
+                write!(
+                    f,
+                    "has {} letters but the puzzle has {} slots",
+                    found, expected
+                )
+            }
+            Rejection::Slot { position, letter } => write!(
+                f,
+                "has '{}' in slot {}, which is not one of its options",
+                letter,
+                position + 1
+            ),
+            Rejection::Blocked => f.write_str("is blocklisted"),
+            Rejection::Constraint(constraint) => {
+                write!(f, "violates constraint {}", constraint)
+            }
+            Rejection::NotInWordLists => f.write_str("is not in the word lists"),
+        }
+    }
+}
+
+impl WordGenerator {
+    /// Checks whether `word` is a solution, naming the first check it fails.
+    ///
+    /// The checks run in the order solving applies them: the slots, the blocklist,
+    /// each constraint in turn, then the word lists. Verifying is not counted in
+    /// [`stats`](WordGenerator::stats).
+    ///
+    /// # Returns
+    ///
+    /// The match the solver would report for the word, or why it would not report it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gallry_puzzle_soulver::{Constraint, Dictionary, LoadOptions, Rejection, Slot, WordGenerator};
+    ///
+    /// let dictionary = Dictionary::from_text("cat\ncot\nbat\n", &LoadOptions::default());
+    /// let mut generator = WordGenerator::with_dictionary(
+    ///     vec![Slot::new(vec!['c', 'b']), Slot::new(vec!['a', 'o']), Slot::new(vec!['t'])],
+    ///     dictionary,
+    /// );
+    /// generator.add_constraint(Constraint::StartsWith("c".to_string()));
+    ///
+    /// assert_eq!(generator.verify("cat").unwrap().word, "cat");
+    /// assert_eq!(generator.verify("cut"), Err(Rejection::Slot { position: 1, letter: 'u' }));
+    /// assert_eq!(
+    ///     generator.verify("bat"),
+    ///     Err(Rejection::Constraint(Constraint::StartsWith("c".to_string())))
+    /// );
+    /// assert_eq!(generator.verify("cott"), Err(Rejection::Length { expected: 3, found: 4 }));
+    /// ```
+    pub fn verify(&self, word: &str) -> Result<WordMatch, Rejection> {
+        let slots = self.slot_index();
+        let found = word.chars().count();
+        if found != slots.len() {
+            return Err(Rejection::Length {
+                expected: slots.len(),
+                found,
+            });
+        }
+        for (position, (letter, slot)) in word.chars().zip(slots).enumerate() {
+            if !slot.contains(letter) {
+                return Err(Rejection::Slot { position, letter });
+            }
+        }
+
+        if self.blocklist.blocks(word) {
+            return Err(Rejection::Blocked);
+        }
+        if let Some(constraint) = self
+            .constraints
+            .iter()
+            .find(|constraint| !self.accepts(constraint, word))
+        {
+            return Err(Rejection::Constraint(constraint.clone()));
+        }
+
+        self.evaluate(word, &mut SolveStats::default())
+            .ok_or(Rejection::NotInWordLists)
+    }
+}
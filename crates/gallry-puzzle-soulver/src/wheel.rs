@@ -0,0 +1,279 @@
+//! Letter wheels, finding dictionary words read by stepping around a circle of letters.
+//!
+//! Clock faces, dials and rings of tiles hide words by reading every second or third
+//! letter around the circle, and sometimes by varying the step from one letter to the
+//! next. Each reading starts from every position and goes either way round, never
+//! landing on a position twice, and stops as soon as its letters no longer begin a
+//! dictionary word.
+
+use crate::Dictionary;
+use crate::grid::word_prefixes;
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+/// The way round a wheel to read.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Rotation {
+    /// In the order the letters are given
+    Clockwise,
+    /// Against the order the letters are given
+    Counterclockwise,
+}
+
+impl Rotation {
+    /// Both rotations, clockwise first.
+    pub const BOTH: [Rotation; 2] = [Rotation::Clockwise, Rotation::Counterclockwise];
+
+    /// Returns the position `stride` letters on from `position` on a wheel of `size`.
+    fn step(self, position: usize, stride: usize, size: usize) -> usize {
+        match self {
+            Rotation::Clockwise => (position + stride) % size,
+            Rotation::Counterclockwise => (position + size - stride % size) % size,
+        }
+    }
+}
+
+impl FromStr for Rotation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "clockwise" | "cw" => Ok(Rotation::Clockwise),
+            "counterclockwise" | "anticlockwise" | "ccw" => Ok(Rotation::Counterclockwise),
+            _ => Err(format!(
+                "unknown rotation '{}', expected clockwise or counterclockwise",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Rotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Rotation::Clockwise => "clockwise",
+            Rotation::Counterclockwise => "counterclockwise",
+        })
+    }
+}
+
+/// Options for [`wheel_words`].
+#[derive(Clone, Debug)]
+pub struct WheelOptions {
+    /// Shortest word to report
+    pub min_len: usize,
+    /// Strides to step by; empty for every stride up to half the wheel, which together
+    /// with both rotations reaches every other position
+    pub strides: Vec<usize>,
+    /// Whether each step may use a different one of the strides, rather than one
+    /// stride for the whole word
+    pub variable: bool,
+    /// Rotations to read in
+    pub rotations: Vec<Rotation>,
+}
+
+impl Default for WheelOptions {
+    fn default() -> Self {
+        WheelOptions {
+            min_len: 3,
+            strides: Vec::new(),
+            variable: false,
+            rotations: Rotation::BOTH.to_vec(),
+        }
+    }
+}
+
+/// A word read off a wheel by [`wheel_words`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WheelMatch {
+    /// The word, in lowercase
+    pub word: String,
+    /// The position of its first letter, from 0 for the first letter given
+    pub start: usize,
+    /// The way round it reads
+    pub rotation: Rotation,
+    /// The stride of each step between its letters, one fewer than its letters
+    pub strides: Vec<usize>,
+}
+
+impl WheelMatch {
+    /// Returns the stride the word was read with, or `None` if its steps differ.
+    pub fn stride(&self) -> Option<usize> {
+        let first = *self.strides.first()?;
+        self.strides
+            .iter()
+            .all(|&stride| stride == first)
+            .then_some(first)
+    }
+
+    /// Returns the position of each of the word's letters on a wheel of `size`, in
+    /// order.
+    pub fn positions(&self, size: usize) -> Vec<usize> {
+        let mut positions = vec![self.start];
+        for &stride in &self.strides {
+            let last = positions[positions.len() - 1];
+            positions.push(self.rotation.step(last, stride, size));
+        }
+        positions
+    }
+}
+
+/// Finds every dictionary word read by stepping around a wheel of letters.
+///
+/// Whitespace among the letters is ignored and the last letter is followed by the
+/// first. A stride of 1 reads neighbouring letters; strides larger than the wheel wrap
+/// around it, and a word never uses a position twice. Readings that visit the same
+/// positions in the same order are reported once, clockwise if possible.
+///
+/// # Parameters
+///
+/// * `letters` - The letters around the wheel, in clockwise order
+/// * `dictionary` - The words to look for, compared in lowercase
+/// * `options` - The strides, rotations and shortest word to report
+///
+/// # Returns
+///
+/// The words found, longest first, then in alphabetical order, by start, rotation and
+/// strides
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::{Dictionary, LoadOptions, Rotation, WheelOptions, wheel_words};
+///
+/// let dictionary = Dictionary::from_text("ten\nnet\nsent\n", &LoadOptions::default());
+/// let found = wheel_words("t s e a n", &dictionary, &WheelOptions::default());
+/// let words: Vec<&str> = found.iter().map(|found| found.word.as_str()).collect();
+/// assert_eq!(words, ["net", "ten"]);
+/// assert_eq!(found[1].start, 0);
+/// assert_eq!(found[1].rotation, Rotation::Clockwise);
+/// assert_eq!(found[1].stride(), Some(2));
+/// assert_eq!(found[1].positions(5), [0, 2, 4]);
+/// ```
+pub fn wheel_words(
+    letters: &str,
+    dictionary: &Dictionary,
+    options: &WheelOptions,
+) -> Vec<WheelMatch> {
+    let wheel: Vec<char> = letters
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    let size = wheel.len();
+    let words: HashSet<String> = dictionary
+        .iter()
+        .map(|(word, _)| word.to_lowercase())
+        .filter(|word| (options.min_len.max(1)..=size).contains(&word.chars().count()))
+        .collect();
+    let prefixes = word_prefixes(&words);
+
+    let mut strides: Vec<usize> = if options.strides.is_empty() {
+        (1..=size / 2).collect()
+    } else {
+        options
+            .strides
+            .iter()
+            .copied()
+            .filter(|stride| stride % size.max(1) != 0)
+            .collect()
+    };
+    strides.sort_unstable();
+    strides.dedup();
+
+    let mut search = WheelSearch {
+        wheel: &wheel,
+        words: &words,
+        prefixes: &prefixes,
+        strides: &strides,
+        variable: options.variable,
+        rotation: Rotation::Clockwise,
+        positions: Vec::new(),
+        steps: Vec::new(),
+        found: Vec::new(),
+    };
+    for &rotation in &options.rotations {
+        search.rotation = rotation;
+        for start in 0..size {
+            if search.variable {
+                search.extend(start, None);
+            } else {
+                for &stride in search.strides {
+                    search.extend(start, Some(stride));
+                }
+            }
+        }
+    }
+
+    let mut found = search.found;
+    found.sort_by(|a, b| {
+        b.word
+            .len()
+            .cmp(&a.word.len())
+            .then_with(|| a.word.cmp(&b.word))
+            .then((a.start, a.rotation).cmp(&(b.start, b.rotation)))
+            .then_with(|| a.strides.cmp(&b.strides))
+    });
+    let mut seen = HashSet::new();
+    found.retain(|found| seen.insert(found.positions(size)));
+    found
+}
+
+/// The state of a search for words around a wheel.
+struct WheelSearch<'a> {
+    /// The letters of the wheel, in lowercase
+    wheel: &'a [char],
+    /// Lowercase dictionary words no longer than the wheel
+    words: &'a HashSet<String>,
+    /// Every proper beginning of those words
+    prefixes: &'a HashSet<&'a str>,
+    /// The strides a step may take
+    strides: &'a [usize],
+    /// Whether each step may take a different stride
+    variable: bool,
+    /// The way round the current reading goes
+    rotation: Rotation,
+    /// The positions read so far
+    positions: Vec<usize>,
+    /// The stride of each step so far
+    steps: Vec<usize>,
+    /// Readings spelling a word
+    found: Vec<WheelMatch>,
+}
+
+impl WheelSearch<'_> {
+    /// Adds a position to the reading and steps on from it, recording every reading
+    /// that spells a word. With a fixed `stride` only that stride is followed.
+    fn extend(&mut self, position: usize, stride: Option<usize>) {
+        self.positions.push(position);
+        let word: String = self
+            .positions
+            .iter()
+            .map(|&position| self.wheel[position])
+            .collect();
+        if self.words.contains(&word) {
+            self.found.push(WheelMatch {
+                word: word.clone(),
+                start: self.positions[0],
+                rotation: self.rotation,
+                strides: self.steps.clone(),
+            });
+        }
+        if self.prefixes.contains(word.as_str()) {
+            let strides = match &stride {
+                Some(stride) => std::slice::from_ref(stride),
+                None => self.strides,
+            };
+            for &next_stride in strides {
+                let next = self.rotation.step(position, next_stride, self.wheel.len());
+                if !self.positions.contains(&next) {
+                    self.steps.push(next_stride);
+                    self.extend(next, stride);
+                    self.steps.pop();
+                }
+            }
+        }
+        self.positions.pop();
+    }
+}
@@ -0,0 +1,238 @@
+//! Wordle-style feedback and guess selection.
+//!
+//! A guess is scored against the answer letter by letter: green for the right letter
+//! in the right place, yellow for a letter that is elsewhere in the answer, and gray
+//! otherwise. Repeated letters are only marked yellow as often as the answer still
+//! holds them, as in the original game.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// The color shown for one letter of a guess.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Mark {
+    /// The letter is in the answer at this position
+    Green,
+    /// The letter is in the answer at another position
+    Yellow,
+    /// The letter is not in the answer, or not as many times as guessed
+    Gray,
+}
+
+/// The marks shown for a whole guess, one per letter.
+///
+/// Written as one character per letter: `g` for green, `y` for yellow, and `b` (black),
+/// `.` or `-` for gray, so `..yg.` is a valid feedback.
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::{Feedback, Mark};
+///
+/// let feedback: Feedback = "gy.b-".parse().unwrap();
+/// assert_eq!(feedback.marks()[..2], [Mark::Green, Mark::Yellow]);
+/// assert_eq!(feedback.to_string(), "gy...");
+/// assert!("gyx".parse::<Feedback>().is_err());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Feedback(Vec<Mark>);
+
+impl Feedback {
+    /// Computes the feedback the game shows for `guess` when the answer is `answer`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gallry_puzzle_soulver::Feedback;
+    ///
+    /// assert_eq!(Feedback::score("crane", "cater").to_string(), "gyy.y");
+    /// // Only one of the guessed e's is in the answer
+    /// assert_eq!(Feedback::score("geese", "those").to_string(), "...gg");
+    /// ```
+    pub fn score(guess: &str, answer: &str) -> Self {
+        let guess: Vec<char> = guess.chars().collect();
+        let answer: Vec<char> = answer.chars().collect();
+        let mut marks = vec![Mark::Gray; guess.len()];
+
+        // Letters of the answer not matched in place, available for yellow marks
+        let mut unmatched: HashMap<char, usize> = HashMap::new();
+        for (position, &c) in guess.iter().enumerate() {
+            match answer.get(position) {
+                Some(&expected) if expected == c => marks[position] = Mark::Green,
+                Some(&expected) => *unmatched.entry(expected).or_default() += 1,
+                None => {}
+            }
+        }
+        for (position, &c) in guess.iter().enumerate() {
+            if marks[position] == Mark::Green {
+                continue;
+            }
+            if let Some(count) = unmatched.get_mut(&c).filter(|count| **count > 0) {
+                *count -= 1;
+                marks[position] = Mark::Yellow;
+            }
+        }
+        Feedback(marks)
+    }
+
+    /// Returns the marks, one per letter of the guess.
+    pub fn marks(&self) -> &[Mark] {
+        &self.0
+    }
+
+    /// Returns `true` if every letter was green.
+    pub fn is_solved(&self) -> bool {
+        self.0.iter().all(|mark| *mark == Mark::Green)
+    }
+}
+
+impl FromStr for Feedback {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.chars()
+            .map(|c| match c.to_ascii_lowercase() {
+                'g' => Ok(Mark::Green),
+                'y' => Ok(Mark::Yellow),
+                'b' | '.' | '-' => Ok(Mark::Gray),
+                _ => Err(format!(
+                    "invalid feedback '{}': use g for green, y for yellow and b, . or - for gray",
+                    s
+                )),
+            })
+            .collect::<Result<_, _>>()
+            .map(Feedback)
+    }
+}
+
+impl fmt::Display for Feedback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for mark in &self.0 {
+            let c = match mark {
+                Mark::Green => 'g',
+                Mark::Yellow => 'y',
+                Mark::Gray => '.',
+            };
+            write!(f, "{}", c)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns how many bits of information `guess` is expected to reveal.
+///
+/// This is the entropy of the feedback over `candidates`, each assumed equally likely
+/// to be the answer: a guess splitting them into many small groups scores highest.
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::expected_information;
+///
+/// let candidates = ["cat", "cot", "bat", "bot"];
+/// assert_eq!(expected_information("cat", &candidates), 2.0);
+/// assert_eq!(expected_information("cut", &candidates), 1.0);
+/// ```
+pub fn expected_information<S: AsRef<str>>(guess: &str, candidates: &[S]) -> f64 {
+    let guess: Vec<char> = guess.chars().collect();
+    information(&guess, &spelled(candidates))
+}
+
+/// Returns the guess expected to reveal the most about the answer, with its score.
+///
+/// Among equally informative guesses a possible answer is preferred, since it may win
+/// outright, and then the earliest in `guesses`.
+///
+/// # Parameters
+///
+/// * `guesses` - Words that may be guessed
+/// * `candidates` - Words that may still be the answer
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::best_guess;
+///
+/// let candidates = ["cat", "cot", "bot"];
+/// let (guess, information) = best_guess(&["cut", "cot", "cat"], &candidates).unwrap();
+/// assert_eq!(guess, "cot");
+/// assert!((information - 3f64.log2()).abs() < 1e-9);
+/// assert_eq!(best_guess(&[], &candidates), None);
+/// ```
+pub fn best_guess<'a, S: AsRef<str>>(guesses: &'a [S], candidates: &[S]) -> Option<(&'a str, f64)> {
+    let spelled_candidates = spelled(candidates);
+    let mut best: Option<(&str, f64, bool)> = None;
+    for guess in guesses {
+        let guess = guess.as_ref();
+        let letters: Vec<char> = guess.chars().collect();
+        let information = information(&letters, &spelled_candidates);
+        let possible = spelled_candidates.contains(&letters);
+        let better = match best {
+            None => true,
+            Some((_, best_information, best_possible)) => {
+                information > best_information + 1e-9
+                    || (possible && !best_possible && information > best_information - 1e-9)
+            }
+        };
+        if better {
+            best = Some((guess, information, possible));
+        }
+    }
+    best.map(|(guess, information, _)| (guess, information))
+}
+
+/// Splits every word into its letters once, ahead of scoring many guesses.
+fn spelled<S: AsRef<str>>(words: &[S]) -> Vec<Vec<char>> {
+    words
+        .iter()
+        .map(|word| word.as_ref().chars().collect())
+        .collect()
+}
+
+/// Returns the entropy, in bits, of the feedback `guess` gets over `candidates`.
+fn information(guess: &[char], candidates: &[Vec<char>]) -> f64 {
+    let mut groups: HashMap<u128, usize> = HashMap::new();
+    for candidate in candidates {
+        *groups.entry(packed(guess, candidate)).or_default() += 1;
+    }
+
+    let total = candidates.len() as f64;
+    groups
+        .values()
+        .map(|&count| {
+            let p = count as f64 / total;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// Computes the feedback as a number, two bits per letter, without allocating.
+///
+/// This agrees with [`Feedback::score`] for words of up to 64 letters; longer guesses
+/// share a key per prefix, which only blurs the estimate for absurd puzzles.
+fn packed(guess: &[char], answer: &[char]) -> u128 {
+    let mut green = 0u64;
+    for (position, c) in guess.iter().enumerate().take(64) {
+        if answer.get(position) == Some(c) {
+            green |= 1 << position;
+        }
+    }
+
+    let mut code = 0u128;
+    let mut used = green;
+    for (position, c) in guess.iter().enumerate().take(64) {
+        let mark = if green & (1 << position) != 0 {
+            1
+        } else if let Some(other) =
+            (0..answer.len().min(64)).find(|&other| used & (1 << other) == 0 && answer[other] == *c)
+        {
+            used |= 1 << other;
+            2
+        } else {
+            0
+        };
+        code |= mark << (2 * position);
+    }
+    code
+}
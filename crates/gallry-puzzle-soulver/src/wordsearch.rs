@@ -0,0 +1,215 @@
+//! Word searches, finding dictionary words along the straight lines of a letter grid.
+//!
+//! Starting from every cell, the search reads letters in each of the eight directions
+//! and stops as soon as they no longer begin a dictionary word, so even the full word
+//! list is searched quickly.
+
+use crate::grid::word_prefixes;
+use crate::{Dictionary, LetterGrid};
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+/// A direction to read a line of a grid in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    /// Left to right along a row
+    Right,
+    /// Diagonally towards the bottom right
+    DownRight,
+    /// Top to bottom down a column
+    Down,
+    /// Diagonally towards the bottom left
+    DownLeft,
+    /// Right to left along a row
+    Left,
+    /// Diagonally towards the top left
+    UpLeft,
+    /// Bottom to top up a column
+    Up,
+    /// Diagonally towards the top right
+    UpRight,
+}
+
+impl Direction {
+    /// Every direction, clockwise from reading left to right.
+    pub const ALL: [Direction; 8] = [
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+        Direction::Up,
+        Direction::UpRight,
+    ];
+
+    /// Returns the row and column steps of one letter in this direction.
+    pub fn step(self) -> (isize, isize) {
+        match self {
+            Direction::Right => (0, 1),
+            Direction::DownRight => (1, 1),
+            Direction::Down => (1, 0),
+            Direction::DownLeft => (1, -1),
+            Direction::Left => (0, -1),
+            Direction::UpLeft => (-1, -1),
+            Direction::Up => (-1, 0),
+            Direction::UpRight => (-1, 1),
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Direction::ALL
+            .into_iter()
+            .find(|direction| direction.to_string() == s)
+            .ok_or_else(|| {
+                format!(
+                    "unknown direction '{}', expected right, down-right, down, down-left, \
+                     left, up-left, up or up-right",
+                    s
+                )
+            })
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Direction::Right => "right",
+            Direction::DownRight => "down-right",
+            Direction::Down => "down",
+            Direction::DownLeft => "down-left",
+            Direction::Left => "left",
+            Direction::UpLeft => "up-left",
+            Direction::Up => "up",
+            Direction::UpRight => "up-right",
+        })
+    }
+}
+
+/// Options for [`word_search`].
+#[derive(Clone, Debug)]
+pub struct WordSearchOptions {
+    /// Shortest word to report
+    pub min_len: usize,
+    /// Directions to read in
+    pub directions: Vec<Direction>,
+}
+
+impl Default for WordSearchOptions {
+    fn default() -> Self {
+        WordSearchOptions {
+            min_len: 3,
+            directions: Direction::ALL.to_vec(),
+        }
+    }
+}
+
+/// A word found in a grid by [`word_search`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WordSearchMatch {
+    /// The word, in lowercase
+    pub word: String,
+    /// The row of its first letter, from 0 at the top
+    pub row: usize,
+    /// The column of its first letter, from 0 at the left
+    pub column: usize,
+    /// The direction it reads in
+    pub direction: Direction,
+}
+
+impl WordSearchMatch {
+    /// Returns the row and column of each of the word's letters, in order.
+    pub fn cells(&self) -> Vec<(usize, usize)> {
+        let (rows, columns) = self.direction.step();
+        (0..self.word.chars().count() as isize)
+            .map(|index| {
+                (
+                    self.row.wrapping_add_signed(rows * index),
+                    self.column.wrapping_add_signed(columns * index),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Finds every dictionary word written along a straight line of a grid.
+///
+/// A word found in several places or directions is reported once for each.
+///
+/// # Returns
+///
+/// The words found, longest first, then in alphabetical order and by position
+///
+/// # Examples
+///
+/// ```
+/// use gallry_puzzle_soulver::{
+///     Dictionary, Direction, LetterGrid, LoadOptions, WordSearchOptions, word_search,
+/// };
+///
+/// let grid = LetterGrid::from_text("cat\noxo\nwet\n").unwrap();
+/// let dictionary = Dictionary::from_text("cat\ncow\ntot\noxo\n", &LoadOptions::default());
+/// let found = word_search(&grid, &dictionary, &WordSearchOptions::default());
+/// let words: Vec<&str> = found.iter().map(|found| found.word.as_str()).collect();
+/// assert_eq!(words, ["cat", "cow", "oxo", "oxo", "tot", "tot"]);
+/// assert_eq!(found[1].direction, Direction::Down);
+/// assert_eq!(found[1].cells(), [(0, 0), (1, 0), (2, 0)]);
+/// ```
+pub fn word_search(
+    grid: &LetterGrid,
+    dictionary: &Dictionary,
+    options: &WordSearchOptions,
+) -> Vec<WordSearchMatch> {
+    let longest = grid.width().max(grid.height());
+    let words: HashSet<String> = dictionary
+        .iter()
+        .map(|(word, _)| word.to_lowercase())
+        .filter(|word| (options.min_len.max(1)..=longest).contains(&word.chars().count()))
+        .collect();
+    let prefixes = word_prefixes(&words);
+
+    let mut found = Vec::new();
+    for row in 0..grid.height() {
+        for column in 0..grid.width() {
+            for &direction in &options.directions {
+                let (rows, columns) = direction.step();
+                let mut letters = String::new();
+                let (mut at_row, mut at_column) = (row, column);
+                while let Some(letter) = grid.get(at_row, at_column) {
+                    letters.push(letter);
+                    if words.contains(&letters) {
+                        found.push(WordSearchMatch {
+                            word: letters.clone(),
+                            row,
+                            column,
+                            direction,
+                        });
+                    }
+                    if !prefixes.contains(letters.as_str()) {
+                        break;
+                    }
+                    let (Some(next_row), Some(next_column)) = (
+                        at_row.checked_add_signed(rows),
+                        at_column.checked_add_signed(columns),
+                    ) else {
+                        break;
+                    };
+                    (at_row, at_column) = (next_row, next_column);
+                }
+            }
+        }
+    }
+    found.sort_by(|a, b| {
+        b.word
+            .len()
+            .cmp(&a.word.len())
+            .then_with(|| a.word.cmp(&b.word))
+            .then((a.row, a.column, a.direction).cmp(&(b.row, b.column, b.direction)))
+    });
+    found
+}
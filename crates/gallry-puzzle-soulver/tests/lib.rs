@@ -1,5 +1,19 @@
-use gallry_puzzle_soulver::{Slot, WordGenerator};
+use gallry_puzzle_soulver::{
+    Acrostic, AcrosticMode, Blocklist, CalendarDate, Constraint, Cryptogram, Cursor,
+    DARTBOARD_SEGMENTS, DartOp, DartThrow, DateLetter, Dictionary, DictionaryPolicy, Direction,
+    Feedback, InflectionRules, Interner, KEYPAD_LETTERS, LetterGrid, LoadOptions, MatchOptions,
+    MorseCode, MorseSpacing, Normalization, NumberTarget, ParlorPuzzle, PartOfSpeech, ProperNouns,
+    PuzzleConfig, Rejection, Rotation, SchemaVersion, SequenceOrder, Slot, SolveStats, Square,
+    Strategy, SymbolTable, Truncation, VigenereOptions, WheelOptions, WordFlags, WordGenerator,
+    WordMatch, WordSearchOptions, a1z26_decode, a1z26_encode, a1z26_readings, a1z26_words,
+    best_guess, caesar_decryptions, caesar_shift, chess_slots, date_slots, expected_information,
+    knight_slots, knight_words, morse_encode, parse_digit_slots, parse_keypad_slots, parse_slots,
+    rhyme_key, rhymes, roman_numeral, roman_numeral_value, score_plaintext, score_throws,
+    spelled_number_value, syllable_count, throws_for, vigenere_decrypt, vigenere_decryptions,
+    vigenere_encrypt, wheel_words, word_search,
+};
 use std::collections::HashSet;
+use std::time::Duration;
 
 #[test]
 fn test_to_string() {
@@ -125,3 +139,1904 @@ fn test_embedded_wordlist() {
         "Some generated words should be filtered out by the wordlist"
     );
 }
+
+#[test]
+fn test_proper_noun_filtering() {
+    let word_list: HashSet<String> = ["Ned".to_string(), "red".to_string(), "bed".to_string()]
+        .into_iter()
+        .collect();
+    let slots = vec![
+        Slot::new(vec!['N', 'r', 'b']),
+        Slot::new(vec!['e']),
+        Slot::new(vec!['d']),
+    ];
+    let mut generator = WordGenerator::new(slots, Some(word_list));
+
+    let mut options = MatchOptions {
+        proper_nouns: ProperNouns::Exclude,
+        ..Default::default()
+    };
+    generator.set_match_options(options.clone());
+    assert_eq!(generator.iter().collect::<Vec<_>>(), vec!["red", "bed"]);
+
+    options.proper_nouns = ProperNouns::Only;
+    generator.set_match_options(options);
+    assert_eq!(generator.iter().collect::<Vec<_>>(), vec!["Ned"]);
+}
+
+#[test]
+fn test_proper_nouns_dropped_at_load() {
+    let options = LoadOptions {
+        proper_nouns: ProperNouns::Exclude,
+        ..Default::default()
+    };
+    let dictionary = Dictionary::from_text("Paris\nparis\ncat\n", &options);
+
+    assert_eq!(dictionary.len(), 2);
+    assert!(!dictionary.contains("Paris"));
+    assert!(
+        dictionary
+            .get("cat")
+            .is_some_and(|info| !info.flags.contains(WordFlags::PROPER_NOUN))
+    );
+}
+
+#[test]
+fn test_blocklist_suppresses_words() {
+    let mut generator = WordGenerator::with_no_filtering(vec![
+        Slot::new(vec!['c', 'b', 'r']),
+        Slot::new(vec!['a']),
+        Slot::new(vec!['t']),
+    ]);
+    generator.set_blocklist(Blocklist::from_text("# comment\nBAT\n\nrat\n"));
+
+    assert_eq!(generator.iter().collect::<Vec<_>>(), vec!["cat"]);
+}
+
+#[test]
+fn test_part_of_speech_filtering() {
+    let dictionary = Dictionary::from_text(
+        "cat\tpos=noun,verb\ncot\tpos=noun\ncut\tpos=verb\nctt\tpos=abbr\ncit\n",
+        &LoadOptions::default(),
+    );
+    let mut generator = WordGenerator::with_dictionary(
+        vec![
+            Slot::new(vec!['c']),
+            Slot::new(vec!['a', 'o', 'u', 'i', 't']),
+            Slot::new(vec!['t']),
+        ],
+        dictionary,
+    );
+
+    generator.set_match_options(MatchOptions {
+        include_pos: vec![PartOfSpeech::Noun],
+        exclude_pos: vec![PartOfSpeech::Verb],
+        ..Default::default()
+    });
+
+    // Untagged entries are unaffected by part-of-speech filters
+    assert_eq!(generator.iter().collect::<Vec<_>>(), vec!["cot", "cit"]);
+}
+
+#[test]
+fn test_inflection_expansion() {
+    let options = LoadOptions {
+        inflections: Some(InflectionRules::default()),
+        ..Default::default()
+    };
+    let dictionary = Dictionary::from_text("cat\nbox\nrun\ncarry\nhope\nParis\n", &options);
+
+    for form in [
+        "cats", "boxes", "ran", "running", "carried", "hoping", "hoped",
+    ] {
+        assert!(
+            dictionary.contains(form),
+            "expected '{}' to be generated",
+            form
+        );
+    }
+    assert!(!dictionary.contains("Pariss"));
+    assert!(
+        !dictionary
+            .get("cat")
+            .unwrap()
+            .flags
+            .contains(WordFlags::INFLECTED)
+    );
+}
+
+#[cfg(feature = "stemming")]
+#[test]
+fn test_stem_matching() {
+    let dictionary = Dictionary::from_text("run\ncat\n", &LoadOptions::default());
+    let mut generator = WordGenerator::with_dictionary(
+        vec![
+            Slot::new(vec!['r', 'c']),
+            Slot::new(vec!['u', 'a']),
+            Slot::new(vec!['n']),
+            Slot::new(vec!['n']),
+            Slot::new(vec!['i']),
+            Slot::new(vec!['n']),
+            Slot::new(vec!['g']),
+        ],
+        dictionary,
+    );
+    assert_eq!(generator.iter().count(), 0);
+
+    generator.set_match_options(MatchOptions {
+        stemming: true,
+        ..Default::default()
+    });
+    let matches: Vec<_> = generator.iter_matches().collect();
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].word, "running");
+    assert_eq!(matches[0].stem.as_deref(), Some("run"));
+}
+
+#[cfg(feature = "definitions")]
+#[test]
+fn test_definitions() {
+    use gallry_puzzle_soulver::Glossary;
+
+    let glossary = Glossary::from_text(
+        "# word<TAB>gloss\ncat\tfeline mammal\ncat\tjazz musician\nbat\tnocturnal flier\n",
+    );
+    let mut dictionary = Dictionary::from_text("cat\nbat\n", &LoadOptions::default());
+    dictionary.set_glossary(glossary);
+
+    assert_eq!(dictionary.definition("cat"), Some("feline mammal"));
+    assert_eq!(dictionary.glossary().definitions("cat").len(), 2);
+    assert_eq!(dictionary.definition("rat"), None);
+}
+
+#[test]
+fn test_case_insensitive_matching() {
+    let word_list: HashSet<String> = ["Paris".to_string(), "McCoy".to_string()]
+        .into_iter()
+        .collect();
+    let mut generator = WordGenerator::new(
+        vec![
+            Slot::new(vec!['p']),
+            Slot::new(vec!['a']),
+            Slot::new(vec!['r']),
+            Slot::new(vec!['i']),
+            Slot::new(vec!['s']),
+        ],
+        Some(word_list),
+    );
+    assert_eq!(generator.iter().count(), 0);
+
+    generator.set_match_options(MatchOptions {
+        case_insensitive: true,
+        ..Default::default()
+    });
+    let matches: Vec<_> = generator.iter_matches().collect();
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].word, "paris");
+    assert_eq!(matches[0].display_word(), "Paris");
+}
+
+#[test]
+fn test_unicode_normalization() {
+    // The list spells "café" with a combining accent, the slot with a precomposed one
+    let dictionary = Dictionary::from_text("cafe\u{301}\n", &LoadOptions::default());
+    assert!(dictionary.contains("caf\u{e9}"));
+
+    let options = LoadOptions {
+        normalization: Normalization::StripDiacritics,
+        ..Default::default()
+    };
+    let dictionary = Dictionary::from_text("cafe\u{301}\nnaïve\n", &options);
+    let generator = WordGenerator::with_dictionary(
+        vec![
+            Slot::new(vec!['c']),
+            Slot::new(vec!['a']),
+            Slot::new(vec!['f']),
+            Slot::new(vec!['e', '\u{e9}']),
+        ],
+        dictionary,
+    );
+    assert_eq!(
+        generator.iter().collect::<Vec<_>>(),
+        vec!["cafe", "caf\u{e9}"]
+    );
+}
+
+#[test]
+fn test_words_of_len() {
+    let mut dictionary = Dictionary::from_text("cat\nhorse\nbat\ncafé\n", &LoadOptions::default());
+    assert_eq!(
+        dictionary.words_of_len(3).collect::<Vec<_>>(),
+        vec!["bat", "cat"]
+    );
+    assert_eq!(dictionary.words_of_len(4).collect::<Vec<_>>(), vec!["café"]);
+    assert_eq!(dictionary.words_of_len(7).count(), 0);
+
+    // The index is rebuilt after the dictionary changes
+    dictionary.insert("rat");
+    assert_eq!(dictionary.words_of_len(3).count(), 3);
+}
+
+#[test]
+fn test_suggestions() {
+    let dictionary = Dictionary::from_text("cat\ncart\ncoat\ndog\n", &LoadOptions::default());
+
+    assert_eq!(dictionary.suggest("cat", 0), vec![("cat", 0)]);
+    assert_eq!(dictionary.suggest("cot", 1), vec![("cat", 1), ("coat", 1)]);
+    assert_eq!(dictionary.suggest("cot", 2).len(), 4);
+    assert!(dictionary.suggest("zebra", 1).is_empty());
+}
+
+#[test]
+fn test_letters_at() {
+    let dictionary = Dictionary::embedded();
+
+    let first = dictionary.letters_at(0, 3);
+    assert!(first.contains(&'c'));
+    assert!(first.len() <= 26);
+
+    // No two-letter word ends in 'q' or 'z' in the embedded list
+    let last = dictionary.letters_at(1, 2);
+    assert!(last.contains(&'o'));
+    assert!(!last.contains(&'q') && !last.contains(&'z'));
+    assert!(dictionary.letters_at(3, 3).is_empty());
+}
+
+#[test]
+fn test_positional_frequencies() {
+    let dictionary = Dictionary::from_text("cat\ncot\ncut\nbat\ndog\n", &LoadOptions::default());
+    let stats = dictionary.positional_frequencies(3);
+
+    assert_eq!(stats.word_len(), 3);
+    assert_eq!(stats.total_words(), 5);
+    assert_eq!(stats.ranked(2), vec![('t', 4), ('g', 1)]);
+
+    let ranked = stats.rank_options(0, &['b', 'c', 'x']);
+    assert_eq!(ranked[0], ('c', 0.6));
+    assert_eq!(ranked[2], ('x', 0.0));
+
+    // Letters common at their position score higher than rare ones
+    assert!(stats.score("cat") > stats.score("dot"));
+    assert!((stats.score("cat") - (0.6f64 * 0.4 * 0.8).cbrt()).abs() < 1e-9);
+    assert_eq!(stats.score("cats"), 0.0);
+}
+
+#[test]
+fn test_multi_dictionary_attribution() {
+    let common = Dictionary::from_text("cat\n", &LoadOptions::default()).with_name("common");
+    let unnamed = Dictionary::from_text("cat\nbat\n", &LoadOptions::default());
+    let mut generator = WordGenerator::with_dictionary(
+        vec![
+            Slot::new(vec!['c', 'b', 'r']),
+            Slot::new(vec!['a']),
+            Slot::new(vec!['t']),
+        ],
+        common,
+    );
+    generator.add_dictionary(unnamed);
+
+    let matches: Vec<_> = generator.iter_matches().collect();
+    assert_eq!(matches.len(), 2);
+    assert_eq!(matches[0].dictionaries, vec!["common", "#2"]);
+    assert_eq!(matches[1].word, "bat");
+    assert_eq!(matches[1].dictionaries, vec!["#2"]);
+
+    generator.set_dictionary_policy(DictionaryPolicy::All);
+    assert_eq!(generator.iter().collect::<Vec<_>>(), vec!["cat"]);
+}
+
+#[test]
+fn test_dictionary_diff() {
+    let old = Dictionary::from_text("cat\nbat\ndog\n", &LoadOptions::default());
+    let new = Dictionary::from_text("cat\nrat\ndog\nhorse\n", &LoadOptions::default());
+    let diff = old.diff(&new);
+
+    let mut added: Vec<_> = diff.added().collect();
+    added.sort();
+    assert_eq!(added, vec!["horse", "rat"]);
+    assert_eq!(diff.removed().collect::<Vec<_>>(), vec!["bat"]);
+    assert!(!diff.is_empty());
+    assert!(old.diff(&old).is_empty());
+
+    let generator = WordGenerator::with_no_filtering(vec![
+        Slot::new(vec!['b', 'r']),
+        Slot::new(vec!['a']),
+        Slot::new(vec!['t']),
+    ]);
+    assert_eq!(diff.affected_words(&generator), vec!["bat", "rat"]);
+}
+
+#[test]
+fn test_contains_detailed() {
+    let options = LoadOptions {
+        rank_by_line_order: true,
+        ..Default::default()
+    };
+    let dictionary =
+        Dictionary::from_text("the\nof\nzebra\trank=50000\n", &options).with_name("freq");
+
+    let membership = dictionary.contains_detailed("of").unwrap();
+    assert_eq!(membership.dictionary, "freq");
+    assert_eq!(membership.entry, "of");
+    assert_eq!(membership.rank(), Some(2));
+    assert!(membership.flags().is_empty());
+
+    // Explicit rank metadata wins over line order
+    assert_eq!(
+        dictionary.contains_detailed("zebra").unwrap().rank(),
+        Some(50000)
+    );
+    assert!(dictionary.contains_detailed("cat").is_none());
+}
+
+#[test]
+fn test_containing() {
+    let dictionary = Dictionary::embedded();
+
+    let words = dictionary.containing("gallery");
+    assert!(words.contains(&"gallery"));
+    assert!(words.contains(&"galleryite"));
+    assert!(words.iter().all(|word| word.contains("gallery")));
+    assert!(words.windows(2).all(|pair| pair[0] < pair[1]));
+
+    assert!(dictionary.containing("qqq").is_empty());
+    assert_eq!(dictionary.containing("").len(), dictionary.len());
+}
+
+#[test]
+fn test_merge_dictionaries() {
+    let mut dictionary = Dictionary::from_text("cat\nbat\n", &LoadOptions::default());
+    dictionary.merge(Dictionary::from_text(
+        "zorp\ncat\n",
+        &LoadOptions::default(),
+    ));
+
+    assert_eq!(dictionary.len(), 3);
+    assert!(dictionary.contains("zorp"));
+    assert!(dictionary.containing("or").contains(&"zorp"));
+}
+
+#[test]
+fn test_sample() {
+    use rand::SeedableRng;
+
+    let dictionary = Dictionary::embedded();
+
+    let words = dictionary.sample(10, Some(5));
+    assert_eq!(words.len(), 10);
+    assert!(
+        words
+            .iter()
+            .all(|word| word.len() == 5 && dictionary.contains(word))
+    );
+    assert_eq!(words.iter().collect::<HashSet<_>>().len(), 10);
+
+    let first = dictionary.sample_with(&mut rand::rngs::StdRng::seed_from_u64(7), 3, None);
+    let second = dictionary.sample_with(&mut rand::rngs::StdRng::seed_from_u64(7), 3, None);
+    assert_eq!(first, second);
+
+    let small = Dictionary::from_text("cat\ndog\n", &LoadOptions::default());
+    assert_eq!(small.sample(5, Some(3)).len(), 2);
+    assert!(small.sample(5, Some(4)).is_empty());
+}
+
+#[test]
+fn test_wildcard_slots_use_dictionary_alphabet() {
+    let dictionary = Dictionary::from_text("ñu\nnu\nmu\n", &LoadOptions::default());
+    let alphabet: String = dictionary.alphabet().iter().collect();
+    assert_eq!(alphabet, "mnuñ");
+
+    let generator =
+        WordGenerator::with_dictionary(vec![Slot::any(), Slot::new(vec!['u'])], dictionary.clone());
+    let words: Vec<String> = generator.iter().collect();
+    assert_eq!(words, vec!["mu", "nu", "ñu"]);
+
+    let generator = WordGenerator::with_dictionary(
+        vec![Slot::none_of(vec!['n', 'm']), Slot::new(vec!['u'])],
+        dictionary,
+    );
+    let words: Vec<String> = generator.iter().collect();
+    assert_eq!(words, vec!["ñu"]);
+    assert!(generator.can_generate("uu"));
+    assert!(!generator.can_generate("nu"));
+
+    // Without a dictionary, wildcards fall back to a-z
+    let generator = WordGenerator::with_no_filtering(vec![Slot::any()]);
+    assert_eq!(generator.all_combinations().count(), 26);
+}
+
+#[test]
+fn test_regex_constraint() {
+    let mut generator = WordGenerator::with_no_filtering(vec![
+        Slot::new(vec!['c', 'b']),
+        Slot::new(vec!['a', 'o']),
+        Slot::new(vec!['t']),
+    ]);
+    generator.add_constraint(Constraint::Regex("^b|ot$".parse().unwrap()));
+    assert_eq!(
+        generator.iter().collect::<Vec<_>>(),
+        vec!["cot", "bat", "bot"]
+    );
+
+    let config = PuzzleConfig::from_toml_str(
+        "slots = [\"cb\", \"ao\", \"t\"]\n[[constraints]]\nregex = \"^c[^o]\"\n",
+    )
+    .unwrap();
+    assert_eq!(
+        config.to_generator().unwrap().iter().collect::<Vec<_>>(),
+        vec!["cat"]
+    );
+    assert!(PuzzleConfig::from_toml_str("[[constraints]]\nregex = \"(\"\n").is_err());
+}
+
+#[test]
+fn test_dictionary_scan_matches_enumeration() {
+    // 26^4 combinations against a handful of words is solved by scanning the words
+    let first = Dictionary::from_text("zoom\nmoon\nabcd\nmoo\n", &LoadOptions::default());
+    let second = Dictionary::from_text("moon\nnoon\n", &LoadOptions::default());
+    let alphabet: Vec<char> = ('a'..='z').rev().collect();
+    let mut generator = WordGenerator::with_dictionary(vec![Slot::new(alphabet); 4], first);
+    generator.add_dictionary(second.with_name("extra"));
+
+    // Reversed options put later letters first, as enumeration would
+    let words: Vec<String> = generator.iter().collect();
+    assert_eq!(words, vec!["zoom", "noon", "moon", "abcd"]);
+
+    generator.add_constraint(Constraint::EndsWith("n".to_string()));
+    let matches: Vec<WordMatch> = generator.iter_matches().collect();
+    assert_eq!(matches.len(), 2);
+    assert_eq!(matches[1].word, "moon");
+    assert_eq!(matches[1].dictionaries, vec!["#1", "extra"]);
+}
+
+#[test]
+fn test_strategies_find_the_same_matches() {
+    let dictionary = Dictionary::from_text("cat\ncot\nbat\ndog\ntab\n", &LoadOptions::default());
+    let slots = vec![
+        Slot::new(vec!['t', 'c', 'b', 'd']),
+        Slot::new(vec!['o', 'a', 'i']),
+        Slot::new(vec!['g', 't', 'b']),
+    ];
+    let mut generator = WordGenerator::with_dictionary(slots, dictionary);
+    generator.set_strategy(Some(Strategy::Enumerate));
+    let expected: Vec<WordMatch> = generator.iter_matches().collect();
+    assert_eq!(expected.len(), 5);
+
+    for strategy in [Strategy::DictScan, Strategy::PositionalIndex] {
+        generator.set_strategy(Some(strategy));
+        assert_eq!(generator.strategy(), strategy);
+        assert_eq!(generator.iter_matches().collect::<Vec<_>>(), expected);
+        assert_eq!(generator.par_matches(4), expected);
+    }
+
+    // Case folding can match entries a scan would miss, so enumeration is used instead
+    generator.set_match_options(MatchOptions {
+        case_insensitive: true,
+        ..MatchOptions::default()
+    });
+    assert_eq!(generator.strategy(), Strategy::Enumerate);
+    assert_eq!("positional-index".parse(), Ok(Strategy::PositionalIndex));
+}
+
+#[test]
+fn test_slot_lookups_follow_slot_and_alphabet_changes() {
+    let dictionary = Dictionary::from_text("çà\nab\n", &LoadOptions::default());
+    let mut generator =
+        WordGenerator::with_dictionary(vec![Slot::any(), Slot::new(vec!['b', 'à'])], dictionary);
+    assert_eq!(generator.choices("çà"), Some(vec![3, 1]));
+    assert!(generator.can_generate("ab"));
+    assert!(!generator.can_generate("zb"));
+
+    // A wider alphabet renumbers the wildcard's options
+    generator.add_dictionary(Dictionary::from_text("zb\n", &LoadOptions::default()));
+    assert!(generator.can_generate("zb"));
+    assert_eq!(generator.choices("çà"), Some(vec![4, 1]));
+
+    generator.set_slots(vec![Slot::new(vec!['z', 'a']), Slot::new(vec!['b'])]);
+    assert_eq!(generator.choices("ab"), Some(vec![1, 0]));
+    assert_eq!(generator.choices("çà"), None);
+}
+
+#[test]
+fn test_count_matches_agrees_with_iteration() {
+    let first = Dictionary::from_text("cat\ncot\nbat\n", &LoadOptions::default());
+    let second = Dictionary::from_text("cat\nbot\n", &LoadOptions::default());
+    let slots = vec![
+        Slot::new(vec!['c', 'b']),
+        Slot::new(vec!['a', 'o']),
+        Slot::new(vec!['t']),
+    ];
+    let mut generator = WordGenerator::with_dictionary(slots, first);
+    generator.add_dictionary(second);
+    assert_eq!(generator.count_matches(), 4);
+    assert_eq!(generator.count_matches(), generator.iter_matches().count());
+
+    generator.set_dictionary_policy(DictionaryPolicy::All);
+    assert_eq!(generator.count_matches(), 1);
+
+    generator.reset_stats();
+    generator.count_matches();
+    assert_eq!(generator.stats().examined, 4);
+}
+
+#[test]
+fn test_profile_times_each_applicable_strategy() {
+    let dictionary = Dictionary::from_text("cat\ncot\nbat\ndog\n", &LoadOptions::default());
+    let slots = vec![
+        Slot::new(('a'..='z').collect()),
+        Slot::new(vec!['a', 'o', 'u']),
+        Slot::new(vec!['t', 'g']),
+    ];
+    let mut generator = WordGenerator::with_dictionary(slots, dictionary);
+    let profile = generator.profile(Duration::MAX);
+    assert_eq!(profile.combinations, 156);
+    assert_eq!(profile.automatic, generator.strategy());
+    assert_eq!(profile.timings.len(), Strategy::ALL.len());
+    for timing in &profile.timings {
+        assert!(timing.completed);
+        assert_eq!(timing.matches, 4);
+        assert_eq!(
+            timing.stats.examined + timing.stats.pruned,
+            156,
+            "{}",
+            timing.strategy
+        );
+    }
+    assert!(profile.fastest().is_some());
+    assert_eq!(generator.stats(), SolveStats::default());
+
+    // Case folding rules out the strategies that only find exact spellings
+    generator.set_match_options(MatchOptions {
+        case_insensitive: true,
+        ..MatchOptions::default()
+    });
+    let strategies: Vec<Strategy> = generator
+        .profile(Duration::MAX)
+        .timings
+        .iter()
+        .map(|timing| timing.strategy)
+        .collect();
+    assert_eq!(strategies, vec![Strategy::Enumerate]);
+
+    // A spent budget stops a large solve early
+    let generator = WordGenerator::with_no_filtering(vec![Slot::any(); 5]);
+    assert!(!generator.profile(Duration::ZERO).timings[0].completed);
+}
+
+#[test]
+fn test_embedded_dictionary_accepts_runtime_entries() {
+    let mut dictionary = Dictionary::embedded();
+    let embedded = dictionary.len();
+    assert!(dictionary.contains("cat"));
+
+    dictionary.insert("zorpq");
+    dictionary.merge(Dictionary::from_text(
+        "cat\tpos=noun\nqwxz\n",
+        &LoadOptions::default(),
+    ));
+    assert_eq!(dictionary.len(), embedded + 2);
+    assert!(dictionary.words_of_len(5).any(|word| word == "zorpq"));
+    assert!(!dictionary.get("cat").unwrap().parts_of_speech.is_empty());
+
+    // Changes stay in the dictionary that made them
+    assert_eq!(Dictionary::embedded().len(), embedded);
+}
+
+#[test]
+fn test_generators_share_the_embedded_dictionary() {
+    let slots = || {
+        vec![
+            Slot::new(vec!['z']),
+            Slot::new(vec!['o']),
+            Slot::new(vec!['r']),
+            Slot::new(vec!['p']),
+            Slot::new(vec!['q']),
+        ]
+    };
+    let mut first = WordGenerator::with_slots(slots());
+    let second = WordGenerator::with_slots(slots());
+    assert_eq!(
+        first.dictionary().unwrap().len(),
+        Dictionary::embedded().len()
+    );
+
+    // Words added for one generator stay out of the others and of later generators
+    first.dictionary_mut().unwrap().insert("zorpq");
+    assert_eq!(first.count_matches(), 1);
+    assert_eq!(second.count_matches(), 0);
+    assert_eq!(WordGenerator::with_slots(slots()).count_matches(), 0);
+    assert!(!Dictionary::embedded().contains("zorpq"));
+}
+
+#[test]
+fn test_interned_word_lists_share_entries() {
+    let path = std::env::temp_dir().join(format!("gallry-interned-{}.txt", std::process::id()));
+    std::fs::write(&path, "cat\ncot\ndog\n").unwrap();
+    let config = PuzzleConfig {
+        slots: vec!["cd".into(), "ao".into(), "tg".into()],
+        word_lists: vec![path.to_string_lossy().into()],
+        ..Default::default()
+    };
+
+    let interner = Interner::new();
+    let first = config.to_generator_interned(&interner).unwrap();
+    let second = config.to_generator_interned(&interner).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    // Both generators hold the same three entries, stored once
+    assert_eq!(interner.len(), 3);
+    let expected = vec!["cat", "cot", "dog"];
+    assert_eq!(first.iter().collect::<Vec<_>>(), expected);
+    assert_eq!(second.iter().collect::<Vec<_>>(), expected);
+
+    let options = LoadOptions {
+        interner: Some(interner.clone()),
+        ..Default::default()
+    };
+    let third = Dictionary::from_text("cat\nbat\n", &options);
+    assert_eq!(interner.len(), 4);
+    assert!(third.contains("cat") && third.contains("bat"));
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn test_large_word_list_loads_mapped() {
+    let path = std::env::temp_dir().join(format!("gallry-mapped-{}.txt", std::process::id()));
+    // Well over a megabyte, so the file is mapped rather than read
+    let words: Vec<String> = (0..200_000).map(|n| format!("word{:06}", n)).collect();
+    std::fs::write(&path, format!("{}\nZorp\tpos=noun\n", words.join("\n"))).unwrap();
+
+    let dictionary = Dictionary::from_path(&path.to_string_lossy(), &LoadOptions::default());
+    std::fs::remove_file(&path).unwrap();
+    let dictionary = dictionary.unwrap();
+    assert_eq!(dictionary.len(), words.len() + 1);
+    assert!(dictionary.contains("word123456"));
+    assert!(!dictionary.get("Zorp").unwrap().parts_of_speech.is_empty());
+}
+
+#[test]
+fn test_parallel_loading_matches_sequential() {
+    // Enough text to split between threads, with duplicates, metadata and proper nouns
+    let mut text = String::new();
+    for n in 0..40_000 {
+        text.push_str(&format!("w{}\n", n % 30_000));
+        if n % 1_000 == 0 {
+            text.push_str(&format!("Name{}\nw{}\trank=7\tpos=noun\n\n", n, n / 2));
+        }
+    }
+
+    for proper_nouns in [ProperNouns::Include, ProperNouns::Exclude] {
+        let sequential = LoadOptions {
+            proper_nouns,
+            rank_by_line_order: true,
+            ..Default::default()
+        };
+        let parallel = LoadOptions {
+            threads: 4,
+            ..sequential.clone()
+        };
+        let expected = Dictionary::from_text(&text, &sequential);
+        let dictionary = Dictionary::from_text(&text, &parallel);
+
+        assert_eq!(dictionary.len(), expected.len());
+        for len in 2..=7 {
+            assert!(dictionary.words_of_len(len).eq(expected.words_of_len(len)));
+        }
+        for word in expected.words_of_len(6) {
+            assert_eq!(dictionary.get(word), expected.get(word), "{}", word);
+        }
+    }
+}
+
+#[test]
+fn test_collect_bounded() {
+    let dictionary = Dictionary::from_text("cat\ncot\nbat\nbot\n", &LoadOptions::default());
+    let slots = vec![
+        Slot::new(vec!['c', 'b']),
+        Slot::new(vec!['a', 'o']),
+        Slot::new(vec!['t']),
+    ];
+    let generator = WordGenerator::with_dictionary(slots, dictionary);
+
+    let all = generator.collect_bounded(4, usize::MAX);
+    assert_eq!(all.truncated, None);
+    assert_eq!(all.matches, generator.iter_matches().collect::<Vec<_>>());
+
+    let few = generator.collect_bounded(3, usize::MAX);
+    assert_eq!(few.truncated, Some(Truncation::Results));
+    assert_eq!(few.matches[..], all.matches[..3]);
+
+    // Every match holds a three-letter word and the same dictionary name
+    let one = std::mem::size_of::<WordMatch>() + 3 + "#1".len();
+    let small = generator.collect_bounded(usize::MAX, 2 * one + 1);
+    assert_eq!(small.truncated, Some(Truncation::Bytes));
+    assert_eq!(small.matches.len(), 2);
+    assert!(generator.collect_bounded(usize::MAX, 0).matches.is_empty());
+}
+
+#[test]
+fn test_cursor_resumes_solving() {
+    let dictionary = Dictionary::from_text("cat\ncot\nbat\nbot\n", &LoadOptions::default());
+    let slots = vec![
+        Slot::new(vec!['c', 'b']),
+        Slot::new(vec!['a', 'o', 'u']),
+        Slot::new(vec!['t', 'g']),
+    ];
+    let generator = WordGenerator::with_dictionary(slots, dictionary);
+
+    let mut cursor = Cursor::new();
+    assert_eq!(cursor.position(), 0);
+    let first = generator.advance(&mut cursor, 1, u128::MAX);
+    assert_eq!(first.len(), 1);
+    assert_eq!(cursor.position(), 1);
+
+    // A combination budget stops a call short of the next match
+    assert!(generator.advance(&mut cursor, 10, 1).is_empty());
+    assert_eq!(cursor.position(), 2);
+
+    let mut matches = first;
+    while !cursor.is_done(&generator) {
+        matches.extend(generator.advance(&mut cursor, 1, 3));
+    }
+    assert_eq!(cursor.position(), generator.combination_count());
+    assert_eq!(matches, generator.iter_matches().collect::<Vec<_>>());
+    assert!(generator.advance(&mut cursor, 10, 10).is_empty());
+}
+
+#[test]
+fn test_constraints_checked_cheapest_first() {
+    let mut generator = WordGenerator::with_no_filtering(vec![
+        Slot::new(vec!['c', 'b']),
+        Slot::new(vec!['a', 'o']),
+        Slot::new(vec!['t']),
+    ]);
+    let pattern = Constraint::Regex("t$".parse().unwrap());
+    let prefix = Constraint::StartsWith("b".to_string());
+    generator.add_constraint(pattern.clone());
+    generator.add_constraint(prefix.clone());
+
+    // Registration order is kept for reporting, whatever order they are checked in
+    assert_eq!(generator.constraints(), [pattern.clone(), prefix.clone()]);
+    assert!(prefix.cost() < pattern.cost());
+    assert_eq!(generator.iter().collect::<Vec<_>>(), vec!["bat", "bot"]);
+
+    generator.clear_constraints();
+    assert_eq!(generator.iter().count(), 4);
+}
+
+#[test]
+fn test_substring_constraints_checked_together() {
+    let constraints = [
+        Constraint::Contains("ar".to_string()),
+        Constraint::Contains("t".to_string()),
+        Constraint::Contains("ar".to_string()),
+        Constraint::ExcludesLetters("xe".to_string()),
+        Constraint::StartsWith("p".to_string()),
+    ];
+    let options = |letters: &str| Slot::new(letters.chars().collect());
+    let mut generator = WordGenerator::with_no_filtering(vec![
+        options("ps"),
+        options("ae"),
+        options("r"),
+        options("tx"),
+        options("sy"),
+    ]);
+    for constraint in &constraints {
+        generator.add_constraint(constraint.clone());
+    }
+
+    let expected: Vec<String> = generator
+        .all_combinations()
+        .filter(|word| {
+            constraints
+                .iter()
+                .all(|constraint| constraint.accepts(word))
+        })
+        .collect();
+    assert_eq!(expected, vec!["parts", "party"]);
+    assert_eq!(generator.iter().collect::<Vec<_>>(), expected);
+}
+
+#[test]
+fn test_rhyme_constraint() {
+    assert!(rhymes("night", "Light"));
+    assert!(rhymes("free", "tree"));
+    assert!(!rhymes("bake", "back"));
+    assert_eq!(rhyme_key("rhythm"), "ythm");
+
+    let mut generator = WordGenerator::with_no_filtering(vec![
+        Slot::new(vec!['b', 'c', 'r']),
+        Slot::new(vec!['a', 'o']),
+        Slot::new(vec!['k', 't']),
+        Slot::new(vec!['e']),
+    ]);
+    generator.add_constraint(Constraint::RhymesWith("lake".to_string()));
+    assert_eq!(
+        generator.iter().collect::<Vec<_>>(),
+        vec!["bake", "cake", "rake"]
+    );
+}
+
+#[test]
+fn test_syllable_constraint() {
+    let counts: Vec<usize> = ["cat", "table", "bake", "baked", "boxes", "banana", "the"]
+        .iter()
+        .map(|word| syllable_count(word))
+        .collect();
+    assert_eq!(counts, vec![1, 2, 1, 1, 2, 3, 1]);
+
+    let config = PuzzleConfig::from_toml_str(
+        "slots = [\"cm\", \"a\", \"k\", \"e\", \"rs\"]\n[[constraints]]\nsyllables = 2\n",
+    )
+    .unwrap();
+    let generator = config.to_generator().unwrap();
+    assert_eq!(generator.iter().collect::<Vec<_>>(), vec!["maker"]);
+}
+
+#[test]
+fn test_slot_notation() {
+    let slot: Slot = "![a-w]yz\\!".parse().unwrap();
+    assert!(slot.is_wildcard());
+    let generator = WordGenerator::with_no_filtering(vec![slot]);
+    assert_eq!(generator.iter().collect::<Vec<_>>(), vec!["x"]);
+
+    let slots = parse_slots(&["b", "[a-ce]", "\\[-"]).unwrap();
+    assert_eq!(slots[1].options(), ['a', 'b', 'c', 'e']);
+    assert_eq!(slots[2].options(), ['[', '-']);
+
+    for (set, error) in [
+        ("", "a set needs at least one character"),
+        ("a?", "'?' at character 2 must be the whole set"),
+        ("![ab", "unclosed '[' at character 2"),
+        ("[z-a]", "range 'z-a' at character 3 is reversed"),
+        ("[]", "empty '[]' at character 1"),
+        ("ab\\", "'\\' at character 3 escapes nothing"),
+    ] {
+        let message = set.parse::<Slot>().unwrap_err();
+        assert!(message.starts_with(error), "{}: {}", set, message);
+    }
+}
+
+#[test]
+fn test_parallel_matches_keep_sequential_order() {
+    let mut generator = WordGenerator::with_slots(vec![
+        Slot::new("bcdfhlmprst".chars().collect()),
+        Slot::any(),
+        Slot::new(vec!['a', 'e', 'i', 'o', 'u']),
+        Slot::new(vec!['t', 'd', 'n', 's']),
+    ]);
+    generator.add_constraint(Constraint::ExcludesLetters("q".to_string()));
+
+    let sequential: Vec<WordMatch> = generator.iter_matches().collect();
+    assert!(sequential.len() > 20);
+    for threads in [0, 1, 3, 8, 64] {
+        assert_eq!(
+            generator.par_matches(threads),
+            sequential,
+            "{} threads",
+            threads
+        );
+    }
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn test_parallel_matches_stream() {
+    use std::io::{BufRead, BufReader};
+    use std::process::{Command, Stdio};
+    use std::time::Duration;
+
+    // Enumerating every nine-letter combination would take hours, but the first
+    // combination is a word
+    let alphabet: String = ('a'..='z').collect();
+    let dictionary = Dictionary::from_text("aaaaaaaaa\nzzzzzzzzz\n", &LoadOptions::default());
+    let mut generator =
+        WordGenerator::with_dictionary(vec![Slot::new(alphabet.chars().collect()); 9], dictionary);
+    generator.set_strategy(Some(Strategy::Enumerate));
+    let first = generator.par_iter_matches(4).next().unwrap();
+    assert_eq!(first.word, "aaaaaaaaa");
+    assert!(u128::from(generator.stats().examined) < generator.combination_count() / 1000);
+
+    // The command line writes the first NDJSON line while the search goes on
+    let path = std::env::temp_dir().join(format!("gallry-stream-{}.txt", std::process::id()));
+    std::fs::write(&path, "aaaaaaaaa\nzzzzzzzzz\n").unwrap();
+    let mut child = Command::new(env!("CARGO_BIN_EXE_gallery-puzzle-soulver"))
+        .args([
+            "solve",
+            "--no-config",
+            "--strategy",
+            "enumerate",
+            "--threads",
+            "4",
+        ])
+        .args(["--output", "ndjson", "-w", path.to_str().unwrap()])
+        .args(std::iter::repeat_n(alphabet.as_str(), 9))
+        .env(
+            "GALLRY_SOCKET",
+            std::env::temp_dir().join("gallry-no-daemon.sock"),
+        )
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+    let stdout = child.stdout.take().unwrap();
+    let (sender, receiver) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let mut line = String::new();
+        BufReader::new(stdout).read_line(&mut line).unwrap();
+        sender.send(line).ok();
+    });
+    let line = receiver.recv_timeout(Duration::from_secs(60));
+    let still_running = child.try_wait().unwrap().is_none();
+    child.kill().ok();
+    child.wait().unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(
+        line.expect("no NDJSON line within a minute")
+            .contains("\"aaaaaaaaa\"")
+    );
+    assert!(still_running);
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn test_verbosity_flags_keep_option_values() {
+    use std::process::Command;
+
+    // `-v` after --format is its template, while -vv and -q are verbosity flags
+    let output = Command::new(env!("CARGO_BIN_EXE_gallery-puzzle-soulver"))
+        .args([
+            "-vv",
+            "solve",
+            "--no-config",
+            "--no-daemon",
+            "--format",
+            "-v",
+        ])
+        .args(["--limit", "2", "cbr", "aio", "tse", "-q"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "-v\n-v\n");
+    // --quiet wins over -vv
+    assert!(output.stderr.is_empty());
+
+    // Without a subcommand the options are those of solve
+    let output = Command::new(env!("CARGO_BIN_EXE_gallery-puzzle-soulver"))
+        .args([
+            "--no-config",
+            "--no-daemon",
+            "--limit",
+            "1",
+            "--format",
+            "-q",
+        ])
+        .args(["cbr", "aio", "tse"])
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "-q\n");
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn test_print0_ends_every_result_line() {
+    use std::process::Command;
+
+    let solve = |mode: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_gallery-puzzle-soulver"))
+            .args(["solve", "--no-config", "--no-daemon", "--print0"])
+            .args(mode)
+            .args(["cb", "ao", "t"])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+    assert_eq!(solve(&[]), "cat\0cot\0bat\0bot\0");
+    assert_eq!(solve(&["--prune"]), "slot 1: c,b\0slot 2: a,o\0slot 3: t\0");
+    assert_eq!(
+        solve(&["--group-by", "first-letter"]),
+        "b (2 matches)\0  bat\0  bot\0c (2 matches)\0  cat\0  cot\0"
+    );
+}
+
+#[test]
+fn test_solve_stats() {
+    let dictionary = Dictionary::from_text("cat\nbat\ncot\n", &LoadOptions::default());
+    let mut generator = WordGenerator::with_dictionary(
+        vec![
+            Slot::new(vec!['c', 'b', 'r']),
+            Slot::new(vec!['a', 'o']),
+            Slot::new(vec!['t']),
+        ],
+        dictionary,
+    );
+    generator.add_constraint(Constraint::ExcludesLetters("r".to_string()));
+    assert_eq!(generator.stats(), SolveStats::default());
+
+    assert_eq!(generator.iter().count(), 3);
+    let sequential = generator.stats();
+    assert_eq!(sequential.examined, 6);
+    assert_eq!(sequential.dictionary_hits, 3);
+
+    // Abandoned iterators count the work they did
+    generator.reset_stats();
+    assert_eq!(generator.iter().next().as_deref(), Some("cat"));
+    assert_eq!(generator.stats().examined, 1);
+
+    generator.reset_stats();
+    generator.par_matches(4);
+    assert_eq!(generator.stats(), sequential);
+}
+
+#[cfg(feature = "instrument")]
+#[test]
+fn test_instrumented_solve_emits_events() {
+    use std::sync::{Arc, Mutex};
+
+    /// Collects formatted events in memory
+    #[derive(Clone)]
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let captured = Captured(Arc::new(Mutex::new(Vec::new())));
+    let writer = captured.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::TRACE)
+        .with_writer(move || writer.clone())
+        .finish();
+    tracing::subscriber::with_default(subscriber, || {
+        let dictionary = Dictionary::from_text("cat\ncot\n", &LoadOptions::default());
+        let generator = WordGenerator::with_dictionary(
+            vec![Slot::new(vec!['c']), Slot::any(), Slot::new(vec!['t'])],
+            dictionary,
+        );
+        assert_eq!(generator.count_matches(), 2);
+    });
+
+    let output = String::from_utf8(captured.0.lock().unwrap().clone()).unwrap();
+    assert!(output.contains("parsed word list"), "{}", output);
+    assert!(output.contains("words=2"), "{}", output);
+    assert!(output.contains("resolved wildcard slots"), "{}", output);
+    assert!(output.contains("solve ended"), "{}", output);
+}
+
+#[test]
+fn test_verify() {
+    let dictionary = Dictionary::from_text("cat\nbat\ncot\nrot\n", &LoadOptions::default());
+    let mut generator = WordGenerator::with_dictionary(
+        vec![
+            Slot::new(vec!['c', 'b', 'r']),
+            Slot::new(vec!['a', 'o']),
+            Slot::new(vec!['t']),
+        ],
+        dictionary,
+    );
+    generator.add_constraint(Constraint::Contains("o".to_string()));
+    generator.add_constraint(Constraint::ExcludesLetters("r".to_string()));
+    generator.set_blocklist(Blocklist::from_text("cot"));
+
+    assert_eq!(generator.verify("bot"), Err(Rejection::NotInWordLists));
+    assert_eq!(generator.verify("cot"), Err(Rejection::Blocked));
+    // The first violated constraint is reported
+    assert_eq!(
+        generator.verify("rat"),
+        Err(Rejection::Constraint(Constraint::Contains("o".to_string())))
+    );
+    assert_eq!(
+        generator.verify("rot"),
+        Err(Rejection::Constraint(Constraint::ExcludesLetters(
+            "r".to_string()
+        )))
+    );
+    assert_eq!(
+        generator.verify("cit"),
+        Err(Rejection::Slot {
+            position: 1,
+            letter: 'i'
+        })
+    );
+    assert_eq!(
+        generator.verify("ca"),
+        Err(Rejection::Length {
+            expected: 3,
+            found: 2
+        })
+    );
+    assert_eq!(generator.stats(), SolveStats::default());
+
+    generator.clear_constraints();
+    assert_eq!(generator.verify("rot").unwrap().dictionaries, vec!["#1"]);
+}
+
+#[test]
+fn test_wordle_feedback() {
+    assert_eq!(Feedback::score("speed", "abide").to_string(), "..y.y");
+    assert_eq!(Feedback::score("speed", "erase").to_string(), "y.yy.");
+    assert_eq!(Feedback::score("abbey", "abbey").to_string(), "ggggg");
+    assert!(Feedback::score("abbey", "abbey").is_solved());
+
+    // Guesses with repeated letters split the candidates as their feedback does
+    let candidates = ["erase", "abide", "steed", "sweet"];
+    let groups: HashSet<String> = candidates
+        .iter()
+        .map(|answer| Feedback::score("speed", answer).to_string())
+        .collect();
+    assert_eq!(groups.len(), 4);
+    assert_eq!(expected_information("speed", &candidates), 2.0);
+    assert_eq!(
+        best_guess(&["fluff", "speed"], &candidates).unwrap().0,
+        "speed"
+    );
+}
+
+#[test]
+fn test_parlor_puzzles() {
+    let boxes = [
+        "blue: in this",
+        "white: empty black",
+        "black: in white or in blue",
+    ];
+    let puzzle = ParlorPuzzle::parse(&boxes).unwrap();
+    let solutions = puzzle.solutions().unwrap();
+    assert_eq!(solutions.len(), 1);
+    assert_eq!(
+        solutions[0].truths,
+        vec![vec![false], vec![true], vec![true]]
+    );
+    assert_eq!(puzzle.answer(), Some(1));
+
+    // Statements about other boxes' truth, with one box showing two statements
+    let boxes = [
+        "Blue: true white",
+        "White: false blue; in this",
+        "Black: empty this",
+    ];
+    let puzzle = ParlorPuzzle::parse(&boxes).unwrap();
+    let solutions = puzzle.solutions().unwrap();
+    assert_eq!(puzzle.answer(), Some(0));
+    assert_eq!(solutions[0].box_truth(1), None);
+
+    // Every box could hold the prize when each reading leaves a true and a false box
+    let symmetric = ["blue: in this", "white: in this", "black: empty blue"];
+    assert_eq!(ParlorPuzzle::parse(&symmetric).unwrap().answer(), None);
+    // Two boxes that are always true break the usual rules, but not relaxed ones
+    let honest = ParlorPuzzle::parse(&["a: in a or in b", "b: in b or in a"]).unwrap();
+    assert!(honest.solutions().unwrap().is_empty());
+    assert_eq!(honest.with_rules(1, 0).solutions().unwrap().len(), 2);
+
+    let error = ParlorPuzzle::parse(&["blue: in red"]).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Invalid box 'blue': no box is named 'red'"
+    );
+    assert!(ParlorPuzzle::parse(&["blue: not (in this"]).is_err());
+    assert!(ParlorPuzzle::parse(&["blue: in this", "Blue: empty this"]).is_err());
+}
+
+#[test]
+fn test_dartboard_throws() {
+    let parse = |throws: &[&str]| -> Vec<DartThrow> {
+        throws.iter().map(|throw| throw.parse().unwrap()).collect()
+    };
+    assert_eq!(
+        score_throws(&parse(&["blue:7", "pink:3", "-1", "purple:4"])),
+        Some(5)
+    );
+    assert_eq!(score_throws(&[]), Some(0));
+    assert!("green:3".parse::<DartThrow>().is_err());
+    assert!("+x".parse::<DartThrow>().is_err());
+
+    // Uneven divisions and overflow give no number
+    assert_eq!(score_throws(&parse(&["+7", "/2"])), None);
+    assert_eq!(score_throws(&parse(&["+7", "/0"])), None);
+    assert_eq!(score_throws(&parse(&["-9223372036854775807", "-2"])), None);
+
+    let found = throws_for(40, 2, &DARTBOARD_SEGMENTS, &DartOp::ALL);
+    assert!(!found.is_empty());
+    assert!(found.iter().all(|throws| throws.len() == 2));
+    assert!(found.iter().all(|throws| score_throws(throws) == Some(40)));
+    assert!(throws_for(40, 1, &DARTBOARD_SEGMENTS, &DartOp::ALL).is_empty());
+    assert_eq!(throws_for(7, 1, &[7], &DartOp::ALL), vec![parse(&["+7"])]);
+}
+
+#[test]
+fn test_digit_codes() {
+    let slots = parse_digit_slots(&["13579", "?", "[2-4]"]).unwrap();
+    let mut generator = WordGenerator::with_no_filtering(slots);
+    generator.add_constraint(Constraint::DigitSum(10));
+    generator.add_constraint(Constraint::Ordered(SequenceOrder::Increasing));
+    assert_eq!(generator.iter().collect::<Vec<_>>(), vec!["334"]);
+
+    let mut generator = WordGenerator::with_no_filtering(parse_digit_slots(&["?"; 3]).unwrap());
+    generator.add_constraint(Constraint::Ordered(SequenceOrder::StrictlyDecreasing));
+    generator.add_constraint(Constraint::EvenDigits(3));
+    assert_eq!(generator.iter().count(), 10);
+    assert!(
+        generator
+            .iter()
+            .all(|code| code.chars().all(|c| c.is_ascii_digit()))
+    );
+
+    assert!(!Constraint::Ordered(SequenceOrder::StrictlyIncreasing).accepts("1223"));
+    assert!(Constraint::Ordered(SequenceOrder::Increasing).accepts("1223"));
+    let error = parse_digit_slots(&["12", "[0-b]"]).unwrap_err();
+    assert!(error.to_string().starts_with("Invalid slot 2 '[0-b]':"));
+
+    // Digit constraints are written in puzzle files like any other
+    let config = PuzzleConfig::from_toml_str(
+        "slots = [\"12\", \"34\"]\n\
+         [[constraints]]\ndigit-sum = 5\n\
+         [[constraints]]\nordered = \"strictly-increasing\"\n",
+    )
+    .unwrap();
+    assert_eq!(
+        config.constraints[1].to_string(),
+        "ordered = \"strictly-increasing\""
+    );
+}
+
+#[test]
+fn test_a1z26_cipher() {
+    let encoded = a1z26_encode("The Gallery");
+    assert_eq!(encoded, "20-8-5 7-1-12-12-5-18-25");
+    assert_eq!(a1z26_decode(&encoded).unwrap(), "the gallery");
+    assert!(a1z26_decode("3 0 1").is_err());
+
+    // Run-together numbers have several readings, narrowed by the dictionary
+    let readings = a1z26_readings("1 2 5").unwrap();
+    assert_eq!(readings, vec!["abe", "ay", "le"]);
+    assert_eq!(a1z26_readings("12 5").unwrap(), readings);
+    assert!(a1z26_readings(&"1".repeat(40)).is_err());
+
+    let dictionary = Dictionary::from_text("ay\nle\nbake\nabe\nrook\n", &LoadOptions::default());
+    assert_eq!(a1z26_words("125", &dictionary), vec!["abe", "ay", "le"]);
+    assert_eq!(a1z26_words("2 1 11 5", &dictionary), vec!["bake"]);
+    let rook = a1z26_encode("rook").replace('-', "");
+    assert_eq!(a1z26_words(&rook, &dictionary), vec!["rook"]);
+    assert!(a1z26_words("", &dictionary).is_empty());
+}
+
+#[test]
+fn test_caesar_decryption() {
+    let plaintext = "Meet me in the Drafting Studio at noon";
+    assert_eq!(caesar_shift(&caesar_shift(plaintext, 13), 13), plaintext);
+
+    let dictionary = Dictionary::embedded();
+    let decryptions = caesar_decryptions(&caesar_shift(plaintext, 7), &dictionary);
+    assert_eq!(decryptions.len(), 26);
+    assert_eq!(decryptions[0].shift, 7);
+    assert_eq!(decryptions[0].plaintext, plaintext);
+    assert!(decryptions[0].score.coverage > 0.9);
+    assert!(
+        decryptions
+            .windows(2)
+            .all(|pair| pair[0].score.compare(&pair[1].score).is_ge())
+    );
+
+    // Without spaces there are no words to find, and letter frequencies decide
+    let unspaced = caesar_shift("thekeyisunderthestaircase", 19);
+    assert_eq!(caesar_decryptions(&unspaced, &dictionary)[0].shift, 19);
+    assert_eq!(score_plaintext("1234", &dictionary).coverage, 0.0);
+}
+
+#[test]
+fn test_cryptogram_solving() {
+    let dictionary = Dictionary::from_text(
+        "the\nbig\ndoor\nsees\nseek\nfeet\nmoon\nroom\npool\nhall\nkey\nred\n",
+        &LoadOptions::default(),
+    );
+    // "Seek the red door" under the key s=q e=x k=b t=i h=o r=p d=u o=m
+    let cryptogram = Cryptogram::new("Qxxb iox pxu UMMP!");
+    let solutions = cryptogram.clone().solve(&dictionary);
+    assert_eq!(solutions.len(), 1);
+    assert_eq!(solutions[0].plaintext, "Seek the red DOOR!");
+    assert_eq!(solutions[0].key.len(), 8);
+    assert_eq!(solutions[0].score.coverage, 1.0);
+
+    // Without the other words, the repeated letters alone leave several readings
+    let ambiguous = Cryptogram::new("umml").solve(&dictionary);
+    let plaintexts: HashSet<&str> = ambiguous.iter().map(|s| s.plaintext.as_str()).collect();
+    assert_eq!(
+        plaintexts,
+        HashSet::from(["door", "moon", "room", "pool", "seek", "feet"])
+    );
+    let hinted = Cryptogram::new("umml")
+        .with_hint('u', 'm')
+        .unwrap()
+        .solve(&dictionary);
+    assert_eq!(hinted[0].plaintext, "moon");
+    assert!(
+        Cryptogram::new("umml")
+            .with_hint('u', 'm')
+            .unwrap()
+            .with_hint('x', 'm')
+            .is_err()
+    );
+    assert_eq!(
+        Cryptogram::new("umml")
+            .with_max_solutions(2)
+            .solve(&dictionary)
+            .len(),
+        2
+    );
+    assert!(Cryptogram::new("abcdefghij").solve(&dictionary).is_empty());
+}
+
+#[test]
+fn test_acrostics() {
+    let lines = ["Hourglass", "", "  old key", "Rapier", "Sextant", "Easel!"];
+    let initials = Acrostic::extract(&lines, 0).unwrap();
+    assert_eq!(initials.letters(), "horse");
+    assert_eq!(Acrostic::extract(&lines, 1).unwrap().letters(), "olaea");
+    let error = Acrostic::extract(&["cat", "ox"], 2).unwrap_err();
+    assert_eq!(error.to_string(), "Line 2 'ox' has no letter 3");
+    assert!(Acrostic::extract(&[""; 2], 0).is_err());
+
+    let words = "horse\nshore\nhoser\nhorses\n";
+    let dictionary = Dictionary::from_text(words, &LoadOptions::default());
+    let mut generator = WordGenerator::with_dictionary(Vec::new(), dictionary);
+    initials.apply(&mut generator, AcrosticMode::Sequence);
+    assert_eq!(generator.iter().collect::<Vec<_>>(), vec!["horse"]);
+
+    generator.set_slots(initials.slots(AcrosticMode::Bag));
+    generator.add_constraint(initials.constraint(AcrosticMode::Bag).unwrap());
+    assert_eq!(
+        generator.iter().collect::<Vec<_>>(),
+        vec!["horse", "hoser", "shore"]
+    );
+    assert!(Constraint::AnagramOf("Shore".to_string()).accepts("horse"));
+    assert!(!Constraint::AnagramOf("shore".to_string()).accepts("horses"));
+}
+
+#[test]
+fn test_keypad_decoding() {
+    let slots = parse_keypad_slots("7?9").unwrap();
+    assert_eq!(slots[0].options(), ['p', 'q', 'r', 's']);
+    assert!(slots[1].is_wildcard());
+    assert_eq!(slots[2].options().len(), KEYPAD_LETTERS[9].len());
+
+    let words = "good\nhome\ngone\nhood\nhoney\n";
+    let dictionary = Dictionary::from_text(words, &LoadOptions::default());
+    let slots = parse_keypad_slots("4663").unwrap();
+    let generator = WordGenerator::with_dictionary(slots, dictionary);
+    assert_eq!(
+        generator.iter().collect::<Vec<_>>(),
+        vec!["gone", "good", "home", "hood"]
+    );
+
+    let error = parse_keypad_slots("2103").unwrap_err();
+    assert_eq!(error.to_string(), "Key 2 '1' has no letters");
+    assert!(parse_keypad_slots("2a").is_err());
+}
+
+#[test]
+fn test_vigenere_decryption() {
+    let plaintext = "When the clock in the great hall strikes nine, open the drawer beneath the \
+                     portrait of the old baron and take the silver key that is hidden inside.";
+    let ciphertext = vigenere_encrypt(plaintext, "Mora").unwrap();
+    assert_eq!(vigenere_decrypt(&ciphertext, "mora").unwrap(), plaintext);
+    assert_eq!(vigenere_decrypt(&ciphertext, "m0ra"), None);
+
+    // Long enough for letter frequencies to give the key away
+    let dictionary = Dictionary::embedded();
+    let decryptions = vigenere_decryptions(&ciphertext, &dictionary, &VigenereOptions::default());
+    // One key per length, less "moramora" which only repeats the key
+    assert_eq!(decryptions.len(), 7);
+    assert_eq!(decryptions[0].key, "mora");
+    assert_eq!(decryptions[0].plaintext, plaintext);
+
+    let options = VigenereOptions {
+        max_key_len: 3,
+        max_results: 2,
+        ..Default::default()
+    };
+    let short = vigenere_decryptions(&ciphertext, &dictionary, &options);
+    assert_eq!(short.len(), 2);
+    assert!(short[0].score.coverage < decryptions[0].score.coverage);
+    assert!(vigenere_decryptions("1234", &dictionary, &options).is_empty());
+}
+
+#[test]
+fn test_calendar_dates() {
+    let parse = |date: &str| date.parse::<CalendarDate>().unwrap();
+    assert_eq!(parse("March 5, 1923"), parse("1923-03-05"));
+    assert_eq!(parse("5 Mar"), CalendarDate::new(None, 3, 5).unwrap());
+    assert_eq!(parse("2024-12-31").day_of_year(), 366);
+    assert_eq!(parse("12-31").day_of_year(), 365);
+    assert_eq!(parse("2000-01-01").weekday_name(), Some("saturday"));
+    assert_eq!(parse("1900-03-01").weekday_name(), Some("thursday"));
+    assert_eq!(parse("Feb 29").to_string(), "02-29");
+    assert!("1900-02-29".parse::<CalendarDate>().is_err());
+    assert!("Smarch 5".parse::<CalendarDate>().is_err());
+
+    let date = parse("2024-02-01");
+    assert_eq!(date.letter(DateLetter::MonthInitial), Some('f'));
+    assert_eq!(date.letter(DateLetter::MonthNumber), Some('b'));
+    assert_eq!(date.letter(DateLetter::DayOfYear), Some('f'));
+    assert_eq!(date.letter(DateLetter::WeekdayInitial), Some('t'));
+    assert_eq!(parse("Feb 1").letter(DateLetter::WeekdayInitial), None);
+    assert_eq!(parse("Jan 30").letter(DateLetter::DayOfMonth), None);
+
+    // The initials of the months spell a word, or the day of the month stands in
+    let dates = [parse("Jan 20"), parse("Apr 15"), parse("1923-03-20")];
+    let readings = [DateLetter::MonthInitial, DateLetter::DayOfMonth];
+    let slots = date_slots(&dates, &readings).unwrap();
+    assert_eq!(slots[0].options(), ['j', 't']);
+    let words: Vec<String> = WordGenerator::with_slots(slots).iter().collect();
+    assert!(words.contains(&"jam".to_string()) && words.contains(&"tot".to_string()));
+    let error = date_slots(&[parse("Jan 30")], &[DateLetter::DayOfMonth]).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Date 01-30 stands for no letter under the chosen readings"
+    );
+}
+
+#[cfg(feature = "presets")]
+#[test]
+fn test_puzzle_presets() {
+    for name in PuzzleConfig::preset_names() {
+        let config = PuzzleConfig::preset(name).unwrap();
+        assert!(
+            config.metadata.name.is_some(),
+            "preset '{}' has no name",
+            name
+        );
+        assert!(
+            config.word_lists.is_empty(),
+            "preset '{}' refers to files",
+            name
+        );
+        config.to_generator().unwrap();
+    }
+
+    let error = PuzzleConfig::preset("east-wing").unwrap_err().to_string();
+    assert!(error.starts_with("Unknown preset 'east-wing'"));
+}
+
+#[test]
+fn test_number_words_and_roman_numerals() {
+    assert_eq!(spelled_number_value("eighteen"), Some(18));
+    assert_eq!(spelled_number_value("Ninety-Nine"), Some(99));
+    assert_eq!(spelled_number_value("threehundredandsixtyfive"), Some(365));
+    assert_eq!(
+        spelled_number_value("one million two thousand and one"),
+        Some(1_002_001)
+    );
+    assert_eq!(spelled_number_value("thousand"), None);
+    assert_eq!(spelled_number_value("one thousand one million"), None);
+    assert_eq!(spelled_number_value("and one"), None);
+    assert_eq!(spelled_number_value(""), None);
+
+    assert_eq!(roman_numeral_value("MCMXCIX"), Some(1999));
+    assert_eq!(roman_numeral_value("dim"), None);
+    assert_eq!(roman_numeral_value("IIII"), None);
+    assert_eq!(roman_numeral_value("IC"), None);
+    assert_eq!(roman_numeral(3999).as_deref(), Some("MMMCMXCIX"));
+    assert_eq!(roman_numeral(4000), None);
+
+    let dictionary = Dictionary::from_text(
+        "ten\nnine\nvine\nmix\nmid\ncivil\nlid\ndix\n",
+        &LoadOptions::default(),
+    );
+    let words = |constraint: Constraint| -> Vec<String> {
+        let slots = vec![Slot::any(); 3];
+        let mut generator = WordGenerator::with_dictionary(slots, dictionary.clone());
+        generator.add_constraint(constraint);
+        generator.iter().collect()
+    };
+    assert_eq!(words(Constraint::SpelledNumber(NumberTarget::Any)), ["ten"]);
+    assert!(words(Constraint::SpelledNumber(NumberTarget::Value(9))).is_empty());
+    assert_eq!(
+        words(Constraint::RomanNumeral(NumberTarget::Any)),
+        ["dix", "mix"]
+    );
+    assert_eq!(
+        words(Constraint::RomanNumeral(NumberTarget::Value(1009))),
+        ["mix"]
+    );
+
+    let config = PuzzleConfig::from_toml_str(
+        "slots = [\"n\", \"i\", \"n\", \"e\"]\n\
+         [[constraints]]\nspelled-number = 9\n\
+         [[constraints]]\nroman-numeral = \"any\"\n",
+    )
+    .unwrap();
+    assert_eq!(
+        config.constraints,
+        vec![
+            Constraint::SpelledNumber(NumberTarget::Value(9)),
+            Constraint::RomanNumeral(NumberTarget::Any),
+        ]
+    );
+    assert_eq!(config.constraints[1].to_string(), "roman-numeral = \"any\"");
+    assert_eq!("any".parse::<NumberTarget>(), Ok(NumberTarget::Any));
+    assert!("many".parse::<NumberTarget>().is_err());
+}
+
+#[test]
+fn test_morse_code() {
+    assert_eq!(morse_encode("Hi, you"), ".... .. / -.-- --- ..-");
+
+    let spaced = MorseCode::parse(".-- ? ... -", MorseSpacing::Letters).unwrap();
+    assert_eq!(spaced.lengths(), [4]);
+    let slots = spaced.slots(4).unwrap();
+    assert_eq!(slots[0].options(), ['w']);
+    assert!(slots[1].is_wildcard());
+    assert!(spaced.slots(3).is_none());
+    assert!(spaced.constraint().is_none());
+    assert!(MorseCode::parse("... ------", MorseSpacing::Letters).is_err());
+    assert!(MorseCode::parse("..x", MorseSpacing::Runs).is_err());
+    assert!(MorseCode::parse("..?", MorseSpacing::Runs).is_err());
+    assert!(MorseCode::parse(" ", MorseSpacing::Runs).is_err());
+
+    // "-...-" could be "bt", "nu", "tv", "tat" and more, but never one letter
+    let runs = MorseCode::parse("-...-", MorseSpacing::Runs).unwrap();
+    assert_eq!(runs.lengths(), [2, 3, 4, 5]);
+    let slots = runs.slots(2).unwrap();
+    assert_eq!(slots[0].options(), ['b', 'd', 'n', 't']);
+    assert_eq!(slots[1].options(), ['a', 't', 'u', 'v']);
+
+    let dictionary = Dictionary::from_text("bt\ntv\nta\nnu\ndat\ntat\n", &LoadOptions::default());
+    let mut generator = WordGenerator::with_dictionary(Vec::new(), dictionary.clone());
+    assert!(runs.apply(&mut generator, 2));
+    assert_eq!(generator.iter().collect::<Vec<_>>(), ["bt", "nu", "tv"]);
+
+    // A gap must fall between letters, so "t" and "v" cannot both come from "-..."
+    let gapped = MorseCode::parse("- ...-", MorseSpacing::Runs).unwrap();
+    let mut generator = WordGenerator::with_dictionary(Vec::new(), dictionary);
+    assert!(gapped.apply(&mut generator, 2));
+    assert_eq!(generator.iter().collect::<Vec<_>>(), ["tv"]);
+    assert!(Constraint::Morse("- ...-".to_string()).accepts("tv"));
+    assert!(!Constraint::Morse("- ...-".to_string()).accepts("bt"));
+    assert!(Constraint::Morse("? ..".to_string()).accepts("qi"));
+}
+
+#[test]
+fn test_symbol_tables() {
+    let table = SymbolTable::from_text(
+        "# sigils from the west wing\n\
+         sun = t\n\
+         moon = h\n\
+         star = ae\n\
+         key = ?\n",
+    )
+    .unwrap();
+    assert_eq!(table.letters("star"), Some(&['a', 'e'][..]));
+    assert_eq!(table.letters("key"), None);
+    assert!(SymbolTable::from_text("sun t\n").is_err());
+    assert!(SymbolTable::from_text("sun = 7\n").is_err());
+
+    let message = ["sun", "moon", "star", "key"];
+    let slots = table.slots(&message);
+    assert_eq!(slots[2].options(), ['a', 'e']);
+    // Unknown symbols cannot be letters already known to belong to another
+    assert_eq!(slots[3].options().len(), 24);
+    assert!(!slots[3].options().contains(&'t'));
+
+    let dictionary = Dictionary::from_text("that\nthen\nthem\nthee\n", &LoadOptions::default());
+    let mut generator = WordGenerator::with_dictionary(Vec::new(), dictionary.clone());
+    table.apply(&mut generator, &message);
+    assert_eq!(generator.iter().collect::<Vec<_>>(), ["them", "then"]);
+
+    // The same symbol twice must be the same letter
+    let repeated = ["sun", "moon", "key", "sun"];
+    assert_eq!(
+        table.constraint(&repeated),
+        Some(Constraint::RepeatPattern("abca".to_string()))
+    );
+    let mut generator = WordGenerator::with_dictionary(Vec::new(), dictionary);
+    table.apply(&mut generator, &repeated);
+    assert_eq!(generator.iter().collect::<Vec<_>>(), ["that"]);
+    assert!(table.constraint(&["sun", "moon"]).is_none());
+    assert!(!Constraint::RepeatPattern("abca".to_string()).accepts("thee"));
+}
+
+#[test]
+fn test_chess_boards() {
+    let board = LetterGrid::from_rows(&["r o o k", "w i n g", "h a t e", "s p e d"]).unwrap();
+    assert_eq!((board.width(), board.height()), (4, 4));
+
+    let square: Square = "d4".parse().unwrap();
+    assert_eq!(square, Square { file: 3, rank: 3 });
+    assert_eq!(square.letter(&board), Some('k'));
+    assert!("e0".parse::<Square>().is_err());
+    assert!("44".parse::<Square>().is_err());
+
+    let slots = chess_slots(&board, &["a2", "b3", "?1"]).unwrap();
+    assert_eq!(slots[0].options(), ['h']);
+    assert_eq!(slots[1].options(), ['i']);
+    assert_eq!(slots[2].options(), ['d', 'e', 'p', 's']);
+    assert!(chess_slots(&board, &["e1"]).is_err());
+    assert!(chess_slots(&board, &["b"]).is_err());
+
+    let dictionary = Dictionary::from_text("hid\nhis\nhip\nsit\ntog\n", &LoadOptions::default());
+    let generator = WordGenerator::with_dictionary(slots, dictionary.clone());
+    assert_eq!(generator.iter().collect::<Vec<_>>(), ["hid", "hip", "his"]);
+
+    // "sit" is on the board, but no knight can hop from its i to its t
+    let paths = knight_words(&board, None, 3, &dictionary);
+    let words: Vec<&str> = paths.iter().map(|path| path.word.as_str()).collect();
+    assert_eq!(words, ["tog"]);
+    let squares: Vec<String> = paths[0].squares.iter().map(Square::to_string).collect();
+    assert_eq!(squares, ["c2", "b4", "d3"]);
+    assert!(knight_words(&board, Some("a1".parse().unwrap()), 3, &dictionary).is_empty());
+
+    let slots = knight_slots(&board, Some("a1".parse().unwrap()), 2);
+    assert_eq!(slots[0].options(), ['s']);
+    assert_eq!(slots[1].options(), ['i', 't']);
+}
+
+#[test]
+fn test_word_search() {
+    let grid = LetterGrid::from_text(
+        "s t a r\n\
+         x o w e\n\
+         p e n d\n\
+         q l a b\n",
+    )
+    .unwrap();
+    let dictionary = Dictionary::from_text(
+        "star\nrats\nsoda\npen\nend\nred\nstow\nowe\nten\nson\n",
+        &LoadOptions::default(),
+    );
+
+    let found = word_search(&grid, &dictionary, &WordSearchOptions::default());
+    let words: Vec<(&str, usize, usize, Direction)> = found
+        .iter()
+        .map(|found| {
+            (
+                found.word.as_str(),
+                found.row,
+                found.column,
+                found.direction,
+            )
+        })
+        .collect();
+    assert_eq!(
+        words,
+        [
+            ("rats", 0, 3, Direction::Left),
+            ("star", 0, 0, Direction::Right),
+            ("end", 2, 1, Direction::Right),
+            ("owe", 1, 1, Direction::Right),
+            ("pen", 2, 0, Direction::Right),
+            ("red", 0, 3, Direction::Down),
+            ("son", 0, 0, Direction::DownRight),
+        ]
+    );
+    assert_eq!(found[3].cells(), [(1, 1), (1, 2), (1, 3)]);
+
+    let options = WordSearchOptions {
+        min_len: 4,
+        directions: vec![Direction::Right, Direction::Down],
+    };
+    let found = word_search(&grid, &dictionary, &options);
+    let words: Vec<&str> = found.iter().map(|found| found.word.as_str()).collect();
+    assert_eq!(words, ["star"]);
+    assert_eq!("down-left".parse::<Direction>(), Ok(Direction::DownLeft));
+    assert!("sideways".parse::<Direction>().is_err());
+}
+
+#[test]
+fn test_dictionary_glob() {
+    let dictionary = Dictionary::from_text(
+        "cat\ncats\ncoat\ncartel\ncart\nact\nscatter\ncafé\n",
+        &LoadOptions::default(),
+    );
+
+    // Without a * the pattern fixes the length
+    assert_eq!(dictionary.glob("c??t"), ["cart", "coat"]);
+    // A * may cover no letters at all
+    assert_eq!(dictionary.glob("cat*"), ["cat", "cats"]);
+    assert_eq!(dictionary.glob("*cat*"), ["cat", "cats", "scatter"]);
+    assert_eq!(dictionary.glob("c*t*l"), ["cartel"]);
+    assert_eq!(dictionary.glob("*t"), ["act", "cart", "cat", "coat"]);
+    // Patterns are normalized like the entries, and ? covers a whole accented letter
+    assert_eq!(dictionary.glob("caf\u{65}\u{301}"), ["café"]);
+    assert_eq!(dictionary.glob("caf?"), ["café"]);
+    assert!(dictionary.glob("z*").is_empty());
+    assert!(dictionary.glob("").is_empty());
+    assert_eq!(dictionary.glob("*").len(), 8);
+}
+
+#[test]
+fn test_letter_wheels() {
+    let dictionary = Dictionary::from_text(
+        "ten\nnet\nsent\nseat\neats\nants\ntea\n",
+        &LoadOptions::default(),
+    );
+
+    // Fixed strides read both ways round, wrapping past the last letter
+    let found = wheel_words("t s e a n", &dictionary, &WheelOptions::default());
+    let words: Vec<(&str, usize, Rotation, Option<usize>)> = found
+        .iter()
+        .map(|found| {
+            (
+                found.word.as_str(),
+                found.start,
+                found.rotation,
+                found.stride(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        words,
+        [
+            ("ants", 3, Rotation::Clockwise, Some(1)),
+            ("net", 4, Rotation::Counterclockwise, Some(2)),
+            ("ten", 0, Rotation::Clockwise, Some(2)),
+        ]
+    );
+    assert_eq!(found[0].positions(5), [3, 4, 0, 1]);
+
+    // Variable strides mix the allowed steps within one word
+    let options = WheelOptions {
+        strides: vec![1, 2],
+        variable: true,
+        rotations: vec![Rotation::Clockwise],
+        ..WheelOptions::default()
+    };
+    let found = wheel_words("tsean", &dictionary, &options);
+    let sent = found.iter().find(|found| found.word == "sent").unwrap();
+    assert_eq!((sent.start, sent.stride()), (1, None));
+    assert_eq!(sent.strides, [1, 2, 1]);
+    let eats = found.iter().find(|found| found.word == "eats").unwrap();
+    assert_eq!(eats.positions(5), [2, 3, 0, 1]);
+    assert!(
+        found
+            .iter()
+            .all(|found| found.rotation == Rotation::Clockwise)
+    );
+
+    // A stride of the whole wheel never moves, and no position is used twice
+    let options = WheelOptions {
+        strides: vec![5],
+        min_len: 1,
+        ..WheelOptions::default()
+    };
+    assert!(wheel_words("tsean", &dictionary, &options).is_empty());
+    assert_eq!(
+        "anticlockwise".parse::<Rotation>(),
+        Ok(Rotation::Counterclockwise)
+    );
+    assert!("sideways".parse::<Rotation>().is_err());
+}
+
+#[test]
+fn test_puzzle_config_files() {
+    let dir = std::env::temp_dir().join(format!("gallry-puzzle-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("words.txt"), "cat\nbat\ncot\n").unwrap();
+    std::fs::write(
+        dir.join("puzzle.json"),
+        r#"{
+            "slots": ["cb", "ao", "t"],
+            "word_lists": ["words.txt"],
+            "proper_nouns": "exclude",
+            "constraints": [{"excludes-letters": "o"}]
+        }"#,
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("puzzle.toml"),
+        "slots = [\"cb\", \"ao\", \"t\"]\nword_lists = [\"words.txt\"]\n",
+    )
+    .unwrap();
+
+    let json = PuzzleConfig::from_path(dir.join("puzzle.json").to_str().unwrap()).unwrap();
+    assert_eq!(json.proper_nouns, ProperNouns::Exclude);
+    assert_eq!(
+        json.constraints,
+        vec![Constraint::ExcludesLetters("o".to_string())]
+    );
+    let words: Vec<String> = json.to_generator().unwrap().iter().collect();
+    assert_eq!(words, vec!["cat", "bat"]);
+
+    let toml = PuzzleConfig::from_path(dir.join("puzzle.toml").to_str().unwrap()).unwrap();
+    let words: Vec<String> = toml.to_generator().unwrap().iter().collect();
+    assert_eq!(words, vec!["cat", "cot", "bat"]);
+
+    assert!(PuzzleConfig::from_toml_str("slot = [\"abc\"]").is_err());
+    let compact = PuzzleConfig::from_toml_str("slots = \"cb/ao/t\"").unwrap();
+    assert_eq!(compact.slots, vec!["cb", "ao", "t"]);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_puzzle_config_save() {
+    let dir = std::env::temp_dir().join(format!("gallry-save-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("lists")).unwrap();
+    std::fs::write(dir.join("lists/words.txt"), "cat\nbat\n").unwrap();
+    std::fs::write(
+        dir.join("puzzle.toml"),
+        "slots = \"cb/a/t\"\nword_lists = [\"lists/words.txt\"]\n[metadata]\nname = \"hall\"\n",
+    )
+    .unwrap();
+
+    let config = PuzzleConfig::from_path(dir.join("puzzle.toml").to_str().unwrap()).unwrap();
+    assert_eq!(config.version, SchemaVersion::CURRENT);
+    assert_eq!(config.metadata.name.as_deref(), Some("hall"));
+
+    // Saved copies read back the same, in either format, with portable relative paths
+    for name in ["copy.json", "copy.toml"] {
+        let path = dir.join(name);
+        config.save(path.to_str().unwrap()).unwrap();
+        assert!(
+            std::fs::read_to_string(&path)
+                .unwrap()
+                .contains("\"lists/words.txt\"")
+        );
+        assert_eq!(
+            PuzzleConfig::from_path(path.to_str().unwrap()).unwrap(),
+            config
+        );
+    }
+
+    let newer = PuzzleConfig::from_json_str(r#"{"version": 2, "slots": ["a"]}"#);
+    assert!(newer.is_err());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_match_stream_yields_between_batches() {
+    use futures_core::Stream;
+    use gallry_puzzle_soulver::MatchStream;
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+
+    let dictionary = Dictionary::from_text("cat\nbot\n", &LoadOptions::default());
+    let generator = WordGenerator::with_dictionary(
+        vec![
+            Slot::new(vec!['c', 'b']),
+            Slot::new(vec!['a', 'i', 'o']),
+            Slot::new(vec!['t']),
+        ],
+        dictionary,
+    );
+
+    let mut stream = pin!(MatchStream::new(&generator, 2));
+    let mut context = Context::from_waker(Waker::noop());
+    let mut polls = Vec::new();
+    loop {
+        match stream.as_mut().poll_next(&mut context) {
+            Poll::Ready(Some(word_match)) => polls.push(word_match.word),
+            Poll::Ready(None) => break,
+            Poll::Pending => polls.push("pending".to_string()),
+        }
+    }
+    // Two combinations per poll: cat cit, then cot bat, then bit bot
+    assert_eq!(polls, vec!["cat", "pending", "bot"]);
+    assert!(stream.cursor().is_done(&generator));
+}
+
+#[cfg(feature = "proptest-support")]
+proptest::proptest! {
+    #![proptest_config(proptest::prelude::ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn test_matches_satisfy_random_constraints(
+        config in gallry_puzzle_soulver::arbitrary::puzzle(1..=5)
+    ) {
+        let config = PuzzleConfig { all_combinations: true, ..config };
+        let generator = config.to_generator().unwrap();
+        let mut cursor = gallry_puzzle_soulver::Cursor::new();
+        for word_match in generator.advance(&mut cursor, 64, 10_000) {
+            for constraint in &config.constraints {
+                proptest::prop_assert!(constraint.accepts(&word_match.word));
+            }
+        }
+    }
+}
@@ -0,0 +1,154 @@
+//! WebAssembly bindings for the gallery puzzle solver.
+//!
+//! The bindings expose a [`Solver`] that a browser page can drive entirely client-side
+//! with the embedded dictionary. Build them with `wasm-pack build crates/gallry-wasm
+//! --target web` and use them from JavaScript:
+//!
+//! ```js
+//! import init, { Solver } from "./pkg/gallry_wasm.js";
+//!
+//! await init();
+//! const solver = new Solver(["cbr", "aio", "tse"]);
+//! while (!solver.done) {
+//!     for (const word of solver.nextBatch(50)) {
+//!         console.log(word);
+//!     }
+//!     await new Promise(requestAnimationFrame);
+//! }
+//! ```
+
+use gallry_puzzle_soulver::{Cursor, PuzzleConfig, WordGenerator, parse_slots};
+use wasm_bindgen::prelude::*;
+
+/// Combinations examined per batch at most, so a page stays responsive between batches
+const BATCH_COMBINATIONS: u128 = 200_000;
+
+/// Matches collected by [`Solver::collect_bounded`].
+#[wasm_bindgen(getter_with_clone)]
+pub struct Matches {
+    /// The matching words, in solve order
+    pub words: Vec<String>,
+    /// Whether more matches were left out to stay within the bounds
+    pub truncated: bool,
+}
+
+/// A puzzle being solved a batch at a time.
+#[wasm_bindgen]
+pub struct Solver {
+    generator: WordGenerator,
+    cursor: Cursor,
+}
+
+#[wasm_bindgen]
+impl Solver {
+    /// Creates a solver for slots written as in the command line, e.g. `["cbr", "?"]`,
+    /// filtering against the embedded dictionary.
+    #[wasm_bindgen(constructor)]
+    pub fn new(slots: Vec<String>) -> Result<Solver, JsError> {
+        Self::try_new(&slots).map_err(to_js)
+    }
+
+    /// Creates a solver from a puzzle definition in JSON, as accepted by the command
+    /// line's `--puzzle`.
+    ///
+    /// Word lists and other files cannot be read in a browser, so the definition must
+    /// leave `word_lists`, `blocklists`, `definitions` and `pronunciations` empty.
+    #[wasm_bindgen(js_name = fromJson)]
+    pub fn from_json(json: &str) -> Result<Solver, JsError> {
+        Self::try_from_json(json).map_err(to_js)
+    }
+
+    /// Returns up to `size` more matches, in solve order.
+    ///
+    /// A batch may hold fewer matches, even none, while the solver is not yet
+    /// [`done`](Solver::done): each call examines a bounded number of combinations.
+    #[wasm_bindgen(js_name = nextBatch)]
+    pub fn next_batch(&mut self, size: usize) -> Vec<String> {
+        self.generator
+            .advance(&mut self.cursor, size, BATCH_COMBINATIONS)
+            .into_iter()
+            .map(|word_match| word_match.display_word().to_string())
+            .collect()
+    }
+
+    /// Returns every match from the first combination, stopping at `maxResults` matches
+    /// or about `maxBytes` of them, whichever comes first.
+    ///
+    /// This solves the whole puzzle in one call, so the page is unresponsive until it
+    /// returns; use [`nextBatch`](Solver::next_batch) for puzzles that may be large.
+    #[wasm_bindgen(js_name = collectBounded)]
+    pub fn collect_bounded(&self, max_results: usize, max_bytes: usize) -> Matches {
+        let bounded = self.generator.collect_bounded(max_results, max_bytes);
+        Matches {
+            truncated: bounded.is_truncated(),
+            words: bounded
+                .matches
+                .iter()
+                .map(|word_match| word_match.display_word().to_string())
+                .collect(),
+        }
+    }
+
+    /// Whether every combination has been examined.
+    #[wasm_bindgen(getter)]
+    pub fn done(&self) -> bool {
+        self.cursor.is_done(&self.generator)
+    }
+
+    /// Share of the combinations examined so far, from 0 to 1.
+    #[wasm_bindgen(getter)]
+    pub fn progress(&self) -> f64 {
+        match self.generator.combination_count() {
+            0 => 1.0,
+            total => self.cursor.position() as f64 / total as f64,
+        }
+    }
+
+    /// Number of combinations the slots produce before filtering.
+    #[wasm_bindgen(getter, js_name = combinationCount)]
+    pub fn combination_count(&self) -> f64 {
+        self.generator.combination_count() as f64
+    }
+
+    /// Starts the solve over from the first combination.
+    pub fn restart(&mut self) {
+        self.cursor = Cursor::new();
+    }
+}
+
+impl Solver {
+    /// Creates a solver for slots written as in the command line, as
+    /// [`new`](Solver::new) does, with errors that need no JavaScript runtime.
+    ///
+    /// # Parameters
+    ///
+    /// * `slots` - The character set of each position, e.g. `["cbr", "?"]`
+    ///
+    /// # Returns
+    ///
+    /// The solver, or an error naming the first slot that cannot be parsed
+    pub fn try_new<S: AsRef<str>>(slots: &[S]) -> anyhow::Result<Solver> {
+        let generator = WordGenerator::with_slots(parse_slots(slots)?);
+        Ok(Self::with_generator(generator))
+    }
+
+    /// Creates a solver from a puzzle definition in JSON, as
+    /// [`from_json`](Solver::from_json) does, with errors that need no JavaScript
+    /// runtime.
+    pub fn try_from_json(json: &str) -> anyhow::Result<Solver> {
+        let config = PuzzleConfig::from_json_str(json)?;
+        Ok(Self::with_generator(config.to_generator()?))
+    }
+
+    fn with_generator(generator: WordGenerator) -> Self {
+        Self {
+            generator,
+            cursor: Cursor::new(),
+        }
+    }
+}
+
+/// Converts a solver error into a JavaScript `Error`.
+fn to_js(error: anyhow::Error) -> JsError {
+    JsError::new(&format!("{:#}", error))
+}
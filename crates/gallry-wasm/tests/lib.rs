@@ -0,0 +1,46 @@
+use gallry_puzzle_soulver::{Slot, WordGenerator};
+use gallry_wasm::Solver;
+
+#[test]
+fn test_solver_batches() {
+    let mut solver = Solver::try_new(&["cbr", "aio", "tse"]).unwrap();
+    assert_eq!(solver.combination_count(), 27.0);
+    assert!(!solver.done());
+    assert_eq!(solver.progress(), 0.0);
+
+    let mut words = Vec::new();
+    while !solver.done() {
+        words.extend(solver.next_batch(2));
+    }
+    let expected: Vec<String> = WordGenerator::with_slots(vec![
+        Slot::new(vec!['c', 'b', 'r']),
+        Slot::new(vec!['a', 'i', 'o']),
+        Slot::new(vec!['t', 's', 'e']),
+    ])
+    .iter_matches()
+    .map(|word_match| word_match.display_word().to_string())
+    .collect();
+    assert!(words.contains(&"cat".to_string()));
+    assert_eq!(words, expected);
+    assert_eq!(solver.progress(), 1.0);
+    assert!(solver.next_batch(10).is_empty());
+
+    // Restarting solves the puzzle again from the first combination
+    solver.restart();
+    assert!(!solver.done());
+    assert_eq!(solver.next_batch(1), expected[..1]);
+
+    let bounded = solver.collect_bounded(2, usize::MAX);
+    assert_eq!(bounded.words, expected[..2]);
+    assert!(bounded.truncated);
+}
+
+#[test]
+fn test_solver_rejects_invalid_slots() {
+    let error = Solver::try_new(&["cbr", "[a-"]).err().unwrap();
+    assert!(format!("{:#}", error).contains("Invalid slot 2 '[a-'"));
+
+    let solver = Solver::try_from_json(r#"{"slots": ["cbr", "aio", "tse"]}"#).unwrap();
+    assert_eq!(solver.combination_count(), 27.0);
+    assert!(Solver::try_from_json(r#"{"slots": "#).is_err());
+}